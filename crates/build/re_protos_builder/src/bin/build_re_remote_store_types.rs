@@ -8,7 +8,11 @@
 use camino::Utf8Path;
 
 const PROTOS_DIR: &str = "crates/store/re_protos/proto";
-const INPUT_V0: &[&str] = &["rerun/v0/remote_store.proto", "rerun/v0/log_msg.proto"];
+const INPUT_V0: &[&str] = &[
+    "rerun/v0/remote_store.proto",
+    "rerun/v0/log_msg.proto",
+    "rerun/v0/sdk_comms.proto",
+];
 const OUTPUT_V0_RUST_DIR: &str = "crates/store/re_protos/src/v0";
 
 fn main() {
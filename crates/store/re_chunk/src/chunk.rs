@@ -930,6 +930,42 @@ impl Chunk {
             .insert(chunk_timeline.timeline, chunk_timeline);
         self.sanity_check()
     }
+
+    /// Shifts all the times on the given `timeline` by `offset`, e.g. to correct for the clock
+    /// offset of the process that logged the data.
+    ///
+    /// Times are clamped to the valid range of [`TimeInt`], so that they never become static.
+    ///
+    /// If `timeline` is not found within the [`Chunk`], the end result will be the same as the
+    /// current chunk.
+    ///
+    /// WARNING: the returned chunk has the same old [`crate::ChunkId`]! Change it with [`Self::with_id`].
+    #[must_use]
+    pub fn time_shifted(mut self, timeline: &Timeline, offset: i64) -> Self {
+        if offset == 0 {
+            return self;
+        }
+
+        if let Some(time_column) = self.timelines.get_mut(timeline) {
+            let times = time_column
+                .times_raw()
+                .iter()
+                .map(|time| {
+                    time.saturating_add(offset)
+                        .clamp(TimeInt::MIN.as_i64(), TimeInt::MAX.as_i64())
+                })
+                .collect_vec();
+
+            // Shifting and clamping both preserve the order of the times.
+            *time_column = TimeColumn::new(
+                Some(time_column.is_sorted),
+                *timeline,
+                Arrow2PrimitiveArray::<i64>::from_vec(times),
+            );
+        }
+
+        self
+    }
 }
 
 impl TimeColumn {
@@ -0,0 +1,121 @@
+use re_chunk::{Chunk, RowId, TimeInt, TimePoint, Timeline};
+use re_log_types::example_components::MyPoint;
+
+// ---
+
+fn frame() -> Timeline {
+    Timeline::new_sequence("frame")
+}
+
+fn log_time() -> Timeline {
+    Timeline::new_temporal("log_time")
+}
+
+/// One point per frame, logged at `frame * 1000` on `log_time`.
+fn points_chunk(frames: &[i64]) -> Chunk {
+    let points = [MyPoint::new(1.0, 2.0)];
+
+    frames
+        .iter()
+        .fold(Chunk::builder("points".into()), |builder, &frame_nr| {
+            builder.with_component_batches(
+                RowId::new(),
+                [(frame(), frame_nr), (log_time(), frame_nr * 1000)],
+                [&points as _],
+            )
+        })
+        .build()
+        .unwrap()
+}
+
+fn times(chunk: &Chunk, timeline: &Timeline) -> Vec<i64> {
+    chunk.timelines()[timeline].times_raw().to_vec()
+}
+
+#[test]
+fn temporal() {
+    let chunk = points_chunk(&[1, 2, 3]);
+    let shifted = chunk.clone().time_shifted(&frame(), 10);
+    shifted.sanity_check().unwrap();
+
+    assert_eq!(chunk.id(), shifted.id());
+    assert_eq!(vec![11, 12, 13], times(&shifted, &frame()));
+    assert!(shifted.timelines()[&frame()].is_sorted());
+
+    // Only the requested timeline moves.
+    assert_eq!(times(&chunk, &log_time()), times(&shifted, &log_time()));
+
+    let shifted = chunk.time_shifted(&log_time(), -1500);
+    shifted.sanity_check().unwrap();
+
+    assert_eq!(vec![1, 2, 3], times(&shifted, &frame()));
+    assert_eq!(vec![-500, 500, 1500], times(&shifted, &log_time()));
+}
+
+#[test]
+fn unsorted() {
+    let chunk = points_chunk(&[3, 1, 2]);
+    assert!(!chunk.timelines()[&frame()].is_sorted());
+
+    let shifted = chunk.time_shifted(&frame(), 10);
+    shifted.sanity_check().unwrap();
+
+    assert_eq!(vec![13, 11, 12], times(&shifted, &frame()));
+    assert!(!shifted.timelines()[&frame()].is_sorted());
+}
+
+#[test]
+fn noop() {
+    let chunk = points_chunk(&[1, 2, 3]);
+
+    let shifted = chunk.clone().time_shifted(&frame(), 0);
+    assert_eq!(chunk, shifted);
+
+    let shifted = chunk
+        .clone()
+        .time_shifted(&Timeline::new_sequence("other"), 10);
+    assert_eq!(chunk, shifted);
+}
+
+#[test]
+fn static_chunk() {
+    let points = [MyPoint::new(1.0, 2.0)];
+    let chunk = Chunk::builder("points".into())
+        .with_component_batches(RowId::new(), TimePoint::default(), [&points as _])
+        .build()
+        .unwrap();
+    assert!(chunk.is_static());
+
+    let shifted = chunk.clone().time_shifted(&frame(), 10);
+    assert!(shifted.is_static());
+    assert_eq!(chunk, shifted);
+}
+
+#[test]
+fn overflow() {
+    let max = TimeInt::MAX.as_i64();
+    let min = TimeInt::MIN.as_i64();
+
+    let points = [MyPoint::new(1.0, 2.0)];
+    let chunk = [min, 0, max]
+        .into_iter()
+        .fold(Chunk::builder("points".into()), |builder, frame_nr| {
+            builder.with_component_batches(RowId::new(), [(frame(), frame_nr)], [&points as _])
+        })
+        .build()
+        .unwrap();
+
+    let shifted = chunk.clone().time_shifted(&frame(), i64::MAX);
+    shifted.sanity_check().unwrap();
+    assert_eq!(vec![max, max, max], times(&shifted, &frame()));
+
+    // Times must never saturate into the static sentinel.
+    let shifted = chunk.time_shifted(&frame(), i64::MIN);
+    shifted.sanity_check().unwrap();
+    assert_eq!(vec![min, min, min], times(&shifted, &frame()));
+    assert!(!shifted.is_static());
+    assert!(shifted
+        .timelines()
+        .values()
+        .all(|time_column| time_column.times().all(|time| !time.is_static())));
+}
@@ -488,8 +488,13 @@ pub enum SparseFillStrategy {
     /// The latest-at semantics are applied on the entire dataset as opposed to just the current
     /// view contents: it is possible to end up with values from outside the view!
     LatestAtGlobal,
-    //
-    // TODO(cmc): `LatestAtView`?
+
+    /// Fill null values using view-scoped latest-at semantics.
+    ///
+    /// Only the data that is part of the view contents and within the
+    /// [`QueryExpression::filtered_index_range`] is considered: values logged before the start of
+    /// the range never leak into the results.
+    LatestAtView,
 }
 
 impl std::fmt::Display for SparseFillStrategy {
@@ -497,6 +502,27 @@ impl std::fmt::Display for SparseFillStrategy {
         match self {
             Self::None => f.write_str("none"),
             Self::LatestAtGlobal => f.write_str("latest-at (global)"),
+            Self::LatestAtView => f.write_str("latest-at (view)"),
+        }
+    }
+}
+
+/// Specifies how the columns of [`QueryExpression::filtered_is_not_null`] are combined.
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum FilterIsNotNullMode {
+    /// Only keep rows where all of the columns contain non-null data.
+    #[default]
+    All,
+
+    /// Keep rows where at least one of the columns contains non-null data.
+    Any,
+}
+
+impl std::fmt::Display for FilterIsNotNullMode {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::All => f.write_str("all"),
+            Self::Any => f.write_str("any"),
         }
     }
 }
@@ -665,14 +691,22 @@ pub struct QueryExpression {
     /// Example: `[TimeInt(12), TimeInt(14)]`.
     pub using_index_values: Option<BTreeSet<IndexValue>>,
 
-    /// The component column used to filter out _rows_ from the view contents.
+    /// The component columns used to filter out _rows_ from the view contents.
     ///
-    /// Only rows where this column contains non-null data be kept in the final dataset.
+    /// Only rows where these columns contain non-null data will be kept in the final dataset, as
+    /// specified by [`QueryExpression::filtered_is_not_null_mode`].
     ///
-    /// Example: `ComponentColumnSelector("rerun.components.Position3D")`.
-    //
-    // TODO(cmc): multi-pov support
-    pub filtered_is_not_null: Option<ComponentColumnSelector>,
+    /// * This has no effect if left empty.
+    /// * A column that isn't part of the view contents never contains any data.
+    ///
+    /// Example: `[ComponentColumnSelector("/robot:Transform3D"), ComponentColumnSelector("/camera:Image")]`.
+    pub filtered_is_not_null: Vec<ComponentColumnSelector>,
+
+    /// Whether all or any of the [`QueryExpression::filtered_is_not_null`] columns must contain
+    /// non-null data for a row to be kept.
+    ///
+    /// Defaults to [`FilterIsNotNullMode::All`].
+    pub filtered_is_not_null_mode: FilterIsNotNullMode,
 
     /// Specifies how null values should be filled in the returned dataframe.
     ///
@@ -891,6 +925,7 @@ impl ChunkStore {
             filtered_index_values: _,
             using_index_values: _,
             filtered_is_not_null: _,
+            filtered_is_not_null_mode: _,
             sparse_fill_strategy: _,
             selection: _,
         } = query;
@@ -1,7 +1,7 @@
-use re_chunk::{ChunkId, Timeline};
+use re_chunk::{Chunk, ChunkId, Timeline};
 use re_log_types::ResolvedTimeRange;
 
-use crate::{ChunkStore, ChunkStoreEvent};
+use crate::{ChunkStore, ChunkStoreDiff, ChunkStoreEvent};
 
 impl ChunkStore {
     /// Drop all events that are in the given range on the given timeline.
@@ -10,6 +10,9 @@ impl ChunkStore {
     ///
     /// Static chunks are unaffected.
     ///
+    /// Chunks that were spilled to disk are dropped as well, which requires reading them back,
+    /// see [`crate::ChunkStoreConfig::spill_directory`].
+    ///
     /// Used to implement undo (erase the last event from the blueprint db).
     pub fn drop_time_range(
         &mut self,
@@ -28,80 +31,51 @@ impl ChunkStore {
         let mut new_chunks = vec![];
 
         for (chunk_id, chunk) in &self.chunks_per_chunk_id {
-            let Some(time_column) = chunk.timelines().get(timeline) else {
-                // static chunk, or chunk that doesn't overlap this timeline
-                continue; // keep it
-            };
-
-            let chunk_range = time_column.time_range();
-
-            if drop_range.contains_range(chunk_range) {
-                // The whole chunk should be dropped!
+            if let Some(kept_chunks) = split_off_time_range(chunk, timeline, drop_range) {
                 chunk_ids_to_drop.push(*chunk_id);
-            } else if drop_range.intersects(chunk_range) {
-                let chunk = chunk.sorted_by_timeline_if_unsorted(timeline);
-
-                let num_rows = chunk.num_rows();
-
-                // Get the sorted times:
-                #[allow(clippy::unwrap_used)] // We already know the chunk has the timeline
-                let time_column = chunk.timelines().get(timeline).unwrap();
-                let times = time_column.times_raw();
-
-                let drop_range_min = drop_range.min().as_i64();
-                let drop_range_max = drop_range.max().as_i64();
-
-                let min_idx = times.partition_point(|&time| time < drop_range_min);
-                let max_idx = times.partition_point(|&time| time <= drop_range_max);
-
-                {
-                    // Sanity check:
-                    debug_assert!(min_idx <= max_idx);
-                    debug_assert!(drop_range_min <= times[min_idx]);
-                    if 0 < min_idx {
-                        debug_assert!(times[min_idx - 1] < drop_range_min);
-                    }
-                    if max_idx < num_rows {
-                        debug_assert!(drop_range_max < times[max_idx]);
-                        if 0 < max_idx {
-                            debug_assert!(times[max_idx - 1] <= drop_range_max);
-                        }
-                    }
-                }
-
-                if min_idx < max_idx {
-                    chunk_ids_to_drop.push(*chunk_id);
-                    if 0 < min_idx {
-                        new_chunks.push(chunk.row_sliced(0, min_idx).with_id(ChunkId::new()));
-                    }
-                    if max_idx < num_rows {
-                        new_chunks.push(
-                            chunk
-                                .row_sliced(max_idx, num_rows - max_idx)
-                                .with_id(ChunkId::new()),
-                        );
-                    }
-                }
+                new_chunks.extend(kept_chunks);
             }
         }
 
+        // Spilled chunks would otherwise be paged back in by queries.
+        let dropped_spilled_chunks = self
+            .spill
+            .as_mut()
+            .map(|spill| spill.drop_time_range(timeline, drop_range))
+            .unwrap_or_default();
+        for chunk in &dropped_spilled_chunks {
+            // The spill only knows about time ranges: the chunk might not have any row within
+            // `drop_range` after all, in which case it is kept whole.
+            new_chunks.extend(
+                split_off_time_range(chunk, timeline, drop_range)
+                    .unwrap_or_else(|| vec![(**chunk).clone().with_id(ChunkId::new())]),
+            );
+        }
+
         // ------------------
         // Apply the changes:
 
         let generation = self.generation();
         let mut events: Vec<ChunkStoreEvent> = vec![];
 
-        for chunk_id in chunk_ids_to_drop {
-            for diff in self.remove_chunk(chunk_id) {
-                events.push(ChunkStoreEvent {
-                    store_id: self.id.clone(),
-                    store_generation: generation.clone(),
-                    event_id: self
-                        .event_id
-                        .fetch_add(1, std::sync::atomic::Ordering::Relaxed),
-                    diff,
-                });
-            }
+        let diffs = chunk_ids_to_drop
+            .into_iter()
+            .flat_map(|chunk_id| self.remove_chunk(chunk_id))
+            .chain(
+                dropped_spilled_chunks
+                    .into_iter()
+                    .map(ChunkStoreDiff::deletion),
+            )
+            .collect::<Vec<_>>();
+        for diff in diffs {
+            events.push(ChunkStoreEvent {
+                store_id: self.id.clone(),
+                store_generation: generation.clone(),
+                event_id: self
+                    .event_id
+                    .fetch_add(1, std::sync::atomic::Ordering::Relaxed),
+                diff,
+            });
         }
         for mut chunk in new_chunks {
             chunk.sort_if_unsorted();
@@ -112,3 +86,71 @@ impl ChunkStore {
         events
     }
 }
+
+/// Returns `None` if `chunk` has no data within `drop_range` on `timeline`.
+///
+/// Otherwise, the chunk must be dropped: returns what it holds outside of `drop_range`, as new
+/// chunks.
+fn split_off_time_range(
+    chunk: &Chunk,
+    timeline: &Timeline,
+    drop_range: ResolvedTimeRange,
+) -> Option<Vec<Chunk>> {
+    // No time column: static chunk, or chunk that doesn't overlap this timeline.
+    let chunk_range = chunk.timelines().get(timeline)?.time_range();
+
+    if drop_range.contains_range(chunk_range) {
+        // The whole chunk should be dropped!
+        return Some(Vec::new());
+    }
+    if !drop_range.intersects(chunk_range) {
+        return None; // keep it
+    }
+
+    let chunk = chunk.sorted_by_timeline_if_unsorted(timeline);
+
+    let num_rows = chunk.num_rows();
+
+    // Get the sorted times:
+    #[allow(clippy::unwrap_used)] // We already know the chunk has the timeline
+    let time_column = chunk.timelines().get(timeline).unwrap();
+    let times = time_column.times_raw();
+
+    let drop_range_min = drop_range.min().as_i64();
+    let drop_range_max = drop_range.max().as_i64();
+
+    let min_idx = times.partition_point(|&time| time < drop_range_min);
+    let max_idx = times.partition_point(|&time| time <= drop_range_max);
+
+    {
+        // Sanity check:
+        debug_assert!(min_idx <= max_idx);
+        debug_assert!(drop_range_min <= times[min_idx]);
+        if 0 < min_idx {
+            debug_assert!(times[min_idx - 1] < drop_range_min);
+        }
+        if max_idx < num_rows {
+            debug_assert!(drop_range_max < times[max_idx]);
+            if 0 < max_idx {
+                debug_assert!(times[max_idx - 1] <= drop_range_max);
+            }
+        }
+    }
+
+    if max_idx <= min_idx {
+        return None; // no row within the range after all
+    }
+
+    let mut kept_chunks = Vec::new();
+    if 0 < min_idx {
+        kept_chunks.push(chunk.row_sliced(0, min_idx).with_id(ChunkId::new()));
+    }
+    if max_idx < num_rows {
+        kept_chunks.push(
+            chunk
+                .row_sliced(max_idx, num_rows - max_idx)
+                .with_id(ChunkId::new()),
+        );
+    }
+    Some(kept_chunks)
+}
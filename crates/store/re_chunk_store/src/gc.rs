@@ -13,8 +13,8 @@ use re_log_types::{EntityPath, ResolvedTimeRange, TimeInt, Timeline};
 use re_types_core::ComponentName;
 
 use crate::{
-    store::ChunkIdSetPerTime, ChunkStore, ChunkStoreChunkStats, ChunkStoreDiff, ChunkStoreDiffKind,
-    ChunkStoreEvent, ChunkStoreStats,
+    spill::ChunkSpill, store::ChunkIdSetPerTime, ChunkStore, ChunkStoreChunkStats, ChunkStoreDiff,
+    ChunkStoreDiffKind, ChunkStoreEvent, ChunkStoreStats,
 };
 
 // Used all over in docstrings.
@@ -32,6 +32,15 @@ pub enum GarbageCollectionTarget {
 
     /// GC Everything that isn't protected.
     Everything,
+
+    /// Drop all chunks whose data on `timeline` is entirely older than `time_span`, relative to
+    /// the latest time on that timeline.
+    ///
+    /// `time_span` is expressed in the native unit of the timeline, i.e. nanoseconds for temporal
+    /// timelines and ticks for sequence timelines.
+    ///
+    /// Chunks that don't have any data on `timeline` are left untouched.
+    KeepLatestTimeSpan { timeline: Timeline, time_span: i64 },
 }
 
 #[derive(Debug, Clone)]
@@ -87,6 +96,10 @@ impl std::fmt::Display for GarbageCollectionTarget {
                 write!(f, "DropAtLeast({:.3}%)", *p * 100.0)
             }
             Self::Everything => write!(f, "Everything"),
+            Self::KeepLatestTimeSpan {
+                timeline,
+                time_span,
+            } => write!(f, "KeepLatestTimeSpan({}, {time_span})", timeline.name()),
         }
     }
 }
@@ -153,7 +166,12 @@ impl ChunkStore {
                     "starting GC"
                 );
 
-                self.gc_drop_at_least_num_bytes(options, num_bytes_to_drop, &protected_chunk_ids)
+                self.gc_drop_at_least_num_bytes(
+                    options,
+                    num_bytes_to_drop,
+                    None,
+                    &protected_chunk_ids,
+                )
             }
             GarbageCollectionTarget::Everything => {
                 re_log::trace!(
@@ -165,10 +183,43 @@ impl ChunkStore {
                     "starting GC"
                 );
 
-                self.gc_drop_at_least_num_bytes(options, f64::INFINITY, &protected_chunk_ids)
+                self.gc_drop_at_least_num_bytes(options, f64::INFINITY, None, &protected_chunk_ids)
+            }
+            GarbageCollectionTarget::KeepLatestTimeSpan {
+                timeline,
+                time_span,
+            } => {
+                if let Some(time_range) = self.time_range(&timeline) {
+                    let cutoff = TimeInt::new_temporal(
+                        time_range.max().as_i64().saturating_sub(time_span.max(0)),
+                    );
+
+                    re_log::trace!(
+                        kind = "gc",
+                        id = self.gc_id,
+                        %options.target,
+                        total_num_rows_before = re_format::format_uint(total_num_rows_before),
+                        total_size_bytes_before = re_format::format_bytes(total_size_bytes_before),
+                        cutoff = cutoff.as_i64(),
+                        "starting GC"
+                    );
+
+                    self.gc_drop_at_least_num_bytes(
+                        options,
+                        f64::INFINITY,
+                        Some((timeline, cutoff)),
+                        &protected_chunk_ids,
+                    )
+                } else {
+                    Vec::new() // nothing on that timeline, nothing to do
+                }
             }
         };
 
+        if !diffs.is_empty() {
+            self.spill(&diffs);
+        }
+
         let stats_after = self.stats();
         let total_size_bytes_after = stats_after.total().total_size_bytes as f64;
         let total_num_chunks_after = stats_after.total().num_chunks;
@@ -218,6 +269,25 @@ impl ChunkStore {
         (events, stats_before - stats_after)
     }
 
+    /// Hands the chunks that were just garbage collected over to the spill, if spilling is enabled.
+    ///
+    /// They are written to disk in the background, see [`crate::ChunkStoreConfig::spill_directory`].
+    fn spill(&mut self, diffs: &[ChunkStoreDiff]) {
+        if !self.spills_to_disk() {
+            return;
+        }
+
+        re_tracing::profile_function!();
+
+        let spill = self.spill.get_or_insert_with(|| {
+            #[allow(clippy::unwrap_used)] // checked by `spills_to_disk` above
+            let directory = self.config.spill_directory.as_deref().unwrap();
+            ChunkSpill::new(directory, self.id.clone())
+        });
+
+        spill.spill(diffs.iter().map(|diff| std::sync::Arc::clone(&diff.chunk)));
+    }
+
     /// For each `EntityPath`, `Timeline`, `Component` find the N latest [`ChunkId`]s.
     //
     // TODO(jleibs): More complex functionality might required expanding this to also
@@ -262,10 +332,13 @@ impl ChunkStore {
             .collect()
     }
 
+    /// If `time_cutoff` is specified, only chunks whose data on the given timeline is entirely
+    /// older than the given time are considered.
     fn gc_drop_at_least_num_bytes(
         &mut self,
         options: &GarbageCollectionOptions,
         mut num_bytes_to_drop: f64,
+        time_cutoff: Option<(Timeline, TimeInt)>,
         protected_chunk_ids: &BTreeSet<ChunkId>,
     ) -> Vec<ChunkStoreDiff> {
         re_tracing::profile_function!(re_format::format_bytes(num_bytes_to_drop));
@@ -290,6 +363,16 @@ impl ChunkStore {
                         continue;
                     }
 
+                    if let Some((timeline, cutoff)) = time_cutoff {
+                        let is_older_than_cutoff = chunk
+                            .timelines()
+                            .get(&timeline)
+                            .is_some_and(|time_column| time_column.time_range().max() < cutoff);
+                        if !is_older_than_cutoff {
+                            continue;
+                        }
+                    }
+
                     // NOTE: Do _NOT_ use `chunk.total_size_bytes` as it is sitting behind an Arc
                     // and would count as amortized (i.e. 0 bytes).
                     num_bytes_to_drop -= <Chunk as SizeBytes>::total_size_bytes(chunk) as f64;
@@ -353,6 +436,7 @@ impl ChunkStore {
                 query_id: _,
                 gc_id: _,
                 event_id: _,
+                spill: _,
             } = self;
 
             let mut diffs = Vec::new();
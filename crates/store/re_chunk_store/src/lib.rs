@@ -19,6 +19,7 @@ mod drop_time_range;
 mod events;
 mod gc;
 mod query;
+mod spill;
 mod stats;
 mod store;
 mod subscribers;
@@ -27,9 +28,9 @@ mod writes;
 mod protobuf_conversions;
 
 pub use self::dataframe::{
-    ColumnDescriptor, ColumnSelector, ComponentColumnDescriptor, ComponentColumnSelector, Index,
-    IndexRange, IndexValue, QueryExpression, SparseFillStrategy, TimeColumnDescriptor,
-    TimeColumnSelector, ViewContentsSelector,
+    ColumnDescriptor, ColumnSelector, ComponentColumnDescriptor, ComponentColumnSelector,
+    FilterIsNotNullMode, Index, IndexRange, IndexValue, QueryExpression, SparseFillStrategy,
+    TimeColumnDescriptor, TimeColumnSelector, ViewContentsSelector,
 };
 pub use self::events::{
     ChunkCompactionReport, ChunkStoreDiff, ChunkStoreDiffKind, ChunkStoreEvent,
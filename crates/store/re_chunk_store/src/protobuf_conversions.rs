@@ -32,6 +32,24 @@ impl TryFrom<re_protos::common::v0::ComponentColumnSelector> for crate::Componen
     }
 }
 
+impl From<re_protos::common::v0::FilterIsNotNullMode> for crate::FilterIsNotNullMode {
+    fn from(value: re_protos::common::v0::FilterIsNotNullMode) -> Self {
+        match value {
+            re_protos::common::v0::FilterIsNotNullMode::All => Self::All,
+            re_protos::common::v0::FilterIsNotNullMode::Any => Self::Any,
+        }
+    }
+}
+
+impl From<crate::FilterIsNotNullMode> for re_protos::common::v0::FilterIsNotNullMode {
+    fn from(value: crate::FilterIsNotNullMode) -> Self {
+        match value {
+            crate::FilterIsNotNullMode::All => Self::All,
+            crate::FilterIsNotNullMode::Any => Self::Any,
+        }
+    }
+}
+
 impl TryFrom<re_protos::common::v0::TimeColumnSelector> for crate::TimeColumnSelector {
     type Error = TypeConversionError;
 
@@ -153,10 +171,13 @@ impl TryFrom<re_protos::common::v0::Query> for crate::QueryExpression {
             })
             .transpose()?;
 
+        let filtered_is_not_null_mode = value.filtered_is_not_null_mode().into();
+
         let filtered_is_not_null = value
             .filtered_is_not_null
+            .into_iter()
             .map(crate::ComponentColumnSelector::try_from)
-            .transpose()?;
+            .collect::<Result<Vec<_>, _>>()?;
 
         Ok(Self {
             view_contents: value.view_contents.map(|vc| vc.try_into()).transpose()?,
@@ -175,6 +196,7 @@ impl TryFrom<re_protos::common::v0::Query> for crate::QueryExpression {
                 .using_index_values
                 .map(|uiv| uiv.time_points.into_iter().map(|v| v.into()).collect()),
             filtered_is_not_null,
+            filtered_is_not_null_mode,
             sparse_fill_strategy: crate::SparseFillStrategy::default(), // TODO(zehiko) implement support for sparse fill strategy
             selection,
         })
@@ -239,20 +261,26 @@ impl From<crate::QueryExpression> for re_protos::common::v0::Query {
                         .collect(),
                 }
             }),
-            filtered_is_not_null: value.filtered_is_not_null.map(|cs| {
-                re_protos::common::v0::ComponentColumnSelector {
+            filtered_is_not_null: value
+                .filtered_is_not_null
+                .into_iter()
+                .map(|cs| re_protos::common::v0::ComponentColumnSelector {
                     entity_path: Some(cs.entity_path.into()),
                     component: Some(re_protos::common::v0::Component {
                         name: cs.component_name,
                     }),
-                }
-            }),
+                })
+                .collect(),
             column_selection: value
                 .selection
                 .map(|cs| re_protos::common::v0::ColumnSelection {
                     columns: cs.into_iter().map(|c| c.into()).collect(),
                 }),
             sparse_fill_strategy: re_protos::common::v0::SparseFillStrategy::None.into(), // TODO(zehiko) implement
+            filtered_is_not_null_mode: re_protos::common::v0::FilterIsNotNullMode::from(
+                value.filtered_is_not_null_mode,
+            )
+            .into(),
         }
     }
 }
@@ -261,9 +289,9 @@ impl From<crate::QueryExpression> for re_protos::common::v0::Query {
 mod tests {
     use re_protos::common::v0::{
         column_selector::SelectorType, ColumnSelection, ColumnSelector, Component,
-        ComponentColumnSelector, ComponentsSet, EntityPath, IndexColumnSelector, IndexRange,
-        IndexValues, Query, SparseFillStrategy, TimeInt, TimeRange, Timeline, ViewContents,
-        ViewContentsPart,
+        ComponentColumnSelector, ComponentsSet, EntityPath, FilterIsNotNullMode,
+        IndexColumnSelector, IndexRange, IndexValues, Query, SparseFillStrategy, TimeInt,
+        TimeRange, Timeline, ViewContents, ViewContentsPart,
     };
 
     #[test]
@@ -306,14 +334,24 @@ mod tests {
                     TimeInt { time: 5 },
                 ],
             }),
-            filtered_is_not_null: Some(ComponentColumnSelector {
-                entity_path: Some(EntityPath {
-                    path: "/somepath/c".to_owned(),
-                }),
-                component: Some(Component {
-                    name: "component".to_owned(),
-                }),
-            }),
+            filtered_is_not_null: vec![
+                ComponentColumnSelector {
+                    entity_path: Some(EntityPath {
+                        path: "/somepath/c".to_owned(),
+                    }),
+                    component: Some(Component {
+                        name: "component".to_owned(),
+                    }),
+                },
+                ComponentColumnSelector {
+                    entity_path: Some(EntityPath {
+                        path: "/somepath/d".to_owned(),
+                    }),
+                    component: Some(Component {
+                        name: "component".to_owned(),
+                    }),
+                },
+            ],
             column_selection: Some(ColumnSelection {
                 columns: vec![ColumnSelector {
                     selector_type: Some(SelectorType::ComponentColumn(ComponentColumnSelector {
@@ -327,6 +365,7 @@ mod tests {
                 }],
             }),
             sparse_fill_strategy: SparseFillStrategy::None.into(),
+            filtered_is_not_null_mode: FilterIsNotNullMode::Any.into(),
         };
 
         let query_expression_native: crate::QueryExpression =
@@ -4,7 +4,7 @@ use std::{
 };
 
 use itertools::Itertools;
-use nohash_hasher::IntSet;
+use nohash_hasher::{IntMap, IntSet};
 
 use re_chunk::{Chunk, LatestAtQuery, RangeQuery};
 use re_log_types::ResolvedTimeRange;
@@ -555,6 +555,10 @@ impl ChunkStore {
     ///
     /// If the entity has static component data associated with it, it will unconditionally
     /// override any temporal component data.
+    ///
+    /// Chunks that were spilled to disk by the garbage collector are paged back in, see
+    /// [`crate::ChunkStoreConfig::spill_directory`]. Those that aren't in memory are read back in
+    /// the background and missing from the results until then, see [`Self::take_paged_in_events`].
     pub fn latest_at_relevant_chunks(
         &self,
         query: &LatestAtQuery,
@@ -578,7 +582,7 @@ impl ChunkStore {
             return vec![Arc::clone(static_chunk)];
         }
 
-        let chunks = self
+        let mut chunks = self
             .temporal_chunk_ids_per_entity_per_component
             .get(entity_path)
             .and_then(|temporal_chunk_ids_per_timeline| {
@@ -592,6 +596,30 @@ impl ChunkStore {
             })
             .unwrap_or_default();
 
+        if let Some(spill) = &self.spill {
+            // Any in-memory chunk that starts before the query time is guaranteed to hold a value
+            // at least that recent: spilled chunks that end before that are irrelevant.
+            let in_memory_lower_bound = chunks
+                .iter()
+                .filter_map(|chunk| {
+                    let time_column = chunk.timelines().get(&query.timeline())?;
+                    time_column
+                        .time_range_per_component(chunk.components())
+                        .get(&component_name)
+                        .and_then(|per_desc| per_desc.values().next())
+                        .map(|time_range| time_range.min())
+                })
+                .filter(|min| *min <= query.at())
+                .max();
+
+            chunks.extend(spill.latest_at_relevant_chunks(
+                query,
+                entity_path,
+                component_name,
+                in_memory_lower_bound,
+            ));
+        }
+
         debug_assert!(
             chunks.iter().map(|chunk| chunk.id()).all_unique(),
             "{entity_path}:{component_name} @ {query:?}",
@@ -613,6 +641,10 @@ impl ChunkStore {
     /// determine what exact row contains the final result.
     ///
     /// **This ignores static data.**
+    ///
+    /// Chunks that were spilled to disk by the garbage collector are paged back in, see
+    /// [`crate::ChunkStoreConfig::spill_directory`]. Those that aren't in memory are read back in
+    /// the background and missing from the results until then, see [`Self::take_paged_in_events`].
     pub fn latest_at_relevant_chunks_for_all_components(
         &self,
         query: &LatestAtQuery,
@@ -622,7 +654,7 @@ impl ChunkStore {
 
         self.query_id.fetch_add(1, Ordering::Relaxed);
 
-        let chunks = self
+        let mut chunks = self
             .temporal_chunk_ids_per_entity
             .get(entity_path)
             .and_then(|temporal_chunk_ids_per_timeline| {
@@ -633,6 +665,34 @@ impl ChunkStore {
             })
             .unwrap_or_default();
 
+        if let Some(spill) = &self.spill {
+            // Same as `latest_at_relevant_chunks`, but for every component at once.
+            let mut in_memory_lower_bounds: IntMap<ComponentName, TimeInt> = IntMap::default();
+            for chunk in &chunks {
+                let Some(time_column) = chunk.timelines().get(&query.timeline()) else {
+                    continue;
+                };
+                for (component_name, per_desc) in
+                    time_column.time_range_per_component(chunk.components())
+                {
+                    let Some(min) = per_desc.values().map(|time_range| time_range.min()).min()
+                    else {
+                        continue;
+                    };
+                    if min <= query.at() {
+                        let bound = in_memory_lower_bounds.entry(component_name).or_insert(min);
+                        *bound = (*bound).max(min);
+                    }
+                }
+            }
+
+            chunks.extend(spill.latest_at_relevant_chunks_for_all_components(
+                query,
+                entity_path,
+                &in_memory_lower_bounds,
+            ));
+        }
+
         debug_assert!(chunks.iter().map(|chunk| chunk.id()).all_unique());
 
         chunks
@@ -703,6 +763,10 @@ impl ChunkStore {
     ///
     /// If the entity has static component data associated with it, it will unconditionally
     /// override any temporal component data.
+    ///
+    /// Chunks that were spilled to disk by the garbage collector are paged back in, see
+    /// [`crate::ChunkStoreConfig::spill_directory`]. Those that aren't in memory are read back in
+    /// the background and missing from the results until then, see [`Self::take_paged_in_events`].
     pub fn range_relevant_chunks(
         &self,
         query: &RangeQuery,
@@ -724,7 +788,7 @@ impl ChunkStore {
             return vec![Arc::clone(static_chunk)];
         }
 
-        let chunks = self
+        let mut chunks = self
             .range(
                 query,
                 self.temporal_chunk_ids_per_entity_per_component
@@ -755,6 +819,10 @@ impl ChunkStore {
             })
             .collect_vec();
 
+        if let Some(spill) = &self.spill {
+            chunks.extend(spill.range_relevant_chunks(query, entity_path, component_name));
+        }
+
         debug_assert!(chunks.iter().map(|chunk| chunk.id()).all_unique());
 
         chunks
@@ -771,6 +839,10 @@ impl ChunkStore {
     /// determine how exactly each row of data fit with the rest.
     ///
     /// **This ignores static data.**
+    ///
+    /// Chunks that were spilled to disk by the garbage collector are paged back in, see
+    /// [`crate::ChunkStoreConfig::spill_directory`]. Those that aren't in memory are read back in
+    /// the background and missing from the results until then, see [`Self::take_paged_in_events`].
     pub fn range_relevant_chunks_for_all_components(
         &self,
         query: &RangeQuery,
@@ -780,7 +852,7 @@ impl ChunkStore {
 
         self.query_id.fetch_add(1, Ordering::Relaxed);
 
-        let chunks = self
+        let mut chunks = self
             .range(
                 query,
                 self.temporal_chunk_ids_per_entity
@@ -804,6 +876,10 @@ impl ChunkStore {
             })
             .collect_vec();
 
+        if let Some(spill) = &self.spill {
+            chunks.extend(spill.range_relevant_chunks_for_all_components(query, entity_path));
+        }
+
         debug_assert!(chunks.iter().map(|chunk| chunk.id()).all_unique());
 
         chunks
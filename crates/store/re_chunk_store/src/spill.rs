@@ -0,0 +1,819 @@
+//! On-disk storage for the chunks evicted by the garbage collector.
+//!
+//! See [`crate::ChunkStoreConfig::spill_directory`].
+
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    fs::File,
+    io::{BufReader, BufWriter},
+    path::{Path, PathBuf},
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        mpsc, Arc,
+    },
+};
+
+use nohash_hasher::IntMap;
+use parking_lot::Mutex;
+
+use re_byte_size::SizeBytes;
+use re_chunk::{Chunk, ChunkId, LatestAtQuery, RangeQuery};
+use re_log_encoding::{decoder::Decoder, encoder::Encoder};
+use re_log_types::{EntityPath, LogMsg, ResolvedTimeRange, StoreId, TimeInt, Timeline};
+use re_types_core::ComponentName;
+
+// ---
+
+/// How many bytes worth of paged-in chunks are kept around in memory.
+///
+/// Scrubbing through spilled history issues the same queries over and over: we don't want to hit
+/// the disk and decode every time.
+const SPILL_CACHE_MAX_BYTES: u64 = 256 * 1024 * 1024;
+
+/// The spilled chunks of every entity, timeline and component, so queries know what to page back in.
+type SpilledChunkIdsPerComponentPerTimelinePerEntity =
+    IntMap<EntityPath, IntMap<Timeline, IntMap<ComponentName, SpilledChunkIdsPerTime>>>;
+
+/// The spilled chunks for a given entity, timeline and component, sorted by start time.
+///
+/// The spilled counterpart of [`crate::store::ChunkIdSetPerTime`], which also keeps track of the
+/// end times so that no chunk is paged back in for nothing.
+#[derive(Default)]
+struct SpilledChunkIdsPerTime {
+    /// The longest time range of all the chunks below, see
+    /// [`crate::store::ChunkIdSetPerTime::max_interval_length`].
+    max_interval_length: u64,
+
+    /// The end time of each chunk, keyed by start time.
+    per_start_time: BTreeMap<TimeInt, Vec<(TimeInt, ChunkId)>>,
+}
+
+impl SpilledChunkIdsPerTime {
+    fn insert(&mut self, time_range: ResolvedTimeRange, chunk_id: ChunkId) {
+        self.max_interval_length = u64::max(self.max_interval_length, time_range.abs_length());
+        self.per_start_time
+            .entry(time_range.min())
+            .or_default()
+            .push((time_range.max(), chunk_id));
+    }
+
+    /// Returns `true` if nothing is left.
+    fn remove(&mut self, chunk_ids: &BTreeSet<ChunkId>) -> bool {
+        self.per_start_time.retain(|_, entries| {
+            entries.retain(|(_, chunk_id)| !chunk_ids.contains(chunk_id));
+            !entries.is_empty()
+        });
+        self.per_start_time.is_empty()
+    }
+
+    fn chunk_ids(&self) -> impl Iterator<Item = ChunkId> + '_ {
+        self.per_start_time
+            .values()
+            .flat_map(|entries| entries.iter().map(|(_, chunk_id)| *chunk_id))
+    }
+
+    /// The earliest start time of a chunk that may overlap `time`.
+    fn min_start_time(&self, time: TimeInt) -> TimeInt {
+        TimeInt::new_temporal(time.as_i64().saturating_sub(self.max_interval_length as _))
+    }
+
+    /// Collects the chunks that have data within `time_range`.
+    fn overlapping(&self, time_range: ResolvedTimeRange, chunk_ids: &mut BTreeSet<ChunkId>) {
+        let min_start_time = self.min_start_time(time_range.min());
+        if time_range.max() < min_start_time {
+            return;
+        }
+
+        chunk_ids.extend(
+            self.per_start_time
+                .range(min_start_time..=time_range.max())
+                .flat_map(|(_, entries)| entries)
+                .filter(|(end_time, _)| time_range.min() <= *end_time)
+                .map(|(_, chunk_id)| *chunk_id),
+        );
+    }
+
+    /// Collects the chunks relevant to a latest-at `query`.
+    ///
+    /// `in_memory_lower_bound` is the latest start time of all the relevant chunks that are still
+    /// in memory, if any: spilled chunks that end before that cannot possibly contain the answer.
+    fn latest_at(
+        &self,
+        query: &LatestAtQuery,
+        in_memory_lower_bound: Option<TimeInt>,
+        chunk_ids: &mut BTreeSet<ChunkId>,
+    ) {
+        // Same logic as the in-memory index: the most recent chunk to start before the query time
+        // is guaranteed to hold a value, so anything that ends before it starts is irrelevant.
+        let Some(lower_bound) = self
+            .per_start_time
+            .range(..=query.at())
+            .next_back()
+            .map(|(start_time, _)| *start_time)
+        else {
+            return;
+        };
+        let lower_bound = in_memory_lower_bound.map_or(lower_bound, |bound| bound.max(lower_bound));
+
+        chunk_ids.extend(
+            self.per_start_time
+                .range(self.min_start_time(lower_bound)..=query.at())
+                .flat_map(|(_, entries)| entries)
+                .filter(|(end_time, _)| TimeInt::min(*end_time, query.at()) >= lower_bound)
+                .map(|(_, chunk_id)| *chunk_id),
+        );
+    }
+}
+
+/// An append-only `.rrd` file holding all the chunks that were garbage collected from a store.
+///
+/// Chunks are written out by a background thread, so that the garbage collector never waits on
+/// the disk. Until they make it there, they stay in memory and are served from there.
+///
+/// Queries never wait on the disk either: they get the spilled chunks that are in memory, and
+/// have the others read back by another background thread, see [`Self::take_paged_in`].
+///
+/// The file is created lazily, the first time a chunk gets spilled, and is deleted when the
+/// [`ChunkSpill`] is dropped: it is a cache, not a recording.
+pub(crate) struct ChunkSpill {
+    store_id: StoreId,
+
+    /// `None` until the first chunk gets spilled.
+    writer: Option<SpillWriter>,
+
+    /// `None` until the first chunk gets spilled.
+    prefetcher: Option<SpillPrefetcher>,
+
+    /// Every chunk spilled so far, whether it already made it to disk or not.
+    spilled_chunk_ids: BTreeSet<ChunkId>,
+
+    /// See [`SpilledChunkIdsPerComponentPerTimelinePerEntity`].
+    index: SpilledChunkIdsPerComponentPerTimelinePerEntity,
+
+    /// State shared with the background threads.
+    shared: Arc<SpillShared>,
+}
+
+struct SpillShared {
+    path: PathBuf,
+
+    /// Chunks handed over to the writer thread that haven't made it to disk yet.
+    pending: Mutex<BTreeMap<ChunkId, Arc<Chunk>>>,
+
+    /// Where each chunk that made it to disk starts in the file.
+    byte_offset_per_chunk_id: Mutex<BTreeMap<ChunkId, u64>>,
+
+    /// Size of the spill file on disk.
+    num_bytes_written: AtomicU64,
+
+    /// Decoders that aren't in use at the moment, see [`Self::decode_at`].
+    idle_decoders: Mutex<Vec<Decoder<BufReader<File>>>>,
+
+    /// Recently paged-in chunks, see [`SPILL_CACHE_MAX_BYTES`].
+    cache: Mutex<SpillCache>,
+
+    /// Chunks requested by queries that are being read back from disk.
+    prefetching: Mutex<BTreeSet<ChunkId>>,
+
+    /// Chunks read back from disk for queries since the last [`ChunkSpill::take_paged_in`].
+    paged_in: Mutex<Vec<Arc<Chunk>>>,
+}
+
+enum SpillCommand {
+    Write(Vec<Arc<Chunk>>),
+
+    /// Acknowledged once everything sent before it made it to disk.
+    Flush(mpsc::Sender<()>),
+}
+
+struct SpillWriter {
+    tx: mpsc::Sender<SpillCommand>,
+    handle: Option<std::thread::JoinHandle<()>>,
+}
+
+/// Reads the chunks requested by queries back from disk.
+struct SpillPrefetcher {
+    tx: mpsc::Sender<ChunkId>,
+    handle: Option<std::thread::JoinHandle<()>>,
+}
+
+/// Least-recently-used cache of paged-in chunks.
+#[derive(Default)]
+struct SpillCache {
+    chunks: BTreeMap<ChunkId, (Arc<Chunk>, u64)>,
+
+    /// Cached chunk IDs, keyed by the tick at which they were last used.
+    lru: BTreeMap<u64, ChunkId>,
+    tick: u64,
+
+    total_size_bytes: u64,
+}
+
+impl SpillCache {
+    fn get(&mut self, chunk_id: &ChunkId) -> Option<Arc<Chunk>> {
+        let (chunk, last_used) = self.chunks.get_mut(chunk_id)?;
+
+        self.lru.remove(last_used);
+        self.tick += 1;
+        *last_used = self.tick;
+        self.lru.insert(self.tick, *chunk_id);
+
+        Some(Arc::clone(chunk))
+    }
+
+    fn insert(&mut self, chunk: Arc<Chunk>) {
+        // NOTE: Do _NOT_ use `chunk.total_size_bytes` as it is sitting behind an Arc
+        // and would count as amortized (i.e. 0 bytes).
+        let size_bytes = <Chunk as SizeBytes>::total_size_bytes(&chunk);
+
+        self.remove(&chunk.id());
+        while self.total_size_bytes + size_bytes > SPILL_CACHE_MAX_BYTES {
+            let Some((_, chunk_id)) = self.lru.pop_first() else {
+                break;
+            };
+            self.remove(&chunk_id);
+        }
+
+        self.tick += 1;
+        self.lru.insert(self.tick, chunk.id());
+        self.total_size_bytes += size_bytes;
+        self.chunks.insert(chunk.id(), (chunk, self.tick));
+    }
+
+    fn remove(&mut self, chunk_id: &ChunkId) {
+        if let Some((chunk, last_used)) = self.chunks.remove(chunk_id) {
+            self.lru.remove(&last_used);
+            self.total_size_bytes -= <Chunk as SizeBytes>::total_size_bytes(&chunk);
+        }
+    }
+}
+
+impl std::fmt::Debug for ChunkSpill {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ChunkSpill")
+            .field("path", &self.shared.path)
+            .field("num_chunks", &self.num_chunks())
+            .field("num_bytes", &self.num_bytes())
+            .finish_non_exhaustive()
+    }
+}
+
+impl Drop for ChunkSpill {
+    fn drop(&mut self) {
+        let Some(SpillWriter { tx, handle }) = self.writer.take() else {
+            return;
+        };
+
+        // Let the writer finish whatever it's doing, so it doesn't recreate the file behind our back.
+        drop(tx);
+        if let Some(handle) = handle {
+            handle.join().ok();
+        }
+
+        // Same for the prefetcher, which keeps the file open.
+        if let Some(SpillPrefetcher { tx, handle }) = self.prefetcher.take() {
+            drop(tx);
+            if let Some(handle) = handle {
+                handle.join().ok();
+            }
+        }
+        self.shared.idle_decoders.lock().clear();
+
+        let path = &self.shared.path;
+        if path.exists() {
+            if let Err(err) = std::fs::remove_file(path) {
+                re_log::warn!("Failed to remove chunk spill file {path:?}: {err}");
+            }
+        }
+    }
+}
+
+impl ChunkSpill {
+    /// The spill file for `store_id` lives at `{directory}/{store_id}.rrd`.
+    ///
+    /// Nothing is written to disk until [`Self::spill`] is first called.
+    pub fn new(directory: &Path, store_id: StoreId) -> Self {
+        let path = directory.join(format!("{}.rrd", store_id.id));
+        Self {
+            store_id,
+            writer: None,
+            prefetcher: None,
+            spilled_chunk_ids: Default::default(),
+            index: Default::default(),
+            shared: Arc::new(SpillShared {
+                path,
+                pending: Default::default(),
+                byte_offset_per_chunk_id: Default::default(),
+                num_bytes_written: AtomicU64::new(0),
+                idle_decoders: Mutex::new(Vec::new()),
+                cache: Mutex::new(SpillCache::default()),
+                prefetching: Default::default(),
+                paged_in: Default::default(),
+            }),
+        }
+    }
+
+    /// Number of chunks spilled so far.
+    #[inline]
+    pub fn num_chunks(&self) -> usize {
+        self.spilled_chunk_ids.len()
+    }
+
+    /// Size of the spill file on disk.
+    #[inline]
+    pub fn num_bytes(&self) -> u64 {
+        self.shared.num_bytes_written.load(Ordering::Relaxed)
+    }
+
+    /// Indexes `chunks` and hands them over to the writer thread.
+    ///
+    /// They can be paged back in right away: until they make it to disk, they're served from
+    /// memory.
+    ///
+    /// Static chunks are never garbage collected, and therefore never spilled.
+    pub fn spill(&mut self, chunks: impl IntoIterator<Item = Arc<Chunk>>) {
+        re_tracing::profile_function!();
+
+        let chunks = chunks
+            .into_iter()
+            .filter(|chunk| !chunk.is_static() && self.spilled_chunk_ids.insert(chunk.id()))
+            .collect::<Vec<_>>();
+        if chunks.is_empty() {
+            return;
+        }
+
+        for chunk in &chunks {
+            let entity_index = self.index.entry(chunk.entity_path().clone()).or_default();
+            for (timeline, per_component) in chunk.time_range_per_component() {
+                let timeline_index = entity_index.entry(timeline).or_default();
+                for (component_name, per_descriptor) in per_component {
+                    for time_range in per_descriptor.into_values() {
+                        timeline_index
+                            .entry(component_name)
+                            .or_default()
+                            .insert(time_range, chunk.id());
+                    }
+                }
+            }
+        }
+
+        self.shared
+            .pending
+            .lock()
+            .extend(chunks.iter().map(|chunk| (chunk.id(), Arc::clone(chunk))));
+
+        self.prefetcher
+            .get_or_insert_with(|| SpillPrefetcher::spawn(Arc::clone(&self.shared)));
+        let writer = self.writer.get_or_insert_with(|| {
+            SpillWriter::spawn(self.store_id.clone(), Arc::clone(&self.shared))
+        });
+        if writer.tx.send(SpillCommand::Write(chunks)).is_err() {
+            re_log::error_once!("Chunk spill thread is gone, data will be lost");
+        }
+    }
+
+    /// Blocks until every chunk spilled so far has made it to disk.
+    pub fn flush_blocking(&self) {
+        let Some(writer) = &self.writer else {
+            return;
+        };
+
+        let (tx, rx) = mpsc::channel();
+        if writer.tx.send(SpillCommand::Flush(tx)).is_ok() {
+            rx.recv().ok();
+        }
+    }
+
+    /// Whether chunks requested by queries are still being read back from disk.
+    pub fn is_paging_in(&self) -> bool {
+        !self.shared.prefetching.lock().is_empty()
+    }
+
+    /// Takes the chunks that were read back from disk for queries since the last call.
+    ///
+    /// Queries return them from now on, for as long as they stay in memory: whatever cached the
+    /// results of earlier queries, which didn't have them yet, must be invalidated.
+    pub fn take_paged_in(&self) -> Vec<Arc<Chunk>> {
+        std::mem::take(&mut *self.shared.paged_in.lock())
+    }
+
+    /// Forgets about all spilled chunks for `entity_path`.
+    ///
+    /// Returns them, read back from disk, so that their deletion can be reported: the data stays
+    /// in the file, but can never be paged back in.
+    pub fn drop_entity_path(&mut self, entity_path: &EntityPath) -> Vec<Arc<Chunk>> {
+        let Some(per_timeline) = self.index.remove(entity_path) else {
+            return Vec::new();
+        };
+
+        let chunk_ids = per_timeline
+            .values()
+            .flat_map(|per_component| per_component.values())
+            .flat_map(|chunk_ids_per_time| chunk_ids_per_time.chunk_ids())
+            .collect();
+
+        self.forget(&chunk_ids)
+    }
+
+    /// Forgets about all spilled chunks that have data within `drop_range` on `timeline`.
+    ///
+    /// Returns them, read back from disk: the caller is responsible for keeping whatever they
+    /// hold outside of `drop_range`, and for reporting their deletion.
+    pub fn drop_time_range(
+        &mut self,
+        timeline: &Timeline,
+        drop_range: ResolvedTimeRange,
+    ) -> Vec<Arc<Chunk>> {
+        let mut chunk_ids = BTreeSet::new();
+        for per_timeline in self.index.values() {
+            for chunk_ids_per_time in per_timeline
+                .get(timeline)
+                .into_iter()
+                .flat_map(|per_component| per_component.values())
+            {
+                chunk_ids_per_time.overlapping(drop_range, &mut chunk_ids);
+            }
+        }
+        if chunk_ids.is_empty() {
+            return Vec::new();
+        }
+
+        // The chunks are indexed on all of their timelines, not just this one.
+        self.index.retain(|_, per_timeline| {
+            per_timeline.retain(|_, per_component| {
+                per_component
+                    .retain(|_, chunk_ids_per_time| !chunk_ids_per_time.remove(&chunk_ids));
+                !per_component.is_empty()
+            });
+            !per_timeline.is_empty()
+        });
+
+        self.forget(&chunk_ids)
+    }
+
+    /// Reads `chunk_ids` back and drops everything else we know about them, except for the index.
+    fn forget(&mut self, chunk_ids: &BTreeSet<ChunkId>) -> Vec<Arc<Chunk>> {
+        re_tracing::profile_function!();
+
+        // Don't race the writer thread: it could record byte offsets we're about to forget.
+        self.flush_blocking();
+
+        let chunks = chunk_ids
+            .iter()
+            .filter_map(|chunk_id| self.shared.load(*chunk_id))
+            .collect();
+
+        let mut pending = self.shared.pending.lock();
+        let mut byte_offset_per_chunk_id = self.shared.byte_offset_per_chunk_id.lock();
+        let mut cache = self.shared.cache.lock();
+        for chunk_id in chunk_ids {
+            self.spilled_chunk_ids.remove(chunk_id);
+            pending.remove(chunk_id);
+            byte_offset_per_chunk_id.remove(chunk_id);
+            cache.remove(chunk_id);
+        }
+        self.shared
+            .paged_in
+            .lock()
+            .retain(|chunk| !chunk_ids.contains(&chunk.id()));
+
+        chunks
+    }
+
+    /// The spilled counterpart of [`crate::ChunkStore::latest_at_relevant_chunks`].
+    ///
+    /// `in_memory_lower_bound` is the latest start time of all the relevant chunks that are still
+    /// in memory, if any: spilled chunks that end before that cannot possibly contain the answer.
+    ///
+    /// Only returns the chunks that are in memory, see [`Self::take_paged_in`].
+    pub fn latest_at_relevant_chunks(
+        &self,
+        query: &LatestAtQuery,
+        entity_path: &EntityPath,
+        component_name: ComponentName,
+        in_memory_lower_bound: Option<TimeInt>,
+    ) -> Vec<Arc<Chunk>> {
+        let Some(chunk_ids_per_time) = self
+            .chunk_ids_per_component(entity_path, query.timeline())
+            .and_then(|per_component| per_component.get(&component_name))
+        else {
+            return Vec::new();
+        };
+
+        let mut chunk_ids = BTreeSet::new();
+        chunk_ids_per_time.latest_at(query, in_memory_lower_bound, &mut chunk_ids);
+
+        self.load_or_prefetch(chunk_ids)
+    }
+
+    /// The spilled counterpart of [`crate::ChunkStore::latest_at_relevant_chunks_for_all_components`].
+    ///
+    /// `in_memory_lower_bounds` is the per-component equivalent of the `in_memory_lower_bound`
+    /// of [`Self::latest_at_relevant_chunks`].
+    ///
+    /// Only returns the chunks that are in memory, see [`Self::take_paged_in`].
+    pub fn latest_at_relevant_chunks_for_all_components(
+        &self,
+        query: &LatestAtQuery,
+        entity_path: &EntityPath,
+        in_memory_lower_bounds: &IntMap<ComponentName, TimeInt>,
+    ) -> Vec<Arc<Chunk>> {
+        let Some(per_component) = self.chunk_ids_per_component(entity_path, query.timeline())
+        else {
+            return Vec::new();
+        };
+
+        let mut chunk_ids = BTreeSet::new();
+        for (component_name, chunk_ids_per_time) in per_component {
+            chunk_ids_per_time.latest_at(
+                query,
+                in_memory_lower_bounds.get(component_name).copied(),
+                &mut chunk_ids,
+            );
+        }
+
+        self.load_or_prefetch(chunk_ids)
+    }
+
+    /// The spilled counterpart of [`crate::ChunkStore::range_relevant_chunks`].
+    ///
+    /// Only returns the chunks that are in memory, see [`Self::take_paged_in`].
+    pub fn range_relevant_chunks(
+        &self,
+        query: &RangeQuery,
+        entity_path: &EntityPath,
+        component_name: ComponentName,
+    ) -> Vec<Arc<Chunk>> {
+        let Some(chunk_ids_per_time) = self
+            .chunk_ids_per_component(entity_path, query.timeline())
+            .and_then(|per_component| per_component.get(&component_name))
+        else {
+            return Vec::new();
+        };
+
+        let mut chunk_ids = BTreeSet::new();
+        chunk_ids_per_time.overlapping(query.range(), &mut chunk_ids);
+
+        self.load_or_prefetch(chunk_ids)
+    }
+
+    /// The spilled counterpart of [`crate::ChunkStore::range_relevant_chunks_for_all_components`].
+    ///
+    /// Only returns the chunks that are in memory, see [`Self::take_paged_in`].
+    pub fn range_relevant_chunks_for_all_components(
+        &self,
+        query: &RangeQuery,
+        entity_path: &EntityPath,
+    ) -> Vec<Arc<Chunk>> {
+        let Some(per_component) = self.chunk_ids_per_component(entity_path, query.timeline())
+        else {
+            return Vec::new();
+        };
+
+        let mut chunk_ids = BTreeSet::new();
+        for chunk_ids_per_time in per_component.values() {
+            chunk_ids_per_time.overlapping(query.range(), &mut chunk_ids);
+        }
+
+        self.load_or_prefetch(chunk_ids)
+    }
+
+    fn chunk_ids_per_component(
+        &self,
+        entity_path: &EntityPath,
+        timeline: Timeline,
+    ) -> Option<&IntMap<ComponentName, SpilledChunkIdsPerTime>> {
+        self.index
+            .get(entity_path)
+            .and_then(|per_timeline| per_timeline.get(&timeline))
+    }
+
+    /// Returns the chunks that are in memory, and has the others read back from disk in the
+    /// background.
+    ///
+    /// Never blocks on the disk: this is on the query path, which runs every frame.
+    fn load_or_prefetch(&self, chunk_ids: BTreeSet<ChunkId>) -> Vec<Arc<Chunk>> {
+        let mut chunks = Vec::with_capacity(chunk_ids.len());
+        let mut chunk_ids_to_prefetch = Vec::new();
+
+        for chunk_id in chunk_ids {
+            match self.shared.get_in_memory(&chunk_id) {
+                Some(chunk) => chunks.push(chunk),
+                None => chunk_ids_to_prefetch.push(chunk_id),
+            }
+        }
+
+        if !chunk_ids_to_prefetch.is_empty() {
+            self.prefetch(chunk_ids_to_prefetch);
+        }
+
+        chunks
+    }
+
+    fn prefetch(&self, chunk_ids: Vec<ChunkId>) {
+        let Some(prefetcher) = &self.prefetcher else {
+            return;
+        };
+
+        let mut prefetching = self.shared.prefetching.lock();
+        for chunk_id in chunk_ids {
+            if prefetching.insert(chunk_id) && prefetcher.tx.send(chunk_id).is_err() {
+                prefetching.remove(&chunk_id);
+                re_log::error_once!("Chunk spill prefetch thread is gone, spilled data is lost");
+            }
+        }
+    }
+}
+
+impl SpillShared {
+    /// Returns the chunk if it can be paged back in without hitting the disk.
+    fn get_in_memory(&self, chunk_id: &ChunkId) -> Option<Arc<Chunk>> {
+        if let Some(chunk) = self.cache.lock().get(chunk_id) {
+            return Some(chunk);
+        }
+
+        self.pending.lock().get(chunk_id).map(Arc::clone)
+    }
+
+    /// Pages a chunk back in, either from memory or from disk.
+    ///
+    /// Failures are logged and result in `None`: missing history is better than no viewer.
+    fn load(&self, chunk_id: ChunkId) -> Option<Arc<Chunk>> {
+        // NOTE: Check `pending` first: the writer thread records the byte offset of a chunk before
+        // removing it from there, never the other way around.
+        if let Some(chunk) = self.get_in_memory(&chunk_id) {
+            return Some(chunk);
+        }
+
+        let byte_offset = *self.byte_offset_per_chunk_id.lock().get(&chunk_id)?;
+
+        re_tracing::profile_function!();
+
+        let chunk = match self.decode_at(byte_offset) {
+            Ok(chunk) => Arc::new(chunk),
+            Err(err) => {
+                re_log::warn_once!("Failed to page chunk back in from {:?}: {err}", self.path);
+                return None;
+            }
+        };
+
+        self.cache.lock().insert(Arc::clone(&chunk));
+
+        Some(chunk)
+    }
+
+    /// Reads and decodes the chunk at `byte_offset`.
+    ///
+    /// No lock is held while hitting the disk: concurrent page-ins each get their own decoder.
+    fn decode_at(&self, byte_offset: u64) -> anyhow::Result<Chunk> {
+        let idle_decoder = self.idle_decoders.lock().pop();
+        let mut decoder = match idle_decoder {
+            Some(decoder) => decoder,
+            None => Decoder::new(
+                re_log_encoding::VersionPolicy::Error,
+                BufReader::new(File::open(&self.path)?),
+            )?,
+        };
+
+        let msg = decoder.decode_at(byte_offset)?;
+        self.idle_decoders.lock().push(decoder);
+
+        match msg {
+            LogMsg::ArrowMsg(_, msg) => Ok(Chunk::from_arrow_msg(&msg)?),
+            _ => anyhow::bail!("expected a chunk at offset {byte_offset}"),
+        }
+    }
+}
+
+impl SpillPrefetcher {
+    fn spawn(shared: Arc<SpillShared>) -> Self {
+        let (tx, rx) = mpsc::channel();
+
+        let handle = std::thread::Builder::new()
+            .name("chunk_spill_prefetch".to_owned())
+            .spawn(move || Self::run(&rx, &shared));
+
+        let handle = match handle {
+            Ok(handle) => Some(handle),
+            Err(err) => {
+                re_log::error!(
+                    "Failed to spawn chunk spill prefetch thread, spilled data is lost: {err}"
+                );
+                None
+            }
+        };
+
+        Self { tx, handle }
+    }
+
+    fn run(rx: &mpsc::Receiver<ChunkId>, shared: &SpillShared) {
+        for chunk_id in rx {
+            if let Some(chunk) = shared.load(chunk_id) {
+                shared.paged_in.lock().push(chunk);
+            }
+
+            // Only once it's been made available, see `ChunkSpill::is_paging_in`.
+            shared.prefetching.lock().remove(&chunk_id);
+        }
+    }
+}
+
+impl SpillWriter {
+    fn spawn(store_id: StoreId, shared: Arc<SpillShared>) -> Self {
+        let (tx, rx) = mpsc::channel();
+
+        let handle = std::thread::Builder::new()
+            .name("chunk_spill".to_owned())
+            .spawn(move || Self::run(&store_id, &rx, &shared));
+
+        let handle = match handle {
+            Ok(handle) => Some(handle),
+            Err(err) => {
+                re_log::error!("Failed to spawn chunk spill thread, data will be lost: {err}");
+                None
+            }
+        };
+
+        Self { tx, handle }
+    }
+
+    fn run(store_id: &StoreId, rx: &mpsc::Receiver<SpillCommand>, shared: &SpillShared) {
+        let mut encoder = None;
+
+        for cmd in rx {
+            match cmd {
+                SpillCommand::Write(chunks) => {
+                    if let Err(err) = Self::write(store_id, &mut encoder, &chunks, shared) {
+                        re_log::error_once!(
+                            "Failed to spill chunks to disk, data will be lost: {err}"
+                        );
+                    }
+
+                    // Whatever didn't make it to disk by now never will.
+                    let mut pending = shared.pending.lock();
+                    for chunk in &chunks {
+                        pending.remove(&chunk.id());
+                    }
+                }
+
+                SpillCommand::Flush(ack) => {
+                    ack.send(()).ok();
+                }
+            }
+        }
+    }
+
+    fn write(
+        store_id: &StoreId,
+        encoder: &mut Option<Encoder<BufWriter<File>>>,
+        chunks: &[Arc<Chunk>],
+        shared: &SpillShared,
+    ) -> anyhow::Result<()> {
+        re_tracing::profile_function!();
+
+        let path = &shared.path;
+        let encoder = match encoder {
+            Some(encoder) => encoder,
+            encoder @ None => {
+                std::fs::create_dir_all(path.parent().unwrap_or(Path::new(".")))?;
+                let file = File::create(path)?;
+                re_log::debug!("Spilling garbage collected chunks to {path:?}");
+                encoder.insert(Encoder::new(
+                    re_build_info::CrateVersion::LOCAL,
+                    re_log_encoding::EncodingOptions::MSGPACK_COMPRESSED,
+                    BufWriter::new(file),
+                )?)
+            }
+        };
+
+        let mut byte_offsets = Vec::with_capacity(chunks.len());
+        let mut result = Ok(());
+        for chunk in chunks {
+            let byte_offset = encoder.num_bytes_written();
+            let appended = chunk
+                .to_arrow_msg()
+                .map_err(anyhow::Error::from)
+                .and_then(|msg| {
+                    encoder
+                        .append(&LogMsg::ArrowMsg(store_id.clone(), msg))
+                        .map_err(anyhow::Error::from)
+                });
+            match appended {
+                Ok(_) => byte_offsets.push((chunk.id(), byte_offset)),
+                Err(err) => {
+                    result = Err(err);
+                    break;
+                }
+            }
+        }
+
+        // Only chunks that were fully flushed can be read back.
+        encoder.flush_blocking()?;
+        shared
+            .num_bytes_written
+            .store(encoder.num_bytes_written(), Ordering::Relaxed);
+        shared.byte_offset_per_chunk_id.lock().extend(byte_offsets);
+
+        result
+    }
+}
@@ -10,7 +10,10 @@ use re_chunk::{Chunk, ChunkId, RowId, TransportChunk};
 use re_log_types::{EntityPath, StoreId, StoreInfo, TimeInt, Timeline};
 use re_types_core::{ComponentDescriptor, ComponentName};
 
-use crate::{ChunkStoreChunkStats, ChunkStoreError, ChunkStoreResult};
+use crate::{
+    spill::ChunkSpill, ChunkStoreChunkStats, ChunkStoreDiff, ChunkStoreError, ChunkStoreEvent,
+    ChunkStoreResult,
+};
 
 // ---
 
@@ -73,6 +76,18 @@ pub struct ChunkStoreConfig {
     /// The default byte threshold is set to 8MiB, which is a reasonable unit of work when e.g.
     /// sending chunks over the network.
     pub chunk_max_rows_if_unsorted: u64,
+
+    /// If set, chunks evicted by the garbage collector are written to an `.rrd` file in this
+    /// directory (one file per store) rather than being dropped.
+    ///
+    /// Spilled chunks are transparently paged back in by the `*_relevant_chunks*` queries (e.g.
+    /// [`ChunkStore::latest_at_relevant_chunks`]), which means that history is never lost: only
+    /// the in-memory working set is bounded by the garbage collector.
+    /// They are written to disk by a background thread, so garbage collection never waits on I/O.
+    ///
+    /// Only applies to recordings, blueprints are never spilled.
+    /// Disabled by default.
+    pub spill_directory: Option<std::path::PathBuf>,
     //
     // TODO(cmc): It could make sense to have time-range-based thresholds in here, since the time
     // range covered by a chunk has direct effects on A) the complexity of backward walks and
@@ -110,6 +125,8 @@ impl ChunkStoreConfig {
         chunk_max_rows: 4096,
 
         chunk_max_rows_if_unsorted: 1024,
+
+        spill_directory: None,
     };
 
     /// [`Self::DEFAULT`], but with compaction entirely disabled.
@@ -132,6 +149,7 @@ impl ChunkStoreConfig {
         chunk_max_bytes: 0,
         chunk_max_rows: 0,
         chunk_max_rows_if_unsorted: 0,
+        spill_directory: None,
     };
 
     /// Environment variable to configure [`Self::enable_changelog`].
@@ -148,6 +166,9 @@ impl ChunkStoreConfig {
     // NOTE: Shared with the same env-var on the batcher side, for consistency.
     pub const ENV_CHUNK_MAX_ROWS_IF_UNSORTED: &'static str = "RERUN_CHUNK_MAX_ROWS_IF_UNSORTED";
 
+    /// Environment variable to configure [`Self::spill_directory`].
+    pub const ENV_CHUNK_SPILL_DIR: &'static str = "RERUN_CHUNK_SPILL_DIR";
+
     /// Creates a new `ChunkStoreConfig` using the default values, optionally overridden
     /// through the environment.
     ///
@@ -160,8 +181,8 @@ impl ChunkStoreConfig {
     /// Returns a copy of `self`, overriding existing fields with values from the environment if
     /// they are present.
     ///
-    /// See [`Self::ENV_STORE_ENABLE_CHANGELOG`], [`Self::ENV_CHUNK_MAX_BYTES`], [`Self::ENV_CHUNK_MAX_ROWS`]
-    /// and [`Self::ENV_CHUNK_MAX_ROWS_IF_UNSORTED`].
+    /// See [`Self::ENV_STORE_ENABLE_CHANGELOG`], [`Self::ENV_CHUNK_MAX_BYTES`], [`Self::ENV_CHUNK_MAX_ROWS`],
+    /// [`Self::ENV_CHUNK_MAX_ROWS_IF_UNSORTED`] and [`Self::ENV_CHUNK_SPILL_DIR`].
     pub fn apply_env(&self) -> ChunkStoreResult<Self> {
         let mut new = self.clone();
 
@@ -198,6 +219,10 @@ impl ChunkStoreConfig {
                 })?;
         }
 
+        if let Ok(s) = std::env::var(Self::ENV_CHUNK_SPILL_DIR) {
+            new.spill_directory = (!s.is_empty()).then(|| s.into());
+        }
+
         Ok(new)
     }
 }
@@ -209,6 +234,7 @@ fn chunk_store_config() {
     std::env::set_var("RERUN_CHUNK_MAX_BYTES", "42");
     std::env::set_var("RERUN_CHUNK_MAX_ROWS", "666");
     std::env::set_var("RERUN_CHUNK_MAX_ROWS_IF_UNSORTED", "999");
+    std::env::set_var("RERUN_CHUNK_SPILL_DIR", "/tmp/rerun_spill");
 
     let config = ChunkStoreConfig::from_env().unwrap();
 
@@ -217,6 +243,7 @@ fn chunk_store_config() {
         chunk_max_bytes: 42,
         chunk_max_rows: 666,
         chunk_max_rows_if_unsorted: 999,
+        spill_directory: Some("/tmp/rerun_spill".into()),
     };
 
     assert_eq!(expected, config);
@@ -466,6 +493,11 @@ pub struct ChunkStore {
 
     /// Monotonically increasing ID for store events.
     pub(crate) event_id: AtomicU64,
+
+    /// Where garbage collected chunks go, if [`ChunkStoreConfig::spill_directory`] is set.
+    ///
+    /// Created lazily on the first garbage collection.
+    pub(crate) spill: Option<ChunkSpill>,
 }
 
 impl Clone for ChunkStore {
@@ -490,6 +522,9 @@ impl Clone for ChunkStore {
             query_id: Default::default(),
             gc_id: Default::default(),
             event_id: Default::default(),
+
+            // The spill file belongs to the original store: clones only get to keep what's in memory.
+            spill: None,
         }
     }
 }
@@ -513,12 +548,16 @@ impl std::fmt::Display for ChunkStore {
             query_id: _,
             gc_id: _,
             event_id: _,
+            spill,
         } = self;
 
         f.write_str("ChunkStore {\n")?;
 
         f.write_str(&indent::indent_all_by(4, format!("id: {id}\n")))?;
         f.write_str(&indent::indent_all_by(4, format!("config: {config:?}\n")))?;
+        if let Some(spill) = spill {
+            f.write_str(&indent::indent_all_by(4, format!("spill: {spill:?}\n")))?;
+        }
 
         f.write_str(&indent::indent_all_by(4, "stats: {\n"))?;
         f.write_str(&indent::indent_all_by(
@@ -570,6 +609,7 @@ impl ChunkStore {
             query_id: AtomicU64::new(0),
             gc_id: 0,
             event_id: AtomicU64::new(0),
+            spill: None,
         }
     }
 
@@ -615,6 +655,62 @@ impl ChunkStore {
         &self.config
     }
 
+    /// Whether garbage collected chunks are spilled to disk rather than dropped.
+    ///
+    /// See [`ChunkStoreConfig::spill_directory`].
+    #[inline]
+    pub fn spills_to_disk(&self) -> bool {
+        self.config.spill_directory.is_some() && self.id.kind == re_log_types::StoreKind::Recording
+    }
+
+    /// Blocks until every chunk spilled so far has been written to disk.
+    ///
+    /// Spilling happens in the background: this is never needed for correctness, spilled chunks
+    /// can always be queried right away.
+    pub fn flush_spill_blocking(&self) {
+        if let Some(spill) = &self.spill {
+            spill.flush_blocking();
+        }
+    }
+
+    /// Whether spilled chunks needed by earlier queries are still being read back from disk.
+    ///
+    /// See [`Self::take_paged_in_events`].
+    pub fn is_paging_in(&self) -> bool {
+        self.spill
+            .as_ref()
+            .is_some_and(|spill| spill.is_paging_in())
+    }
+
+    /// Takes the spilled chunks that were read back from disk since the last call, in the form of
+    /// addition [`ChunkStoreEvent`]s.
+    ///
+    /// Queries never wait on the disk: they only return the spilled chunks that are in memory, and
+    /// have the others read back in the background. Query caches must be told about these chunks
+    /// once they're there, since they are missing from the results of the earlier queries.
+    ///
+    /// These events are not sent to the store subscribers: as far as the store is concerned, the
+    /// chunks are still garbage collected.
+    pub fn take_paged_in_events(&self) -> Vec<ChunkStoreEvent> {
+        let Some(spill) = &self.spill else {
+            return Vec::new();
+        };
+
+        let generation = self.generation();
+        spill
+            .take_paged_in()
+            .into_iter()
+            .map(|chunk| ChunkStoreEvent {
+                store_id: self.id.clone(),
+                store_generation: generation.clone(),
+                event_id: self
+                    .event_id
+                    .fetch_add(1, std::sync::atomic::Ordering::Relaxed),
+                diff: ChunkStoreDiff::addition(chunk, None),
+            })
+            .collect()
+    }
+
     /// Iterate over all chunks in the store, in ascending [`ChunkId`] order.
     #[inline]
     pub fn iter_chunks(&self) -> impl Iterator<Item = &Arc<Chunk>> + '_ {
@@ -437,6 +437,7 @@ impl ChunkStore {
                     chunk_max_bytes,
                     chunk_max_rows,
                     chunk_max_rows_if_unsorted,
+                    spill_directory: _,
                 } = store.config;
 
                 *candidates_below_threshold
@@ -569,10 +570,17 @@ impl ChunkStore {
             query_id: _,
             gc_id: _,
             event_id,
+            spill,
         } = self;
 
         per_column_metadata.remove(entity_path);
 
+        // Spilled chunks are reported as well, since they could still be paged back in until now.
+        let dropped_spilled_chunks = spill
+            .as_mut()
+            .map(|spill| spill.drop_entity_path(entity_path))
+            .unwrap_or_default();
+
         let dropped_static_chunks = {
             let dropped_static_chunk_ids: BTreeSet<_> = static_chunk_ids_per_entity
                 .remove(entity_path)
@@ -634,6 +642,7 @@ impl ChunkStore {
             let events: Vec<_> = dropped_static_chunks
                 .into_iter()
                 .chain(dropped_temporal_chunks)
+                .chain(dropped_spilled_chunks)
                 .map(ChunkStoreDiff::deletion)
                 .map(|diff| ChunkStoreEvent {
                     store_id: id.clone(),
@@ -41,6 +41,15 @@ fn query_latest_array(
         .map(|array| (data_time, row_id, array))
 }
 
+/// Queries never wait on spilled chunks to be read back from disk: runs `query` again once they are.
+fn paged_in<T>(store: &ChunkStore, query: impl Fn(&ChunkStore) -> T) -> T {
+    query(store);
+    while store.is_paging_in() {
+        std::thread::sleep(std::time::Duration::from_millis(1));
+    }
+    query(store)
+}
+
 // ---
 
 #[test]
@@ -403,6 +412,146 @@ fn protected_time_ranges() -> anyhow::Result<()> {
     Ok(())
 }
 
+#[test]
+fn keep_latest_time_span() -> anyhow::Result<()> {
+    re_log::setup_logging();
+
+    let entity_path = EntityPath::from("this/that");
+
+    let frame1 = TimeInt::new_temporal(1);
+    let frame2 = TimeInt::new_temporal(2);
+    let frame3 = TimeInt::new_temporal(3);
+    let frame4 = TimeInt::new_temporal(4);
+
+    let (indices1, colors1) = (MyIndex::from_iter(0..3), MyColor::from_iter(0..3));
+    let chunk1 = Arc::new(
+        Chunk::builder(entity_path.clone())
+            .with_component_batches(
+                RowId::new(),
+                [build_frame_nr(frame1)],
+                [&indices1 as _, &colors1 as _],
+            )
+            .build()?,
+    );
+
+    let points2 = MyPoint::from_iter(0..3);
+    let chunk2 = Arc::new(
+        Chunk::builder(entity_path.clone())
+            .with_component_batches(
+                RowId::new(),
+                [build_frame_nr(frame2)],
+                [&indices1 as _, &points2 as _],
+            )
+            .build()?,
+    );
+
+    let points3 = MyPoint::from_iter(0..10);
+    let chunk3 = Arc::new(
+        Chunk::builder(entity_path.clone())
+            .with_component_batches(RowId::new(), [build_frame_nr(frame3)], [&points3 as _])
+            .build()?,
+    );
+
+    let colors4 = MyColor::from_iter(0..5);
+    let chunk4 = Arc::new(
+        Chunk::builder(entity_path.clone())
+            .with_component_batches(RowId::new(), [build_frame_nr(frame4)], [&colors4 as _])
+            .build()?,
+    );
+
+    // Not on `frame_nr` at all: must never be touched.
+    let chunk5 = Arc::new(
+        Chunk::builder(entity_path.clone())
+            .with_component_batches(
+                RowId::new(),
+                [build_log_time(Time::from_seconds_since_epoch(0.0))],
+                [&colors1 as _],
+            )
+            .build()?,
+    );
+
+    let new_store = || -> anyhow::Result<ChunkStore> {
+        let mut store = ChunkStore::new(
+            re_log_types::StoreId::random(re_log_types::StoreKind::Recording),
+            ChunkStoreConfig::COMPACTION_DISABLED,
+        );
+        for chunk in [&chunk1, &chunk2, &chunk3, &chunk4, &chunk5] {
+            store.insert_chunk(chunk)?;
+        }
+        Ok(store)
+    };
+
+    fn keep_latest(time_span: i64, protect_latest: usize) -> GarbageCollectionOptions {
+        GarbageCollectionOptions {
+            target: GarbageCollectionTarget::KeepLatestTimeSpan {
+                timeline: Timeline::new_sequence("frame_nr"),
+                time_span,
+            },
+            protect_latest,
+            ..GarbageCollectionOptions::gc_everything()
+        }
+    }
+
+    let dropped_chunk_ids = |store: &mut ChunkStore, options: &GarbageCollectionOptions| {
+        let (events, _) = store.gc(options);
+        assert!(events
+            .iter()
+            .all(|event| event.kind == ChunkStoreDiffKind::Deletion));
+        let mut chunk_ids: Vec<ChunkId> = events.iter().map(|event| event.chunk.id()).collect();
+        chunk_ids.sort();
+        chunk_ids
+    };
+
+    let sorted = |mut chunk_ids: Vec<ChunkId>| {
+        chunk_ids.sort();
+        chunk_ids
+    };
+
+    {
+        let mut store = new_store()?;
+        eprintln!("{store}");
+
+        // Latest time is 4, so the cutoff is 3: everything strictly before that goes.
+        assert_eq!(
+            sorted(vec![chunk1.id(), chunk2.id()]),
+            dropped_chunk_ids(&mut store, &keep_latest(1, 0)),
+        );
+
+        // Nothing left to collect.
+        assert!(dropped_chunk_ids(&mut store, &keep_latest(1, 0)).is_empty());
+    }
+
+    {
+        let mut store = new_store()?;
+
+        // The span covers the whole timeline.
+        assert!(dropped_chunk_ids(&mut store, &keep_latest(10, 0)).is_empty());
+    }
+
+    {
+        let mut store = new_store()?;
+
+        // `chunk2` holds the latest `MyIndex`, and is therefore protected.
+        assert_eq!(
+            vec![chunk1.id()],
+            dropped_chunk_ids(&mut store, &keep_latest(1, 1)),
+        );
+    }
+
+    {
+        let mut store = new_store()?;
+
+        // Only `chunk4` is left on `frame_nr`, `chunk5` is untouched.
+        assert_eq!(
+            sorted(vec![chunk1.id(), chunk2.id(), chunk3.id()]),
+            dropped_chunk_ids(&mut store, &keep_latest(0, 0)),
+        );
+        assert!(store.chunk(&chunk5.id()).is_some());
+    }
+
+    Ok(())
+}
+
 // ---
 
 #[test]
@@ -555,3 +704,169 @@ fn manual_drop_entity_path() -> anyhow::Result<()> {
 
     Ok(())
 }
+
+#[test]
+fn spill_to_disk() -> anyhow::Result<()> {
+    re_log::setup_logging();
+
+    let spill_directory = tempfile::tempdir()?;
+
+    let entity_path = EntityPath::from("this/that");
+    let timeline_frame_nr = Timeline::new_sequence("frame_nr");
+
+    let mut store = ChunkStore::new(
+        re_log_types::StoreId::random(re_log_types::StoreKind::Recording),
+        ChunkStoreConfig {
+            spill_directory: Some(spill_directory.path().to_owned()),
+            ..ChunkStoreConfig::COMPACTION_DISABLED
+        },
+    );
+    assert!(store.spills_to_disk());
+
+    let chunks = (1..=4)
+        .map(|frame_nr| {
+            let points = MyPoint::from_iter(0..frame_nr as u32);
+            Chunk::builder(entity_path.clone())
+                .with_component_batches(
+                    RowId::new(),
+                    [build_frame_nr(TimeInt::new_temporal(frame_nr))],
+                    [&points as _],
+                )
+                .build()
+                .map(Arc::new)
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+    for chunk in &chunks {
+        store.insert_chunk(chunk)?;
+    }
+
+    let (events, _) = store.gc(&GarbageCollectionOptions {
+        protect_latest: 1,
+        ..GarbageCollectionOptions::gc_everything()
+    });
+    assert_eq!(3, events.len());
+    assert_eq!(1, store.num_chunks());
+
+    // Spilled chunks are written in the background, but can be queried right away.
+    let query = LatestAtQuery::new(timeline_frame_nr, 1);
+    assert!(query_latest_array(&store, &entity_path, MyPoint::name(), &query).is_some());
+
+    store.flush_spill_blocking();
+    assert_eq!(
+        1,
+        std::fs::read_dir(spill_directory.path())?.count(),
+        "expected exactly one spill file"
+    );
+
+    // Queries don't wait on the disk: spilled chunks are read back in the background, and
+    // reported once they're there.
+    let query = LatestAtQuery::new(timeline_frame_nr, 1);
+    assert!(query_latest_array(&store, &entity_path, MyPoint::name(), &query).is_none());
+    assert!(paged_in(&store, |store| {
+        query_latest_array(store, &entity_path, MyPoint::name(), &query)
+    })
+    .is_some());
+    let events = store.take_paged_in_events();
+    assert_eq!(1, events.len());
+    assert_eq!(ChunkStoreDiffKind::Addition, events[0].kind);
+    assert_eq!(chunks[0].id(), events[0].chunk.id());
+    assert!(store.take_paged_in_events().is_empty());
+
+    // Latest-at queries page the relevant spilled chunks back in.
+    for frame_nr in 1..=4 {
+        let query = LatestAtQuery::new(timeline_frame_nr, frame_nr);
+        let (data_time, row_id, array) = paged_in(&store, |store| {
+            query_latest_array(store, &entity_path, MyPoint::name(), &query)
+        })
+        .unwrap();
+
+        let expected = &chunks[frame_nr as usize - 1];
+        assert_eq!(TimeInt::new_temporal(frame_nr), data_time);
+        assert_eq!(expected.row_id_range().unwrap().0, row_id);
+        assert_eq!(frame_nr as usize, array.len());
+    }
+
+    // Nothing was logged before frame #1, spilled or not.
+    let query = LatestAtQuery::new(timeline_frame_nr, 0);
+    assert!(paged_in(&store, |store| {
+        query_latest_array(store, &entity_path, MyPoint::name(), &query)
+    })
+    .is_none());
+
+    // Range queries too.
+    let query = re_chunk::RangeQuery::new(
+        timeline_frame_nr,
+        ResolvedTimeRange::new(TimeInt::new_temporal(2), TimeInt::new_temporal(4)),
+    );
+    let mut chunk_ids = paged_in(&store, |store| {
+        store.range_relevant_chunks(&query, &entity_path, MyPoint::name())
+    })
+    .into_iter()
+    .map(|chunk| chunk.id())
+    .collect::<Vec<_>>();
+    chunk_ids.sort();
+    let mut expected_chunk_ids = chunks[1..]
+        .iter()
+        .map(|chunk| chunk.id())
+        .collect::<Vec<_>>();
+    expected_chunk_ids.sort();
+    assert_eq!(expected_chunk_ids, chunk_ids);
+
+    // As well as queries for all components at once.
+    let mut chunk_ids = paged_in(&store, |store| {
+        store.range_relevant_chunks_for_all_components(&query, &entity_path)
+    })
+    .into_iter()
+    .map(|chunk| chunk.id())
+    .collect::<Vec<_>>();
+    chunk_ids.sort();
+    assert_eq!(expected_chunk_ids, chunk_ids);
+
+    let query = LatestAtQuery::new(timeline_frame_nr, 2);
+    let chunk_ids = paged_in(&store, |store| {
+        store.latest_at_relevant_chunks_for_all_components(&query, &entity_path)
+    })
+    .into_iter()
+    .map(|chunk| chunk.id())
+    .collect::<Vec<_>>();
+    assert_eq!(vec![chunks[1].id()], chunk_ids);
+
+    // Dropping a time range drops the spilled data within it too.
+    let events = store.drop_time_range(
+        &timeline_frame_nr,
+        ResolvedTimeRange::new(TimeInt::new_temporal(2), TimeInt::new_temporal(3)),
+    );
+    let mut chunk_ids = events
+        .iter()
+        .map(|event| {
+            assert_eq!(ChunkStoreDiffKind::Deletion, event.kind);
+            event.chunk.id()
+        })
+        .collect::<Vec<_>>();
+    chunk_ids.sort();
+    let mut expected_chunk_ids = vec![chunks[1].id(), chunks[2].id()];
+    expected_chunk_ids.sort();
+    assert_eq!(expected_chunk_ids, chunk_ids);
+
+    let query = LatestAtQuery::new(timeline_frame_nr, 3);
+    let (data_time, _, _) = paged_in(&store, |store| {
+        query_latest_array(store, &entity_path, MyPoint::name(), &query)
+    })
+    .unwrap();
+    assert_eq!(TimeInt::new_temporal(1), data_time);
+
+    // Dropping an entity drops its spilled data too, which gets reported as well.
+    let events = store.drop_entity_path(&entity_path);
+    assert_eq!(2, events.len());
+    let query = LatestAtQuery::new(timeline_frame_nr, 2);
+    assert!(paged_in(&store, |store| {
+        query_latest_array(store, &entity_path, MyPoint::name(), &query)
+    })
+    .is_none());
+
+    // The spill file is a cache: it goes away with the store.
+    drop(store);
+    assert_eq!(0, std::fs::read_dir(spill_directory.path())?.count());
+
+    Ok(())
+}
@@ -6,28 +6,35 @@ use once_cell::sync::Lazy;
 
 use re_chunk::{Chunk, ChunkResult};
 use re_log_types::{ArrowMsg, EntityPath, LogMsg, TimePoint};
+use re_types::{ComponentBatch as _, LoggableBatch as _};
 
 // ----------------------------------------------------------------------------
 
 mod load_file;
+mod load_uri;
 mod loader_archetype;
 mod loader_directory;
+mod loader_mcap;
+mod loader_ply;
+mod loader_point_cloud;
 mod loader_rrd;
 
 #[cfg(not(target_arch = "wasm32"))]
 mod loader_external;
 
 pub use self::{
-    load_file::load_from_file_contents, loader_archetype::ArchetypeLoader,
-    loader_directory::DirectoryLoader, loader_rrd::RrdLoader,
+    load_file::load_from_file_contents, load_uri::load_from_uri, loader_archetype::ArchetypeLoader,
+    loader_directory::DirectoryLoader, loader_mcap::McapLoader,
+    loader_point_cloud::PointCloudLoader, loader_rrd::RrdLoader,
 };
 
 #[cfg(not(target_arch = "wasm32"))]
 pub use self::{
     load_file::load_from_path,
     loader_external::{
-        iter_external_loaders, ExternalLoader, EXTERNAL_DATA_LOADER_INCOMPATIBLE_EXIT_CODE,
-        EXTERNAL_DATA_LOADER_PREFIX,
+        iter_external_loaders, ExternalLoader, ExternalLoaderManifest, ExternalLoaderStatus,
+        EXTERNAL_DATA_LOADER_DESCRIBE_FLAG, EXTERNAL_DATA_LOADER_INCOMPATIBLE_EXIT_CODE,
+        EXTERNAL_DATA_LOADER_PREFIX, EXTERNAL_DATA_LOADER_STATUS_PREFIX, EXTERNAL_LOADER_MANIFESTS,
     },
 };
 
@@ -162,18 +169,17 @@ pub type DataLoaderName = String;
 
 /// A [`DataLoader`] loads data from a file path and/or a file's contents.
 ///
-/// Files can be loaded in 3 different ways:
+/// Files can be loaded in 4 different ways:
 /// - via the Rerun CLI (`rerun myfile.jpeg`),
 /// - using drag-and-drop,
-/// - using the open dialog in the Rerun Viewer.
+/// - using the open dialog in the Rerun Viewer,
+/// - from a URI, e.g. `rerun http://example.com/scan.ply` or the `?url=` parameter of the Web Viewer.
 ///
 /// All these file loading methods support loading a single file, many files at once, or even
 /// folders.
 /// ⚠ Drag-and-drop of folders does not yet work on the web version of Rerun Viewer ⚠
 ///
-/// We only support loading files from the local filesystem at the moment, and consequently only
-/// accept filepaths as input.
-/// [There are plans to make this generic over any URI](https://github.com/rerun-io/rerun/issues/4525).
+/// See [`DataLoader::load_from_uri`] for how URIs are dispatched.
 ///
 /// Rerun comes with a few [`DataLoader`]s by default:
 /// - [`RrdLoader`] for [Rerun files].
@@ -182,6 +188,8 @@ pub type DataLoaderName = String;
 ///     - [Images]
 ///     - [Point clouds]
 ///     - [Text files]
+/// - [`McapLoader`] for [MCAP files], e.g. ROS 2 bags.
+/// - [`PointCloudLoader`] for [large point clouds], which are streamed in bounded-size chunks.
 /// - [`DirectoryLoader`] for recursively loading folders.
 /// - [`ExternalLoader`], which looks for user-defined data loaders in $PATH.
 ///
@@ -209,9 +217,8 @@ pub type DataLoaderName = String;
 /// [Images]: crate::SUPPORTED_IMAGE_EXTENSIONS
 /// [Point clouds]: crate::SUPPORTED_POINT_CLOUD_EXTENSIONS
 /// [Text files]: crate::SUPPORTED_TEXT_EXTENSIONS
-//
-// TODO(#4525): `DataLoader`s should support arbitrary URIs
-// TODO(#4527): Web Viewer `?url` parameter should accept anything our `DataLoader`s support
+/// [MCAP files]: crate::SUPPORTED_MCAP_EXTENSIONS
+/// [large point clouds]: crate::SUPPORTED_STREAMED_POINT_CLOUD_EXTENSIONS
 pub trait DataLoader: Send + Sync {
     /// Name of the [`DataLoader`].
     ///
@@ -280,6 +287,38 @@ pub trait DataLoader: Send + Sync {
         contents: std::borrow::Cow<'_, [u8]>,
         tx: std::sync::mpsc::Sender<LoadedData>,
     ) -> Result<(), DataLoaderError>;
+
+    /// Loads data from an arbitrary URI and sends it to `tx`.
+    ///
+    /// This is generally called when opening URLs, e.g. `http://example.com/scan.ply` or
+    /// `file:///tmp/scan.ply?frame=3`: implementers are free to interpret any part of the URI,
+    /// including its query parameters.
+    ///
+    /// The default implementation forwards `file://` URIs to [`Self::load_from_path`] (on native
+    /// platforms), and is incompatible with anything else.
+    /// If _all_ [`DataLoader`]s are incompatible with an `http(s)://` URL, its contents are
+    /// downloaded and handed to [`Self::load_from_file_contents`] instead, with the last segment of
+    /// the URL's path as the file name.
+    ///
+    /// ## Error handling
+    ///
+    /// Same as [`Self::load_from_path`].
+    #[cfg_attr(target_arch = "wasm32", allow(unused_variables))]
+    fn load_from_uri(
+        &self,
+        settings: &DataLoaderSettings,
+        uri: &url::Url,
+        tx: std::sync::mpsc::Sender<LoadedData>,
+    ) -> Result<(), DataLoaderError> {
+        #[cfg(not(target_arch = "wasm32"))]
+        if uri.scheme() == "file" {
+            if let Ok(path) = uri.to_file_path() {
+                return self.load_from_path(settings, path, tx);
+            }
+        }
+
+        Err(DataLoaderError::Incompatible(uri.as_str().into()))
+    }
 }
 
 /// Errors that might happen when loading data through a [`DataLoader`].
@@ -362,6 +401,8 @@ static BUILTIN_LOADERS: Lazy<Vec<Arc<dyn DataLoader>>> = Lazy::new(|| {
     vec![
         Arc::new(RrdLoader) as Arc<dyn DataLoader>,
         Arc::new(ArchetypeLoader),
+        Arc::new(McapLoader),
+        Arc::new(PointCloudLoader),
         Arc::new(DirectoryLoader),
         #[cfg(not(target_arch = "wasm32"))]
         Arc::new(ExternalLoader),
@@ -404,6 +445,19 @@ pub(crate) fn extension(path: &std::path::Path) -> String {
         .to_string()
 }
 
+/// Serializes all the components of an archetype, so that extra columns can be added to the row.
+pub(crate) fn archetype_row(
+    arch: &dyn re_types::AsComponents,
+) -> Result<Vec<(re_types::ComponentDescriptor, Box<dyn arrow2::array::Array>)>, DataLoaderError> {
+    arch.as_component_batches()
+        .iter()
+        .map(|batch| {
+            let array = batch.to_arrow2().map_err(re_chunk::ChunkError::from)?;
+            Ok((batch.descriptor().into_owned(), array))
+        })
+        .collect()
+}
+
 // ----------------------------------------------------------------------------
 
 // …given that all feature flags are turned on for the `image` crate.
@@ -418,11 +472,15 @@ pub const SUPPORTED_VIDEO_EXTENSIONS: &[&str] = &["mp4"];
 
 pub const SUPPORTED_MESH_EXTENSIONS: &[&str] = &["glb", "gltf", "obj", "stl"];
 
-// TODO(#4532): `.ply` data loader should support 2D point cloud & meshes
 pub const SUPPORTED_POINT_CLOUD_EXTENSIONS: &[&str] = &["ply"];
 
+/// Point clouds that are potentially too large to fit in memory, and are thus streamed.
+pub const SUPPORTED_STREAMED_POINT_CLOUD_EXTENSIONS: &[&str] = &["las", "laz", "pcd"];
+
 pub const SUPPORTED_RERUN_EXTENSIONS: &[&str] = &["rbl", "rrd"];
 
+pub const SUPPORTED_MCAP_EXTENSIONS: &[&str] = &["mcap"];
+
 // TODO(#4555): Add catch-all builtin `DataLoader` for text files
 pub const SUPPORTED_TEXT_EXTENSIONS: &[&str] = &["txt", "md"];
 
@@ -434,6 +492,8 @@ pub fn supported_extensions() -> impl Iterator<Item = &'static str> {
         .chain(SUPPORTED_VIDEO_EXTENSIONS)
         .chain(SUPPORTED_MESH_EXTENSIONS)
         .chain(SUPPORTED_POINT_CLOUD_EXTENSIONS)
+        .chain(SUPPORTED_STREAMED_POINT_CLOUD_EXTENSIONS)
+        .chain(SUPPORTED_MCAP_EXTENSIONS)
         .chain(SUPPORTED_TEXT_EXTENSIONS)
         .copied()
 }
@@ -444,6 +504,8 @@ pub fn is_supported_file_extension(extension: &str) -> bool {
         || SUPPORTED_VIDEO_EXTENSIONS.contains(&extension)
         || SUPPORTED_MESH_EXTENSIONS.contains(&extension)
         || SUPPORTED_POINT_CLOUD_EXTENSIONS.contains(&extension)
+        || SUPPORTED_STREAMED_POINT_CLOUD_EXTENSIONS.contains(&extension)
         || SUPPORTED_RERUN_EXTENSIONS.contains(&extension)
+        || SUPPORTED_MCAP_EXTENSIONS.contains(&extension)
         || SUPPORTED_TEXT_EXTENSIONS.contains(&extension)
 }
@@ -0,0 +1,309 @@
+use std::borrow::Cow;
+
+use re_log_types::{FileSource, LogMsg};
+use re_smart_channel::Sender;
+
+use crate::{DataLoaderError, LoadedData};
+
+// ---
+
+/// Loads the given `uri` using all [`crate::DataLoader`]s available.
+///
+/// Every loader is first handed the URI itself, see [`crate::DataLoader::load_from_uri`].
+/// If none of them is compatible with it and it is an `http(s)://` URL, its contents are
+/// downloaded in the background and then loaded just like with [`crate::load_from_file_contents`].
+///
+/// Synchronously checks whether the URI is valid and can be loaded. Beyond that, all
+/// errors are asynchronous and handled directly by the [`crate::DataLoader`]s themselves
+/// (i.e. they're logged).
+pub fn load_from_uri(
+    settings: &crate::DataLoaderSettings,
+    file_source: FileSource,
+    uri: &str,
+    // NOTE: This channel must be unbounded since we serialize all operations when running on wasm.
+    tx: &Sender<LogMsg>,
+) -> Result<(), DataLoaderError> {
+    re_tracing::profile_function!(uri);
+
+    let url = url::Url::parse(uri).map_err(|err| anyhow::anyhow!("Invalid URI {uri:?}: {err}"))?;
+
+    #[cfg(not(target_arch = "wasm32"))]
+    if url.scheme() == "file" {
+        let path = url
+            .to_file_path()
+            .map_err(|()| anyhow::anyhow!("Invalid file URI {uri:?}"))?;
+        if !path.exists() {
+            return Err(std::io::Error::new(
+                std::io::ErrorKind::NotFound,
+                format!("path does not exist: {path:?}"),
+            )
+            .into());
+        }
+    }
+
+    re_log::info!("Loading {uri}…");
+
+    match load(settings, &url) {
+        Ok(rx) => {
+            crate::load_file::send(settings.clone(), file_source, rx, tx);
+            Ok(())
+        }
+
+        Err(err) if err.is_incompatible() && matches!(url.scheme(), "http" | "https") => {
+            download_and_load(settings.clone(), file_source, url, tx.clone());
+            Ok(())
+        }
+
+        Err(err) => Err(err),
+    }
+}
+
+/// Downloads the contents of `url`, then hands them over to all [`crate::DataLoader`]s.
+fn download_and_load(
+    settings: crate::DataLoaderSettings,
+    file_source: FileSource,
+    url: url::Url,
+    tx: Sender<LogMsg>,
+) {
+    re_log::debug!("Downloading {url}…");
+
+    // The loaders expect a file name, e.g. to look at its extension.
+    let filepath = std::path::PathBuf::from(
+        url.path_segments()
+            .and_then(|mut segments| segments.next_back())
+            .unwrap_or_default(),
+    );
+
+    ehttp::fetch(ehttp::Request::get(url.as_str()), move |response| {
+        let contents = response.and_then(|response| {
+            if response.ok {
+                Ok(response.bytes)
+            } else {
+                Err(format!("{} {}", response.status, response.status_text))
+            }
+        });
+
+        let result = contents
+            .map_err(|err| {
+                DataLoaderError::from(anyhow::anyhow!("Failed to download {url}: {err}"))
+            })
+            .and_then(|contents| {
+                crate::load_from_file_contents(
+                    &settings,
+                    file_source,
+                    &filepath,
+                    Cow::Owned(contents),
+                    &tx,
+                )
+            });
+
+        if let Err(err) = result {
+            re_log::error!("Failed to load {url}: {err}");
+            tx.quit(Some(Box::new(err))).ok();
+        }
+    });
+}
+
+/// Hands `url` over to all available [`crate::DataLoader`]s.
+///
+/// On success, returns a channel with all the [`LoadedData`]:
+/// - On native, this is filled asynchronously from other threads.
+/// - On wasm, this is pre-filled synchronously.
+///
+/// There is only one way this function can return an error: not a single [`crate::DataLoader`]
+/// (whether it is builtin, custom or external) was capable of loading the data, in which case
+/// [`DataLoaderError::Incompatible`] will be returned.
+#[cfg(not(target_arch = "wasm32"))]
+fn load(
+    settings: &crate::DataLoaderSettings,
+    url: &url::Url,
+) -> Result<std::sync::mpsc::Receiver<LoadedData>, DataLoaderError> {
+    re_tracing::profile_function!(url.as_str());
+
+    let (tx_loader, rx_loader) = std::sync::mpsc::channel();
+
+    let any_compatible_loader = {
+        #[derive(PartialEq, Eq)]
+        struct CompatibleLoaderFound;
+        let (tx_feedback, rx_feedback) = std::sync::mpsc::channel::<CompatibleLoaderFound>();
+
+        // Prevent passing RRD URIs to external (and other) loaders.
+        // See <https://github.com/rerun-io/rerun/issues/6530>.
+        let loaders = {
+            use crate::DataLoader as _;
+            use rayon::iter::Either;
+
+            let extension = crate::extension(std::path::Path::new(url.path()));
+            if crate::SUPPORTED_RERUN_EXTENSIONS.contains(&extension.as_str()) {
+                Either::Left(
+                    crate::iter_loaders().filter(|loader| loader.name() == crate::RrdLoader.name()),
+                )
+            } else {
+                Either::Right(crate::iter_loaders())
+            }
+        };
+
+        for loader in loaders {
+            let settings = settings.clone();
+            let url = url.clone();
+
+            let tx_loader = tx_loader.clone();
+            let tx_feedback = tx_feedback.clone();
+
+            rayon::spawn(move || {
+                re_tracing::profile_scope!("inner", loader.name());
+
+                if let Err(err) = loader.load_from_uri(&settings, &url, tx_loader) {
+                    if err.is_incompatible() {
+                        return;
+                    }
+                    re_log::error!(%url, loader = loader.name(), %err, "Failed to load data from URI");
+                }
+
+                re_log::debug!(loader = loader.name(), %url, "compatible loader found");
+                tx_feedback.send(CompatibleLoaderFound).ok();
+            });
+        }
+
+        re_tracing::profile_wait!("compatible_loader");
+
+        drop(tx_feedback);
+
+        rx_feedback.recv() == Ok(CompatibleLoaderFound)
+    };
+
+    // Implicitly closing `tx_loader`!
+
+    if any_compatible_loader {
+        Ok(rx_loader)
+    } else {
+        Err(DataLoaderError::Incompatible(url.as_str().into()))
+    }
+}
+
+/// Hands `url` over to all available [`crate::DataLoader`]s.
+///
+/// On success, returns a channel (pre-filled synchronously) with all the [`LoadedData`].
+///
+/// There is only one way this function can return an error: not a single [`crate::DataLoader`]
+/// (whether it is builtin, custom or external) was capable of loading the data, in which case
+/// [`DataLoaderError::Incompatible`] will be returned.
+#[cfg(target_arch = "wasm32")]
+fn load(
+    settings: &crate::DataLoaderSettings,
+    url: &url::Url,
+) -> Result<std::sync::mpsc::Receiver<LoadedData>, DataLoaderError> {
+    re_tracing::profile_function!(url.as_str());
+
+    let (tx_loader, rx_loader) = std::sync::mpsc::channel();
+
+    let any_compatible_loader = crate::iter_loaders()
+        .map(|loader| {
+            if let Err(err) = loader.load_from_uri(settings, url, tx_loader.clone()) {
+                if err.is_incompatible() {
+                    return false;
+                }
+                re_log::error!(%url, loader = loader.name(), %err, "Failed to load data from URI");
+            }
+
+            true
+        })
+        .fold(false, |any_compatible, is_compatible| {
+            any_compatible || is_compatible
+        });
+
+    // Implicitly closing `tx_loader`!
+
+    if any_compatible_loader {
+        Ok(rx_loader)
+    } else {
+        Err(DataLoaderError::Incompatible(url.as_str().into()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use re_log_types::{EntityPath, LogMsg};
+    use re_smart_channel::{SmartChannelSource, SmartMessagePayload, SmartMessageSource};
+
+    use super::*;
+
+    /// Serves `contents` for a single request, returns the address of the server.
+    fn serve_once(contents: &'static str) -> std::net::SocketAddr {
+        let server = tiny_http::Server::http("127.0.0.1:0").unwrap();
+        let addr = server.server_addr().to_ip().unwrap();
+        std::thread::spawn(move || {
+            let request = server.recv().unwrap();
+            request
+                .respond(tiny_http::Response::from_string(contents))
+                .unwrap();
+        });
+        addr
+    }
+
+    /// Loads `uri`, returns the entity paths of all the chunks that were loaded, or the error
+    /// the loading ended with.
+    fn load_entity_paths(uri: &str) -> Result<Vec<EntityPath>, String> {
+        let (tx, rx) = re_smart_channel::smart_channel(
+            SmartMessageSource::File(uri.into()),
+            SmartChannelSource::File(uri.into()),
+        );
+        let settings = crate::DataLoaderSettings::recommended(re_log_types::StoreId::random(
+            re_log_types::StoreKind::Recording,
+        ));
+        load_from_uri(&settings, FileSource::Uri, uri, &tx).unwrap();
+        drop(tx);
+
+        let mut entity_paths = Vec::new();
+        while let Ok(msg) = rx.recv() {
+            match msg.payload {
+                SmartMessagePayload::Msg(LogMsg::ArrowMsg(_, arrow_msg)) => {
+                    let chunk = re_chunk::Chunk::from_arrow_msg(&arrow_msg).unwrap();
+                    entity_paths.push(chunk.entity_path().clone());
+                }
+                SmartMessagePayload::Quit(Some(err)) => return Err(err.to_string()),
+                SmartMessagePayload::Quit(None) => break,
+                _ => {}
+            }
+        }
+
+        Ok(entity_paths)
+    }
+
+    #[test]
+    fn load_http_uri() {
+        let addr = serve_once("Hello from the other side");
+
+        let entity_paths =
+            load_entity_paths(&format!("http://{addr}/docs/notes.txt?version=2")).unwrap();
+
+        assert_eq!(
+            vec![EntityPath::from_file_path(std::path::Path::new(
+                "notes.txt"
+            ))],
+            entity_paths
+        );
+    }
+
+    #[test]
+    fn load_http_uri_incompatible() {
+        let addr = serve_once("Not something anyone can load");
+
+        let err = load_entity_paths(&format!("http://{addr}/data.unknown")).unwrap_err();
+
+        assert!(err.contains("data.unknown"), "{err}");
+    }
+
+    #[test]
+    fn load_file_uri() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("notes.txt");
+        std::fs::write(&path, "Hello from the filesystem").unwrap();
+
+        let mut url = url::Url::from_file_path(&path).unwrap();
+        url.set_query(Some("version=2"));
+        let entity_paths = load_entity_paths(url.as_str()).unwrap();
+
+        assert_eq!(vec![EntityPath::from_file_path(&path)], entity_paths);
+    }
+}
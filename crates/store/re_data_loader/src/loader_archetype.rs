@@ -33,7 +33,9 @@ impl DataLoader for ArchetypeLoader {
     ) -> Result<(), crate::DataLoaderError> {
         use anyhow::Context as _;
 
-        if filepath.is_dir() {
+        // NOTE: Check the extension before reading anything, other loaders might be streaming
+        // files that are way too large to be read in one go.
+        if filepath.is_dir() || !is_archetype_extension(&crate::extension(&filepath)) {
             return Err(crate::DataLoaderError::Incompatible(filepath.clone()));
         }
 
@@ -56,7 +58,7 @@ impl DataLoader for ArchetypeLoader {
         tx: std::sync::mpsc::Sender<LoadedData>,
     ) -> Result<(), crate::DataLoaderError> {
         let extension = crate::extension(&filepath);
-        if !crate::is_supported_file_extension(&extension) {
+        if !is_archetype_extension(&extension) {
             return Err(crate::DataLoaderError::Incompatible(filepath.clone()));
         }
 
@@ -121,7 +123,7 @@ impl DataLoader for ArchetypeLoader {
                 contents.into_owned(),
             )?);
         } else if crate::SUPPORTED_POINT_CLOUD_EXTENSIONS.contains(&extension.as_str()) {
-            re_log::debug!(?filepath, loader = self.name(), "Loading 3D point cloud…",);
+            re_log::debug!(?filepath, loader = self.name(), "Loading point cloud…",);
             rows.extend(load_point_cloud(timepoint, entity_path, &contents)?);
         } else if crate::SUPPORTED_TEXT_EXTENSIONS.contains(&extension.as_str()) {
             re_log::debug!(?filepath, loader = self.name(), "Loading text document…",);
@@ -150,6 +152,15 @@ impl DataLoader for ArchetypeLoader {
 
 // ---
 
+/// Is this an extension that maps 1-to-1 with one of our builtin archetypes?
+fn is_archetype_extension(extension: &str) -> bool {
+    crate::SUPPORTED_IMAGE_EXTENSIONS.contains(&extension)
+        || crate::SUPPORTED_VIDEO_EXTENSIONS.contains(&extension)
+        || crate::SUPPORTED_MESH_EXTENSIONS.contains(&extension)
+        || crate::SUPPORTED_POINT_CLOUD_EXTENSIONS.contains(&extension)
+        || crate::SUPPORTED_TEXT_EXTENSIONS.contains(&extension)
+}
+
 fn load_image(
     filepath: &std::path::Path,
     timepoint: TimePoint,
@@ -287,16 +298,11 @@ fn load_point_cloud(
 ) -> Result<impl ExactSizeIterator<Item = Chunk>, DataLoaderError> {
     re_tracing::profile_function!();
 
-    let rows = [
-        {
-            // TODO(#4532): `.ply` data loader should support 2D point cloud & meshes
-            let points3d = re_types::archetypes::Points3D::from_file_contents(contents)?;
-            Chunk::builder(entity_path)
-                .with_archetype(RowId::new(), timepoint, &points3d)
-                .build()?
-        },
-        //
-    ];
+    let rows = [crate::loader_ply::load_ply(
+        timepoint,
+        entity_path,
+        contents,
+    )?];
 
     Ok(rows.into_iter())
 }
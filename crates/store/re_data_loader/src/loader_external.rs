@@ -1,5 +1,5 @@
 use std::{
-    io::Read,
+    io::BufRead as _,
     sync::{atomic::AtomicBool, Arc},
 };
 
@@ -21,13 +21,29 @@ pub const EXTERNAL_DATA_LOADER_PREFIX: &str = "rerun-loader-";
 // NOTE: this constant is duplicated in `rerun` to avoid an extra dependency there.
 pub const EXTERNAL_DATA_LOADER_INCOMPATIBLE_EXIT_CODE: i32 = 66;
 
+/// External [`crate::DataLoader`]s can optionally describe themselves: when called with this
+/// flag (and nothing else), they should print their [`ExternalLoaderManifest`] as JSON on their
+/// standard output, and exit successfully.
+// NOTE: this constant is duplicated in `rerun` to avoid an extra dependency there.
+pub const EXTERNAL_DATA_LOADER_DESCRIBE_FLAG: &str = "--describe";
+
+/// Lines that an external [`crate::DataLoader`] writes to its standard error and that start with
+/// this prefix are parsed as an [`ExternalLoaderStatus`] (JSON), and surfaced in the viewer.
+///
+/// Any other line is treated as free-form logs.
+// NOTE: this constant is duplicated in `rerun` to avoid an extra dependency there.
+pub const EXTERNAL_DATA_LOADER_STATUS_PREFIX: &str = "rerun-loader-status:";
+
+/// How long we're willing to wait for an external loader to answer [`EXTERNAL_DATA_LOADER_DESCRIBE_FLAG`].
+const DESCRIBE_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(2);
+
 /// Keeps track of the paths all external executable [`crate::DataLoader`]s.
 ///
 /// Lazy initialized the first time a file is opened by running a full scan of the `$PATH`.
 ///
-/// External loaders are _not_ registered on a per-extension basis: we want users to be able to
-/// filter data on a much more fine-grained basis that just file extensions (e.g. checking the file
-/// itself for magic bytes).
+/// External loaders are _not_ required to register on a per-extension basis: we want users to be
+/// able to filter data on a much more fine-grained basis that just file extensions (e.g. checking
+/// the file itself for magic bytes). They can still opt into it, see [`EXTERNAL_LOADER_MANIFESTS`].
 pub static EXTERNAL_LOADER_PATHS: Lazy<Vec<std::path::PathBuf>> = Lazy::new(|| {
     re_tracing::profile_scope!("initialize-external-loaders");
 
@@ -99,10 +115,281 @@ pub fn iter_external_loaders() -> impl ExactSizeIterator<Item = std::path::PathB
 
 // ---
 
+/// What an external [`crate::DataLoader`] reports about itself when called with
+/// [`EXTERNAL_DATA_LOADER_DESCRIBE_FLAG`], e.g.:
+/// ```json
+/// { "extensions": ["bag", "db3"], "mime_types": ["application/x-rosbag"], "priority": 10 }
+/// ```
+///
+/// All fields are optional.
+#[derive(Debug, Clone, Default, PartialEq, Eq, serde::Deserialize)]
+#[serde(default)]
+pub struct ExternalLoaderManifest {
+    /// The file extensions supported by the loader, without the leading dot (e.g. `bag`).
+    pub extensions: Vec<String>,
+
+    /// The media types supported by the loader (e.g. `application/x-rosbag`).
+    ///
+    /// These are matched against the media type guessed from the extension of the file.
+    pub mime_types: Vec<String>,
+
+    /// When several loaders with a manifest support the same file, only the ones with the
+    /// highest priority will be executed.
+    ///
+    /// Defaults to `0`.
+    pub priority: i32,
+}
+
+impl ExternalLoaderManifest {
+    /// Whether the loader claims to support the file at `filepath`.
+    ///
+    /// A manifest that lists neither extensions nor media types supports everything.
+    pub fn supports(&self, filepath: &std::path::Path) -> bool {
+        if self.extensions.is_empty() && self.mime_types.is_empty() {
+            return true;
+        }
+
+        let extension = crate::extension(filepath);
+        if self
+            .extensions
+            .iter()
+            .any(|ext| ext.trim_start_matches('.').eq_ignore_ascii_case(&extension))
+        {
+            return true;
+        }
+
+        re_types::components::MediaType::guess_from_path(filepath).map_or(false, |media_type| {
+            self.mime_types
+                .iter()
+                .any(|mime_type| mime_type.eq_ignore_ascii_case(media_type.as_str()))
+        })
+    }
+}
+
+/// The manifests of all registered external [`crate::DataLoader`]s, if they provide one.
+///
+/// Lazy initialized the first time a file is opened, by running every loader in
+/// [`EXTERNAL_LOADER_PATHS`] with [`EXTERNAL_DATA_LOADER_DESCRIBE_FLAG`], in parallel.
+///
+/// Loaders that fail to describe themselves (e.g. because they predate manifests) are mapped to
+/// `None`: they are executed for every file, just like before.
+pub static EXTERNAL_LOADER_MANIFESTS: Lazy<
+    HashMap<std::path::PathBuf, Option<ExternalLoaderManifest>>,
+> = Lazy::new(|| {
+    re_tracing::profile_scope!("describe-external-loaders");
+
+    let exe_paths = {
+        re_tracing::profile_wait!("EXTERNAL_LOADER_PATHS");
+        EXTERNAL_LOADER_PATHS.clone()
+    };
+
+    // NOTE: This is completely IO bound (spawning and waiting for child processes), each of them
+    // gets a dedicated thread.
+    std::thread::scope(|scope| {
+        let handles: Vec<_> = exe_paths
+            .iter()
+            .map(|exe| (exe, scope.spawn(|| describe(exe))))
+            .collect();
+
+        handles
+            .into_iter()
+            .map(|(exe, handle)| (exe.clone(), handle.join().ok().flatten()))
+            .collect()
+    })
+});
+
+/// Runs `exe` with [`EXTERNAL_DATA_LOADER_DESCRIBE_FLAG`] and parses its manifest, if any.
+fn describe(exe: &std::path::Path) -> Option<ExternalLoaderManifest> {
+    use std::io::Read as _;
+    use std::process::{Command, Stdio};
+
+    re_tracing::profile_function!(exe.to_string_lossy());
+
+    let child = Command::new(exe)
+        .env_remove("RERUN_APP_ONLY")
+        .arg(EXTERNAL_DATA_LOADER_DESCRIBE_FLAG)
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::null())
+        .spawn();
+
+    let mut child = match child {
+        Ok(child) => child,
+        Err(err) => {
+            re_log::debug!(loader = ?exe, %err, "Failed to describe external loader");
+            return None;
+        }
+    };
+
+    let deadline = std::time::Instant::now() + DESCRIBE_TIMEOUT;
+    let status = loop {
+        match child.try_wait() {
+            Ok(Some(status)) => break status,
+            Ok(None) if std::time::Instant::now() < deadline => {
+                std::thread::sleep(std::time::Duration::from_millis(10));
+            }
+            Ok(None) => {
+                re_log::debug!(loader = ?exe, "External loader took too long to describe itself");
+                child.kill().ok();
+                child.wait().ok();
+                return None;
+            }
+            Err(err) => {
+                re_log::debug!(loader = ?exe, %err, "Failed to describe external loader");
+                return None;
+            }
+        }
+    };
+
+    if !status.success() {
+        re_log::debug!(loader = ?exe, ?status, "External loader doesn't provide a manifest");
+        return None;
+    }
+
+    let mut stdout = String::new();
+    child.stdout.take()?.read_to_string(&mut stdout).ok()?;
+
+    match serde_json::from_str::<ExternalLoaderManifest>(stdout.trim()) {
+        Ok(manifest) => {
+            re_log::debug!(loader = ?exe, ?manifest, "Found external loader manifest");
+            Some(manifest)
+        }
+        Err(err) => {
+            re_log::warn!("Ignoring invalid manifest of external loader {exe:?}: {err}");
+            None
+        }
+    }
+}
+
+/// The external loaders that should be executed for the file at `filepath`.
+///
+/// These are all the loaders without a manifest, as well as the highest priority ones amongst
+/// those whose manifest supports the file.
+fn external_loaders_for(filepath: &std::path::Path) -> Vec<std::path::PathBuf> {
+    let manifests = {
+        re_tracing::profile_wait!("EXTERNAL_LOADER_MANIFESTS");
+        &*EXTERNAL_LOADER_MANIFESTS
+    };
+
+    let max_priority = manifests
+        .values()
+        .flatten()
+        .filter(|manifest| manifest.supports(filepath))
+        .map(|manifest| manifest.priority)
+        .max();
+
+    manifests
+        .iter()
+        .filter(|(_, manifest)| match manifest {
+            Some(manifest) => {
+                manifest.supports(filepath) && Some(manifest.priority) == max_priority
+            }
+            None => true,
+        })
+        .map(|(exe, _)| exe.clone())
+        .collect()
+}
+
+// ---
+
+/// A status update reported by an external [`crate::DataLoader`] on its standard error, as a
+/// single line starting with [`EXTERNAL_DATA_LOADER_STATUS_PREFIX`], e.g.:
+/// ```text
+/// rerun-loader-status: {"type": "progress", "message": "Decoding frames", "fraction": 0.25}
+/// rerun-loader-status: {"type": "warning", "message": "Unknown topic /foo, skipping"}
+/// rerun-loader-status: {"type": "error", "message": "Corrupt header"}
+/// ```
+///
+/// These are surfaced in the viewer's notifications.
+#[derive(Debug, Clone, PartialEq, serde::Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ExternalLoaderStatus {
+    /// Loading is making progress.
+    Progress {
+        message: String,
+
+        /// How far along the loader is, in the `[0, 1]` range, if known.
+        #[serde(default)]
+        fraction: Option<f32>,
+    },
+
+    /// Something went wrong, but the loader keeps going.
+    Warning { message: String },
+
+    /// Something went wrong, and the data won't be (completely) loaded.
+    Error { message: String },
+}
+
+/// Reads the standard error of an external loader line by line, until it closes.
+///
+/// Status lines are reported as they come, see [`ExternalLoaderStatus`].
+/// Returns all the other lines, so they can be reported if the loader fails.
+fn read_stderr(
+    exe: &std::path::Path,
+    filepath: &std::path::Path,
+    stderr: impl std::io::Read,
+) -> String {
+    re_tracing::profile_function!(exe.to_string_lossy());
+
+    let loader = exe.file_name().unwrap_or_default().to_string_lossy();
+    let filename = filepath.file_name().unwrap_or_default().to_string_lossy();
+
+    // Progress is only reported to the user every 10%, or whenever its message changes.
+    let mut last_progress: Option<(String, Option<i32>)> = None;
+
+    let mut logs = String::new();
+    for line in std::io::BufReader::new(stderr).lines() {
+        let Ok(line) = line else {
+            break;
+        };
+
+        let Some(status) = line.strip_prefix(EXTERNAL_DATA_LOADER_STATUS_PREFIX) else {
+            logs.push_str(&line);
+            logs.push('\n');
+            continue;
+        };
+
+        match serde_json::from_str::<ExternalLoaderStatus>(status.trim()) {
+            Ok(ExternalLoaderStatus::Progress { message, fraction }) => {
+                let step = fraction.map(|fraction| (fraction.clamp(0.0, 1.0) * 10.0) as i32);
+                if last_progress.as_ref() == Some(&(message.clone(), step)) {
+                    continue;
+                }
+
+                if let Some(fraction) = fraction {
+                    let percent = (fraction.clamp(0.0, 1.0) * 100.0).round();
+                    re_log::info!("{loader}: {message} ({filename}, {percent}%)");
+                } else {
+                    re_log::info!("{loader}: {message} ({filename})");
+                }
+
+                last_progress = Some((message, step));
+            }
+            Ok(ExternalLoaderStatus::Warning { message }) => {
+                re_log::warn!("{loader}: {message} ({filename})");
+            }
+            Ok(ExternalLoaderStatus::Error { message }) => {
+                re_log::error!("{loader}: {message} ({filename})");
+            }
+            Err(err) => {
+                re_log::warn_once!("{loader} reported an invalid status {status:?}: {err}");
+            }
+        }
+    }
+
+    logs
+}
+
+// ---
+
 /// A [`crate::DataLoader`] that forwards the path to load to all executables present in
 /// the user's `PATH` with a name that starts with [`EXTERNAL_DATA_LOADER_PREFIX`].
 ///
-/// The external loaders are expected to log rrd data to their standard output.
+/// Executables that describe themselves with an [`ExternalLoaderManifest`] are only executed
+/// for the files they claim to support, see [`EXTERNAL_LOADER_MANIFESTS`].
+///
+/// The external loaders are expected to log rrd data to their standard output, and can report
+/// their progress and errors on their standard error, see [`ExternalLoaderStatus`].
 ///
 /// Refer to our `external_data_loader` example for more information.
 ///
@@ -126,10 +413,7 @@ impl crate::DataLoader for ExternalLoader {
 
         re_tracing::profile_function!(filepath.display().to_string());
 
-        let external_loaders = {
-            re_tracing::profile_wait!("EXTERNAL_LOADER_PATHS");
-            EXTERNAL_LOADER_PATHS.iter()
-        };
+        let external_loaders = external_loaders_for(&filepath);
 
         #[derive(PartialEq, Eq)]
         struct CompatibleLoaderFound;
@@ -147,7 +431,7 @@ impl crate::DataLoader for ExternalLoader {
             _ = std::thread::Builder::new().name(exe.to_string_lossy().to_string()).spawn(move || {
                 re_tracing::profile_function!(exe.to_string_lossy());
 
-                let child = Command::new(exe)
+                let child = Command::new(&exe)
                     // Make sure the child dataloader doesn't think it's a Rerun Viewer, otherwise
                     // it's never gonna be able to log anything.
                     .env_remove("RERUN_APP_ONLY")
@@ -178,6 +462,23 @@ impl crate::DataLoader for ExternalLoader {
 
                 re_log::debug!(?filepath, loader = ?exe, "Loading data from filesystem using external loader…",);
 
+                // NOTE: Always drain stderr as it comes, otherwise a chatty loader would end up
+                // blocked on a full pipe.
+                let stderr = std::thread::Builder::new()
+                    .name(format!("read_stderr({filepath:?})"))
+                    .spawn({
+                        let exe = exe.clone();
+                        let filepath = filepath.clone();
+                        move || read_stderr(&exe, &filepath, stderr)
+                    });
+                let stderr = match stderr {
+                    Ok(stderr) => stderr,
+                    Err(err) => {
+                        re_log::error!(?filepath, loader = ?exe, %err, "Failed to spawn IO thread");
+                        return;
+                    }
+                };
+
                 // A single value will be sent on this channel as soon as the child process starts
                 // streaming data to stdout.
                 let is_sending_data = Arc::new(AtomicBool::new(false));
@@ -260,9 +561,7 @@ impl crate::DataLoader for ExternalLoader {
                     status.code() != Some(crate::EXTERNAL_DATA_LOADER_INCOMPATIBLE_EXIT_CODE);
 
                 if is_compatible && !status.success() {
-                    let mut stderr = std::io::BufReader::new(stderr);
-                    let mut reason = String::new();
-                    stderr.read_to_string(&mut reason).ok();
+                    let reason = stderr.join().unwrap_or_default();
                     re_log::error!(?filepath, loader = ?exe, %reason, "Failed to execute external loader");
                 }
 
@@ -327,3 +626,56 @@ fn decode_and_stream<R: std::io::Read>(
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn manifest_supports() {
+        let manifest: ExternalLoaderManifest = serde_json::from_str(
+            r#"{ "extensions": [".BAG", "db3"], "mime_types": ["image/png"] }"#,
+        )
+        .unwrap();
+        assert_eq!(0, manifest.priority);
+
+        assert!(manifest.supports("/data/my_robot.bag".as_ref()));
+        assert!(manifest.supports("/data/my_robot.DB3".as_ref()));
+        assert!(manifest.supports("/data/screenshot.png".as_ref()));
+        assert!(!manifest.supports("/data/notes.txt".as_ref()));
+        assert!(!manifest.supports("/data/bag".as_ref()));
+
+        let manifest: ExternalLoaderManifest =
+            serde_json::from_str(r#"{ "priority": 3 }"#).unwrap();
+        assert_eq!(3, manifest.priority);
+        assert!(manifest.supports("/data/notes.txt".as_ref()));
+    }
+
+    #[test]
+    fn parse_status() {
+        assert_eq!(
+            ExternalLoaderStatus::Progress {
+                message: "Decoding frames".to_owned(),
+                fraction: Some(0.25),
+            },
+            serde_json::from_str(
+                r#"{"type": "progress", "message": "Decoding frames", "fraction": 0.25}"#
+            )
+            .unwrap()
+        );
+        assert_eq!(
+            ExternalLoaderStatus::Progress {
+                message: "Indexing".to_owned(),
+                fraction: None,
+            },
+            serde_json::from_str(r#"{"type": "progress", "message": "Indexing"}"#).unwrap()
+        );
+        assert_eq!(
+            ExternalLoaderStatus::Error {
+                message: "Corrupt header".to_owned(),
+            },
+            serde_json::from_str(r#"{"type": "error", "message": "Corrupt header"}"#).unwrap()
+        );
+        assert!(serde_json::from_str::<ExternalLoaderStatus>(r#"{"type": "oops"}"#).is_err());
+    }
+}
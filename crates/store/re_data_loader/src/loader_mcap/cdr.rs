@@ -0,0 +1,267 @@
+//! A minimal reader for the OMG CDR (XCDR1) encoding, as used by ROS 2 messages.
+//!
+//! Only what's needed to decode the handful of message schemas we support, see [`super::ros2`].
+
+/// Errors that might happen when decoding a CDR payload.
+#[derive(thiserror::Error, Debug)]
+pub enum CdrError {
+    #[error("CDR payload is too short: expected at least {expected} bytes, got {actual}")]
+    UnexpectedEof { expected: usize, actual: usize },
+
+    #[error("Unsupported CDR representation identifier: {0:#06x}")]
+    UnsupportedRepresentation(u16),
+
+    #[error("Invalid UTF-8 in CDR string: {0}")]
+    InvalidUtf8(#[from] std::str::Utf8Error),
+}
+
+/// Reads primitives out of a CDR payload, taking care of alignment and endianness.
+pub struct CdrReader<'a> {
+    /// The payload, _without_ its 4-byte encapsulation header: alignment is relative to it.
+    buf: &'a [u8],
+    pos: usize,
+    little_endian: bool,
+}
+
+macro_rules! impl_read_primitive {
+    ($name:ident, $ty:ty) => {
+        #[inline]
+        pub fn $name(&mut self) -> Result<$ty, CdrError> {
+            const SIZE: usize = std::mem::size_of::<$ty>();
+            self.align(SIZE);
+            let bytes: [u8; SIZE] = self
+                .read_bytes(SIZE)?
+                .try_into()
+                .expect("length checked by read_bytes");
+            Ok(if self.little_endian {
+                <$ty>::from_le_bytes(bytes)
+            } else {
+                <$ty>::from_be_bytes(bytes)
+            })
+        }
+    };
+}
+
+impl<'a> CdrReader<'a> {
+    /// Parses the encapsulation header of `data`.
+    pub fn new(data: &'a [u8]) -> Result<Self, CdrError> {
+        let Some((header, buf)) = data.split_first_chunk::<4>() else {
+            return Err(CdrError::UnexpectedEof {
+                expected: 4,
+                actual: data.len(),
+            });
+        };
+
+        let little_endian = match u16::from_be_bytes([header[0], header[1]]) {
+            0x0000 => false, // CDR_BE
+            0x0001 => true,  // CDR_LE
+            representation => return Err(CdrError::UnsupportedRepresentation(representation)),
+        };
+
+        Ok(Self {
+            buf,
+            pos: 0,
+            little_endian,
+        })
+    }
+
+    #[inline]
+    fn align(&mut self, alignment: usize) {
+        self.pos = self.pos.next_multiple_of(alignment);
+    }
+
+    /// `len` usually comes from the payload itself, and must not be trusted.
+    #[inline]
+    fn read_bytes(&mut self, len: usize) -> Result<&'a [u8], CdrError> {
+        let end = self.pos.saturating_add(len);
+        let bytes = self.buf.get(self.pos..end).ok_or(CdrError::UnexpectedEof {
+            expected: end,
+            actual: self.buf.len(),
+        })?;
+        self.pos = end;
+        Ok(bytes)
+    }
+
+    impl_read_primitive!(u8, u8);
+    impl_read_primitive!(u32, u32);
+    impl_read_primitive!(i32, i32);
+    impl_read_primitive!(f32, f32);
+    impl_read_primitive!(f64, f64);
+
+    #[inline]
+    pub fn bool(&mut self) -> Result<bool, CdrError> {
+        Ok(self.u8()? != 0)
+    }
+
+    /// A `string`, which is serialized with its NUL terminator.
+    pub fn string(&mut self) -> Result<&'a str, CdrError> {
+        let len = self.u32()? as usize;
+        let bytes = self.read_bytes(len)?;
+        let bytes = bytes.strip_suffix(&[0]).unwrap_or(bytes);
+        Ok(std::str::from_utf8(bytes)?)
+    }
+
+    /// A `sequence<uint8>`, e.g. the pixels of an image.
+    pub fn byte_sequence(&mut self) -> Result<&'a [u8], CdrError> {
+        let len = self.sequence_len()?;
+        self.read_bytes(len)
+    }
+
+    /// A `sequence<float32>`.
+    pub fn f32_sequence(&mut self) -> Result<Vec<f32>, CdrError> {
+        const SIZE: usize = std::mem::size_of::<f32>();
+
+        // The elements directly follow the (already aligned) length: check that they are all
+        // there before allocating anything.
+        let len = self.sequence_len()?;
+        let bytes = self.read_bytes(len.saturating_mul(SIZE))?;
+
+        Ok(bytes
+            .chunks_exact(SIZE)
+            .map(|bytes| {
+                let bytes = bytes.try_into().expect("chunks are exactly SIZE long");
+                if self.little_endian {
+                    f32::from_le_bytes(bytes)
+                } else {
+                    f32::from_be_bytes(bytes)
+                }
+            })
+            .collect())
+    }
+
+    /// A fixed-size `float64[N]`, e.g. a vector or a quaternion.
+    pub fn f64_array<const N: usize>(&mut self) -> Result<[f64; N], CdrError> {
+        let mut array = [0.0; N];
+        for value in &mut array {
+            *value = self.f64()?;
+        }
+        Ok(array)
+    }
+
+    /// The length of a sequence, which is then followed by as many elements.
+    #[inline]
+    pub fn sequence_len(&mut self) -> Result<usize, CdrError> {
+        Ok(self.u32()? as usize)
+    }
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+
+    /// Writes little-endian CDR payloads, to test the readers.
+    pub(crate) struct CdrWriter {
+        buf: Vec<u8>,
+    }
+
+    macro_rules! impl_write_primitive {
+        ($name:ident, $ty:ty) => {
+            pub(crate) fn $name(&mut self, value: $ty) -> &mut Self {
+                self.align(std::mem::size_of::<$ty>());
+                self.buf.extend(value.to_le_bytes());
+                self
+            }
+        };
+    }
+
+    impl CdrWriter {
+        pub(crate) fn new() -> Self {
+            Self {
+                buf: vec![0x00, 0x01, 0x00, 0x00], // CDR_LE
+            }
+        }
+
+        fn align(&mut self, alignment: usize) {
+            // Alignment is relative to the end of the encapsulation header.
+            while (self.buf.len() - 4) % alignment != 0 {
+                self.buf.push(0);
+            }
+        }
+
+        impl_write_primitive!(u8, u8);
+        impl_write_primitive!(u32, u32);
+        impl_write_primitive!(i32, i32);
+        impl_write_primitive!(f32, f32);
+        impl_write_primitive!(f64, f64);
+
+        pub(crate) fn string(&mut self, value: &str) -> &mut Self {
+            self.u32(value.len() as u32 + 1);
+            self.buf.extend(value.as_bytes());
+            self.buf.push(0);
+            self
+        }
+
+        pub(crate) fn bytes(&mut self, value: &[u8]) -> &mut Self {
+            self.u32(value.len() as u32);
+            self.buf.extend(value);
+            self
+        }
+
+        pub(crate) fn finish(&self) -> Vec<u8> {
+            self.buf.clone()
+        }
+    }
+
+    #[test]
+    fn primitives() {
+        let data = CdrWriter::new()
+            .u8(1)
+            .u32(7)
+            .string("hello")
+            .f64(1.5)
+            .i32(-3)
+            .bytes(&[1, 2, 3])
+            .u32(2)
+            .f32(0.5)
+            .f32(-2.0)
+            .finish();
+
+        let mut reader = CdrReader::new(&data).unwrap();
+        assert!(reader.bool().unwrap());
+        assert_eq!(7, reader.u32().unwrap());
+        assert_eq!("hello", reader.string().unwrap());
+        assert_eq!(1.5, reader.f64().unwrap());
+        assert_eq!(-3, reader.i32().unwrap());
+        assert_eq!(&[1, 2, 3], reader.byte_sequence().unwrap());
+        assert_eq!(vec![0.5, -2.0], reader.f32_sequence().unwrap());
+        assert!(reader.u8().is_err());
+    }
+
+    #[test]
+    fn big_endian() {
+        let data = [
+            0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x2A, 0x3F, 0xC0, 0x00, 0x00,
+        ];
+
+        let mut reader = CdrReader::new(&data).unwrap();
+        assert_eq!(42, reader.u32().unwrap());
+        assert_eq!(1.5, reader.f32().unwrap());
+    }
+
+    #[test]
+    fn invalid() {
+        assert!(matches!(
+            CdrReader::new(&[0x00]),
+            Err(CdrError::UnexpectedEof {
+                expected: 4,
+                actual: 1
+            })
+        ));
+        assert!(matches!(
+            CdrReader::new(&[0x00, 0x03, 0x00, 0x00]),
+            Err(CdrError::UnsupportedRepresentation(0x0003))
+        ));
+
+        // Lengths that point way past the end of the payload must fail before allocating.
+        let data = CdrWriter::new().u32(u32::MAX).finish();
+        assert!(CdrReader::new(&data).unwrap().f32_sequence().is_err());
+        assert!(CdrReader::new(&data).unwrap().byte_sequence().is_err());
+        assert!(CdrReader::new(&data).unwrap().string().is_err());
+
+        let data = CdrWriter::new().bytes(&[0xFF, 0xFE]).finish();
+        assert!(matches!(
+            CdrReader::new(&data).unwrap().string(),
+            Err(CdrError::InvalidUtf8(_))
+        ));
+    }
+}
@@ -0,0 +1,218 @@
+//! Loading of MCAP files, e.g. ROS 2 bags.
+
+mod cdr;
+mod ros2;
+
+use re_chunk::{Chunk, RowId};
+use re_log_types::{EntityPath, TimeInt, Timeline};
+use re_types::{archetypes::TextDocument, components::Blob};
+
+use crate::{DataLoader, DataLoaderError, DataLoaderSettings, LoadedData};
+
+// ---
+
+/// Loads data from any `mcap` file or in-memory contents.
+///
+/// Each topic is logged to the entity path of the same name. Messages are logged on the
+/// `log_time` & `publish_time` timelines of the file, as well as on `header_stamp` if they have a
+/// ROS header.
+///
+/// CDR-encoded ROS 2 messages of the following schemas are mapped onto the matching archetypes:
+/// * `sensor_msgs/msg/Image`: [`re_types::archetypes::Image`], or
+///   [`re_types::archetypes::DepthImage`] for single channel `16UC1` & `32FC1` encodings.
+/// * `sensor_msgs/msg/CompressedImage`: [`re_types::archetypes::EncodedImage`].
+/// * `sensor_msgs/msg/PointCloud2` & `sensor_msgs/msg/LaserScan`: [`re_types::archetypes::Points3D`].
+/// * `sensor_msgs/msg/Imu`: a [`re_types::archetypes::Transform3D`] for the orientation, and
+///   [`re_types::archetypes::Scalar`]s for the angular velocity & linear acceleration.
+/// * `tf2_msgs/msg/TFMessage`: a [`re_types::archetypes::Transform3D`] per child frame.
+/// * `geometry_msgs/msg/Pose` & `geometry_msgs/msg/PoseStamped`: [`re_types::archetypes::Transform3D`].
+///
+/// Messages of any other topic are logged as-is, as a [`TextDocument`] if they are JSON or text,
+/// or as a raw [`Blob`] otherwise.
+///
+/// On the web, only uncompressed files are supported: `lz4` & `zstd` chunks fail to load.
+pub struct McapLoader;
+
+impl DataLoader for McapLoader {
+    #[inline]
+    fn name(&self) -> String {
+        "rerun.data_loaders.Mcap".into()
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    fn load_from_path(
+        &self,
+        settings: &DataLoaderSettings,
+        filepath: std::path::PathBuf,
+        tx: std::sync::mpsc::Sender<LoadedData>,
+    ) -> Result<(), DataLoaderError> {
+        use anyhow::Context as _;
+
+        if !is_mcap(&filepath) {
+            return Err(DataLoaderError::Incompatible(filepath));
+        }
+
+        re_tracing::profile_function!(filepath.display().to_string());
+
+        re_log::debug!(
+            ?filepath,
+            loader = self.name(),
+            "Loading mcap data from filesystem…",
+        );
+
+        // Bags easily get larger than memory: map the file rather than reading it all at once.
+        let contents = {
+            re_tracing::profile_scope!("mmap");
+            let file = std::fs::File::open(&filepath)
+                .with_context(|| format!("Failed to open file {filepath:?}"))?;
+            // SAFETY: the mapping is read-only. As with any memory-mapped file, the file being
+            // truncated while we read it is undefined behavior: we accept that risk, like the
+            // `mcap` tools do.
+            #[allow(unsafe_code)]
+            let mmap = unsafe { memmap2::Mmap::map(&file) };
+            mmap.with_context(|| format!("Failed to map file {filepath:?}"))?
+        };
+
+        // NOTE: Decoding is CPU bound but can take a while for large bags, don't block the caller.
+        std::thread::Builder::new()
+            .name(format!("load_mcap({filepath:?})"))
+            .spawn({
+                let filepath = filepath.clone();
+                let settings = settings.clone();
+                move || {
+                    if let Err(err) = load_mcap(&settings, &contents, &tx) {
+                        re_log::error!("Failed to load {filepath:?}: {err}");
+                    }
+                }
+            })
+            .with_context(|| format!("Failed to spawn IO thread for {filepath:?}"))?;
+
+        Ok(())
+    }
+
+    fn load_from_file_contents(
+        &self,
+        settings: &DataLoaderSettings,
+        filepath: std::path::PathBuf,
+        contents: std::borrow::Cow<'_, [u8]>,
+        tx: std::sync::mpsc::Sender<LoadedData>,
+    ) -> Result<(), DataLoaderError> {
+        if !is_mcap(&filepath) {
+            return Err(DataLoaderError::Incompatible(filepath));
+        }
+
+        re_tracing::profile_function!(filepath.display().to_string());
+
+        load_mcap(settings, &contents, &tx)
+    }
+}
+
+fn is_mcap(filepath: &std::path::Path) -> bool {
+    crate::SUPPORTED_MCAP_EXTENSIONS.contains(&crate::extension(filepath).as_str())
+}
+
+fn load_mcap(
+    settings: &DataLoaderSettings,
+    contents: &[u8],
+    tx: &std::sync::mpsc::Sender<LoadedData>,
+) -> Result<(), DataLoaderError> {
+    re_tracing::profile_function!();
+
+    let store_id = settings
+        .opened_store_id
+        .clone()
+        .unwrap_or_else(|| settings.store_id.clone());
+
+    let timeline_log_time = Timeline::new_temporal("log_time");
+    let timeline_publish_time = Timeline::new_temporal("publish_time");
+    let timeline_header_stamp = Timeline::new_temporal("header_stamp");
+
+    let messages = mcap::MessageStream::new(contents)
+        .map_err(|err| anyhow::anyhow!("Failed to read mcap file: {err}"))?;
+
+    for message in messages {
+        let message =
+            message.map_err(|err| anyhow::anyhow!("Failed to read mcap message: {err}"))?;
+        let channel = &message.channel;
+
+        let mut entity_path = EntityPath::parse_forgiving(&channel.topic);
+        if let Some(prefix) = &settings.entity_path_prefix {
+            entity_path = prefix.join(&entity_path);
+        }
+
+        let mut timepoint = settings.timepoint.clone().unwrap_or_default();
+        timepoint.insert(
+            timeline_log_time,
+            TimeInt::new_temporal(message.log_time as i64),
+        );
+        timepoint.insert(
+            timeline_publish_time,
+            TimeInt::new_temporal(message.publish_time as i64),
+        );
+
+        let schema_name = channel
+            .schema
+            .as_ref()
+            .map_or("", |schema| schema.name.as_str());
+
+        let decoded = (channel.message_encoding == "cdr")
+            .then(|| ros2::decode(schema_name, &message.data))
+            .flatten();
+
+        let chunks = match decoded {
+            Some(Ok(decoded)) => decoded
+                .into_iter()
+                .map(|decoded| {
+                    let entity_path = match &decoded.entity_path_suffix {
+                        Some(suffix) => entity_path.join(&EntityPath::parse_forgiving(suffix)),
+                        None => entity_path.clone(),
+                    };
+                    let mut timepoint = timepoint.clone();
+                    if let Some(stamp) = decoded.stamp {
+                        timepoint.insert(timeline_header_stamp, TimeInt::new_temporal(stamp));
+                    }
+                    Chunk::builder(entity_path)
+                        .with_archetype(RowId::new(), timepoint, decoded.archetype.as_ref())
+                        .build()
+                })
+                .collect::<Result<Vec<_>, _>>()?,
+
+            Some(Err(err)) => {
+                re_log::warn_once!(
+                    "Failed to decode {schema_name:?} message on topic {:?}, ignoring it: {err}",
+                    channel.topic
+                );
+                continue;
+            }
+
+            None => {
+                let builder = Chunk::builder(entity_path);
+                let builder = match std::str::from_utf8(&message.data) {
+                    Ok(text) if is_text_encoding(&channel.message_encoding) => {
+                        builder.with_archetype(RowId::new(), timepoint, &TextDocument::new(text))
+                    }
+                    _ => builder.with_component_batch(
+                        RowId::new(),
+                        timepoint,
+                        &Blob::from(message.data.to_vec()),
+                    ),
+                };
+                vec![builder.build()?]
+            }
+        };
+
+        for chunk in chunks {
+            let data = LoadedData::Chunk(McapLoader.name(), store_id.clone(), chunk);
+            if tx.send(data).is_err() {
+                return Ok(()); // The other end has decided to hang up, not our problem.
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// See <https://mcap.dev/spec/registry#message-encodings>.
+fn is_text_encoding(message_encoding: &str) -> bool {
+    matches!(message_encoding, "json" | "text" | "")
+}
@@ -0,0 +1,632 @@
+//! Mapping of common ROS 2 message schemas onto Rerun archetypes.
+
+use re_types::{
+    archetypes::{DepthImage, EncodedImage, Image, Points3D, Scalar, Transform3D},
+    components::{Color, Position3D},
+    datatypes::{ChannelDatatype, ColorModel, Quaternion},
+    AsComponents,
+};
+
+use super::cdr::{CdrError, CdrReader};
+
+// ---
+
+/// Errors that might happen when decoding a ROS 2 message.
+#[derive(thiserror::Error, Debug)]
+pub enum Ros2Error {
+    #[error(transparent)]
+    Cdr(#[from] CdrError),
+
+    #[error("Unsupported image encoding: {0:?}")]
+    UnsupportedImageEncoding(String),
+
+    #[error("Point cloud has no `x`, `y` & `z` fields")]
+    MissingPointFields,
+}
+
+/// Something to log, decoded from a ROS 2 message.
+pub struct Decoded {
+    /// Where to log it, relative to the entity path of the topic.
+    pub entity_path_suffix: Option<String>,
+
+    /// The `header.stamp` of the message, in nanoseconds, if it has one.
+    pub stamp: Option<i64>,
+
+    pub archetype: Box<dyn AsComponents>,
+}
+
+impl Decoded {
+    fn new(stamp: Option<i64>, archetype: impl AsComponents + 'static) -> Self {
+        Self {
+            entity_path_suffix: None,
+            stamp,
+            archetype: Box::new(archetype),
+        }
+    }
+
+    fn with_suffix(mut self, suffix: impl Into<String>) -> Self {
+        self.entity_path_suffix = Some(suffix.into());
+        self
+    }
+}
+
+/// Decodes a CDR-encoded ROS 2 message.
+///
+/// Returns `None` if the schema isn't one we know about.
+pub fn decode(schema_name: &str, data: &[u8]) -> Option<Result<Vec<Decoded>, Ros2Error>> {
+    let decode: fn(&mut CdrReader<'_>) -> Result<Vec<Decoded>, Ros2Error> = match schema_name {
+        "sensor_msgs/msg/Image" => decode_image,
+        "sensor_msgs/msg/CompressedImage" => decode_compressed_image,
+        "sensor_msgs/msg/PointCloud2" => decode_point_cloud,
+        "sensor_msgs/msg/LaserScan" => decode_laser_scan,
+        "sensor_msgs/msg/Imu" => decode_imu,
+        "tf2_msgs/msg/TFMessage" => decode_tf,
+        "geometry_msgs/msg/PoseStamped" => decode_pose_stamped,
+        "geometry_msgs/msg/Pose" => decode_pose,
+        _ => return None,
+    };
+
+    Some(
+        CdrReader::new(data)
+            .map_err(Ros2Error::from)
+            .and_then(|mut reader| decode(&mut reader)),
+    )
+}
+
+// --- std_msgs & geometry_msgs ---
+
+/// `std_msgs/msg/Header`, returns the stamp in nanoseconds.
+fn header(reader: &mut CdrReader<'_>) -> Result<i64, CdrError> {
+    let sec = reader.i32()?;
+    let nanosec = reader.u32()?;
+    let _frame_id = reader.string()?;
+    Ok(sec as i64 * 1_000_000_000 + nanosec as i64)
+}
+
+/// `geometry_msgs/msg/Vector3` or `geometry_msgs/msg/Point`.
+fn vec3(reader: &mut CdrReader<'_>) -> Result<[f32; 3], CdrError> {
+    Ok(reader.f64_array::<3>()?.map(|v| v as f32))
+}
+
+/// `geometry_msgs/msg/Quaternion`.
+fn quaternion(reader: &mut CdrReader<'_>) -> Result<Quaternion, CdrError> {
+    Ok(Quaternion::from_xyzw(
+        reader.f64_array::<4>()?.map(|v| v as f32),
+    ))
+}
+
+/// `geometry_msgs/msg/Pose`.
+fn pose(reader: &mut CdrReader<'_>) -> Result<Transform3D, CdrError> {
+    let position = vec3(reader)?;
+    let orientation = quaternion(reader)?;
+    Ok(Transform3D::from_translation_rotation(
+        position,
+        orientation,
+    ))
+}
+
+fn decode_pose(reader: &mut CdrReader<'_>) -> Result<Vec<Decoded>, Ros2Error> {
+    Ok(vec![Decoded::new(None, pose(reader)?)])
+}
+
+fn decode_pose_stamped(reader: &mut CdrReader<'_>) -> Result<Vec<Decoded>, Ros2Error> {
+    let stamp = header(reader)?;
+    Ok(vec![Decoded::new(Some(stamp), pose(reader)?)])
+}
+
+/// Each transform is logged under the name of its child frame.
+fn decode_tf(reader: &mut CdrReader<'_>) -> Result<Vec<Decoded>, Ros2Error> {
+    let num_transforms = reader.sequence_len()?;
+    (0..num_transforms)
+        .map(|_| {
+            let stamp = header(reader)?;
+            let child_frame_id = reader.string()?;
+            let translation = vec3(reader)?;
+            let rotation = quaternion(reader)?;
+            let transform = Transform3D::from_translation_rotation(translation, rotation);
+            Ok(Decoded::new(Some(stamp), transform).with_suffix(child_frame_id))
+        })
+        .collect()
+}
+
+// --- sensor_msgs ---
+
+fn decode_image(reader: &mut CdrReader<'_>) -> Result<Vec<Decoded>, Ros2Error> {
+    let stamp = header(reader)?;
+    let height = reader.u32()?;
+    let width = reader.u32()?;
+    let encoding = reader.string()?;
+    let is_bigendian = reader.bool()?;
+    let step = reader.u32()? as usize;
+    let data = reader.byte_sequence()?;
+
+    // See `sensor_msgs/image_encodings.hpp`.
+    enum Kind {
+        Color(ColorModel),
+        Depth { meter: f32 },
+    }
+    let (kind, datatype) = match encoding {
+        "mono8" | "8UC1" => (Kind::Color(ColorModel::L), ChannelDatatype::U8),
+        "mono16" => (Kind::Color(ColorModel::L), ChannelDatatype::U16),
+        "rgb8" | "8UC3" => (Kind::Color(ColorModel::RGB), ChannelDatatype::U8),
+        "rgba8" | "8UC4" => (Kind::Color(ColorModel::RGBA), ChannelDatatype::U8),
+        "bgr8" => (Kind::Color(ColorModel::BGR), ChannelDatatype::U8),
+        "bgra8" => (Kind::Color(ColorModel::BGRA), ChannelDatatype::U8),
+        "rgb16" | "16UC3" => (Kind::Color(ColorModel::RGB), ChannelDatatype::U16),
+        "rgba16" | "16UC4" => (Kind::Color(ColorModel::RGBA), ChannelDatatype::U16),
+        "bgr16" => (Kind::Color(ColorModel::BGR), ChannelDatatype::U16),
+        "bgra16" => (Kind::Color(ColorModel::BGRA), ChannelDatatype::U16),
+        // By convention, single channel 16-bit and float images are depth maps, in mm and m respectively.
+        "16UC1" => (Kind::Depth { meter: 1000.0 }, ChannelDatatype::U16),
+        "32FC1" => (Kind::Depth { meter: 1.0 }, ChannelDatatype::F32),
+        _ => return Err(Ros2Error::UnsupportedImageEncoding(encoding.to_owned())),
+    };
+
+    let num_channels = match &kind {
+        Kind::Color(color_model) => color_model.num_channels(),
+        Kind::Depth { .. } => 1,
+    };
+    let bytes_per_channel = datatype.bits() / 8;
+    // `width` is untrusted: saturating is fine since rows are clamped to the data anyway.
+    let row_len = (width as usize)
+        .saturating_mul(num_channels)
+        .saturating_mul(bytes_per_channel);
+
+    // Get rid of any padding at the end of the rows.
+    let mut bytes = if step == row_len {
+        data.to_vec()
+    } else {
+        data.chunks(step.max(1))
+            .flat_map(|row| &row[..row_len.min(row.len())])
+            .copied()
+            .collect()
+    };
+
+    if is_bigendian && bytes_per_channel > 1 {
+        for channel in bytes.chunks_exact_mut(bytes_per_channel) {
+            channel.reverse();
+        }
+    }
+
+    let decoded = match kind {
+        Kind::Color(color_model) => Decoded::new(
+            Some(stamp),
+            Image::from_color_model_and_bytes(bytes, [width, height], color_model, datatype),
+        ),
+        Kind::Depth { meter } => Decoded::new(
+            Some(stamp),
+            DepthImage::from_data_type_and_bytes(bytes, [width, height], datatype)
+                .with_meter(meter),
+        ),
+    };
+
+    Ok(vec![decoded])
+}
+
+/// The actual format (`jpeg`, `png`, …) is guessed from the data itself.
+fn decode_compressed_image(reader: &mut CdrReader<'_>) -> Result<Vec<Decoded>, Ros2Error> {
+    let stamp = header(reader)?;
+    let _format = reader.string()?;
+    let data = reader.byte_sequence()?;
+
+    Ok(vec![Decoded::new(
+        Some(stamp),
+        EncodedImage::from_file_contents(data.to_vec()),
+    )])
+}
+
+fn decode_laser_scan(reader: &mut CdrReader<'_>) -> Result<Vec<Decoded>, Ros2Error> {
+    let stamp = header(reader)?;
+    let angle_min = reader.f32()?;
+    let _angle_max = reader.f32()?;
+    let angle_increment = reader.f32()?;
+    let _time_increment = reader.f32()?;
+    let _scan_time = reader.f32()?;
+    let range_min = reader.f32()?;
+    let range_max = reader.f32()?;
+    let ranges = reader.f32_sequence()?;
+    let _intensities = reader.f32_sequence()?;
+
+    let positions = ranges
+        .iter()
+        .enumerate()
+        .filter(|(_, range)| (range_min..=range_max).contains(*range))
+        .map(|(i, range)| {
+            let angle = angle_min + i as f32 * angle_increment;
+            Position3D::new(range * angle.cos(), range * angle.sin(), 0.0)
+        });
+
+    Ok(vec![Decoded::new(Some(stamp), Points3D::new(positions))])
+}
+
+/// The orientation is logged as a rotation on the topic itself, the angular velocity & linear
+/// acceleration as scalars under it.
+fn decode_imu(reader: &mut CdrReader<'_>) -> Result<Vec<Decoded>, Ros2Error> {
+    let stamp = header(reader)?;
+    let orientation = quaternion(reader)?;
+    let orientation_covariance = reader.f64_array::<9>()?;
+    let angular_velocity = vec3(reader)?;
+    let _angular_velocity_covariance = reader.f64_array::<9>()?;
+    let linear_acceleration = vec3(reader)?;
+    let _linear_acceleration_covariance = reader.f64_array::<9>()?;
+
+    let mut decoded = Vec::with_capacity(7);
+
+    // A covariance of -1 means that the orientation is not estimated by this sensor.
+    if orientation_covariance[0] != -1.0 {
+        decoded.push(Decoded::new(
+            Some(stamp),
+            Transform3D::from_rotation(orientation),
+        ));
+    }
+
+    for (name, values) in [
+        ("angular_velocity", angular_velocity),
+        ("linear_acceleration", linear_acceleration),
+    ] {
+        for (axis, value) in ["x", "y", "z"].into_iter().zip(values) {
+            decoded.push(
+                Decoded::new(Some(stamp), Scalar::new(value as f64))
+                    .with_suffix(format!("{name}/{axis}")),
+            );
+        }
+    }
+
+    Ok(decoded)
+}
+
+/// `sensor_msgs/msg/PointField`.
+struct PointField {
+    name: String,
+    offset: usize,
+    datatype: u8,
+}
+
+impl PointField {
+    /// Reads the first element of this field within `point`, as a `f64`.
+    fn read(&self, point: &[u8], is_bigendian: bool) -> Option<f64> {
+        macro_rules! read {
+            ($ty:ty) => {{
+                let end = self.offset.checked_add(std::mem::size_of::<$ty>())?;
+                let bytes = point.get(self.offset..end)?;
+                let bytes = bytes.try_into().ok()?;
+                if is_bigendian {
+                    <$ty>::from_be_bytes(bytes) as f64
+                } else {
+                    <$ty>::from_le_bytes(bytes) as f64
+                }
+            }};
+        }
+
+        // See the constants in `sensor_msgs/msg/PointField`.
+        Some(match self.datatype {
+            1 => read!(i8),
+            2 => read!(u8),
+            3 => read!(i16),
+            4 => read!(u16),
+            5 => read!(i32),
+            6 => read!(u32),
+            7 => read!(f32),
+            8 => read!(f64),
+            _ => return None,
+        })
+    }
+
+    /// Reads a packed `0x00RRGGBB` color, as found in the `rgb` & `rgba` fields.
+    fn read_color(&self, point: &[u8], is_bigendian: bool) -> Option<Color> {
+        let end = self.offset.checked_add(4)?;
+        let bytes = point.get(self.offset..end)?.try_into().ok()?;
+        let packed = if is_bigendian {
+            u32::from_be_bytes(bytes)
+        } else {
+            u32::from_le_bytes(bytes)
+        };
+        let [a, r, g, b] = packed.to_be_bytes();
+        let a = if self.name == "rgba" { a } else { 255 };
+        Some(Color::from_unmultiplied_rgba(r, g, b, a))
+    }
+}
+
+/// Points with a non-finite position (i.e. invalid points in non-dense clouds) are skipped.
+fn decode_point_cloud(reader: &mut CdrReader<'_>) -> Result<Vec<Decoded>, Ros2Error> {
+    let stamp = header(reader)?;
+    let height = reader.u32()? as usize;
+    let width = reader.u32()? as usize;
+    let num_fields = reader.sequence_len()?;
+    let fields = (0..num_fields)
+        .map(|_| {
+            let name = reader.string()?.to_owned();
+            let offset = reader.u32()? as usize;
+            let datatype = reader.u8()?;
+            let _count = reader.u32()?;
+            Ok(PointField {
+                name,
+                offset,
+                datatype,
+            })
+        })
+        .collect::<Result<Vec<_>, CdrError>>()?;
+    let is_bigendian = reader.bool()?;
+    let point_step = reader.u32()? as usize;
+    let row_step = reader.u32()? as usize;
+    let data = reader.byte_sequence()?;
+    let _is_dense = reader.bool()?;
+
+    let field = |name: &str| fields.iter().find(|field| field.name == name);
+    let (Some(x), Some(y), Some(z)) = (field("x"), field("y"), field("z")) else {
+        return Err(Ros2Error::MissingPointFields);
+    };
+    let color = field("rgb").or_else(|| field("rgba"));
+
+    let points = data
+        .chunks(row_step.max(1))
+        .take(height)
+        .flat_map(|row| row.chunks_exact(point_step.max(1)).take(width));
+
+    // `height` & `width` are untrusted: never reserve more points than the data can hold.
+    let max_num_points = data.len() / point_step.max(1);
+    let num_points = height
+        .checked_mul(width)
+        .map_or(max_num_points, |num_points| num_points.min(max_num_points));
+
+    let mut positions = Vec::with_capacity(num_points);
+    let mut colors = Vec::with_capacity(if color.is_some() { num_points } else { 0 });
+    for point in points {
+        let [Some(x), Some(y), Some(z)] = [x, y, z].map(|field| field.read(point, is_bigendian))
+        else {
+            continue;
+        };
+        if !(x.is_finite() && y.is_finite() && z.is_finite()) {
+            continue;
+        }
+
+        positions.push(Position3D::new(x as f32, y as f32, z as f32));
+        if let Some(color) = color {
+            colors.push(
+                color
+                    .read_color(point, is_bigendian)
+                    .unwrap_or(Color::WHITE),
+            );
+        }
+    }
+
+    let mut points = Points3D::new(positions);
+    if color.is_some() {
+        points = points.with_colors(colors);
+    }
+
+    Ok(vec![Decoded::new(Some(stamp), points)])
+}
+
+#[cfg(test)]
+mod tests {
+    use re_chunk::{Chunk, RowId};
+    use re_log_types::TimePoint;
+    use re_types::components::{ImageBuffer, ImageFormat, Translation3D};
+
+    use super::*;
+    use crate::loader_mcap::cdr::tests::CdrWriter;
+
+    /// A `std_msgs/msg/Header` stamped at `sec` + `nanosec`.
+    fn writer_with_header(sec: i32, nanosec: u32) -> CdrWriter {
+        let mut writer = CdrWriter::new();
+        writer.i32(sec).u32(nanosec).string("frame");
+        writer
+    }
+
+    fn decode_one(schema_name: &str, data: &[u8]) -> (Decoded, Chunk) {
+        let mut decoded = decode(schema_name, data).unwrap().unwrap();
+        assert_eq!(1, decoded.len());
+        let decoded = decoded.remove(0);
+        let chunk = to_chunk(&decoded);
+        (decoded, chunk)
+    }
+
+    fn to_chunk(decoded: &Decoded) -> Chunk {
+        Chunk::builder("test".into())
+            .with_archetype(
+                RowId::new(),
+                TimePoint::default(),
+                decoded.archetype.as_ref(),
+            )
+            .build()
+            .unwrap()
+    }
+
+    fn batch<C: re_types::Component>(chunk: &Chunk) -> Vec<C> {
+        chunk.component_batch::<C>(0).unwrap().unwrap()
+    }
+
+    /// A `sensor_msgs/msg/PointCloud2` with `x`, `y`, `z` & `rgb` float fields.
+    fn point_cloud(height: u32, width: u32, row_step: u32, points: &[[f32; 4]]) -> Vec<u8> {
+        let data = points
+            .iter()
+            .flatten()
+            .flat_map(|v| v.to_le_bytes())
+            .collect::<Vec<_>>();
+
+        let mut writer = writer_with_header(3, 0);
+        writer.u32(height).u32(width).u32(4);
+        for (offset, name) in ["x", "y", "z", "rgb"].into_iter().enumerate() {
+            writer.string(name).u32(offset as u32 * 4).u8(7).u32(1);
+        }
+        writer
+            .u8(0) // is_bigendian
+            .u32(16) // point_step
+            .u32(row_step)
+            .bytes(&data)
+            .u8(0) // is_dense
+            .finish()
+    }
+
+    #[test]
+    fn unknown_schema() {
+        assert!(decode("std_msgs/msg/String", &[]).is_none());
+        assert!(decode("sensor_msgs/msg/Image", &[0x00, 0x01, 0x00, 0x00])
+            .is_some_and(|res| res.is_err()));
+    }
+
+    #[test]
+    fn point_cloud2() {
+        let red = f32::from_bits(0x00FF_0000);
+        let data = point_cloud(
+            1,
+            3,
+            48,
+            &[
+                [1.0, 2.0, 3.0, red],
+                [f32::NAN, 0.0, 0.0, red], // invalid point
+                [4.0, 5.0, 6.0, red],
+            ],
+        );
+
+        let (decoded, chunk) = decode_one("sensor_msgs/msg/PointCloud2", &data);
+        assert_eq!(Some(3_000_000_000), decoded.stamp);
+        assert_eq!(
+            vec![
+                Position3D::new(1.0, 2.0, 3.0),
+                Position3D::new(4.0, 5.0, 6.0)
+            ],
+            batch::<Position3D>(&chunk)
+        );
+        assert_eq!(vec![Color::from_rgb(255, 0, 0); 2], batch::<Color>(&chunk));
+    }
+
+    #[test]
+    fn point_cloud2_untrusted_size() {
+        // The claimed size of the cloud is way off: only decode what's actually there.
+        let data = point_cloud(u32::MAX, u32::MAX, 16, &[[1.0, 2.0, 3.0, 0.0]]);
+
+        let (_, chunk) = decode_one("sensor_msgs/msg/PointCloud2", &data);
+        assert_eq!(
+            vec![Position3D::new(1.0, 2.0, 3.0)],
+            batch::<Position3D>(&chunk)
+        );
+    }
+
+    #[test]
+    fn point_cloud2_missing_fields() {
+        let mut writer = writer_with_header(0, 0);
+        writer.u32(1).u32(1).u32(1);
+        writer.string("intensity").u32(0).u8(7).u32(1);
+        let data = writer.u8(0).u32(4).u32(4).bytes(&[0; 4]).u8(0).finish();
+
+        assert!(matches!(
+            decode("sensor_msgs/msg/PointCloud2", &data),
+            Some(Err(Ros2Error::MissingPointFields))
+        ));
+    }
+
+    #[test]
+    fn image() {
+        // 2x2 mono8 image, with one byte of padding at the end of each row.
+        let data = writer_with_header(1, 2)
+            .u32(2) // height
+            .u32(2) // width
+            .string("mono8")
+            .u8(0) // is_bigendian
+            .u32(3) // step
+            .bytes(&[1, 2, 0, 3, 4, 0])
+            .finish();
+
+        let (decoded, chunk) = decode_one("sensor_msgs/msg/Image", &data);
+        assert_eq!(Some(1_000_000_002), decoded.stamp);
+        assert_eq!(&[1, 2, 3, 4], batch::<ImageBuffer>(&chunk)[0].as_slice());
+
+        let format = batch::<ImageFormat>(&chunk)[0];
+        assert_eq!((2, 2), (format.width, format.height));
+        assert_eq!(Some(ColorModel::L), format.color_model);
+    }
+
+    #[test]
+    fn depth_image() {
+        // Big endian 16-bit depth in millimeters.
+        let data = writer_with_header(0, 0)
+            .u32(1)
+            .u32(2)
+            .string("16UC1")
+            .u8(1)
+            .u32(4)
+            .bytes(&[0x01, 0x02, 0x03, 0x04])
+            .finish();
+
+        let (_, chunk) = decode_one("sensor_msgs/msg/Image", &data);
+        assert_eq!(
+            &[0x02, 0x01, 0x04, 0x03],
+            batch::<ImageBuffer>(&chunk)[0].as_slice()
+        );
+        assert_eq!(
+            Some(ChannelDatatype::U16),
+            batch::<ImageFormat>(&chunk)[0].channel_datatype
+        );
+
+        let data = writer_with_header(0, 0)
+            .u32(1)
+            .u32(1)
+            .string("yuv422")
+            .u8(0)
+            .u32(2)
+            .bytes(&[0, 0])
+            .finish();
+        assert!(matches!(
+            decode("sensor_msgs/msg/Image", &data),
+            Some(Err(Ros2Error::UnsupportedImageEncoding(_)))
+        ));
+    }
+
+    #[test]
+    fn tf() {
+        let mut writer = CdrWriter::new();
+        writer.u32(1);
+        writer.i32(1).u32(5).string("world").string("base_link");
+        for v in [1.0, 2.0, 3.0, 0.0, 0.0, 0.0, 1.0] {
+            writer.f64(v);
+        }
+
+        let (decoded, chunk) = decode_one("tf2_msgs/msg/TFMessage", &writer.finish());
+        assert_eq!(Some("base_link"), decoded.entity_path_suffix.as_deref());
+        assert_eq!(Some(1_000_000_005), decoded.stamp);
+        assert_eq!(
+            vec![Translation3D::new(1.0, 2.0, 3.0)],
+            batch::<Translation3D>(&chunk)
+        );
+    }
+
+    #[test]
+    fn imu() {
+        let mut writer = writer_with_header(0, 0);
+        let mut f64s = |values: &[f64]| {
+            for &v in values {
+                writer.f64(v);
+            }
+        };
+        f64s(&[0.0, 0.0, 0.0, 1.0]); // orientation
+        f64s(&[-1.0; 9]); // no orientation estimate
+        f64s(&[0.1, 0.2, 0.3]); // angular velocity
+        f64s(&[0.0; 9]);
+        f64s(&[0.0, 0.0, 9.5]); // linear acceleration
+        f64s(&[0.0; 9]);
+
+        let decoded = decode("sensor_msgs/msg/Imu", &writer.finish())
+            .unwrap()
+            .unwrap();
+        assert_eq!(
+            vec![
+                "angular_velocity/x",
+                "angular_velocity/y",
+                "angular_velocity/z",
+                "linear_acceleration/x",
+                "linear_acceleration/y",
+                "linear_acceleration/z",
+            ],
+            decoded
+                .iter()
+                .map(|decoded| decoded.entity_path_suffix.as_deref().unwrap())
+                .collect::<Vec<_>>()
+        );
+        assert_eq!(
+            vec![re_types::components::Scalar::from(9.5)],
+            batch::<re_types::components::Scalar>(&to_chunk(&decoded[5]))
+        );
+    }
+}
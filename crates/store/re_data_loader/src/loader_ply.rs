@@ -0,0 +1,291 @@
+//! Loading of `.ply` files, as either meshes or 2D/3D point clouds.
+
+use arrow2::array::{Array as _, PrimitiveArray as Arrow2PrimitiveArray};
+
+use re_chunk::{Chunk, RowId};
+use re_log_types::{EntityPath, TimePoint};
+use re_types::{
+    archetypes::{Mesh3D, Points2D, Points3D},
+    components::Position2D,
+    ply::PlyData,
+    ComponentDescriptor,
+};
+
+use crate::{archetype_row, DataLoaderError};
+
+// ---
+
+/// Loads the contents of a `.ply` file as a single [`Chunk`].
+///
+/// * If the file has faces, it is loaded as a [`Mesh3D`], with vertex normals, colors and texture
+///   coordinates if present.
+/// * Otherwise, it is loaded as a [`Points3D`], or a [`Points2D`] if the vertices have no `z`
+///   property, with colors, radii and labels if present.
+///
+/// Any other scalar vertex property (e.g. `intensity` or `confidence`) is logged as-is in an extra
+/// component column named after it, alongside the archetype.
+pub(crate) fn load_ply(
+    timepoint: TimePoint,
+    entity_path: EntityPath,
+    contents: &[u8],
+) -> Result<Chunk, DataLoaderError> {
+    re_tracing::profile_function!();
+
+    let PlyData {
+        positions,
+        has_z,
+        colors,
+        radii,
+        labels,
+        normals,
+        texcoords,
+        triangles,
+        extra_scalars,
+    } = PlyData::read(contents)?;
+
+    let mut row = if let Some(triangles) = triangles {
+        let mut arch = Mesh3D::new(positions).with_triangle_indices(triangles);
+        if let Some(colors) = colors {
+            arch = arch.with_vertex_colors(colors);
+        }
+        if let Some(normals) = normals {
+            arch = arch.with_vertex_normals(normals);
+        }
+        if let Some(texcoords) = texcoords {
+            arch = arch.with_vertex_texcoords(texcoords);
+        }
+        archetype_row(&arch)?
+    } else if has_z {
+        let mut arch = Points3D::new(positions);
+        if let Some(colors) = colors {
+            arch = arch.with_colors(colors);
+        }
+        if let Some(radii) = radii {
+            arch = arch.with_radii(radii);
+        }
+        if let Some(labels) = labels {
+            arch = arch.with_labels(labels);
+        }
+        archetype_row(&arch)?
+    } else {
+        let positions = positions
+            .into_iter()
+            .map(|[x, y, _z]| Position2D::new(x, y));
+        let mut arch = Points2D::new(positions);
+        if let Some(colors) = colors {
+            arch = arch.with_colors(colors);
+        }
+        if let Some(radii) = radii {
+            arch = arch.with_radii(radii);
+        }
+        if let Some(labels) = labels {
+            arch = arch.with_labels(labels);
+        }
+        archetype_row(&arch)?
+    };
+
+    // Everything else goes into extra columns, so that it shows up in the UI.
+    for (name, values) in extra_scalars {
+        row.push((
+            ComponentDescriptor::new(name),
+            Arrow2PrimitiveArray::<f64>::from(values).boxed(),
+        ));
+    }
+
+    Ok(Chunk::builder(entity_path)
+        .with_row_arrow2(RowId::new(), timepoint, row)
+        .build()?)
+}
+
+#[cfg(test)]
+mod tests {
+    use re_types::{
+        components::{Color, Position3D, Radius, Text, TriangleIndices, Vector3D},
+        ComponentName,
+    };
+
+    use super::*;
+
+    fn load(contents: &str) -> Result<Chunk, DataLoaderError> {
+        load_ply(TimePoint::default(), "ply".into(), contents.as_bytes())
+    }
+
+    fn batch<C: re_types::Component>(chunk: &Chunk) -> Option<Vec<C>> {
+        chunk.component_batch::<C>(0).map(|res| res.unwrap())
+    }
+
+    fn extra_column(chunk: &Chunk, name: &str) -> Vec<Option<f64>> {
+        let array = chunk
+            .component_batch_raw(&ComponentName::from(name), 0)
+            .unwrap()
+            .unwrap();
+        array
+            .as_any()
+            .downcast_ref::<Arrow2PrimitiveArray<f64>>()
+            .unwrap()
+            .iter()
+            .map(|v| v.copied())
+            .collect()
+    }
+
+    #[test]
+    fn mesh() {
+        let chunk = load(
+            "ply
+format ascii 1.0
+element vertex 4
+property float x
+property float y
+property float z
+property float nx
+property float ny
+property float nz
+property uchar red
+property uchar green
+property uchar blue
+property float confidence
+element face 1
+property list uchar int vertex_indices
+end_header
+0 0 0 0 0 1 255 0 0 0.5
+1 0 0 0 0 1 0 255 0 0.25
+1 1 0 0 0 1 0 0 255 1
+0 1 0 0 0 1 255 255 255 0
+4 0 1 2 3
+",
+        )
+        .unwrap();
+
+        assert_eq!(
+            Some(vec![
+                Position3D::new(0.0, 0.0, 0.0),
+                Position3D::new(1.0, 0.0, 0.0),
+                Position3D::new(1.0, 1.0, 0.0),
+                Position3D::new(0.0, 1.0, 0.0),
+            ]),
+            batch::<Position3D>(&chunk)
+        );
+
+        // The quad is turned into a fan of two triangles.
+        assert_eq!(
+            Some(vec![
+                TriangleIndices::from([0, 1, 2]),
+                TriangleIndices::from([0, 2, 3]),
+            ]),
+            batch::<TriangleIndices>(&chunk)
+        );
+        assert_eq!(
+            Some(vec![Vector3D::from([0.0, 0.0, 1.0]); 4]),
+            batch::<Vector3D>(&chunk)
+        );
+        assert_eq!(
+            Some(vec![
+                Color::from_rgb(255, 0, 0),
+                Color::from_rgb(0, 255, 0),
+                Color::from_rgb(0, 0, 255),
+                Color::from_rgb(255, 255, 255),
+            ]),
+            batch::<Color>(&chunk)
+        );
+
+        assert_eq!(
+            vec![Some(0.5), Some(0.25), Some(1.0), Some(0.0)],
+            extra_column(&chunk, "confidence")
+        );
+    }
+
+    #[test]
+    fn points_2d() {
+        let chunk = load(
+            "ply
+format ascii 1.0
+element vertex 3
+property double x
+property double y
+property int label
+property float intensity
+end_header
+0 0 7 0.5
+1 2 -1 1.5
+3 4 42 2
+",
+        )
+        .unwrap();
+
+        assert_eq!(
+            Some(vec![
+                Position2D::new(0.0, 0.0),
+                Position2D::new(1.0, 2.0),
+                Position2D::new(3.0, 4.0),
+            ]),
+            batch::<Position2D>(&chunk)
+        );
+        assert!(batch::<Position3D>(&chunk).is_none());
+
+        // Numeric labels, e.g. class ids, are kept as-is.
+        assert_eq!(
+            Some(vec![Text::from("7"), Text::from("-1"), Text::from("42")]),
+            batch::<Text>(&chunk)
+        );
+
+        assert_eq!(
+            vec![Some(0.5), Some(1.5), Some(2.0)],
+            extra_column(&chunk, "intensity")
+        );
+        assert!(chunk
+            .component_batch_raw(&ComponentName::from("label"), 0)
+            .is_none());
+    }
+
+    #[test]
+    fn points_3d() {
+        let chunk = load(
+            "ply
+format ascii 1.0
+element vertex 2
+property float x
+property float y
+property float z
+property float radius
+property list uchar uchar label
+end_header
+0 0 0 0.5 3 99 97 116
+1 1 1 2 3 100 111 103
+",
+        )
+        .unwrap();
+
+        assert_eq!(
+            Some(vec![
+                Position3D::new(0.0, 0.0, 0.0),
+                Position3D::new(1.0, 1.0, 1.0),
+            ]),
+            batch::<Position3D>(&chunk)
+        );
+        assert_eq!(
+            Some(vec![Radius::from(0.5), Radius::from(2.0)]),
+            batch::<Radius>(&chunk)
+        );
+        assert_eq!(
+            Some(vec![Text::from("cat"), Text::from("dog")]),
+            batch::<Text>(&chunk)
+        );
+        assert!(batch::<TriangleIndices>(&chunk).is_none());
+    }
+
+    #[test]
+    fn no_positions() {
+        let res = load(
+            "ply
+format ascii 1.0
+element vertex 1
+property float intensity
+end_header
+1
+",
+        );
+        assert!(res.is_err());
+
+        assert!(load("not a ply file").is_err());
+    }
+}
@@ -0,0 +1,1091 @@
+//! Streamed loading of (potentially very large) `.las`, `.laz` & `.pcd` point clouds.
+
+use std::io::BufRead;
+
+use arrow2::array::{Array as Arrow2Array, PrimitiveArray as Arrow2PrimitiveArray};
+
+use re_chunk::{Chunk, RowId};
+use re_log_types::{EntityPath, TimePoint};
+use re_types::{
+    archetypes::{Points3D, Transform3D},
+    components::{ClassId, Color, Position3D},
+    ComponentDescriptor,
+};
+
+use crate::{DataLoader, DataLoaderError, DataLoaderSettings, LoadedData};
+
+// ---
+
+/// How many points go into a single [`Chunk`].
+///
+/// This bounds the memory used while loading, regardless of the size of the file.
+const MAX_POINTS_PER_CHUNK: usize = 1 << 20;
+
+/// Loads `.las`, `.laz` & `.pcd` point clouds as [`Points3D`], a bounded number of points at a time.
+///
+/// The only exception are `binary_compressed` `.pcd` files, which are decompressed all at once.
+///
+/// Each batch of points is logged to its own child entity (`<file>/0`, `<file>/1`, …), so that
+/// they all show up together.
+///
+/// * RGB colors are mapped to [`Color`]s.
+/// * LAS classifications & PCD `label`s are mapped to [`ClassId`]s.
+/// * Intensities are logged as-is in an extra `intensity` component column.
+///
+/// LAS coordinates are usually georeferenced, i.e. far away from the origin: the points are made
+/// relative to the minimum of the file's bounds, which is then logged as a [`Transform3D`] on the
+/// file's entity, so as to not lose precision.
+pub struct PointCloudLoader;
+
+impl DataLoader for PointCloudLoader {
+    #[inline]
+    fn name(&self) -> String {
+        "rerun.data_loaders.PointCloud".into()
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    fn load_from_path(
+        &self,
+        settings: &DataLoaderSettings,
+        filepath: std::path::PathBuf,
+        tx: std::sync::mpsc::Sender<LoadedData>,
+    ) -> Result<(), DataLoaderError> {
+        use anyhow::Context as _;
+
+        let extension = crate::extension(&filepath);
+        if !crate::SUPPORTED_STREAMED_POINT_CLOUD_EXTENSIONS.contains(&extension.as_str()) {
+            return Err(DataLoaderError::Incompatible(filepath));
+        }
+
+        re_tracing::profile_function!(filepath.display().to_string());
+
+        re_log::debug!(
+            ?filepath,
+            loader = self.name(),
+            "Streaming point cloud from filesystem…",
+        );
+
+        let file = std::fs::File::open(&filepath)
+            .with_context(|| format!("Failed to open file {filepath:?}"))?;
+        let file = std::io::BufReader::new(file);
+
+        // NOTE: This is IO bound, it must run on a dedicated thread, not the shared rayon thread pool.
+        std::thread::Builder::new()
+            .name(format!("load_point_cloud({filepath:?})"))
+            .spawn({
+                let filepath = filepath.clone();
+                let settings = settings.clone();
+                move || {
+                    if let Err(err) = load(&settings, &filepath, &extension, file, &tx) {
+                        re_log::error!("Failed to load {filepath:?}: {err}");
+                    }
+                }
+            })
+            .with_context(|| format!("Failed to spawn IO thread for {filepath:?}"))?;
+
+        Ok(())
+    }
+
+    fn load_from_file_contents(
+        &self,
+        settings: &DataLoaderSettings,
+        filepath: std::path::PathBuf,
+        contents: std::borrow::Cow<'_, [u8]>,
+        tx: std::sync::mpsc::Sender<LoadedData>,
+    ) -> Result<(), DataLoaderError> {
+        let extension = crate::extension(&filepath);
+        if !crate::SUPPORTED_STREAMED_POINT_CLOUD_EXTENSIONS.contains(&extension.as_str()) {
+            return Err(DataLoaderError::Incompatible(filepath));
+        }
+
+        re_tracing::profile_function!(filepath.display().to_string());
+
+        load(
+            settings,
+            &filepath,
+            &extension,
+            std::io::Cursor::new(contents),
+            &tx,
+        )
+    }
+}
+
+fn load<R: BufRead + std::io::Seek + Send + std::fmt::Debug>(
+    settings: &DataLoaderSettings,
+    filepath: &std::path::Path,
+    extension: &str,
+    reader: R,
+    tx: &std::sync::mpsc::Sender<LoadedData>,
+) -> Result<(), DataLoaderError> {
+    let mut entity_path = EntityPath::from_file_path(filepath);
+    if let Some(prefix) = &settings.entity_path_prefix {
+        entity_path = prefix.join(&entity_path);
+    }
+
+    let mut sender = ChunkSender {
+        loader_name: PointCloudLoader.name(),
+        store_id: settings
+            .opened_store_id
+            .clone()
+            .unwrap_or_else(|| settings.store_id.clone()),
+        timepoint: settings.timepoint.clone().unwrap_or_default(),
+        entity_path,
+        tx,
+        max_points_per_chunk: MAX_POINTS_PER_CHUNK,
+        num_chunks: 0,
+    };
+
+    match extension {
+        "las" | "laz" => load_las(reader, &mut sender),
+        "pcd" => load_pcd(reader, &mut sender),
+        _ => unreachable!("checked by the caller"),
+    }
+}
+
+// ---
+
+/// A single point, as read from a file.
+struct Point {
+    position: [f32; 3],
+    color: Option<Color>,
+    class_id: Option<u16>,
+    intensity: Option<f32>,
+}
+
+/// Which attributes the points of a file have.
+#[derive(Clone, Copy)]
+struct PointLayout {
+    has_colors: bool,
+    has_class_ids: bool,
+    has_intensities: bool,
+}
+
+/// Accumulates the points of a single [`Chunk`].
+struct PointBatch {
+    layout: PointLayout,
+    positions: Vec<Position3D>,
+    colors: Vec<Color>,
+    class_ids: Vec<ClassId>,
+    intensities: Vec<f32>,
+}
+
+impl PointBatch {
+    fn new(layout: PointLayout) -> Self {
+        Self {
+            layout,
+            positions: Vec::new(),
+            colors: Vec::new(),
+            class_ids: Vec::new(),
+            intensities: Vec::new(),
+        }
+    }
+
+    fn is_full(&self, max_points: usize) -> bool {
+        self.positions.len() >= max_points
+    }
+
+    fn push(&mut self, point: Point) {
+        let Point {
+            position: [x, y, z],
+            color,
+            class_id,
+            intensity,
+        } = point;
+
+        self.positions.push(Position3D::new(x, y, z));
+        if self.layout.has_colors {
+            self.colors.push(color.unwrap_or(Color::WHITE));
+        }
+        if self.layout.has_class_ids {
+            self.class_ids.push(ClassId::from(class_id.unwrap_or(0)));
+        }
+        if self.layout.has_intensities {
+            self.intensities.push(intensity.unwrap_or(0.0));
+        }
+    }
+
+    fn take_components(
+        &mut self,
+    ) -> Result<Vec<(ComponentDescriptor, Box<dyn Arrow2Array>)>, DataLoaderError> {
+        let Self {
+            layout,
+            positions,
+            colors,
+            class_ids,
+            intensities,
+        } = std::mem::replace(self, Self::new(self.layout));
+
+        let mut points = Points3D::new(positions);
+        if layout.has_colors {
+            points = points.with_colors(colors);
+        }
+        if layout.has_class_ids {
+            points = points.with_class_ids(class_ids);
+        }
+
+        let mut components = crate::archetype_row(&points)?;
+        if layout.has_intensities {
+            components.push((
+                ComponentDescriptor::new("intensity"),
+                Arrow2PrimitiveArray::<f32>::from_vec(intensities).boxed(),
+            ));
+        }
+
+        Ok(components)
+    }
+}
+
+/// Sends batches of points to the viewer, each to its own child entity.
+struct ChunkSender<'a> {
+    loader_name: String,
+    store_id: re_log_types::StoreId,
+    timepoint: TimePoint,
+    entity_path: EntityPath,
+    tx: &'a std::sync::mpsc::Sender<LoadedData>,
+
+    /// [`MAX_POINTS_PER_CHUNK`], unless testing.
+    max_points_per_chunk: usize,
+
+    num_chunks: usize,
+}
+
+impl ChunkSender<'_> {
+    /// Returns `false` if the other end has hung up, in which case loading should stop.
+    fn send(&mut self, chunk: Chunk) -> bool {
+        let data = LoadedData::Chunk(self.loader_name.clone(), self.store_id.clone(), chunk);
+        self.tx.send(data).is_ok()
+    }
+
+    /// Returns `false` if the other end has hung up, in which case loading should stop.
+    fn send_batch(&mut self, batch: &mut PointBatch) -> Result<bool, DataLoaderError> {
+        if batch.positions.is_empty() {
+            return Ok(true);
+        }
+
+        let entity_path = self
+            .entity_path
+            .join(&EntityPath::from_single_string(self.num_chunks.to_string()));
+        self.num_chunks += 1;
+
+        let chunk = Chunk::builder(entity_path)
+            .with_row_arrow2(
+                RowId::new(),
+                self.timepoint.clone(),
+                batch.take_components()?,
+            )
+            .build()?;
+
+        Ok(self.send(chunk))
+    }
+
+    /// Streams all `points` in batches of at most `max_points_per_chunk`.
+    fn send_points(
+        &mut self,
+        layout: PointLayout,
+        points: impl Iterator<Item = Result<Point, DataLoaderError>>,
+    ) -> Result<(), DataLoaderError> {
+        let mut batch = PointBatch::new(layout);
+
+        for point in points {
+            batch.push(point?);
+            if batch.is_full(self.max_points_per_chunk) && !self.send_batch(&mut batch)? {
+                return Ok(()); // The other end has decided to hang up, not our problem.
+            }
+        }
+
+        self.send_batch(&mut batch)?;
+
+        Ok(())
+    }
+}
+
+// --- LAS & LAZ ---
+
+fn load_las<R: BufRead + std::io::Seek + Send + std::fmt::Debug>(
+    reader: R,
+    sender: &mut ChunkSender<'_>,
+) -> Result<(), DataLoaderError> {
+    re_tracing::profile_function!();
+
+    let mut reader = las::Reader::new(reader)
+        .map_err(|err| anyhow::anyhow!("Failed to read LAS header: {err}"))?;
+
+    let header = reader.header();
+    let has_colors = header.point_format().has_color;
+    let origin = header.bounds().min;
+    let origin = [origin.x, origin.y, origin.z];
+
+    let transform = Chunk::builder(sender.entity_path.clone())
+        .with_archetype(
+            RowId::new(),
+            sender.timepoint.clone(),
+            &Transform3D::from_translation(origin.map(|v| v as f32)),
+        )
+        .build()?;
+    if !sender.send(transform) {
+        return Ok(()); // The other end has decided to hang up, not our problem.
+    }
+
+    let layout = PointLayout {
+        has_colors,
+        has_class_ids: true,
+        has_intensities: true,
+    };
+    let points = reader.points().map(|point| {
+        let point = point.map_err(|err| anyhow::anyhow!("Failed to read LAS point: {err}"))?;
+        Ok(Point {
+            position: [
+                (point.x - origin[0]) as f32,
+                (point.y - origin[1]) as f32,
+                (point.z - origin[2]) as f32,
+            ],
+            // LAS colors are 16-bit.
+            color: point.color.map(|color| {
+                Color::from_rgb(
+                    (color.red >> 8) as u8,
+                    (color.green >> 8) as u8,
+                    (color.blue >> 8) as u8,
+                )
+            }),
+            class_id: Some(u8::from(point.classification) as u16),
+            intensity: Some(point.intensity as f32),
+        })
+    });
+
+    sender.send_points(layout, points)
+}
+
+// --- PCD ---
+
+/// A field of a `.pcd` file, see <https://pointclouds.org/documentation/tutorials/pcd_file_format.html>.
+struct PcdField {
+    name: String,
+
+    /// Size of a single element, in bytes: 1, 2, 4 or 8.
+    size: usize,
+
+    /// `I`, `U` or `F`.
+    kind: u8,
+
+    /// Number of elements.
+    count: usize,
+
+    /// Offset of the field within a binary point, in bytes.
+    offset: usize,
+}
+
+impl PcdField {
+    /// Size of all the elements of this field, in bytes.
+    ///
+    /// Cannot overflow: this is checked when reading the header.
+    fn num_bytes(&self) -> usize {
+        self.size * self.count
+    }
+
+    /// Reads the first element of this field within a binary `point`, as a `f64`.
+    fn read(&self, point: &[u8]) -> Option<f64> {
+        let bytes = point.get(self.offset..self.offset.checked_add(self.size)?)?;
+        Some(match (self.kind, self.size) {
+            (b'F', 4) => f32::from_le_bytes(bytes.try_into().ok()?) as f64,
+            (b'F', 8) => f64::from_le_bytes(bytes.try_into().ok()?),
+            (b'U', _) => self.read_bits(point)? as f64,
+            (b'I', 1) => i8::from_le_bytes(bytes.try_into().ok()?) as f64,
+            (b'I', 2) => i16::from_le_bytes(bytes.try_into().ok()?) as f64,
+            (b'I', 4) => i32::from_le_bytes(bytes.try_into().ok()?) as f64,
+            (b'I', 8) => i64::from_le_bytes(bytes.try_into().ok()?) as f64,
+            _ => return None,
+        })
+    }
+
+    /// Reads the raw bits of the first element of this field within a binary `point`.
+    fn read_bits(&self, point: &[u8]) -> Option<u64> {
+        let bytes = point.get(self.offset..self.offset.checked_add(self.size)?)?;
+        let mut bits = [0; 8];
+        bits.get_mut(..bytes.len())?.copy_from_slice(bytes);
+        Some(u64::from_le_bytes(bits))
+    }
+
+    /// Reads a packed `0x00RRGGBB` color, as found in the `rgb` & `rgba` fields.
+    fn read_color(&self, point: &[u8]) -> Option<Color> {
+        let [b, g, r, a, ..] = self.read_bits(point)?.to_le_bytes();
+        let a = if self.name == "rgba" { a } else { 255 };
+        Some(Color::from_unmultiplied_rgba(r, g, b, a))
+    }
+
+    /// Writes all the elements of this field as binary, from their ASCII representation.
+    fn ascii_to_binary<'a>(
+        &self,
+        tokens: &mut impl Iterator<Item = &'a str>,
+        point: &mut Vec<u8>,
+    ) -> Option<()> {
+        for _ in 0..self.count {
+            let token = tokens.next()?;
+            match (self.kind, self.size) {
+                (b'F', 4) => point.extend(token.parse::<f32>().ok()?.to_le_bytes()),
+                (b'F', 8) => point.extend(token.parse::<f64>().ok()?.to_le_bytes()),
+                (b'U', size @ (1 | 2 | 4 | 8)) => {
+                    point.extend(&token.parse::<u64>().ok()?.to_le_bytes()[..size])
+                }
+                (b'I', size @ (1 | 2 | 4 | 8)) => {
+                    point.extend(&token.parse::<i64>().ok()?.to_le_bytes()[..size])
+                }
+                _ => return None,
+            }
+        }
+        Some(())
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum PcdData {
+    Ascii,
+    Binary,
+
+    /// LZF-compressed, with all the values of each field stored one after the other.
+    BinaryCompressed,
+}
+
+struct PcdHeader {
+    fields: Vec<PcdField>,
+
+    /// Size of a single binary point, in bytes.
+    point_size: usize,
+
+    /// As advertised by the header: there might be fewer points in the data.
+    num_points: usize,
+
+    data: PcdData,
+}
+
+impl PcdHeader {
+    fn read(reader: &mut impl BufRead) -> Result<Self, DataLoaderError> {
+        let mut names = Vec::new();
+        let mut sizes = Vec::new();
+        let mut kinds = Vec::new();
+        let mut counts = Vec::new();
+        let mut width = 0;
+        let mut height = 1;
+        let mut num_points = None;
+
+        let mut line = String::new();
+        let data = loop {
+            line.clear();
+            if reader.read_line(&mut line).map_err(anyhow::Error::from)? == 0 {
+                return Err(anyhow::anyhow!("PCD header has no DATA entry").into());
+            }
+
+            let mut tokens = line.split_whitespace();
+            let Some(key) = tokens.next() else {
+                continue;
+            };
+            let parse_all = |tokens: std::str::SplitWhitespace<'_>| {
+                tokens
+                    .map(|token| token.parse::<usize>())
+                    .collect::<Result<Vec<_>, _>>()
+                    .map_err(|err| anyhow::anyhow!("Invalid {key} in PCD header: {err}"))
+            };
+
+            match key {
+                "FIELDS" => names = tokens.map(ToOwned::to_owned).collect(),
+                "SIZE" => sizes = parse_all(tokens)?,
+                "TYPE" => kinds = tokens.map(|kind| kind.as_bytes()[0]).collect(),
+                "COUNT" => counts = parse_all(tokens)?,
+                "WIDTH" => width = parse_all(tokens)?.first().copied().unwrap_or(0),
+                "HEIGHT" => height = parse_all(tokens)?.first().copied().unwrap_or(1),
+                "POINTS" => num_points = parse_all(tokens)?.first().copied(),
+                "DATA" => match tokens.next() {
+                    Some("ascii") => break PcdData::Ascii,
+                    Some("binary") => break PcdData::Binary,
+                    Some("binary_compressed") => break PcdData::BinaryCompressed,
+                    data => {
+                        return Err(anyhow::anyhow!("Unsupported PCD data format: {data:?}").into())
+                    }
+                },
+                _ => {} // comments, VERSION, VIEWPOINT…
+            }
+        };
+
+        if names.len() != sizes.len() || names.len() != kinds.len() {
+            return Err(anyhow::anyhow!("Inconsistent FIELDS, SIZE & TYPE in PCD header").into());
+        }
+        if counts.is_empty() {
+            counts = vec![1; names.len()];
+        } else if counts.len() != names.len() {
+            return Err(anyhow::anyhow!("Inconsistent FIELDS & COUNT in PCD header").into());
+        }
+
+        // Sizes & counts come straight from the file: don't trust them.
+        let mut point_size = 0_usize;
+        let mut fields = Vec::with_capacity(names.len());
+        for (name, size, kind, count) in itertools::izip!(names, sizes, kinds, counts) {
+            if !matches!(size, 1 | 2 | 4 | 8) || count == 0 {
+                return Err(anyhow::anyhow!(
+                    "Invalid PCD field {name:?}: SIZE {size} & COUNT {count}"
+                )
+                .into());
+            }
+
+            let offset = point_size;
+            point_size = size
+                .checked_mul(count)
+                .and_then(|num_bytes| offset.checked_add(num_bytes))
+                .ok_or_else(|| anyhow::anyhow!("PCD points are too large"))?;
+
+            fields.push(PcdField {
+                name,
+                size,
+                kind,
+                count,
+                offset,
+            });
+        }
+
+        Ok(Self {
+            fields,
+            point_size,
+            num_points: num_points.unwrap_or(width.saturating_mul(height)),
+            data,
+        })
+    }
+
+    fn field(&self, name: &str) -> Option<&PcdField> {
+        self.fields.iter().find(|field| field.name == name)
+    }
+
+    /// Gathers the point at `index` out of decompressed `binary_compressed` data.
+    fn gather_point(&self, data: &[u8], index: usize, point: &mut Vec<u8>) -> Option<()> {
+        point.clear();
+        for field in &self.fields {
+            let num_bytes = field.num_bytes();
+            // All the values of a field come after all the values of the previous fields.
+            let start = self
+                .num_points
+                .checked_mul(field.offset)?
+                .checked_add(index.checked_mul(num_bytes)?)?;
+            point.extend_from_slice(data.get(start..start.checked_add(num_bytes)?)?);
+        }
+        Some(())
+    }
+}
+
+/// Reads & decompresses the data of a `binary_compressed` PCD file.
+///
+/// Unlike the other formats, this cannot be streamed: all the points are decompressed at once.
+fn read_pcd_compressed(
+    reader: &mut impl std::io::Read,
+    header: &PcdHeader,
+    data_len: u64,
+) -> Result<Vec<u8>, DataLoaderError> {
+    let mut sizes = [0; 8];
+    reader
+        .read_exact(&mut sizes)
+        .map_err(|err| anyhow::anyhow!("Failed to read compressed PCD sizes: {err}"))?;
+    let [c0, c1, c2, c3, u0, u1, u2, u3] = sizes;
+    let compressed_size = u32::from_le_bytes([c0, c1, c2, c3]) as usize;
+    let uncompressed_size = u32::from_le_bytes([u0, u1, u2, u3]) as usize;
+
+    if header.num_points.checked_mul(header.point_size) != Some(uncompressed_size) {
+        return Err(anyhow::anyhow!(
+            "Compressed PCD data holds {uncompressed_size} bytes, expected {} points of {} bytes",
+            header.num_points,
+            header.point_size
+        )
+        .into());
+    }
+    if data_len < 8 + compressed_size as u64 {
+        return Err(anyhow::anyhow!(
+            "Compressed PCD data is truncated: expected {compressed_size} bytes, got {}",
+            data_len.saturating_sub(8)
+        )
+        .into());
+    }
+
+    let mut compressed = vec![0; compressed_size];
+    reader
+        .read_exact(&mut compressed)
+        .map_err(|err| anyhow::anyhow!("Failed to read compressed PCD data: {err}"))?;
+
+    lzf_decompress(&compressed, uncompressed_size)
+        .ok_or_else(|| anyhow::anyhow!("Invalid LZF-compressed PCD data").into())
+}
+
+/// Decompresses LZF data, as written by `liblzf` (and thus PCL).
+///
+/// Returns `None` if the data is corrupt, or doesn't decompress to exactly `uncompressed_size`
+/// bytes.
+fn lzf_decompress(compressed: &[u8], uncompressed_size: usize) -> Option<Vec<u8>> {
+    // `uncompressed_size` comes from the file: let the output grow rather than trusting it.
+    let mut output = Vec::with_capacity(uncompressed_size.min(compressed.len().saturating_mul(4)));
+
+    let mut input = compressed.iter().copied();
+    while let Some(ctrl) = input.next() {
+        let ctrl = ctrl as usize;
+
+        if ctrl < 1 << 5 {
+            // A run of `ctrl + 1` literal bytes.
+            for _ in 0..=ctrl {
+                output.push(input.next()?);
+            }
+        } else {
+            // A back-reference of `len + 2` bytes, which can overlap with what it produces.
+            let mut len = ctrl >> 5;
+            if len == 7 {
+                len += input.next()? as usize;
+            }
+            let distance = (((ctrl & 0x1F) << 8) | input.next()? as usize) + 1;
+            if output.len() < distance {
+                return None;
+            }
+            for _ in 0..len + 2 {
+                output.push(output[output.len() - distance]);
+            }
+        }
+
+        if output.len() > uncompressed_size {
+            return None;
+        }
+    }
+
+    (output.len() == uncompressed_size).then_some(output)
+}
+
+/// Invalid points are stored as NaNs in non-dense point clouds.
+fn is_valid_point(point: &Result<Point, DataLoaderError>) -> bool {
+    point
+        .as_ref()
+        .map_or(true, |point| point.position.iter().all(|v| v.is_finite()))
+}
+
+fn load_pcd(
+    mut reader: impl BufRead + std::io::Seek,
+    sender: &mut ChunkSender<'_>,
+) -> Result<(), DataLoaderError> {
+    re_tracing::profile_function!();
+
+    let header = PcdHeader::read(&mut reader)?;
+
+    let (Some(x), Some(y), Some(z)) = (header.field("x"), header.field("y"), header.field("z"))
+    else {
+        return Err(anyhow::anyhow!("PCD file has no `x`, `y` & `z` fields").into());
+    };
+    let color = header.field("rgb").or_else(|| header.field("rgba"));
+    let label = header.field("label");
+    let intensity = header.field("intensity");
+
+    let layout = PointLayout {
+        has_colors: color.is_some(),
+        has_class_ids: label.is_some(),
+        has_intensities: intensity.is_some(),
+    };
+
+    // Every point is read as binary, converting from ASCII or gathering its fields first if needed.
+    let to_point = |point: &[u8]| Point {
+        position: [x, y, z].map(|field| field.read(point).unwrap_or(0.0) as f32),
+        color: color.and_then(|field| field.read_color(point)),
+        class_id: label.and_then(|field| field.read(point)).map(|v| v as u16),
+        intensity: intensity
+            .and_then(|field| field.read(point))
+            .map(|v| v as f32),
+    };
+
+    // The header cannot be trusted with the size of the data that follows it.
+    let data_len = {
+        let io_err = |err: std::io::Error| DataLoaderError::from(anyhow::Error::from(err));
+        let pos = reader.stream_position().map_err(io_err)?;
+        let end = reader.seek(std::io::SeekFrom::End(0)).map_err(io_err)?;
+        reader.seek(std::io::SeekFrom::Start(pos)).map_err(io_err)?;
+        end.saturating_sub(pos)
+    };
+
+    let mut point = Vec::new();
+    match header.data {
+        PcdData::Ascii => {
+            let mut line = String::new();
+            let points = (0..header.num_points).map_while(|_| {
+                line.clear();
+                match reader.read_line(&mut line) {
+                    Ok(_) if line.trim().is_empty() => return None, // Fewer points than advertised.
+                    Ok(_) => {}
+                    Err(err) => return Some(Err(anyhow::Error::from(err).into())),
+                }
+
+                point.clear();
+                let mut tokens = line.split_whitespace();
+                for field in &header.fields {
+                    if field.ascii_to_binary(&mut tokens, &mut point).is_none() {
+                        return Some(Err(anyhow::anyhow!("Invalid PCD point: {line:?}").into()));
+                    }
+                }
+
+                Some(Ok(to_point(&point)))
+            });
+
+            sender.send_points(layout, points.filter(is_valid_point))
+        }
+
+        PcdData::Binary => {
+            if 0 < header.num_points && data_len < header.point_size as u64 {
+                return Err(anyhow::anyhow!(
+                    "PCD data is too short for points of {} bytes",
+                    header.point_size
+                )
+                .into());
+            }
+
+            point.resize(header.point_size, 0);
+            let points =
+                (0..header.num_points).map_while(|_| match reader.read_exact(&mut point) {
+                    Ok(()) => Some(Ok(to_point(&point))),
+                    // Fewer points than advertised: keep what we have.
+                    Err(err) if err.kind() == std::io::ErrorKind::UnexpectedEof => None,
+                    Err(err) => Some(Err(anyhow::Error::from(err).into())),
+                });
+
+            sender.send_points(layout, points.filter(is_valid_point))
+        }
+
+        PcdData::BinaryCompressed => {
+            let data = read_pcd_compressed(&mut reader, &header, data_len)?;
+
+            let points = (0..header.num_points).map(|index| -> Result<_, DataLoaderError> {
+                header
+                    .gather_point(&data, index, &mut point)
+                    .ok_or_else(|| anyhow::anyhow!("Invalid compressed PCD point #{index}"))?;
+                Ok(to_point(&point))
+            });
+
+            sender.send_points(layout, points.filter(is_valid_point))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use re_log_types::{StoreId, StoreKind};
+    use re_types::{components::Translation3D, ComponentName};
+
+    use super::*;
+
+    const HEADER: &str = "# .PCD v0.7 - Point Cloud Data file format
+VERSION 0.7
+FIELDS x y z rgb label intensity
+SIZE 4 4 4 4 4 4
+TYPE F F F U U F
+COUNT 1 1 1 1 1 1
+WIDTH 3
+HEIGHT 1
+VIEWPOINT 0 0 0 1 0 0 0
+POINTS 3
+";
+
+    /// The fields of the points described by [`HEADER`], as little-endian bytes.
+    ///
+    /// The second point is invalid.
+    fn point_fields() -> Vec<[[u8; 4]; 6]> {
+        [
+            ([1.0, 2.0, 3.0], 0x00FF_0000_u32, 7_u32, 0.5_f32),
+            ([f32::NAN; 3], 0, 0, 0.0),
+            ([4.0, 5.0, 6.0], 0x0000_00FF, 2, 1.5),
+        ]
+        .into_iter()
+        .map(|([x, y, z], rgb, label, intensity): ([f32; 3], _, _, _)| {
+            [
+                x.to_le_bytes(),
+                y.to_le_bytes(),
+                z.to_le_bytes(),
+                rgb.to_le_bytes(),
+                label.to_le_bytes(),
+                intensity.to_le_bytes(),
+            ]
+        })
+        .collect()
+    }
+
+    /// Compresses `data` as LZF literal runs only.
+    fn lzf_literals(data: &[u8]) -> Vec<u8> {
+        data.chunks(32)
+            .flat_map(|run| std::iter::once(run.len() as u8 - 1).chain(run.iter().copied()))
+            .collect()
+    }
+
+    fn received_chunks(rx: std::sync::mpsc::Receiver<LoadedData>) -> Vec<Chunk> {
+        rx.into_iter()
+            .filter_map(|data| match data {
+                LoadedData::Chunk(_, _, chunk) => Some(chunk),
+                LoadedData::ArrowMsg(..) | LoadedData::LogMsg(..) => None,
+            })
+            .collect()
+    }
+
+    fn load_pcd_chunks(contents: &[u8]) -> Result<Vec<Chunk>, DataLoaderError> {
+        let settings = DataLoaderSettings::recommended(StoreId::random(StoreKind::Recording));
+        let (tx, rx) = std::sync::mpsc::channel();
+        load(
+            &settings,
+            std::path::Path::new("cloud.pcd"),
+            "pcd",
+            std::io::Cursor::new(contents),
+            &tx,
+        )?;
+        drop(tx);
+
+        Ok(received_chunks(rx))
+    }
+
+    /// Loads `contents` with at most `max_points_per_chunk` points per chunk.
+    fn load_las_chunks(contents: Vec<u8>, max_points_per_chunk: usize) -> Vec<Chunk> {
+        let (tx, rx) = std::sync::mpsc::channel();
+        let mut sender = ChunkSender {
+            loader_name: PointCloudLoader.name(),
+            store_id: StoreId::random(StoreKind::Recording),
+            timepoint: TimePoint::default(),
+            entity_path: "cloud.las".into(),
+            tx: &tx,
+            max_points_per_chunk,
+            num_chunks: 0,
+        };
+        load_las(std::io::Cursor::new(contents), &mut sender).unwrap();
+        drop(tx);
+
+        received_chunks(rx)
+    }
+
+    /// Checks that the valid points of [`point_fields`] have been loaded, with all their
+    /// attributes.
+    fn assert_points(chunks: &[Chunk]) {
+        assert_eq!(1, chunks.len());
+        let chunk = &chunks[0];
+
+        assert_eq!(
+            Some(vec![
+                Position3D::new(1.0, 2.0, 3.0),
+                Position3D::new(4.0, 5.0, 6.0),
+            ]),
+            chunk.component_batch::<Position3D>(0).map(Result::unwrap)
+        );
+        assert_eq!(
+            Some(vec![Color::from_rgb(255, 0, 0), Color::from_rgb(0, 0, 255)]),
+            chunk.component_batch::<Color>(0).map(Result::unwrap)
+        );
+        assert_eq!(
+            Some(vec![ClassId::from(7_u16), ClassId::from(2_u16)]),
+            chunk.component_batch::<ClassId>(0).map(Result::unwrap)
+        );
+
+        let intensities = chunk
+            .component_batch_raw(&ComponentName::from("intensity"), 0)
+            .unwrap()
+            .unwrap();
+        let intensities = intensities
+            .as_any()
+            .downcast_ref::<Arrow2PrimitiveArray<f32>>()
+            .unwrap();
+        assert_eq!(&[0.5, 1.5], intensities.values().as_slice());
+    }
+
+    #[test]
+    fn header() {
+        let header = "FIELDS x y z normal
+SIZE 4 4 8 4
+TYPE F F F F
+COUNT 1 1 1 3
+WIDTH 4
+HEIGHT 2
+DATA binary
+";
+        let header = PcdHeader::read(&mut header.as_bytes()).unwrap();
+        assert_eq!(PcdData::Binary, header.data);
+        assert_eq!(
+            vec![0, 4, 8, 16],
+            header
+                .fields
+                .iter()
+                .map(|field| field.offset)
+                .collect::<Vec<_>>()
+        );
+        assert_eq!(28, header.point_size);
+        assert_eq!(8, header.num_points); // WIDTH * HEIGHT, without POINTS
+
+        let invalid = [
+            // No DATA.
+            "FIELDS x\nSIZE 4\nTYPE F\n",
+            "FIELDS x\nSIZE 4\nTYPE F\nDATA lzma\n",
+            "FIELDS x y\nSIZE 4\nTYPE F F\nDATA ascii\n",
+            "FIELDS x\nSIZE 3\nTYPE F\nDATA ascii\n",
+            "FIELDS x\nSIZE 4\nTYPE F\nCOUNT 0\nDATA ascii\n",
+            "FIELDS x\nSIZE 4\nTYPE F\nCOUNT -1\nDATA ascii\n",
+            "FIELDS x y\nSIZE 4 4\nTYPE F F\nCOUNT 1\nDATA ascii\n",
+            "FIELDS x\nSIZE 4\nTYPE F\nCOUNT 1 1\nDATA ascii\n",
+            // Overflowing point size.
+            "FIELDS x\nSIZE 8\nTYPE F\nCOUNT 18446744073709551615\nDATA ascii\n",
+            "FIELDS x y\nSIZE 8 8\nTYPE F F\nCOUNT 2305843009213693951 1\nDATA ascii\n",
+        ];
+        for header in invalid {
+            assert!(
+                PcdHeader::read(&mut header.as_bytes()).is_err(),
+                "{header:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn ascii() {
+        let contents = format!(
+            "{HEADER}DATA ascii\n1 2 3 16711680 7 0.5\nnan nan nan 0 0 0\n4 5 6 255 2 1.5\n"
+        );
+        assert_points(&load_pcd_chunks(contents.as_bytes()).unwrap());
+
+        let contents = format!("{HEADER}DATA ascii\n1 2 3 oops 7 0.5\n");
+        assert!(load_pcd_chunks(contents.as_bytes()).is_err());
+    }
+
+    #[test]
+    fn binary() {
+        let mut contents = format!("{HEADER}DATA binary\n").into_bytes();
+        contents.extend(point_fields().into_iter().flatten().flatten());
+        assert_points(&load_pcd_chunks(&contents).unwrap());
+
+        // Fewer points than advertised: keep what's there.
+        let contents = format!(
+            "{}DATA binary\n",
+            HEADER.replace("POINTS 3", "POINTS 1000000000000")
+        );
+        let mut contents = contents.into_bytes();
+        contents.extend(point_fields().into_iter().flatten().flatten());
+        assert_points(&load_pcd_chunks(&contents).unwrap());
+
+        // Not even a single point.
+        let mut contents = format!("{HEADER}DATA binary\n").into_bytes();
+        contents.extend([0; 4]);
+        assert!(load_pcd_chunks(&contents).is_err());
+    }
+
+    #[test]
+    fn binary_compressed() {
+        // Each field is stored for all points, one after the other.
+        let points = point_fields();
+        let data = (0..6)
+            .flat_map(|field| points.iter().flat_map(move |point| point[field]))
+            .collect::<Vec<_>>();
+        let compressed = lzf_literals(&data);
+
+        let with_sizes = |compressed_size: usize, uncompressed_size: usize| {
+            let mut contents = format!("{HEADER}DATA binary_compressed\n").into_bytes();
+            contents.extend((compressed_size as u32).to_le_bytes());
+            contents.extend((uncompressed_size as u32).to_le_bytes());
+            contents.extend(&compressed);
+            contents
+        };
+
+        let contents = with_sizes(compressed.len(), data.len());
+        assert_points(&load_pcd_chunks(&contents).unwrap());
+
+        // Sizes that don't match the header or the data.
+        assert!(load_pcd_chunks(&with_sizes(compressed.len(), data.len() - 4)).is_err());
+        assert!(load_pcd_chunks(&with_sizes(compressed.len() + 1, data.len())).is_err());
+        assert!(load_pcd_chunks(&with_sizes(compressed.len(), u32::MAX as usize)).is_err());
+    }
+
+    #[test]
+    fn las() {
+        let mut builder = las::Builder::from((1, 2));
+        builder.point_format = las::point::Format::new(2).unwrap(); // with colors
+        let mut writer = las::Writer::new(
+            std::io::Cursor::new(Vec::new()),
+            builder.into_header().unwrap(),
+        )
+        .unwrap();
+
+        // Georeferenced points, far away from the origin.
+        let points = [
+            ([1000.5, 2000.0, 3000.0], [0xFFFF, 0, 0], 2),
+            ([1001.5, 2002.0, 3003.0], [0, 0x8000, 0], 7),
+            ([1002.5, 2004.0, 3006.0], [0, 0, 0x00FF], 9),
+        ];
+        for ([x, y, z], [red, green, blue], classification) in points {
+            writer
+                .write_point(las::Point {
+                    x,
+                    y,
+                    z,
+                    color: Some(las::Color::new(red, green, blue)),
+                    classification: las::point::Classification::new(classification).unwrap(),
+                    ..Default::default()
+                })
+                .unwrap();
+        }
+        let contents = writer.into_inner().unwrap().into_inner();
+
+        let chunks = load_las_chunks(contents, 2);
+        assert_eq!(3, chunks.len());
+
+        // The minimum of the bounds becomes the origin of the file's entity.
+        assert_eq!(&EntityPath::from("cloud.las"), chunks[0].entity_path());
+        assert_eq!(
+            Some(vec![Translation3D::new(1000.5, 2000.0, 3000.0)]),
+            chunks[0]
+                .component_batch::<Translation3D>(0)
+                .map(Result::unwrap)
+        );
+
+        // The points are split into chunks of at most 2 points, each on its own child entity.
+        assert_eq!(&EntityPath::from("cloud.las/0"), chunks[1].entity_path());
+        assert_eq!(&EntityPath::from("cloud.las/1"), chunks[2].entity_path());
+        assert_eq!(
+            Some(vec![
+                Position3D::new(0.0, 0.0, 0.0),
+                Position3D::new(1.0, 2.0, 3.0),
+            ]),
+            chunks[1]
+                .component_batch::<Position3D>(0)
+                .map(Result::unwrap)
+        );
+        assert_eq!(
+            Some(vec![Position3D::new(2.0, 4.0, 6.0)]),
+            chunks[2]
+                .component_batch::<Position3D>(0)
+                .map(Result::unwrap)
+        );
+
+        // 16-bit colors are truncated to 8 bits, classifications become class ids.
+        assert_eq!(
+            Some(vec![Color::from_rgb(255, 0, 0), Color::from_rgb(0, 128, 0)]),
+            chunks[1].component_batch::<Color>(0).map(Result::unwrap)
+        );
+        assert_eq!(
+            Some(vec![Color::from_rgb(0, 0, 0)]),
+            chunks[2].component_batch::<Color>(0).map(Result::unwrap)
+        );
+        assert_eq!(
+            Some(vec![ClassId::from(2_u16), ClassId::from(7_u16)]),
+            chunks[1].component_batch::<ClassId>(0).map(Result::unwrap)
+        );
+        assert_eq!(
+            Some(vec![ClassId::from(9_u16)]),
+            chunks[2].component_batch::<ClassId>(0).map(Result::unwrap)
+        );
+    }
+
+    #[test]
+    fn lzf() {
+        // Literals, then a back-reference overlapping with its own output.
+        assert_eq!(
+            Some(b"abcabcabc".to_vec()),
+            lzf_decompress(&[2, b'a', b'b', b'c', 0x80, 2], 9)
+        );
+
+        // A long back-reference: 7 + 1 + 2 bytes.
+        assert_eq!(
+            Some(vec![b'x'; 11]),
+            lzf_decompress(&[0, b'x', 0xE0, 1, 0], 11)
+        );
+
+        // Wrong size.
+        assert_eq!(None, lzf_decompress(&[2, b'a', b'b', b'c', 0x80, 2], 8));
+        assert_eq!(None, lzf_decompress(&[2, b'a', b'b', b'c', 0x80, 2], 10));
+
+        // Truncated literals, and a back-reference before the start of the output.
+        assert_eq!(None, lzf_decompress(&[2, b'a'], 3));
+        assert_eq!(None, lzf_decompress(&[0, b'a', 0x20, 5], 4));
+    }
+}
@@ -149,6 +149,70 @@ impl crate::DataLoader for RrdLoader {
 
         Ok(())
     }
+
+    /// Streams `http(s)://` URLs as they get downloaded, rather than waiting for the whole file.
+    fn load_from_uri(
+        &self,
+        settings: &crate::DataLoaderSettings,
+        uri: &url::Url,
+        tx: std::sync::mpsc::Sender<crate::LoadedData>,
+    ) -> Result<(), crate::DataLoaderError> {
+        use re_log_encoding::stream_rrd_from_http::{stream_rrd_from_http, HttpMessage};
+        use std::ops::ControlFlow;
+
+        let extension = crate::extension(std::path::Path::new(uri.path()));
+        if !matches!(extension.as_str(), "rbl" | "rrd") {
+            // NOTE: blueprints and recordings has the same file format
+            return Err(crate::DataLoaderError::Incompatible(uri.as_str().into()));
+        }
+
+        match uri.scheme() {
+            "http" | "https" => {}
+
+            #[cfg(not(target_arch = "wasm32"))]
+            "file" => {
+                if let Ok(path) = uri.to_file_path() {
+                    return self.load_from_path(settings, path, tx);
+                }
+                return Err(crate::DataLoaderError::Incompatible(uri.as_str().into()));
+            }
+
+            _ => return Err(crate::DataLoaderError::Incompatible(uri.as_str().into())),
+        }
+
+        re_log::debug!(%uri, loader = self.name(), "Streaming rrd data from http…");
+
+        // Same as for file contents: we only ever patch the app IDs of blueprints.
+        let forced_application_id = if extension == "rbl" {
+            settings.opened_application_id.clone()
+        } else {
+            None
+        };
+
+        let url = uri.to_string();
+        stream_rrd_from_http(
+            url.clone(),
+            std::sync::Arc::new(move |msg| match msg {
+                HttpMessage::LogMsg(msg) => {
+                    let msg = with_forced_ids(msg, forced_application_id.as_ref(), None);
+                    let data = LoadedData::LogMsg(RrdLoader::name(&RrdLoader), msg);
+                    if tx.send(data).is_ok() {
+                        ControlFlow::Continue(())
+                    } else {
+                        re_log::info_once!("Closing connection to {url}");
+                        ControlFlow::Break(())
+                    }
+                }
+                HttpMessage::Success => ControlFlow::Break(()),
+                HttpMessage::Failure(err) => {
+                    re_log::error!("Failed to stream {url}: {err}");
+                    ControlFlow::Break(())
+                }
+            }),
+        );
+
+        Ok(())
+    }
 }
 
 fn decode_and_stream<R: std::io::Read>(
@@ -169,37 +233,7 @@ fn decode_and_stream<R: std::io::Read>(
             }
         };
 
-        let msg = if forced_application_id.is_some() || forced_store_id.is_some() {
-            match msg {
-                re_log_types::LogMsg::SetStoreInfo(set_store_info) => {
-                    re_log_types::LogMsg::SetStoreInfo(re_log_types::SetStoreInfo {
-                        info: re_log_types::StoreInfo {
-                            application_id: forced_application_id
-                                .cloned()
-                                .unwrap_or(set_store_info.info.application_id),
-                            store_id: forced_store_id
-                                .cloned()
-                                .unwrap_or(set_store_info.info.store_id),
-                            ..set_store_info.info
-                        },
-                        ..set_store_info
-                    })
-                }
-
-                re_log_types::LogMsg::ArrowMsg(store_id, arrow_msg) => {
-                    re_log_types::LogMsg::ArrowMsg(
-                        forced_store_id.cloned().unwrap_or(store_id),
-                        arrow_msg,
-                    )
-                }
-
-                re_log_types::LogMsg::BlueprintActivationCommand(blueprint_activation_command) => {
-                    re_log_types::LogMsg::BlueprintActivationCommand(blueprint_activation_command)
-                }
-            }
-        } else {
-            msg
-        };
+        let msg = with_forced_ids(msg, forced_application_id, forced_store_id);
 
         let data = LoadedData::LogMsg(RrdLoader::name(&RrdLoader), msg);
         if tx.send(data).is_err() {
@@ -208,6 +242,43 @@ fn decode_and_stream<R: std::io::Read>(
     }
 }
 
+/// Patches the application and/or store IDs of `msg`, if forced.
+fn with_forced_ids(
+    msg: re_log_types::LogMsg,
+    forced_application_id: Option<&ApplicationId>,
+    forced_store_id: Option<&StoreId>,
+) -> re_log_types::LogMsg {
+    if forced_application_id.is_some() || forced_store_id.is_some() {
+        match msg {
+            re_log_types::LogMsg::SetStoreInfo(set_store_info) => {
+                re_log_types::LogMsg::SetStoreInfo(re_log_types::SetStoreInfo {
+                    info: re_log_types::StoreInfo {
+                        application_id: forced_application_id
+                            .cloned()
+                            .unwrap_or(set_store_info.info.application_id),
+                        store_id: forced_store_id
+                            .cloned()
+                            .unwrap_or(set_store_info.info.store_id),
+                        ..set_store_info.info
+                    },
+                    ..set_store_info
+                })
+            }
+
+            re_log_types::LogMsg::ArrowMsg(store_id, arrow_msg) => re_log_types::LogMsg::ArrowMsg(
+                forced_store_id.cloned().unwrap_or(store_id),
+                arrow_msg,
+            ),
+
+            re_log_types::LogMsg::BlueprintActivationCommand(blueprint_activation_command) => {
+                re_log_types::LogMsg::BlueprintActivationCommand(blueprint_activation_command)
+            }
+        }
+    } else {
+        msg
+    }
+}
+
 // Retryable file reader that keeps retrying to read more data despite
 // reading zero bytes or reaching EOF.
 #[cfg(not(target_arch = "wasm32"))]
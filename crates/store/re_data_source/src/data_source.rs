@@ -19,6 +19,12 @@ pub enum DataSource {
     #[cfg(not(target_arch = "wasm32"))]
     FilePath(re_log_types::FileSource, std::path::PathBuf),
 
+    /// Any other URI that our [`re_data_loader::DataLoader`]s might support, e.g.
+    /// `http://example.com/scan.ply` or `file:///tmp/scan.ply?frame=3`.
+    ///
+    /// See [`re_data_loader::load_from_uri`].
+    Uri(re_log_types::FileSource, String),
+
     /// The contents of a file.
     ///
     /// This is what you get when loading a file on Web, or when using drag-n-drop.
@@ -95,8 +101,14 @@ impl DataSource {
             return Self::RerunGrpcUrl { url: uri };
         }
 
-        if uri.starts_with("file://") || path.exists() {
+        if uri.starts_with("file://") {
+            Self::Uri(file_source, uri)
+        } else if path.exists() {
             Self::FilePath(file_source, path)
+        } else if (uri.starts_with("http://") || uri.starts_with("https://"))
+            && !is_rrd_http_url(&uri)
+        {
+            Self::Uri(file_source, uri)
         } else if uri.starts_with("http://")
             || uri.starts_with("https://")
             || (uri.starts_with("www.") && (uri.ends_with(".rrd") || uri.ends_with(".rbl")))
@@ -133,6 +145,11 @@ impl DataSource {
             #[cfg(not(target_arch = "wasm32"))]
             Self::FilePath(_, path) => path.file_name().map(|s| s.to_string_lossy().to_string()),
             Self::FileContents(_, file_contents) => Some(file_contents.name.clone()),
+            Self::Uri(_, uri) => url::Url::parse(uri)
+                .ok()?
+                .path_segments()?
+                .next_back()
+                .map(ToOwned::to_owned),
             Self::WebSocketAddr(_) => None,
             #[cfg(not(target_arch = "wasm32"))]
             Self::Stdin => None,
@@ -225,6 +242,43 @@ impl DataSource {
                 Ok(rx)
             }
 
+            Self::Uri(file_source, uri) => {
+                let (tx, rx) = if uri.starts_with("file://") {
+                    re_smart_channel::smart_channel(
+                        SmartMessageSource::File(uri.clone().into()),
+                        SmartChannelSource::File(uri.clone().into()),
+                    )
+                } else {
+                    re_smart_channel::smart_channel(
+                        SmartMessageSource::RrdHttpStream { url: uri.clone() },
+                        SmartChannelSource::RrdHttpStream {
+                            url: uri.clone(),
+                            follow: false,
+                        },
+                    )
+                };
+
+                // This `StoreId` will be communicated to all `DataLoader`s, which may or may not
+                // decide to use it depending on whether they want to share a common recording
+                // or not.
+                let shared_store_id =
+                    re_log_types::StoreId::random(re_log_types::StoreKind::Recording);
+                let settings = re_data_loader::DataLoaderSettings {
+                    opened_application_id: file_source.recommended_application_id().cloned(),
+                    opened_store_id: file_source.recommended_recording_id().cloned(),
+                    force_store_info: file_source.force_store_info(),
+                    ..re_data_loader::DataLoaderSettings::recommended(shared_store_id)
+                };
+                re_data_loader::load_from_uri(&settings, file_source, &uri, &tx)
+                    .map_err(|err| anyhow::anyhow!("{uri}: {err}"))?;
+
+                if let Some(on_msg) = on_msg {
+                    on_msg();
+                }
+
+                Ok(rx)
+            }
+
             Self::WebSocketAddr(rerun_server_ws_url) => {
                 crate::web_sockets::connect_to_ws_url(&rerun_server_ws_url, on_msg)
             }
@@ -253,18 +307,30 @@ impl DataSource {
     }
 }
 
+/// Is this an `http(s)://` URL to an `.rrd` recording or `.rbl` blueprint, which we can stream
+/// (and follow) as it is being downloaded?
+///
+/// URLs without any extension are assumed to be RRDs too, as that's what a Rerun server serves.
+/// Anything else is handed over to all [`re_data_loader::DataLoader`]s.
+pub fn is_rrd_http_url(url: &str) -> bool {
+    let Ok(url) = url::Url::parse(url) else {
+        return false;
+    };
+
+    let path = std::path::Path::new(url.path());
+    match path.extension().and_then(|extension| extension.to_str()) {
+        Some(extension) => re_data_loader::SUPPORTED_RERUN_EXTENSIONS
+            .contains(&extension.to_ascii_lowercase().as_str()),
+        None => true,
+    }
+}
+
 #[cfg(not(target_arch = "wasm32"))]
 #[test]
 fn test_data_source_from_uri() {
     use re_log_types::FileSource;
 
-    let file = [
-        "file://foo",
-        "foo.rrd",
-        "foo.png",
-        "/foo/bar/baz",
-        "D:/file",
-    ];
+    let file = ["foo.rrd", "foo.png", "/foo/bar/baz", "D:/file"];
     let http = [
         "http://foo.zip",
         "https://foo.zip",
@@ -273,6 +339,12 @@ fn test_data_source_from_uri() {
         "www.foo.zip/blueprint.rbl",
     ];
     let ws = ["ws://foo.zip", "wss://foo.zip", "127.0.0.1"];
+    let uri = [
+        "file://foo",
+        "file:///tmp/scan.ply?frame=3",
+        "http://example.com/scan.ply",
+        "https://example.com/data/bag.mcap?token=1234",
+    ];
 
     let file_source = FileSource::DragAndDrop {
         recommended_application_id: None,
@@ -309,4 +381,14 @@ fn test_data_source_from_uri() {
             "Expected {uri:?} to be categorized as WebSocketAddr"
         );
     }
+
+    for uri in uri {
+        assert!(
+            matches!(
+                DataSource::from_uri(file_source.clone(), uri.to_owned()),
+                DataSource::Uri { .. }
+            ),
+            "Expected {uri:?} to be categorized as Uri"
+        );
+    }
 }
@@ -12,7 +12,7 @@ mod web_sockets;
 #[cfg(not(target_arch = "wasm32"))]
 mod load_stdin;
 
-pub use self::data_source::DataSource;
+pub use self::data_source::{is_rrd_http_url, DataSource};
 pub use self::web_sockets::connect_to_ws_url;
 
 // ----------------------------------------------------------------------------
@@ -15,10 +15,10 @@ pub fn connect_to_ws_url(
         },
     );
 
-    re_log::info!("Connecting to WebSocket server at {url:?}…");
+    let redacted_url = re_ws_comms::redact_auth_token(url);
+    re_log::info!("Connecting to WebSocket server at {redacted_url:?}…");
 
     let callback = {
-        let url = url.to_owned();
         move |binary: Vec<u8>| match re_ws_comms::decode_log_msg(&binary) {
             Ok(log_msg) => {
                 if tx.send(log_msg).is_ok() {
@@ -27,7 +27,7 @@ pub fn connect_to_ws_url(
                     }
                     std::ops::ControlFlow::Continue(())
                 } else {
-                    re_log::info_once!("Closing connection to {url}");
+                    re_log::info_once!("Closing connection to {redacted_url}");
                     std::ops::ControlFlow::Break(())
                 }
             }
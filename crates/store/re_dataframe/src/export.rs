@@ -0,0 +1,316 @@
+//! Exporting query results to Parquet and Arrow IPC files.
+//!
+//! See [`QueryHandle::export`].
+
+use std::sync::Arc;
+
+use arrow::{
+    array::{RecordBatch as ArrowRecordBatch, RecordBatchOptions},
+    datatypes::{Field as ArrowField, Schema as ArrowSchema, SchemaRef as ArrowSchemaRef},
+};
+use arrow2::datatypes::Schema as Arrow2Schema;
+use itertools::Itertools as _;
+
+use re_chunk::util::concatenate_record_batches;
+use re_query::StorageEngineLike;
+
+use crate::QueryHandle;
+
+// ---
+
+/// The file formats that query results can be exported to.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ExportFormat {
+    /// [Apache Parquet](https://parquet.apache.org/), compressed with zstd.
+    #[default]
+    Parquet,
+
+    /// The [Arrow IPC file format](https://arrow.apache.org/docs/format/Columnar.html#ipc-file-format),
+    /// also known as Feather V2.
+    ArrowIpc,
+}
+
+impl ExportFormat {
+    /// Guesses the format from the extension of `path`: `.parquet`, or `.arrow`/`.ipc`/`.feather`.
+    pub fn from_path(path: impl AsRef<std::path::Path>) -> Option<Self> {
+        let extension = path.as_ref().extension()?.to_str()?.to_lowercase();
+        extension.parse().ok()
+    }
+
+    /// The canonical file extension for this format, without the leading dot.
+    pub fn file_extension(&self) -> &'static str {
+        match self {
+            Self::Parquet => "parquet",
+            Self::ArrowIpc => "arrow",
+        }
+    }
+}
+
+impl std::fmt::Display for ExportFormat {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Parquet => write!(f, "parquet"),
+            Self::ArrowIpc => write!(f, "arrow"),
+        }
+    }
+}
+
+impl std::str::FromStr for ExportFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "parquet" => Ok(Self::Parquet),
+            "arrow" | "ipc" | "feather" => Ok(Self::ArrowIpc),
+            _ => Err(format!(
+                "unknown export format {s:?}, expected one of: parquet, arrow"
+            )),
+        }
+    }
+}
+
+// ---
+
+/// How many rows are accumulated before being written out as a single record batch.
+const EXPORT_BATCH_NUM_ROWS: usize = 4096;
+
+impl<E: StorageEngineLike + Clone> QueryHandle<E> {
+    /// Runs the query to completion and writes all of its results to `write`, using the
+    /// specified format.
+    ///
+    /// The output strictly follows [`Self::schema`], including its column metadata.
+    /// Rows are written in batches of a few thousands, so the whole dataframe never has to fit in
+    /// memory at once.
+    ///
+    /// Like any other iteration method, this advances the internal cursors of the handle: use
+    /// [`Self::seek_to_row`] beforehand in order to only export part of the results.
+    ///
+    /// Returns the number of rows written.
+    pub fn export(
+        &self,
+        format: ExportFormat,
+        write: impl std::io::Write + Send,
+    ) -> anyhow::Result<u64> {
+        re_tracing::profile_function!(format.to_string());
+
+        let schema = self.schema().clone();
+        let arrow_schema = to_arrow_schema(&schema);
+
+        let mut writer = BatchWriter::new(format, write, arrow_schema.clone())?;
+
+        let mut num_rows = 0;
+        for rows in &self.batch_iter().chunks(EXPORT_BATCH_NUM_ROWS) {
+            let rows = rows.collect_vec();
+            let batch = concatenate_record_batches(schema.clone(), &rows)?;
+
+            // The schema of the resulting batch is the same as the handle's, modulo the
+            // arrow2->arrow-rs conversion: make sure the writer sees them as strictly identical.
+            let columns = batch.try_to_arrow_record_batch()?.columns().to_vec();
+            let batch = ArrowRecordBatch::try_new_with_options(
+                arrow_schema.clone(),
+                columns,
+                &RecordBatchOptions::new().with_row_count(Some(rows.len())),
+            )?;
+
+            writer.write(&batch)?;
+            num_rows += rows.len() as u64;
+        }
+
+        writer.finish()?;
+
+        Ok(num_rows)
+    }
+
+    /// Same as [`Self::export`], but writes to a new file at `path`.
+    ///
+    /// If the file already exists, it is overwritten.
+    pub fn export_to_file(
+        &self,
+        format: ExportFormat,
+        path: impl AsRef<std::path::Path>,
+    ) -> anyhow::Result<u64> {
+        use anyhow::Context as _;
+
+        let path = path.as_ref();
+        let file = std::fs::File::create(path).with_context(|| format!("{path:?}"))?;
+
+        self.export(format, std::io::BufWriter::new(file))
+    }
+}
+
+fn to_arrow_schema(schema: &Arrow2Schema) -> ArrowSchemaRef {
+    let fields: Vec<ArrowField> = schema.fields.iter().map(|f| f.clone().into()).collect();
+    let metadata = schema.metadata.clone().into_iter().collect();
+
+    Arc::new(ArrowSchema::new(fields).with_metadata(metadata))
+}
+
+enum BatchWriter<W: std::io::Write + Send> {
+    Parquet(parquet::arrow::ArrowWriter<W>),
+    ArrowIpc(arrow::ipc::writer::FileWriter<W>),
+}
+
+impl<W: std::io::Write + Send> BatchWriter<W> {
+    fn new(format: ExportFormat, write: W, schema: ArrowSchemaRef) -> anyhow::Result<Self> {
+        Ok(match format {
+            ExportFormat::Parquet => {
+                use parquet::{
+                    basic::{Compression, ZstdLevel},
+                    file::properties::WriterProperties,
+                };
+
+                let props = WriterProperties::builder()
+                    .set_compression(Compression::ZSTD(ZstdLevel::default()))
+                    .build();
+
+                Self::Parquet(parquet::arrow::ArrowWriter::try_new(
+                    write,
+                    schema,
+                    Some(props),
+                )?)
+            }
+
+            ExportFormat::ArrowIpc => {
+                Self::ArrowIpc(arrow::ipc::writer::FileWriter::try_new(write, &schema)?)
+            }
+        })
+    }
+
+    fn write(&mut self, batch: &ArrowRecordBatch) -> anyhow::Result<()> {
+        match self {
+            Self::Parquet(writer) => writer.write(batch)?,
+            Self::ArrowIpc(writer) => writer.write(batch)?,
+        }
+        Ok(())
+    }
+
+    /// Writes the footer of the file and flushes everything to the underlying writer.
+    fn finish(self) -> anyhow::Result<()> {
+        use std::io::Write as _;
+
+        let mut write = match self {
+            Self::Parquet(writer) => writer.into_inner()?,
+            Self::ArrowIpc(writer) => writer.into_inner()?,
+        };
+        write.flush()?;
+        Ok(())
+    }
+}
+
+// ---
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use re_chunk::{Chunk, RowId};
+    use re_chunk_store::{ChunkStore, ChunkStoreConfig, ChunkStoreHandle, QueryExpression};
+    use re_log_types::{
+        build_frame_nr,
+        example_components::{MyColor, MyPoint},
+        EntityPath, TimeInt, Timeline,
+    };
+
+    use crate::QueryEngine;
+
+    use super::*;
+
+    fn create_store(num_rows: i64) -> anyhow::Result<ChunkStoreHandle> {
+        let mut store = ChunkStore::new(
+            re_log_types::StoreId::random(re_log_types::StoreKind::Recording),
+            ChunkStoreConfig::COMPACTION_DISABLED,
+        );
+
+        let mut builder = Chunk::builder(EntityPath::from("/this/that"));
+        for i in 0..num_rows {
+            let points = MyPoint::from_iter(i as u32..i as u32 + 1);
+            let colors = MyColor::from_iter(i as u32..i as u32 + 1);
+            builder = builder.with_component_batches(
+                RowId::new(),
+                [build_frame_nr(TimeInt::new_temporal(i))],
+                [&points as _, &colors as _],
+            );
+        }
+        store.insert_chunk(&Arc::new(builder.build()?))?;
+
+        Ok(ChunkStoreHandle::new(store))
+    }
+
+    fn query() -> QueryExpression {
+        QueryExpression {
+            filtered_index: Some(Timeline::new_sequence("frame_nr")),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn export_parquet() -> anyhow::Result<()> {
+        // More than a single batch's worth.
+        let num_rows = EXPORT_BATCH_NUM_ROWS as i64 + 10;
+
+        let query_engine = QueryEngine::from_store(create_store(num_rows)?);
+        let query_handle = query_engine.query(query());
+
+        let mut file = tempfile::tempfile()?;
+        let num_rows_written = query_handle.export(ExportFormat::Parquet, &mut file)?;
+        assert_eq!(num_rows as u64, num_rows_written);
+
+        let reader = parquet::arrow::arrow_reader::ParquetRecordBatchReaderBuilder::try_new(file)?
+            .build()?;
+        let batches: Vec<_> = reader.collect::<Result<_, _>>()?;
+
+        assert_eq!(
+            num_rows as usize,
+            batches.iter().map(|batch| batch.num_rows()).sum::<usize>()
+        );
+        for batch in &batches {
+            assert_eq!(
+                query_handle.schema().fields.len(),
+                batch.schema().fields().len()
+            );
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn export_arrow_ipc() -> anyhow::Result<()> {
+        let num_rows = 100;
+
+        let query_engine = QueryEngine::from_store(create_store(num_rows)?);
+        let query_handle = query_engine.query(query());
+
+        let mut buffer = Vec::new();
+        let num_rows_written = query_handle.export(ExportFormat::ArrowIpc, &mut buffer)?;
+        assert_eq!(num_rows as u64, num_rows_written);
+
+        let reader = arrow::ipc::reader::FileReader::try_new(std::io::Cursor::new(buffer), None)?;
+        assert_eq!(to_arrow_schema(query_handle.schema()), reader.schema());
+
+        let batches: Vec<_> = reader.collect::<Result<_, _>>()?;
+        assert_eq!(
+            num_rows as usize,
+            batches.iter().map(|batch| batch.num_rows()).sum::<usize>()
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn export_format_from_path() {
+        assert_eq!(
+            Some(ExportFormat::Parquet),
+            ExportFormat::from_path("/tmp/out.parquet")
+        );
+        assert_eq!(
+            Some(ExportFormat::ArrowIpc),
+            ExportFormat::from_path("/tmp/out.ARROW")
+        );
+        assert_eq!(
+            Some(ExportFormat::ArrowIpc),
+            ExportFormat::from_path("out.feather")
+        );
+        assert_eq!(None, ExportFormat::from_path("out.csv"));
+        assert_eq!(None, ExportFormat::from_path("out"));
+    }
+}
@@ -3,17 +3,24 @@
 mod engine;
 mod query;
 
+#[cfg(feature = "export")]
+mod export;
+
 pub use self::engine::{QueryEngine, RecordBatch};
 pub use self::query::QueryHandle;
 
+#[cfg(feature = "export")]
+pub use self::export::ExportFormat;
+
 #[doc(no_inline)]
 pub use self::external::arrow2::chunk::Chunk as Arrow2Chunk;
 #[doc(no_inline)]
 pub use self::external::re_chunk::{util::concatenate_record_batches, TransportChunk};
 #[doc(no_inline)]
 pub use self::external::re_chunk_store::{
-    ChunkStoreConfig, ChunkStoreHandle, ColumnSelector, ComponentColumnSelector, Index, IndexRange,
-    IndexValue, QueryExpression, SparseFillStrategy, TimeColumnSelector, ViewContentsSelector,
+    ChunkStoreConfig, ChunkStoreHandle, ColumnSelector, ComponentColumnSelector,
+    FilterIsNotNullMode, Index, IndexRange, IndexValue, QueryExpression, SparseFillStrategy,
+    TimeColumnSelector, ViewContentsSelector,
 };
 #[doc(no_inline)]
 pub use self::external::re_log_types::{
@@ -13,7 +13,6 @@ use arrow2::{
     },
     chunk::Chunk as Arrow2Chunk,
     datatypes::Schema as Arrow2Schema,
-    Either,
 };
 use itertools::Itertools;
 
@@ -24,8 +23,8 @@ use re_chunk::{
 };
 use re_chunk_store::{
     ChunkStore, ColumnDescriptor, ColumnSelector, ComponentColumnDescriptor,
-    ComponentColumnSelector, Index, IndexValue, QueryExpression, SparseFillStrategy,
-    TimeColumnDescriptor, TimeColumnSelector,
+    ComponentColumnSelector, FilterIsNotNullMode, Index, IndexValue, QueryExpression,
+    SparseFillStrategy, TimeColumnDescriptor, TimeColumnSelector,
 };
 use re_log_types::ResolvedTimeRange;
 use re_query::{QueryCache, StorageEngineLike};
@@ -298,24 +297,44 @@ impl<E: StorageEngineLike> QueryHandle<E> {
         } else {
             re_tracing::profile_scope!("index_values");
 
-            let mut view_chunks = view_chunks.iter();
-            let view_chunks = if let Some(view_pov_chunks_idx) = view_pov_chunks_idx {
-                Either::Left(view_chunks.nth(view_pov_chunks_idx).into_iter())
-            } else {
-                Either::Right(view_chunks)
-            };
-
-            let mut all_unique_index_values: BTreeSet<TimeInt> = view_chunks
-                .flat_map(|chunks| {
-                    chunks.iter().filter_map(|(_cursor, chunk)| {
+            let unique_index_values_of = |chunks: &[(AtomicU64, Chunk)]| -> BTreeSet<TimeInt> {
+                chunks
+                    .iter()
+                    .filter_map(|(_cursor, chunk)| {
                         chunk
                             .timelines()
                             .get(&filtered_index)
                             .map(|time_column| time_column.times())
                     })
-                })
-                .flatten()
-                .collect();
+                    .flatten()
+                    .collect()
+            };
+
+            let mut all_unique_index_values: BTreeSet<TimeInt> = if view_pov_chunks_idx.is_empty() {
+                view_chunks
+                    .iter()
+                    .flat_map(|chunks| unique_index_values_of(chunks))
+                    .collect()
+            } else {
+                // Point-of-view columns that aren't part of the view contents never contain any data.
+                let mut pov_unique_index_values = view_pov_chunks_idx.iter().map(|view_idx| {
+                    view_idx
+                        .and_then(|view_idx| view_chunks.get(view_idx))
+                        .map(|chunks| unique_index_values_of(chunks))
+                        .unwrap_or_default()
+                });
+
+                match self.query.filtered_is_not_null_mode {
+                    FilterIsNotNullMode::All => {
+                        let first = pov_unique_index_values.next().unwrap_or_default();
+                        pov_unique_index_values.fold(first, |acc, index_values| {
+                            acc.intersection(&index_values).copied().collect()
+                        })
+                    }
+
+                    FilterIsNotNullMode::Any => pov_unique_index_values.flatten().collect(),
+                }
+            };
 
             if let Some(filtered_index_values) = self.query.filtered_index_values.as_ref() {
                 all_unique_index_values.retain(|time| filtered_index_values.contains(time));
@@ -459,8 +478,9 @@ impl<E: StorageEngineLike> QueryHandle<E> {
         cache: &QueryCache,
         query: &RangeQuery,
         view_contents: &[ColumnDescriptor],
-    ) -> (Option<usize>, Vec<Vec<(AtomicU64, Chunk)>>) {
-        let mut view_pov_chunks_idx = self.query.filtered_is_not_null.as_ref().map(|_| usize::MAX);
+    ) -> (Vec<Option<usize>>, Vec<Vec<(AtomicU64, Chunk)>>) {
+        // One entry per `filtered_is_not_null` column: `None` if it isn't part of the view contents.
+        let mut view_pov_chunks_idx = vec![None; self.query.filtered_is_not_null.len()];
 
         let view_chunks = view_contents
             .iter()
@@ -473,11 +493,16 @@ impl<E: StorageEngineLike> QueryHandle<E> {
                         .fetch_chunks(store, cache, query, &column.entity_path, [&column.into()])
                         .unwrap_or_default();
 
-                    if let Some(pov) = self.query.filtered_is_not_null.as_ref() {
+                    for (pov, pov_chunks_idx) in self
+                        .query
+                        .filtered_is_not_null
+                        .iter()
+                        .zip(&mut view_pov_chunks_idx)
+                    {
                         if pov.entity_path == column.entity_path
                             && column.component_name.matches(&pov.component_name)
                         {
-                            view_pov_chunks_idx = Some(idx);
+                            *pov_chunks_idx = Some(idx);
                         }
                     }
 
@@ -1114,6 +1139,41 @@ impl<E: StorageEngineLike> QueryHandle<E> {
                         .map(|unit| StreamingJoinState::Retrofilled(unit.clone()));
                 }
             }
+
+            SparseFillStrategy::LatestAtView => {
+                // Everything that yielded `null` for the current iteration.
+                let null_streaming_states = view_streaming_state
+                    .iter_mut()
+                    .enumerate()
+                    .filter(|(_view_idx, streaming_state)| streaming_state.is_none());
+
+                for (view_idx, streaming_state) in null_streaming_states {
+                    let Some(ColumnDescriptor::Component(descr)) =
+                        state.view_contents.get(view_idx)
+                    else {
+                        continue;
+                    };
+                    let Some(view_chunks) = state.view_chunks.get(view_idx) else {
+                        continue;
+                    };
+
+                    // The view chunks have already been filtered down to the queried index range
+                    // (clears included), so a latest-at query on those never looks past the start
+                    // of the range.
+                    //
+                    // TODO(cmc): Same as above, this could cache results across consecutive nulls.
+                    let query =
+                        re_chunk::LatestAtQuery::new(state.filtered_index, *cur_index_value);
+
+                    *streaming_state = view_chunks
+                        .iter()
+                        .filter_map(|(_cursor, chunk)| {
+                            chunk.latest_at(&query, descr.component_name).into_unit()
+                        })
+                        .max_by_key(|unit| unit.index(&state.filtered_index))
+                        .map(StreamingJoinState::Retrofilled);
+                }
+            }
         }
 
         // We are stitching a bunch of unrelated cells together in order to create the final row
@@ -1507,6 +1567,90 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn sparse_fill_strategy_latestatview() -> anyhow::Result<()> {
+        re_log::setup_logging();
+
+        let store = ChunkStoreHandle::new(create_nasty_store()?);
+        eprintln!("{store}");
+        let query_cache = QueryCache::new_handle(store.clone());
+        let query_engine = QueryEngine::new(store.clone(), query_cache.clone());
+
+        let filtered_index = Some(Timeline::new_sequence("frame_nr"));
+
+        // Within the range: must behave just like `LatestAtGlobal`.
+        {
+            let query = QueryExpression {
+                filtered_index,
+                filtered_index_range: Some(ResolvedTimeRange::new(30, 70)),
+                sparse_fill_strategy: SparseFillStrategy::LatestAtView,
+                ..Default::default()
+            };
+            eprintln!("{query:#?}:");
+
+            let query_handle = query_engine.query(query.clone());
+            assert_eq!(
+                query_engine.query(query.clone()).into_iter().count() as u64,
+                query_handle.num_rows()
+            );
+            let dataframe = concatenate_record_batches(
+                query_handle.schema().clone(),
+                &query_handle.into_batch_iter().collect_vec(),
+            )?;
+            eprintln!("{dataframe}");
+
+            let got = format!("{:#?}", dataframe.data.iter().collect_vec());
+            let expected = unindent::unindent(
+                "\
+                [
+                    Int64[30, 40, 50, 60, 70],
+                    Timestamp(Nanosecond, None)[None, None, 1970-01-01 00:00:00.000000050, None, 1970-01-01 00:00:00.000000070],
+                    ListArray[[2], [3], [4], [4], [6]],
+                    ListArray[[c], [c], [c], [c], [c]],
+                    ListArray[[{x: 2, y: 2}], [{x: 3, y: 3}], [{x: 4, y: 4}], [{x: 5, y: 5}], [{x: 8, y: 8}]],
+                ]\
+                "
+            );
+
+            similar_asserts::assert_eq!(expected, got);
+        }
+
+        // Starting in a gap: nothing from before the range may leak in.
+        {
+            let query = QueryExpression {
+                filtered_index,
+                filtered_index_range: Some(ResolvedTimeRange::new(55, 70)),
+                sparse_fill_strategy: SparseFillStrategy::LatestAtView,
+                ..Default::default()
+            };
+            eprintln!("{query:#?}:");
+
+            let query_handle = query_engine.query(query.clone());
+            let dataframe = concatenate_record_batches(
+                query_handle.schema().clone(),
+                &query_handle.into_batch_iter().collect_vec(),
+            )?;
+            eprintln!("{dataframe}");
+
+            let got = format!("{:#?}", dataframe.data.iter().collect_vec());
+            let expected = unindent::unindent(
+                "\
+                [
+                    Int64[60, 70],
+                    Timestamp(Nanosecond, None)[None, 1970-01-01 00:00:00.000000070],
+                    ListArray[None, [6]],
+                    ListArray[[c], [c]],
+                    ListArray[[{x: 5, y: 5}], [{x: 8, y: 8}]],
+                ]\
+                ",
+            );
+
+            similar_asserts::assert_eq!(expected, got);
+        }
+
+        Ok(())
+    }
+
     #[test]
     fn filtered_index_range() -> anyhow::Result<()> {
         re_log::setup_logging();
@@ -1720,10 +1864,10 @@ mod tests {
         {
             let query = QueryExpression {
                 filtered_index,
-                filtered_is_not_null: Some(ComponentColumnSelector {
+                filtered_is_not_null: vec![ComponentColumnSelector {
                     entity_path: "no/such/entity".into(),
                     component_name: MyPoint::name().to_string(),
-                }),
+                }],
                 ..Default::default()
             };
             eprintln!("{query:#?}:");
@@ -1749,10 +1893,10 @@ mod tests {
         {
             let query = QueryExpression {
                 filtered_index,
-                filtered_is_not_null: Some(ComponentColumnSelector {
+                filtered_is_not_null: vec![ComponentColumnSelector {
                     entity_path: entity_path.clone(),
                     component_name: "AComponentColumnThatDoesntExist".into(),
-                }),
+                }],
                 ..Default::default()
             };
             eprintln!("{query:#?}:");
@@ -1778,10 +1922,10 @@ mod tests {
         {
             let query = QueryExpression {
                 filtered_index,
-                filtered_is_not_null: Some(ComponentColumnSelector {
+                filtered_is_not_null: vec![ComponentColumnSelector {
                     entity_path: entity_path.clone(),
                     component_name: MyPoint::name().to_string(),
-                }),
+                }],
                 ..Default::default()
             };
             eprintln!("{query:#?}:");
@@ -1817,10 +1961,10 @@ mod tests {
         {
             let query = QueryExpression {
                 filtered_index,
-                filtered_is_not_null: Some(ComponentColumnSelector {
+                filtered_is_not_null: vec![ComponentColumnSelector {
                     entity_path: entity_path.clone(),
                     component_name: MyColor::name().to_string(),
-                }),
+                }],
                 ..Default::default()
             };
             eprintln!("{query:#?}:");
@@ -1855,6 +1999,105 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn filtered_is_not_null_multiple() -> anyhow::Result<()> {
+        re_log::setup_logging();
+
+        let store = ChunkStoreHandle::new(create_nasty_store()?);
+        eprintln!("{store}");
+        let query_cache = QueryCache::new_handle(store.clone());
+        let query_engine = QueryEngine::new(store.clone(), query_cache.clone());
+
+        let filtered_index = Some(Timeline::new_sequence("frame_nr"));
+        let entity_path: EntityPath = "this/that".into();
+
+        let my_point = ComponentColumnSelector {
+            entity_path: entity_path.clone(),
+            component_name: MyPoint::name().to_string(),
+        };
+        let my_color = ComponentColumnSelector {
+            entity_path: entity_path.clone(),
+            component_name: MyColor::name().to_string(),
+        };
+        let no_such_entity = ComponentColumnSelector {
+            entity_path: "no/such/entity".into(),
+            component_name: MyPoint::name().to_string(),
+        };
+
+        let only_my_color = unindent::unindent(
+            "\
+            [
+                Int64[30, 40, 50, 70],
+                Timestamp(Nanosecond, None)[None, None, 1970-01-01 00:00:00.000000050, 1970-01-01 00:00:00.000000070],
+                ListArray[[2], [3], [4], [6]],
+                ListArray[[c], [c], [c], [c]],
+                ListArray[[{x: 2, y: 2}], [{x: 3, y: 3}], [{x: 4, y: 4}], [{x: 8, y: 8}]],
+            ]\
+            ",
+        );
+        let everything = unindent::unindent(
+            "\
+            [
+                Int64[10, 20, 30, 40, 50, 60, 70],
+                Timestamp(Nanosecond, None)[1970-01-01 00:00:00.000000010, None, None, None, 1970-01-01 00:00:00.000000050, None, 1970-01-01 00:00:00.000000070],
+                ListArray[None, None, [2], [3], [4], None, [6]],
+                ListArray[[c], [c], [c], [c], [c], [c], [c]],
+                ListArray[[{x: 0, y: 0}], [{x: 1, y: 1}], [{x: 2, y: 2}], [{x: 3, y: 3}], [{x: 4, y: 4}], [{x: 5, y: 5}], [{x: 8, y: 8}]],
+            ]\
+            ",
+        );
+
+        let test_cases = [
+            (
+                vec![my_point.clone(), my_color.clone()],
+                FilterIsNotNullMode::All,
+                only_my_color.clone(),
+            ),
+            (
+                vec![my_point.clone(), my_color.clone()],
+                FilterIsNotNullMode::Any,
+                everything,
+            ),
+            (
+                vec![my_color.clone(), no_such_entity.clone()],
+                FilterIsNotNullMode::All,
+                "[]".to_owned(),
+            ),
+            (
+                vec![my_color, no_such_entity],
+                FilterIsNotNullMode::Any,
+                only_my_color,
+            ),
+        ];
+
+        for (filtered_is_not_null, filtered_is_not_null_mode, expected) in test_cases {
+            let query = QueryExpression {
+                filtered_index,
+                filtered_is_not_null,
+                filtered_is_not_null_mode,
+                ..Default::default()
+            };
+            eprintln!("{query:#?}:");
+
+            let query_handle = query_engine.query(query.clone());
+            assert_eq!(
+                query_engine.query(query.clone()).into_iter().count() as u64,
+                query_handle.num_rows()
+            );
+            let dataframe = concatenate_record_batches(
+                query_handle.schema().clone(),
+                &query_handle.into_batch_iter().collect_vec(),
+            )?;
+            eprintln!("{dataframe}");
+
+            let got = format!("{:#?}", dataframe.data.iter().collect_vec());
+
+            similar_asserts::assert_eq!(expected, got);
+        }
+
+        Ok(())
+    }
+
     #[test]
     fn view_contents() -> anyhow::Result<()> {
         re_log::setup_logging();
@@ -2396,10 +2639,10 @@ mod tests {
         {
             let query = QueryExpression {
                 filtered_index,
-                filtered_is_not_null: Some(ComponentColumnSelector {
+                filtered_is_not_null: vec![ComponentColumnSelector {
                     entity_path: entity_path.clone(),
                     component_name: MyPoint::name().to_string(),
-                }),
+                }],
                 ..Default::default()
             };
             eprintln!("{query:#?}:");
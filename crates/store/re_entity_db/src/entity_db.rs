@@ -441,6 +441,26 @@ impl EntityDb {
         store_events
     }
 
+    /// Free up some RAM by forgetting all the data that is older than `time_span` on `timeline`,
+    /// relative to the latest time on that timeline.
+    ///
+    /// The latest value of each component is always kept around.
+    ///
+    /// See [`GarbageCollectionTarget::KeepLatestTimeSpan`].
+    pub fn purge_older_than(&mut self, timeline: Timeline, time_span: i64) -> Vec<ChunkStoreEvent> {
+        re_tracing::profile_function!();
+
+        self.gc(&GarbageCollectionOptions {
+            target: GarbageCollectionTarget::KeepLatestTimeSpan {
+                timeline,
+                time_span,
+            },
+            protect_latest: 1,
+            time_budget: DEFAULT_GC_TIME_BUDGET,
+            protected_time_ranges: Default::default(),
+        })
+    }
+
     pub fn gc(&mut self, gc_options: &GarbageCollectionOptions) -> Vec<ChunkStoreEvent> {
         re_tracing::profile_function!();
 
@@ -453,17 +473,43 @@ impl EntityDb {
             "purged datastore"
         );
 
-        Self::on_store_deletions(
-            &mut self.times_per_timeline,
-            &mut self.time_histogram_per_timeline,
-            &mut self.tree,
-            engine,
-            &store_events,
-        );
+        if engine.store().spills_to_disk() {
+            // The data is still reachable by queries, it just isn't in memory anymore: the time
+            // panel and entity tree should keep showing it as is. Only the query cache must let
+            // go of it, so that the memory actually gets freed.
+            engine.cache().on_events(&store_events);
+        } else {
+            Self::on_store_deletions(
+                &mut self.times_per_timeline,
+                &mut self.time_histogram_per_timeline,
+                &mut self.tree,
+                engine,
+                &store_events,
+            );
+        }
 
         store_events
     }
 
+    /// Lets the query cache know about the spilled chunks that were read back from disk since the
+    /// last call, see [`re_chunk_store::ChunkStore::take_paged_in_events`].
+    ///
+    /// Returns `true` while more are on their way, i.e. while query results may still be missing
+    /// some spilled data.
+    pub fn handle_paged_in_chunks(&mut self) -> bool {
+        if !self.storage_engine.read().store().spills_to_disk() {
+            return false;
+        }
+
+        let mut engine = self.storage_engine.write();
+        let store_events = engine.store().take_paged_in_events();
+        if !store_events.is_empty() {
+            engine.cache().on_events(&store_events);
+        }
+
+        engine.store().is_paging_in()
+    }
+
     /// Drop all events in the given time range from the given timeline.
     ///
     /// Used to implement undo (erase the last event from the blueprint db).
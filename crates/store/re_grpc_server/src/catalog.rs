@@ -0,0 +1,594 @@
+use std::collections::{BTreeMap, BTreeSet};
+use std::path::{Path, PathBuf};
+
+use arrow2::array::{
+    new_null_array, Array as Arrow2Array, Int64Array as Arrow2Int64Array,
+    Utf8Array as Arrow2Utf8Array,
+};
+use arrow2::chunk::Chunk as Arrow2Chunk;
+use arrow2::datatypes::{DataType as Arrow2Datatype, Field as Arrow2Field, Schema as Arrow2Schema};
+use itertools::Itertools as _;
+
+use re_chunk::{RowId, TransportChunk};
+use re_chunk_store::{ChunkStore, ChunkStoreConfig, ChunkStoreHandle};
+use re_log_types::{ApplicationId, StoreKind, Time};
+use re_types_core::{Component as _, Loggable as _};
+
+// ---
+
+/// Columns that are always present in the catalog, in order.
+///
+/// These cannot be overwritten by user-provided metadata.
+pub const BUILTIN_COLUMNS: [&str; 5] = [
+    CatalogEntry::COLUMN_ID,
+    CatalogEntry::COLUMN_APPLICATION_ID,
+    CatalogEntry::COLUMN_START_TIME,
+    CatalogEntry::COLUMN_STORAGE_URL,
+    CatalogEntry::COLUMN_DESCRIPTION,
+];
+
+#[derive(thiserror::Error, Debug)]
+pub enum CatalogError {
+    #[error("couldn't read directory {path:?}: {err}")]
+    ReadDirectory { path: PathBuf, err: std::io::Error },
+
+    #[error("couldn't load {path:?}: {err:#}")]
+    Load { path: PathBuf, err: anyhow::Error },
+
+    #[error("unknown recording: {0:?}")]
+    UnknownRecording(String),
+
+    #[error("recording {id:?} is already registered (from {storage_url:?})")]
+    AlreadyRegistered { id: String, storage_url: String },
+
+    #[error("unknown catalog column: {0:?}")]
+    UnknownColumn(String),
+
+    #[error("invalid metadata: {0}")]
+    InvalidMetadata(String),
+
+    #[error(transparent)]
+    Arrow(#[from] arrow2::error::Error),
+}
+
+/// A single recording in the [`Catalog`].
+pub struct CatalogEntry {
+    /// The ID of the recording, i.e. the ID of its [`re_log_types::StoreId`].
+    pub id: String,
+
+    pub application_id: ApplicationId,
+
+    /// When the recording was started, as specified by its `StoreInfo`.
+    pub start_time: Time,
+
+    /// Where the recording was loaded from, e.g. `file:///path/to/file.rrd`.
+    pub storage_url: String,
+
+    /// Human readable description of the recording.
+    pub description: String,
+
+    /// User-defined metadata columns, each holding exactly one value.
+    pub metadata: BTreeMap<String, Box<dyn Arrow2Array>>,
+
+    /// The actual data.
+    pub store: ChunkStoreHandle,
+}
+
+impl CatalogEntry {
+    pub const COLUMN_ID: &'static str = "id";
+    pub const COLUMN_APPLICATION_ID: &'static str = "application_id";
+    pub const COLUMN_START_TIME: &'static str = "start_time";
+    pub const COLUMN_STORAGE_URL: &'static str = "storage_url";
+    pub const COLUMN_DESCRIPTION: &'static str = "description";
+
+    /// Loads all the recordings contained in the .rrd file at `path`.
+    ///
+    /// Blueprints are ignored.
+    pub fn load_rrd(path: &Path) -> Result<Vec<Self>, CatalogError> {
+        re_tracing::profile_function!(path.to_string_lossy());
+
+        let storage_url =
+            url::Url::from_file_path(path.canonicalize().unwrap_or_else(|_| path.to_owned()))
+                .map_or_else(
+                    |()| path.to_string_lossy().to_string(),
+                    |url| url.to_string(),
+                );
+
+        // TODO(cmc): might want to make this configurable at some point.
+        let version_policy = re_log_encoding::VersionPolicy::Warn;
+        let stores =
+            ChunkStore::handle_from_rrd_filepath(&ChunkStoreConfig::DEFAULT, path, version_policy)
+                .map_err(|err| CatalogError::Load {
+                    path: path.to_owned(),
+                    err,
+                })?;
+
+        Ok(stores
+            .into_iter()
+            .filter(|(store_id, _store)| store_id.kind == StoreKind::Recording)
+            .map(|(store_id, store)| {
+                let (application_id, start_time) = store.read().info().map_or_else(
+                    || (ApplicationId::unknown(), Time::from_ns_since_epoch(0)),
+                    |info| (info.application_id.clone(), info.started),
+                );
+
+                Self {
+                    id: store_id.id.to_string(),
+                    application_id,
+                    start_time,
+                    storage_url: storage_url.clone(),
+                    description: String::new(),
+                    metadata: BTreeMap::new(),
+                    store,
+                }
+            })
+            .collect())
+    }
+}
+
+/// The set of recordings served by a [`crate::StorageNodeService`], indexed by recording ID.
+///
+/// Everything is kept in memory.
+#[derive(Default)]
+pub struct Catalog {
+    entries: BTreeMap<String, CatalogEntry>,
+}
+
+impl Catalog {
+    /// Indexes all the .rrd files found in `directory`, recursively.
+    ///
+    /// Files that fail to load are skipped with a warning, as are recordings whose ID has already
+    /// been seen in another file.
+    pub fn from_directory(directory: impl AsRef<Path>) -> Result<Self, CatalogError> {
+        let directory = directory.as_ref();
+
+        re_tracing::profile_function!(directory.to_string_lossy());
+
+        let mut paths = Vec::new();
+        collect_rrd_paths(directory, &mut paths)?;
+        paths.sort();
+
+        let mut catalog = Self::default();
+        for path in paths {
+            match CatalogEntry::load_rrd(&path) {
+                Ok(entries) => {
+                    for entry in entries {
+                        if let Err(err) = catalog.insert(entry) {
+                            re_log::warn!("Skipping recording from {path:?}: {err}");
+                        }
+                    }
+                }
+                Err(err) => re_log::warn!("Skipping {path:?}: {err}"),
+            }
+        }
+
+        re_log::info!(
+            "Indexed {} recording(s) from {directory:?}",
+            re_format::format_uint(catalog.len())
+        );
+
+        Ok(catalog)
+    }
+
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    #[inline]
+    pub fn get(&self, id: &str) -> Option<&CatalogEntry> {
+        self.entries.get(id)
+    }
+
+    #[inline]
+    pub fn iter(&self) -> impl Iterator<Item = &CatalogEntry> {
+        self.entries.values()
+    }
+
+    /// Adds a new recording to the catalog.
+    ///
+    /// Fails if a recording with the same ID is already registered, or if its metadata is not
+    /// compatible with the rest of the catalog.
+    pub fn insert(&mut self, entry: CatalogEntry) -> Result<(), CatalogError> {
+        if let Some(existing) = self.entries.get(&entry.id) {
+            return Err(CatalogError::AlreadyRegistered {
+                id: entry.id,
+                storage_url: existing.storage_url.clone(),
+            });
+        }
+
+        for (name, value) in &entry.metadata {
+            self.check_metadata_column(name, value.as_ref())?;
+        }
+
+        self.entries.insert(entry.id.clone(), entry);
+
+        Ok(())
+    }
+
+    /// Updates the user-defined metadata of existing recordings.
+    ///
+    /// `metadata` must contain a Utf8 `id` column identifying the recordings to update, one per
+    /// row. Every other non-control column is stored as-is, overwriting any previous value.
+    pub fn update_metadata(&mut self, metadata: &TransportChunk) -> Result<(), CatalogError> {
+        let ids = metadata
+            .all_columns()
+            .find(|(field, _column)| field.name == CatalogEntry::COLUMN_ID)
+            .and_then(|(_field, column)| column.as_any().downcast_ref::<Arrow2Utf8Array<i32>>())
+            .ok_or_else(|| {
+                CatalogError::InvalidMetadata(format!(
+                    "expected a utf8 {:?} column",
+                    CatalogEntry::COLUMN_ID
+                ))
+            })?;
+
+        let mut updates = Vec::with_capacity(ids.len());
+        for (row_idx, id) in ids.iter().enumerate() {
+            let id = id.ok_or_else(|| {
+                CatalogError::InvalidMetadata(format!(
+                    "{:?} column cannot contain nulls",
+                    CatalogEntry::COLUMN_ID
+                ))
+            })?;
+            if !self.entries.contains_key(id) {
+                return Err(CatalogError::UnknownRecording(id.to_owned()));
+            }
+
+            let columns = metadata_columns(metadata, row_idx)
+                .filter(|(name, _value)| name != CatalogEntry::COLUMN_ID)
+                .collect_vec();
+            for (name, value) in &columns {
+                self.check_metadata_column(name, value.as_ref())?;
+            }
+
+            updates.push((id.to_owned(), columns));
+        }
+
+        // Only apply the changes once everything has been validated.
+        for (id, columns) in updates {
+            if let Some(entry) = self.entries.get_mut(&id) {
+                entry.metadata.extend(columns);
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Returns the catalog as a single [`TransportChunk`], with one row per recording.
+    ///
+    /// * `ids`: only include these recordings, in this order. All recordings if `None`.
+    /// * `columns`: only include these columns. All columns if `None`.
+    pub fn to_transport_chunk(
+        &self,
+        ids: Option<&[String]>,
+        columns: Option<&[String]>,
+    ) -> Result<TransportChunk, CatalogError> {
+        re_tracing::profile_function!();
+
+        let entries = if let Some(ids) = ids {
+            ids.iter()
+                .map(|id| {
+                    self.entries
+                        .get(id)
+                        .ok_or_else(|| CatalogError::UnknownRecording(id.clone()))
+                })
+                .collect::<Result<Vec<_>, _>>()?
+        } else {
+            self.entries.values().collect_vec()
+        };
+
+        let metadata_names: BTreeSet<&str> = self
+            .entries
+            .values()
+            .flat_map(|entry| entry.metadata.keys().map(String::as_str))
+            .collect();
+
+        if let Some(columns) = columns {
+            if let Some(unknown) = columns.iter().find(|name| {
+                !BUILTIN_COLUMNS.contains(&name.as_str()) && !metadata_names.contains(name.as_str())
+            }) {
+                return Err(CatalogError::UnknownColumn(unknown.clone()));
+            }
+        }
+        let is_selected =
+            |name: &str| columns.map_or(true, |columns| columns.iter().any(|c| c == name));
+
+        let mut fields = Vec::new();
+        let mut arrays: Vec<Box<dyn Arrow2Array>> = Vec::new();
+
+        // Row IDs
+        {
+            let row_ids = std::iter::repeat_with(RowId::new)
+                .take(entries.len())
+                .collect_vec();
+            #[allow(clippy::unwrap_used)] // row ids always serialize
+            let row_ids = RowId::to_arrow2(&row_ids).unwrap();
+
+            fields.push(
+                Arrow2Field::new(
+                    RowId::descriptor().to_string(),
+                    row_ids.data_type().clone(),
+                    false,
+                )
+                .with_metadata(TransportChunk::field_metadata_control_column()),
+            );
+            arrays.push(row_ids);
+        }
+
+        let mut push_column = |name: &str, array: Box<dyn Arrow2Array>, is_nullable: bool| {
+            if is_selected(name) {
+                fields.push(
+                    Arrow2Field::new(name, array.data_type().clone(), is_nullable)
+                        .with_metadata(TransportChunk::field_metadata_data_column()),
+                );
+                arrays.push(array);
+            }
+        };
+
+        push_column(
+            CatalogEntry::COLUMN_ID,
+            Arrow2Utf8Array::<i32>::from_iter_values(entries.iter().map(|entry| &entry.id)).boxed(),
+            false,
+        );
+        push_column(
+            CatalogEntry::COLUMN_APPLICATION_ID,
+            Arrow2Utf8Array::<i32>::from_iter_values(
+                entries.iter().map(|entry| entry.application_id.as_str()),
+            )
+            .boxed(),
+            false,
+        );
+        push_column(
+            CatalogEntry::COLUMN_START_TIME,
+            Arrow2Int64Array::from_iter_values(
+                entries
+                    .iter()
+                    .map(|entry| entry.start_time.nanos_since_epoch()),
+            )
+            .boxed(),
+            false,
+        );
+        push_column(
+            CatalogEntry::COLUMN_STORAGE_URL,
+            Arrow2Utf8Array::<i32>::from_iter_values(
+                entries.iter().map(|entry| &entry.storage_url),
+            )
+            .boxed(),
+            false,
+        );
+        push_column(
+            CatalogEntry::COLUMN_DESCRIPTION,
+            Arrow2Utf8Array::<i32>::from_iter_values(
+                entries.iter().map(|entry| &entry.description),
+            )
+            .boxed(),
+            false,
+        );
+
+        for name in metadata_names {
+            let Some(datatype) = self.metadata_datatype(name) else {
+                continue;
+            };
+
+            let values = entries
+                .iter()
+                .map(|entry| {
+                    entry
+                        .metadata
+                        .get(name)
+                        .cloned()
+                        .unwrap_or_else(|| new_null_array(datatype.clone(), 1))
+                })
+                .collect_vec();
+            let values = re_chunk::util::concat_arrays(
+                &values.iter().map(|value| value.as_ref()).collect_vec(),
+            )?;
+
+            push_column(name, values, true);
+        }
+
+        Ok(TransportChunk {
+            schema: Arrow2Schema::from(fields),
+            data: Arrow2Chunk::new(arrays),
+        })
+    }
+
+    /// The datatype of the user-defined metadata column `name`, if any recording has it.
+    fn metadata_datatype(&self, name: &str) -> Option<&Arrow2Datatype> {
+        self.entries
+            .values()
+            .find_map(|entry| entry.metadata.get(name))
+            .map(|value| value.data_type())
+    }
+
+    fn check_metadata_column(
+        &self,
+        name: &str,
+        value: &dyn Arrow2Array,
+    ) -> Result<(), CatalogError> {
+        if BUILTIN_COLUMNS.contains(&name) {
+            return Err(CatalogError::InvalidMetadata(format!(
+                "{name:?} is a reserved column name"
+            )));
+        }
+
+        if let Some(datatype) = self.metadata_datatype(name) {
+            if datatype != value.data_type() {
+                return Err(CatalogError::InvalidMetadata(format!(
+                    "column {name:?} has type {:?}, but the catalog expects {datatype:?}",
+                    value.data_type()
+                )));
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Iterates over all the non-control columns of `metadata`, sliced to the row at `row_idx`.
+pub(crate) fn metadata_columns(
+    metadata: &TransportChunk,
+    row_idx: usize,
+) -> impl Iterator<Item = (String, Box<dyn Arrow2Array>)> + '_ {
+    metadata
+        .all_columns()
+        .filter(|(field, _column)| {
+            field
+                .metadata
+                .get(TransportChunk::FIELD_METADATA_KEY_KIND)
+                .map(String::as_str)
+                != Some(TransportChunk::FIELD_METADATA_VALUE_KIND_CONTROL)
+        })
+        .map(move |(field, column)| (field.name.clone(), column.sliced(row_idx, 1)))
+}
+
+fn collect_rrd_paths(directory: &Path, paths: &mut Vec<PathBuf>) -> Result<(), CatalogError> {
+    let read_dir = std::fs::read_dir(directory).map_err(|err| CatalogError::ReadDirectory {
+        path: directory.to_owned(),
+        err,
+    })?;
+
+    for dir_entry in read_dir {
+        let path = dir_entry
+            .map_err(|err| CatalogError::ReadDirectory {
+                path: directory.to_owned(),
+                err,
+            })?
+            .path();
+
+        if path.is_dir() {
+            collect_rrd_paths(&path, paths)?;
+        } else if path.extension().is_some_and(|ext| ext == "rrd") {
+            paths.push(path);
+        }
+    }
+
+    Ok(())
+}
+
+// ---
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use re_chunk::Chunk;
+    use re_log_types::{
+        example_components::MyPoint, LogMsg, SetStoreInfo, StoreId, StoreInfo, StoreSource,
+        Timeline,
+    };
+
+    use super::*;
+
+    pub(crate) fn write_rrd(path: &Path, recording_id: &str) -> anyhow::Result<()> {
+        let store_id = StoreId::from_string(StoreKind::Recording, recording_id.to_owned());
+
+        let chunk = Chunk::builder("points".into())
+            .with_component_batches(
+                RowId::new(),
+                [(Timeline::new_sequence("frame"), 1)],
+                [&[MyPoint::new(1.0, 1.0)] as _],
+            )
+            .build()?;
+
+        let messages = [
+            LogMsg::SetStoreInfo(SetStoreInfo {
+                row_id: *RowId::new(),
+                info: StoreInfo {
+                    application_id: "test_app".into(),
+                    store_id: store_id.clone(),
+                    cloned_from: None,
+                    is_official_example: false,
+                    started: Time::from_ns_since_epoch(42),
+                    store_source: StoreSource::Unknown,
+                    store_version: None,
+                },
+            }),
+            LogMsg::ArrowMsg(store_id, chunk.to_arrow_msg()?),
+        ];
+
+        let file = std::fs::File::create(path)?;
+        re_log_encoding::encoder::encode(
+            re_build_info::CrateVersion::LOCAL,
+            re_log_encoding::EncodingOptions::MSGPACK_UNCOMPRESSED,
+            messages.into_iter().map(Ok),
+            &mut std::io::BufWriter::new(file),
+        )?;
+
+        Ok(())
+    }
+
+    #[test]
+    fn index_and_update_metadata() -> anyhow::Result<()> {
+        let dir = tempfile::tempdir()?;
+        std::fs::create_dir(dir.path().join("nested"))?;
+        write_rrd(&dir.path().join("a.rrd"), "rec_a")?;
+        write_rrd(&dir.path().join("nested").join("b.rrd"), "rec_b")?;
+        write_rrd(&dir.path().join("duplicate.rrd"), "rec_a")?;
+        std::fs::write(dir.path().join("not_an_rrd.txt"), "hello")?;
+
+        let mut catalog = Catalog::from_directory(dir.path())?;
+        assert_eq!(2, catalog.len());
+
+        let entry = catalog.get("rec_a").unwrap();
+        assert_eq!("test_app", entry.application_id.as_str());
+        assert_eq!(42, entry.start_time.nanos_since_epoch());
+
+        let metadata = TransportChunk {
+            schema: Arrow2Schema::from(vec![
+                Arrow2Field::new("id", Arrow2Datatype::Utf8, false),
+                Arrow2Field::new("robot", Arrow2Datatype::Utf8, true),
+            ]),
+            data: Arrow2Chunk::new(vec![
+                Arrow2Utf8Array::<i32>::from_slice(["rec_b"]).boxed(),
+                Arrow2Utf8Array::<i32>::from_slice(["wall-e"]).boxed(),
+            ]),
+        };
+        catalog.update_metadata(&metadata)?;
+
+        let chunk = catalog.to_transport_chunk(None, None)?;
+        assert_eq!(2, chunk.num_rows());
+        assert_eq!(1, chunk.controls().count());
+
+        let (_field, robots) = chunk
+            .components()
+            .find(|(field, _column)| field.name == "robot")
+            .unwrap();
+        let robots = robots
+            .as_any()
+            .downcast_ref::<Arrow2Utf8Array<i32>>()
+            .unwrap();
+        assert_eq!(vec![None, Some("wall-e")], robots.iter().collect_vec());
+
+        let chunk =
+            catalog.to_transport_chunk(Some(&["rec_b".to_owned()]), Some(&["id".to_owned()]))?;
+        assert_eq!(1, chunk.num_rows());
+        assert_eq!(1, chunk.components().count());
+
+        // Wrong type for an existing column.
+        let metadata = TransportChunk {
+            schema: Arrow2Schema::from(vec![
+                Arrow2Field::new("id", Arrow2Datatype::Utf8, false),
+                Arrow2Field::new("robot", Arrow2Datatype::Int64, true),
+            ]),
+            data: Arrow2Chunk::new(vec![
+                Arrow2Utf8Array::<i32>::from_slice(["rec_a"]).boxed(),
+                Arrow2Int64Array::from_slice([1]).boxed(),
+            ]),
+        };
+        assert!(matches!(
+            catalog.update_metadata(&metadata),
+            Err(CatalogError::InvalidMetadata(_))
+        ));
+
+        assert!(matches!(
+            catalog.to_transport_chunk(Some(&["rec_c".to_owned()]), None),
+            Err(CatalogError::UnknownRecording(_))
+        ));
+
+        Ok(())
+    }
+}
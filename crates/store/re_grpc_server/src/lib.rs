@@ -0,0 +1,33 @@
+//! Reference implementation of the Rerun Data Platform `StorageNode` gRPC service.
+//!
+//! The server indexes a directory of .rrd files into a [`Catalog`], and answers catalog, query and
+//! fetch requests using [`re_dataframe`].
+//! Everything is loaded in memory: this is meant for self-hosting small recording libraries and for
+//! testing the `rerun://` data source offline, not for production workloads.
+
+mod catalog;
+mod service;
+
+pub use self::catalog::{Catalog, CatalogEntry, CatalogError, BUILTIN_COLUMNS};
+pub use self::service::StorageNodeService;
+
+/// Default port of the gRPC storage node server.
+pub const DEFAULT_SERVER_PORT: u16 = 51234;
+
+/// Serves the given [`StorageNodeService`] on `addr`.
+///
+/// Runs until the server fails, or until the returned future is dropped.
+pub async fn serve(
+    addr: std::net::SocketAddr,
+    service: StorageNodeService,
+) -> Result<(), tonic::transport::Error> {
+    re_log::info!(
+        "Serving {} recording(s) over gRPC on {addr}. Open rerun://{addr}/catalog in the viewer to browse them.",
+        re_format::format_uint(service.catalog().read().len())
+    );
+
+    tonic::transport::Server::builder()
+        .add_service(service.into_server())
+        .serve(addr)
+        .await
+}
@@ -0,0 +1,527 @@
+use std::path::PathBuf;
+use std::sync::Arc;
+
+use itertools::Itertools as _;
+use parking_lot::RwLock;
+use tokio::sync::mpsc;
+use tokio_stream::wrappers::ReceiverStream;
+use tonic::{Request, Response, Status};
+
+use re_chunk::TransportChunk;
+use re_chunk_store::QueryExpression;
+use re_dataframe::{concatenate_record_batches, QueryEngine};
+use re_log_encoding::codec::wire::{decode, encode};
+use re_protos::common::v0::{EncoderVersion, RerunChunk};
+use re_protos::remote_store::v0::{
+    storage_node_server::{StorageNode, StorageNodeServer},
+    DataframePart, FetchRecordingRequest, QueryCatalogRequest, QueryRequest, RecordingType,
+    RegisterRecordingRequest, UpdateCatalogRequest, UpdateCatalogResponse,
+};
+
+use crate::catalog::{metadata_columns, Catalog, CatalogEntry, CatalogError};
+
+// ---
+
+/// How many rows of a dataframe query are sent in a single [`DataframePart`].
+const QUERY_BATCH_NUM_ROWS: usize = 4096;
+
+/// How many encoded messages can be in flight before the producer waits for the client.
+const STREAM_CHANNEL_CAPACITY: usize = 16;
+
+impl From<CatalogError> for Status {
+    fn from(err: CatalogError) -> Self {
+        match err {
+            CatalogError::UnknownRecording(_) => Self::not_found(err.to_string()),
+            CatalogError::AlreadyRegistered { .. } => Self::already_exists(err.to_string()),
+            CatalogError::UnknownColumn(_) | CatalogError::InvalidMetadata(_) => {
+                Self::invalid_argument(err.to_string())
+            }
+            CatalogError::ReadDirectory { .. }
+            | CatalogError::Load { .. }
+            | CatalogError::Arrow(_) => Self::internal(err.to_string()),
+        }
+    }
+}
+
+/// Implementation of the `StorageNode` gRPC service on top of an in-memory [`Catalog`].
+#[derive(Clone)]
+pub struct StorageNodeService {
+    catalog: Arc<RwLock<Catalog>>,
+
+    /// `RegisterRecording` requests are only accepted for files within this directory.
+    ///
+    /// `None` (the default) rejects all of them.
+    registration_root: Option<PathBuf>,
+}
+
+impl StorageNodeService {
+    /// Creates a read-only service: `RegisterRecording` requests are rejected.
+    ///
+    /// See [`Self::with_registration_root`].
+    pub fn new(catalog: Catalog) -> Self {
+        Self {
+            catalog: Arc::new(RwLock::new(catalog)),
+            registration_root: None,
+        }
+    }
+
+    /// Accept `RegisterRecording` requests, as long as they point to files within `root`.
+    ///
+    /// Clients get to make the server read any file they can name: never point this at a
+    /// directory containing anything you wouldn't serve anyway.
+    pub fn with_registration_root(mut self, root: impl Into<PathBuf>) -> Self {
+        self.registration_root = Some(root.into());
+        self
+    }
+
+    /// The catalog served by this service.
+    #[inline]
+    pub fn catalog(&self) -> &Arc<RwLock<Catalog>> {
+        &self.catalog
+    }
+
+    /// Wraps the service into a [`StorageNodeServer`], ready to be added to a
+    /// [`tonic::transport::Server`].
+    pub fn into_server(self) -> StorageNodeServer<Self> {
+        // TODO(#8411): figure out the right size for this
+        StorageNodeServer::new(self)
+            .max_decoding_message_size(usize::MAX)
+            .max_encoding_message_size(usize::MAX)
+    }
+
+    /// Makes sure that `path` is within [`Self::registration_root`], and returns its canonical form.
+    fn check_registration_path(&self, path: PathBuf) -> Result<PathBuf, Status> {
+        let Some(root) = &self.registration_root else {
+            return Err(Status::permission_denied(
+                "registering recordings is disabled on this server",
+            ));
+        };
+
+        // NOTE: Canonicalize both sides so that neither `..` nor symlinks can escape the root.
+        // Don't leak whether files outside the root exist: all failures look the same.
+        let root = root
+            .canonicalize()
+            .map_err(|err| Status::internal(format!("invalid registration root: {err}")))?;
+        match path.canonicalize() {
+            Ok(path) if path.starts_with(&root) => Ok(path),
+            _ => Err(Status::permission_denied(format!(
+                "can only register recordings from within {root:?}"
+            ))),
+        }
+    }
+
+    fn catalog_part(
+        &self,
+        ids: Option<&[String]>,
+        columns: Option<&[String]>,
+    ) -> Result<DataframePart, Status> {
+        let chunk = self.catalog.read().to_transport_chunk(ids, columns)?;
+        encode_dataframe_part(&chunk)
+    }
+}
+
+#[tonic::async_trait]
+impl StorageNode for StorageNodeService {
+    type QueryStream = ReceiverStream<Result<DataframePart, Status>>;
+
+    async fn query(
+        &self,
+        request: Request<QueryRequest>,
+    ) -> Result<Response<Self::QueryStream>, Status> {
+        let QueryRequest {
+            recording_id,
+            query,
+        } = request.into_inner();
+
+        let recording_id = recording_id
+            .ok_or_else(|| Status::invalid_argument("missing recording_id"))?
+            .id;
+        let query = QueryExpression::try_from(
+            query.ok_or_else(|| Status::invalid_argument("missing query"))?,
+        )
+        .map_err(|err| Status::invalid_argument(err.to_string()))?;
+
+        let store = self
+            .catalog
+            .read()
+            .get(&recording_id)
+            .map(|entry| entry.store.clone())
+            .ok_or(CatalogError::UnknownRecording(recording_id))?;
+
+        let (tx, rx) = mpsc::channel(STREAM_CHANNEL_CAPACITY);
+
+        tokio::task::spawn_blocking(move || {
+            re_tracing::profile_scope!("query");
+
+            let engine = QueryEngine::from_store(store);
+            let query_handle = engine.query(query);
+            let schema = query_handle.schema().clone();
+
+            let mut num_parts = 0;
+            for rows in &query_handle.batch_iter().chunks(QUERY_BATCH_NUM_ROWS) {
+                let part = concatenate_record_batches(schema.clone(), &rows.collect_vec())
+                    .map_err(|err| Status::internal(err.to_string()))
+                    .and_then(|batch| encode_dataframe_part(&batch));
+
+                num_parts += 1;
+                if tx.blocking_send(part).is_err() {
+                    re_log::debug!("Client disconnected");
+                    return;
+                }
+            }
+
+            // Always send at least one part, so that clients get to know the schema.
+            if num_parts == 0 {
+                let data = schema
+                    .fields
+                    .iter()
+                    .map(|field| arrow2::array::new_empty_array(field.data_type().clone()))
+                    .collect_vec();
+                let batch = TransportChunk {
+                    schema,
+                    data: arrow2::chunk::Chunk::new(data),
+                };
+                tx.blocking_send(encode_dataframe_part(&batch)).ok();
+            }
+        });
+
+        Ok(Response::new(ReceiverStream::new(rx)))
+    }
+
+    type FetchRecordingStream = ReceiverStream<Result<RerunChunk, Status>>;
+
+    async fn fetch_recording(
+        &self,
+        request: Request<FetchRecordingRequest>,
+    ) -> Result<Response<Self::FetchRecordingStream>, Status> {
+        let recording_id = request
+            .into_inner()
+            .recording_id
+            .ok_or_else(|| Status::invalid_argument("missing recording_id"))?
+            .id;
+
+        let store = self
+            .catalog
+            .read()
+            .get(&recording_id)
+            .map(|entry| entry.store.clone())
+            .ok_or(CatalogError::UnknownRecording(recording_id))?;
+
+        let (tx, rx) = mpsc::channel(STREAM_CHANNEL_CAPACITY);
+
+        tokio::task::spawn_blocking(move || {
+            re_tracing::profile_scope!("fetch_recording");
+
+            let chunks = store.read().iter_chunks().cloned().collect_vec();
+            for chunk in chunks {
+                let chunk = chunk
+                    .to_transport()
+                    .map_err(|err| Status::internal(err.to_string()))
+                    .and_then(|chunk| {
+                        encode(EncoderVersion::V0, &chunk)
+                            .map_err(|err| Status::internal(err.to_string()))
+                    })
+                    .map(|payload| RerunChunk {
+                        encoder_version: EncoderVersion::V0 as i32,
+                        payload,
+                    });
+
+                if tx.blocking_send(chunk).is_err() {
+                    re_log::debug!("Client disconnected");
+                    return;
+                }
+            }
+        });
+
+        Ok(Response::new(ReceiverStream::new(rx)))
+    }
+
+    type QueryCatalogStream = tokio_stream::Once<Result<DataframePart, Status>>;
+
+    async fn query_catalog(
+        &self,
+        request: Request<QueryCatalogRequest>,
+    ) -> Result<Response<Self::QueryCatalogStream>, Status> {
+        let QueryCatalogRequest {
+            column_projection,
+            filter,
+        } = request.into_inner();
+
+        let ids = filter.map(|filter| {
+            filter
+                .recording_ids
+                .into_iter()
+                .map(|recording_id| recording_id.id)
+                .collect_vec()
+        });
+        let columns = column_projection.map(|projection| projection.columns);
+
+        let part = self.catalog_part(ids.as_deref(), columns.as_deref())?;
+
+        Ok(Response::new(tokio_stream::once(Ok(part))))
+    }
+
+    async fn update_catalog(
+        &self,
+        request: Request<UpdateCatalogRequest>,
+    ) -> Result<Response<UpdateCatalogResponse>, Status> {
+        let metadata = request
+            .into_inner()
+            .metadata
+            .ok_or_else(|| Status::invalid_argument("missing metadata"))?;
+        let metadata = decode_dataframe_part(&metadata)?;
+
+        self.catalog.write().update_metadata(&metadata)?;
+
+        Ok(Response::new(UpdateCatalogResponse {}))
+    }
+
+    async fn register_recording(
+        &self,
+        request: Request<RegisterRecordingRequest>,
+    ) -> Result<Response<DataframePart>, Status> {
+        let request = request.into_inner();
+
+        match request.typ() {
+            RecordingType::Rrd => {}
+        }
+
+        let storage_url = url::Url::parse(&request.storage_url)
+            .map_err(|err| Status::invalid_argument(format!("invalid storage_url: {err}")))?;
+        if storage_url.scheme() != "file" {
+            return Err(Status::unimplemented(format!(
+                "only file:// storage urls are supported, got {storage_url}"
+            )));
+        }
+        let path = storage_url
+            .to_file_path()
+            .map_err(|()| Status::invalid_argument(format!("invalid file path: {storage_url}")))?;
+        let path = self.check_registration_path(path)?;
+
+        let metadata = request
+            .metadata
+            .as_ref()
+            .map(decode_dataframe_part)
+            .transpose()?;
+        if let Some(metadata) = &metadata {
+            if metadata.num_rows() != 1 {
+                return Err(Status::invalid_argument(format!(
+                    "expected exactly one row of metadata, got {}",
+                    metadata.num_rows()
+                )));
+            }
+        }
+
+        let mut entries = tokio::task::spawn_blocking(move || CatalogEntry::load_rrd(&path))
+            .await
+            .map_err(|err| Status::internal(err.to_string()))??;
+        if entries.is_empty() {
+            return Err(Status::invalid_argument(format!(
+                "no recording found in {storage_url}"
+            )));
+        }
+
+        for entry in &mut entries {
+            entry.description.clone_from(&request.description);
+            if let Some(metadata) = &metadata {
+                entry.metadata.extend(metadata_columns(metadata, 0));
+            }
+        }
+
+        let ids = entries.iter().map(|entry| entry.id.clone()).collect_vec();
+        {
+            let mut catalog = self.catalog.write();
+
+            // Check everything up-front, so that we don't end up with a half-registered file.
+            for entry in &entries {
+                if let Some(existing) = catalog.get(&entry.id) {
+                    return Err(CatalogError::AlreadyRegistered {
+                        id: entry.id.clone(),
+                        storage_url: existing.storage_url.clone(),
+                    }
+                    .into());
+                }
+            }
+
+            for entry in entries {
+                catalog.insert(entry)?;
+            }
+        }
+
+        re_log::info!("Registered {} recording(s) from {storage_url}", ids.len());
+
+        Ok(Response::new(self.catalog_part(Some(&ids), None)?))
+    }
+}
+
+fn encode_dataframe_part(chunk: &TransportChunk) -> Result<DataframePart, Status> {
+    let payload =
+        encode(EncoderVersion::V0, chunk).map_err(|err| Status::internal(err.to_string()))?;
+
+    Ok(DataframePart {
+        encoder_version: EncoderVersion::V0 as i32,
+        payload,
+    })
+}
+
+fn decode_dataframe_part(part: &DataframePart) -> Result<TransportChunk, Status> {
+    decode(part.encoder_version(), &part.payload)
+        .map_err(|err| Status::invalid_argument(format!("couldn't decode dataframe: {err}")))
+}
+
+// ---
+
+#[cfg(test)]
+mod tests {
+    use re_chunk::Chunk;
+    use re_log_types::Timeline;
+    use re_protos::common::v0::RecordingId;
+    use re_protos::remote_store::v0::storage_node_client::StorageNodeClient;
+    use tonic::transport::Channel;
+
+    use super::*;
+    use crate::catalog::tests::write_rrd;
+
+    /// Serves `service` on a random local port, and returns a client connected to it.
+    async fn serve(service: StorageNodeService) -> anyhow::Result<StorageNodeClient<Channel>> {
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await?;
+        let addr = listener.local_addr()?;
+
+        tokio::spawn(
+            tonic::transport::Server::builder()
+                .add_service(service.into_server())
+                .serve_with_incoming(tokio_stream::wrappers::TcpListenerStream::new(listener)),
+        );
+
+        Ok(StorageNodeClient::connect(format!("http://{addr}")).await?)
+    }
+
+    fn register_request(path: &std::path::Path) -> RegisterRecordingRequest {
+        RegisterRecordingRequest {
+            description: "registered".to_owned(),
+            storage_url: url::Url::from_file_path(path).unwrap().to_string(),
+            typ: RecordingType::Rrd as i32,
+            metadata: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn register_query_fetch() -> anyhow::Result<()> {
+        let root = tempfile::tempdir()?;
+        write_rrd(&root.path().join("a.rrd"), "rec_a")?;
+        let catalog = Catalog::from_directory(root.path())?;
+        write_rrd(&root.path().join("b.rrd"), "rec_b")?;
+
+        let outside = tempfile::tempdir()?;
+        write_rrd(&outside.path().join("c.rrd"), "rec_c")?;
+
+        let mut client =
+            serve(StorageNodeService::new(catalog).with_registration_root(root.path())).await?;
+
+        // Register
+        let part = client
+            .register_recording(register_request(&root.path().join("b.rrd")))
+            .await?
+            .into_inner();
+        assert_eq!(1, decode_dataframe_part(&part)?.num_rows());
+
+        let status = client
+            .register_recording(register_request(&outside.path().join("c.rrd")))
+            .await
+            .unwrap_err();
+        assert_eq!(tonic::Code::PermissionDenied, status.code());
+
+        let escape = root.path().join("..").join(
+            outside
+                .path()
+                .file_name()
+                .ok_or_else(|| anyhow::anyhow!("no file name"))?,
+        );
+        let status = client
+            .register_recording(register_request(&escape.join("c.rrd")))
+            .await
+            .unwrap_err();
+        assert_eq!(tonic::Code::PermissionDenied, status.code());
+
+        let mut catalog = client
+            .query_catalog(QueryCatalogRequest {
+                column_projection: None,
+                filter: None,
+            })
+            .await?
+            .into_inner();
+        let mut num_rows = 0;
+        while let Some(part) = catalog.message().await? {
+            num_rows += decode_dataframe_part(&part)?.num_rows();
+        }
+        assert_eq!(2, num_rows);
+
+        // Query
+        let mut parts = client
+            .query(QueryRequest {
+                recording_id: Some(RecordingId {
+                    id: "rec_b".to_owned(),
+                }),
+                query: Some(
+                    QueryExpression {
+                        filtered_index: Some(Timeline::new_sequence("frame")),
+                        ..Default::default()
+                    }
+                    .into(),
+                ),
+            })
+            .await?
+            .into_inner();
+        let mut num_rows = 0;
+        while let Some(part) = parts.message().await? {
+            num_rows += decode_dataframe_part(&part)?.num_rows();
+        }
+        assert_eq!(1, num_rows);
+
+        // Fetch
+        let mut chunks = client
+            .fetch_recording(FetchRecordingRequest {
+                recording_id: Some(RecordingId {
+                    id: "rec_b".to_owned(),
+                }),
+            })
+            .await?
+            .into_inner();
+        let mut num_chunks = 0;
+        while let Some(chunk) = chunks.message().await? {
+            let chunk = decode(chunk.encoder_version(), &chunk.payload)?;
+            let chunk = Chunk::from_transport(&chunk)?;
+            assert_eq!(1, chunk.num_rows());
+            num_chunks += 1;
+        }
+        assert_eq!(1, num_chunks);
+
+        let status = client
+            .fetch_recording(FetchRecordingRequest {
+                recording_id: Some(RecordingId {
+                    id: "rec_c".to_owned(),
+                }),
+            })
+            .await
+            .unwrap_err();
+        assert_eq!(tonic::Code::NotFound, status.code());
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn register_disabled_by_default() -> anyhow::Result<()> {
+        let root = tempfile::tempdir()?;
+        write_rrd(&root.path().join("a.rrd"), "rec_a")?;
+
+        let mut client = serve(StorageNodeService::new(Catalog::default())).await?;
+
+        let status = client
+            .register_recording(register_request(&root.path().join("a.rrd")))
+            .await
+            .unwrap_err();
+        assert_eq!(tonic::Code::PermissionDenied, status.code());
+
+        Ok(())
+    }
+}
@@ -68,6 +68,8 @@ pub(crate) fn encode_arrow(
     let data = match compression {
         crate::Compression::Off => uncompressed,
         crate::Compression::LZ4 => lz4_flex::block::compress(&uncompressed),
+        crate::Compression::Zstd { level } => crate::zstd_codec::compress(&uncompressed, level)
+            .map_err(crate::encoder::EncodeError::Zstd)?,
     };
 
     Ok(Payload {
@@ -90,6 +92,12 @@ pub(crate) fn decode_arrow(
             lz4_flex::block::decompress_into(data, &mut uncompressed)?;
             uncompressed.as_slice()
         }
+        crate::Compression::Zstd { .. } => {
+            uncompressed.resize(uncompressed_size, 0);
+            crate::zstd_codec::decompress_to_buffer(data, &mut uncompressed)
+                .map_err(crate::decoder::DecodeError::Zstd)?;
+            uncompressed.as_slice()
+        }
     };
 
     Ok(read_arrow_from_bytes(&mut &data[..])?)
@@ -1,13 +1,11 @@
 use super::{MessageHeader, MessageKind};
-use crate::codec::arrow::decode_arrow;
-use crate::codec::CodecError;
 use crate::decoder::DecodeError;
+use crate::protobuf_conversions::arrow_msg_from_proto;
 use re_log_types::LogMsg;
-use re_protos::missing_field;
 
 pub(crate) fn decode(data: &mut impl std::io::Read) -> Result<(u64, Option<LogMsg>), DecodeError> {
     use re_protos::external::prost::Message;
-    use re_protos::log_msg::v0::{ArrowMsg, BlueprintActivationCommand, Encoding, SetStoreInfo};
+    use re_protos::log_msg::v0::{ArrowMsg, BlueprintActivationCommand, SetStoreInfo};
 
     let mut read_bytes = 0u64;
     let header = MessageHeader::decode(data)?;
@@ -23,27 +21,7 @@ pub(crate) fn decode(data: &mut impl std::io::Read) -> Result<(u64, Option<LogMs
         }
         MessageKind::ArrowMsg => {
             let arrow_msg = ArrowMsg::decode(&buf[..])?;
-            if arrow_msg.encoding() != Encoding::ArrowIpc {
-                return Err(DecodeError::Codec(CodecError::UnsupportedEncoding));
-            }
-
-            let (schema, chunk) = decode_arrow(
-                &arrow_msg.payload,
-                arrow_msg.uncompressed_size as usize,
-                arrow_msg.compression().into(),
-            )?;
-
-            let store_id: re_log_types::StoreId = arrow_msg
-                .store_id
-                .ok_or_else(|| missing_field!(re_protos::log_msg::v0::ArrowMsg, "store_id"))?
-                .into();
-
-            let chunk = re_chunk::Chunk::from_transport(&re_chunk::TransportChunk {
-                schema,
-                data: chunk,
-            })?;
-
-            Some(LogMsg::ArrowMsg(store_id, chunk.to_arrow_msg()?))
+            Some(arrow_msg_from_proto(arrow_msg)?)
         }
         MessageKind::BlueprintActivationCommand => {
             let blueprint_activation_command = BlueprintActivationCommand::decode(&buf[..])?;
@@ -1,6 +1,6 @@
 use super::{MessageHeader, MessageKind};
-use crate::codec::arrow::encode_arrow;
 use crate::encoder::EncodeError;
+use crate::protobuf_conversions::arrow_msg_to_proto;
 use crate::Compression;
 use re_log_types::LogMsg;
 
@@ -10,9 +10,7 @@ pub(crate) fn encode(
     compression: Compression,
 ) -> Result<(), EncodeError> {
     use re_protos::external::prost::Message;
-    use re_protos::log_msg::v0::{
-        self as proto, ArrowMsg, BlueprintActivationCommand, Encoding, SetStoreInfo,
-    };
+    use re_protos::log_msg::v0::{BlueprintActivationCommand, SetStoreInfo};
 
     match message {
         LogMsg::SetStoreInfo(set_store_info) => {
@@ -25,17 +23,7 @@ pub(crate) fn encode(
             set_store_info.encode(buf)?;
         }
         LogMsg::ArrowMsg(store_id, arrow_msg) => {
-            let payload = encode_arrow(&arrow_msg.schema, &arrow_msg.chunk, compression)?;
-            let arrow_msg = ArrowMsg {
-                store_id: Some(store_id.clone().into()),
-                compression: match compression {
-                    Compression::Off => proto::Compression::None as i32,
-                    Compression::LZ4 => proto::Compression::Lz4 as i32,
-                },
-                uncompressed_size: payload.uncompressed_size as i32,
-                encoding: Encoding::ArrowIpc as i32,
-                payload: payload.data,
-            };
+            let arrow_msg = arrow_msg_to_proto(store_id, arrow_msg, compression)?;
             let header = MessageHeader {
                 kind: MessageKind::ArrowMsg,
                 len: arrow_msg.encoded_len() as u64,
@@ -4,12 +4,14 @@ pub mod stream;
 
 use std::io::BufRead as _;
 use std::io::Read;
+use std::io::Seek as _;
 
 use re_build_info::CrateVersion;
 use re_log_types::LogMsg;
 
 use crate::codec;
 use crate::codec::file::decoder;
+use crate::footer::{ChunkIndexQuery, RrdFooter, FOOTER_FRAME_SIZE};
 use crate::FileHeader;
 use crate::MessageHeader;
 use crate::VersionPolicy;
@@ -76,6 +78,9 @@ pub enum DecodeError {
     #[error("lz4 error: {0}")]
     Lz4(#[from] lz4_flex::block::DecompressError),
 
+    #[error("zstd error: {0}")]
+    Zstd(std::io::Error),
+
     #[error("Protobuf error: {0}")]
     Protobuf(#[from] re_protos::external::prost::DecodeError),
 
@@ -244,8 +249,18 @@ impl<R: std::io::Read> Decoder<R> {
         self.size_bytes
     }
 
+    /// Returns the underlying reader, dropping any data that was buffered but not decoded yet.
+    fn into_inner(self) -> R {
+        match self.read {
+            Reader::Raw(read) => read,
+            Reader::Buffered(read) => read.into_inner(),
+        }
+    }
+
     /// Peeks ahead in search of additional `FileHeader`s in the stream.
     ///
+    /// Any [`RrdFooter`] found on the way is skipped over.
+    ///
     /// Returns true if a valid header was found.
     ///
     /// No-op if the decoder wasn't initialized with [`Decoder::new_concatenated`].
@@ -257,6 +272,17 @@ impl<R: std::io::Read> Decoder<R> {
                     return false;
                 }
 
+                if let Some(payload_len) = RrdFooter::decode_frame(read.buffer()) {
+                    let footer_len = payload_len + 2 * FOOTER_FRAME_SIZE as u64;
+                    let mut footer = read.by_ref().take(footer_len);
+                    let Ok(skipped) = std::io::copy(&mut footer, &mut std::io::sink()) else {
+                        return false;
+                    };
+                    self.size_bytes += skipped;
+
+                    return self.peek_file_header();
+                }
+
                 let mut read = std::io::Cursor::new(read.buffer());
                 if FileHeader::decode(&mut read).is_err() {
                     return false;
@@ -268,6 +294,36 @@ impl<R: std::io::Read> Decoder<R> {
     }
 }
 
+impl<R: std::io::Read + std::io::Seek> Decoder<R> {
+    fn seek(&mut self, byte_offset: u64) -> std::io::Result<()> {
+        let pos = std::io::SeekFrom::Start(byte_offset);
+        match &mut self.read {
+            Reader::Raw(read) => read.seek(pos)?,
+            Reader::Buffered(read) => read.seek(pos)?,
+        };
+        Ok(())
+    }
+
+    /// Decodes the single message that starts at `byte_offset` in the stream.
+    ///
+    /// `byte_offset` must be the start of a message, as returned by
+    /// [`crate::encoder::Encoder::num_bytes_written`] right before appending it, or as recorded
+    /// in an [`RrdFooter`].
+    ///
+    /// Iterating the decoder afterwards resumes from the message that follows.
+    pub fn decode_at(&mut self, byte_offset: u64) -> Result<LogMsg, DecodeError> {
+        self.seek(byte_offset).map_err(DecodeError::Read)?;
+
+        match self.next() {
+            Some(res) => res,
+            None => Err(DecodeError::Read(std::io::Error::new(
+                std::io::ErrorKind::UnexpectedEof,
+                format!("no message found at offset {byte_offset}"),
+            ))),
+        }
+    }
+}
+
 impl<R: std::io::Read> Iterator for Decoder<R> {
     type Item = Result<LogMsg, DecodeError>;
 
@@ -359,6 +415,30 @@ impl<R: std::io::Read> Iterator for Decoder<R> {
 
                                 self.size_bytes += compressed_len as u64;
                             }
+
+                            Compression::Zstd { .. } => {
+                                self.compressed
+                                    .resize(self.compressed.len().max(compressed_len), 0);
+
+                                {
+                                    re_tracing::profile_scope!("read compressed");
+                                    if let Err(err) =
+                                        self.read.read_exact(&mut self.compressed[..compressed_len])
+                                    {
+                                        return Some(Err(DecodeError::Read(err)));
+                                    }
+                                }
+
+                                re_tracing::profile_scope!("zstd");
+                                if let Err(err) = crate::zstd_codec::decompress_to_buffer(
+                                    &self.compressed[..compressed_len],
+                                    &mut self.uncompressed[..uncompressed_len],
+                                ) {
+                                    return Some(Err(DecodeError::Zstd(err)));
+                                }
+
+                                self.size_bytes += compressed_len as u64;
+                            }
                         }
 
                         let data = &self.uncompressed[..uncompressed_len];
@@ -400,6 +480,243 @@ impl<R: std::io::Read> Iterator for Decoder<R> {
 
 // ----------------------------------------------------------------------------
 
+/// Reads the [`RrdFooter`] at the end of a seekable `.rrd` stream, if any.
+///
+/// Concatenated streams each end with their own footer: these are walked backwards from the end
+/// of the file and merged into a single footer, whose byte offsets are all relative to the start
+/// of the file.
+///
+/// Returns `Ok(None)` if any of the concatenated streams was written without a footer.
+///
+/// The position of the reader is unspecified after this call.
+pub fn read_footer<R: std::io::Read + std::io::Seek>(
+    read: &mut R,
+) -> Result<Option<RrdFooter>, DecodeError> {
+    re_tracing::profile_function!();
+
+    let Some(footers) = read_footers(read)? else {
+        return Ok(None);
+    };
+
+    Ok(Some(merge_footers(footers)))
+}
+
+/// Merges the footers returned by [`read_footers`], rebasing their byte offsets onto the start
+/// of the file.
+fn merge_footers(footers: Vec<(u64, RrdFooter)>) -> RrdFooter {
+    let mut merged = RrdFooter::default();
+    for (stream_start, footer) in footers {
+        merged
+            .messages
+            .extend(footer.messages.into_iter().map(|mut entry| {
+                entry.byte_offset += stream_start;
+                entry
+            }));
+        merged.stream_len = stream_start + footer.stream_len;
+    }
+    merged
+}
+
+/// Reads the footers of all the concatenated streams in a seekable `.rrd` stream, in order,
+/// along with the byte offset at which each of these streams starts.
+///
+/// Returns `Ok(None)` if any of the streams was written without a footer.
+fn read_footers<R: std::io::Read + std::io::Seek>(
+    read: &mut R,
+) -> Result<Option<Vec<(u64, RrdFooter)>>, DecodeError> {
+    let mut end = read.seek(std::io::SeekFrom::End(0))?;
+
+    let mut footers = Vec::new();
+    while end > 0 {
+        let Some((footer_start, footer)) = read_footer_ending_at(read, end)? else {
+            return Ok(None);
+        };
+        let Some(stream_start) = footer_start.checked_sub(footer.stream_len) else {
+            return Ok(None);
+        };
+        footers.push((stream_start, footer));
+        end = stream_start;
+    }
+
+    if footers.is_empty() {
+        return Ok(None);
+    }
+
+    footers.reverse();
+    Ok(Some(footers))
+}
+
+/// Reads the footer that ends right before `end`, returning the byte offset at which it starts.
+fn read_footer_ending_at<R: std::io::Read + std::io::Seek>(
+    read: &mut R,
+    end: u64,
+) -> Result<Option<(u64, RrdFooter)>, DecodeError> {
+    use std::io::SeekFrom;
+
+    if end < (FileHeader::SIZE + 2 * FOOTER_FRAME_SIZE) as u64 {
+        return Ok(None);
+    }
+
+    let mut frame = [0_u8; FOOTER_FRAME_SIZE];
+    read.seek(SeekFrom::Start(end - FOOTER_FRAME_SIZE as u64))?;
+    read.read_exact(&mut frame)?;
+
+    let Some(payload_len) = RrdFooter::decode_trailing_frame(&frame) else {
+        return Ok(None);
+    };
+
+    let footer_len = payload_len + 2 * FOOTER_FRAME_SIZE as u64;
+    if footer_len > end {
+        return Ok(None);
+    }
+
+    let footer_start = end - footer_len;
+    read.seek(SeekFrom::Start(footer_start))?;
+    read.read_exact(&mut frame)?;
+    if RrdFooter::decode_frame(&frame) != Some(payload_len) {
+        return Ok(None);
+    }
+
+    let mut payload = vec![0_u8; payload_len as usize];
+    read.read_exact(&mut payload)?;
+
+    Ok(Some((footer_start, RrdFooter::decode(&payload)?)))
+}
+
+/// Decodes only the messages of an `.rrd` file matching a [`ChunkIndexQuery`].
+///
+/// This relies on the [`RrdFooter`] written by [`crate::encoder::Encoder::with_footer`] to seek
+/// directly to the relevant messages, without decoding anything else.
+///
+/// Concatenated `.rrd` files are supported, as long as every one of their streams has a footer.
+pub struct IndexedDecoder<R: std::io::Read + std::io::Seek> {
+    version_policy: VersionPolicy,
+    version: CrateVersion,
+
+    /// The decoder for the stream currently being read.
+    ///
+    /// Only `None` if switching to another stream failed.
+    decoder: Option<Decoder<R>>,
+
+    /// The byte offset at which the stream currently being read starts.
+    stream_start: u64,
+
+    footer: RrdFooter,
+
+    /// The start of the stream of each matching message, and its offset within that stream.
+    byte_offsets: std::vec::IntoIter<(u64, u64)>,
+}
+
+impl<R: std::io::Read + std::io::Seek> IndexedDecoder<R> {
+    /// Returns `Ok(None)` if the file has no footer, in which case the caller should fall back
+    /// to streaming the whole file with a [`Decoder`].
+    pub fn new(
+        version_policy: VersionPolicy,
+        mut read: R,
+        query: &ChunkIndexQuery,
+    ) -> Result<Option<Self>, DecodeError> {
+        re_tracing::profile_function!();
+
+        let Some(footers) = read_footers(&mut read)? else {
+            return Ok(None);
+        };
+
+        // Check every header upfront, so that we fail early and know the most recent version.
+        let mut version = None;
+        for (stream_start, _) in &footers {
+            read.seek(std::io::SeekFrom::Start(*stream_start))?;
+            let mut data = [0_u8; FileHeader::SIZE];
+            read.read_exact(&mut data).map_err(DecodeError::Read)?;
+            let (stream_version, _) = read_options(version_policy, &data)?;
+            version = Some(version.map_or(stream_version, |version| {
+                CrateVersion::max(version, stream_version)
+            }));
+        }
+
+        let stream_start = footers.first().map_or(0, |(stream_start, _)| *stream_start);
+        read.seek(std::io::SeekFrom::Start(stream_start))?;
+        let decoder = Decoder::new(version_policy, read)?;
+
+        let byte_offsets = footers
+            .iter()
+            .flat_map(|(stream_start, footer)| {
+                footer
+                    .matching(query)
+                    .map(|entry| (*stream_start, *stream_start + entry.byte_offset))
+            })
+            .collect::<Vec<_>>()
+            .into_iter();
+
+        let footer = merge_footers(footers);
+
+        Ok(Some(Self {
+            version_policy,
+            version: version.unwrap_or_else(|| decoder.version()),
+            decoder: Some(decoder),
+            stream_start,
+            footer,
+            byte_offsets,
+        }))
+    }
+
+    /// Returns the Rerun version that was used to encode the data in the first place.
+    ///
+    /// For concatenated files, this is the most recent version among all of their streams.
+    #[inline]
+    pub fn version(&self) -> CrateVersion {
+        self.version
+    }
+
+    /// The index of the whole file, regardless of the query.
+    ///
+    /// Byte offsets are relative to the start of the file, see [`read_footer`].
+    #[inline]
+    pub fn footer(&self) -> &RrdFooter {
+        &self.footer
+    }
+
+    /// Every stream has its own header, and therefore its own encoding options: start over with
+    /// a fresh decoder when crossing into another one.
+    fn switch_stream(&mut self, stream_start: u64) -> Result<(), DecodeError> {
+        let Some(decoder) = self.decoder.take() else {
+            return Ok(());
+        };
+
+        let mut read = decoder.into_inner();
+        read.seek(std::io::SeekFrom::Start(stream_start))?;
+        self.decoder = Some(Decoder::new(self.version_policy, read)?);
+        self.stream_start = stream_start;
+
+        Ok(())
+    }
+}
+
+impl<R: std::io::Read + std::io::Seek> Iterator for IndexedDecoder<R> {
+    type Item = Result<LogMsg, DecodeError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let (stream_start, byte_offset) = self.byte_offsets.next()?;
+
+        if stream_start != self.stream_start {
+            if let Err(err) = self.switch_stream(stream_start) {
+                self.byte_offsets = Vec::new().into_iter();
+                return Some(Err(err));
+            }
+        }
+
+        let decoder = self.decoder.as_mut()?;
+        let mut msg = decoder.decode_at(byte_offset);
+
+        if let Ok(LogMsg::SetStoreInfo(msg)) = &mut msg {
+            msg.info.store_version = Some(self.version);
+        }
+
+        Some(msg)
+    }
+}
+
+// ----------------------------------------------------------------------------
+
 #[cfg(all(test, feature = "decoder", feature = "encoder"))]
 mod tests {
     #![allow(clippy::unwrap_used)] // acceptable for tests
@@ -483,6 +800,10 @@ mod tests {
                 compression: Compression::LZ4,
                 serializer: Serializer::MsgPack,
             },
+            EncodingOptions {
+                compression: Compression::ZSTD,
+                serializer: Serializer::MsgPack,
+            },
             EncodingOptions {
                 compression: Compression::Off,
                 serializer: Serializer::Protobuf,
@@ -491,6 +812,10 @@ mod tests {
                 compression: Compression::LZ4,
                 serializer: Serializer::Protobuf,
             },
+            EncodingOptions {
+                compression: Compression::Zstd { level: 19 },
+                serializer: Serializer::Protobuf,
+            },
         ];
 
         for options in options {
@@ -520,6 +845,10 @@ mod tests {
                 compression: Compression::LZ4,
                 serializer: Serializer::MsgPack,
             },
+            EncodingOptions {
+                compression: Compression::ZSTD,
+                serializer: Serializer::MsgPack,
+            },
             EncodingOptions {
                 compression: Compression::Off,
                 serializer: Serializer::Protobuf,
@@ -528,6 +857,10 @@ mod tests {
                 compression: Compression::LZ4,
                 serializer: Serializer::Protobuf,
             },
+            EncodingOptions {
+                compression: Compression::Zstd { level: 19 },
+                serializer: Serializer::Protobuf,
+            },
         ];
 
         for options in options {
@@ -570,4 +903,208 @@ mod tests {
             assert_eq!([messages.clone(), messages].concat(), decoded_messages);
         }
     }
+
+    #[test]
+    fn test_footer() {
+        let messages = fake_log_messages();
+
+        let options = [
+            EncodingOptions::MSGPACK_COMPRESSED,
+            EncodingOptions::PROTOBUF_COMPRESSED,
+        ];
+
+        for options in options {
+            let mut data = vec![];
+            {
+                let mut encoder =
+                    crate::encoder::Encoder::new(CrateVersion::LOCAL, options, &mut data)
+                        .unwrap()
+                        .with_footer();
+                for message in &messages {
+                    encoder.append(message).unwrap();
+                }
+                encoder.finish().unwrap();
+            }
+
+            // Decoders that don't care about the footer must keep working as usual…
+            let mut decoded_messages = Decoder::new(VersionPolicy::Error, data.as_slice())
+                .unwrap()
+                .collect::<Result<Vec<_>, _>>()
+                .unwrap();
+            clear_arrow_extension_metadata(&mut decoded_messages);
+            assert_eq!(messages, decoded_messages);
+
+            // …even when the footer sits in the middle of a concatenated stream.
+            let concatenated = [data.clone(), data.clone()].concat();
+            let mut decoded_messages = Decoder::new_concatenated(
+                VersionPolicy::Error,
+                std::io::BufReader::new(concatenated.as_slice()),
+            )
+            .unwrap()
+            .collect::<Result<Vec<_>, _>>()
+            .unwrap();
+            clear_arrow_extension_metadata(&mut decoded_messages);
+            assert_eq!(
+                [messages.clone(), messages.clone()].concat(),
+                decoded_messages
+            );
+
+            let footer = read_footer(&mut std::io::Cursor::new(&data))
+                .unwrap()
+                .unwrap();
+            assert_eq!(messages.len(), footer.messages.len());
+
+            let decode_indexed = |query: &ChunkIndexQuery| {
+                let mut decoded_messages =
+                    IndexedDecoder::new(VersionPolicy::Error, std::io::Cursor::new(&data), query)
+                        .unwrap()
+                        .unwrap()
+                        .collect::<Result<Vec<_>, _>>()
+                        .unwrap();
+                clear_arrow_extension_metadata(&mut decoded_messages);
+                decoded_messages
+            };
+
+            assert_eq!(messages, decode_indexed(&ChunkIndexQuery::default()));
+
+            let query = ChunkIndexQuery {
+                entity_path_filter: Some(
+                    re_log_types::EntityPathFilter::parse_forgiving("/test_entity")
+                        .resolve_forgiving(&re_log_types::EntityPathSubs::empty()),
+                ),
+                time_range: None,
+            };
+            assert_eq!(messages, decode_indexed(&query));
+
+            // Non-chunk messages always make it through.
+            let query = ChunkIndexQuery {
+                entity_path_filter: Some(
+                    re_log_types::EntityPathFilter::parse_forgiving("/nothing/**")
+                        .resolve_forgiving(&re_log_types::EntityPathSubs::empty()),
+                ),
+                time_range: None,
+            };
+            let decoded_messages = decode_indexed(&query);
+            assert_eq!(2, decoded_messages.len());
+            assert!(decoded_messages
+                .iter()
+                .all(|msg| !matches!(msg, LogMsg::ArrowMsg(..))));
+
+            let query = ChunkIndexQuery {
+                entity_path_filter: None,
+                time_range: Some((
+                    re_log_types::Timeline::new_sequence("other_timeline"),
+                    re_log_types::ResolvedTimeRange::EVERYTHING,
+                )),
+            };
+            assert_eq!(2, decode_indexed(&query).len());
+        }
+
+        // Files without a footer
+        let mut data = vec![];
+        crate::encoder::encode_ref(
+            CrateVersion::LOCAL,
+            EncodingOptions::MSGPACK_COMPRESSED,
+            messages.iter().map(Ok),
+            &mut data,
+        )
+        .unwrap();
+        assert!(read_footer(&mut std::io::Cursor::new(&data))
+            .unwrap()
+            .is_none());
+        assert!(IndexedDecoder::new(
+            VersionPolicy::Error,
+            std::io::Cursor::new(&data),
+            &ChunkIndexQuery::default()
+        )
+        .unwrap()
+        .is_none());
+    }
+
+    #[test]
+    fn test_footer_concatenated() {
+        let messages = fake_log_messages();
+
+        // Streams with different options, so that switching decoders between them matters.
+        let streams = [
+            EncodingOptions::MSGPACK_COMPRESSED,
+            EncodingOptions::PROTOBUF_COMPRESSED,
+            EncodingOptions::MSGPACK_UNCOMPRESSED,
+        ]
+        .map(|options| {
+            let mut data = vec![];
+            let mut encoder = crate::encoder::Encoder::new(CrateVersion::LOCAL, options, &mut data)
+                .unwrap()
+                .with_footer();
+            for message in &messages {
+                encoder.append(message).unwrap();
+            }
+            encoder.finish().unwrap();
+            data
+        });
+        let data = streams.concat();
+
+        let footer = read_footer(&mut std::io::Cursor::new(&data))
+            .unwrap()
+            .unwrap();
+        assert_eq!(streams.len() * messages.len(), footer.messages.len());
+        assert_eq!(
+            (data.len() - FOOTER_FRAME_SIZE * 2) as u64,
+            footer.stream_len + footer_payload_len(streams.last().unwrap())
+        );
+
+        // Offsets are relative to the whole file: every stream starts with its own header.
+        let mut stream_start = 0;
+        for (stream, entries) in streams.iter().zip(footer.messages.chunks(messages.len())) {
+            assert_eq!(
+                stream_start + FileHeader::SIZE as u64,
+                entries[0].byte_offset
+            );
+            stream_start += stream.len() as u64;
+        }
+
+        let decode_indexed = |query: &ChunkIndexQuery| {
+            let mut decoded_messages =
+                IndexedDecoder::new(VersionPolicy::Error, std::io::Cursor::new(&data), query)
+                    .unwrap()
+                    .unwrap()
+                    .collect::<Result<Vec<_>, _>>()
+                    .unwrap();
+            clear_arrow_extension_metadata(&mut decoded_messages);
+            decoded_messages
+        };
+
+        assert_eq!(
+            messages.repeat(streams.len()),
+            decode_indexed(&ChunkIndexQuery::default())
+        );
+
+        let query = ChunkIndexQuery {
+            entity_path_filter: Some(
+                re_log_types::EntityPathFilter::parse_forgiving("/nothing/**")
+                    .resolve_forgiving(&re_log_types::EntityPathSubs::empty()),
+            ),
+            time_range: None,
+        };
+        assert_eq!(2 * streams.len(), decode_indexed(&query).len());
+
+        // A single stream without a footer means the file cannot be indexed.
+        let mut unindexed = vec![];
+        crate::encoder::encode_ref(
+            CrateVersion::LOCAL,
+            EncodingOptions::MSGPACK_COMPRESSED,
+            messages.iter().map(Ok),
+            &mut unindexed,
+        )
+        .unwrap();
+        let data = [unindexed, streams[0].clone()].concat();
+        assert!(read_footer(&mut std::io::Cursor::new(&data))
+            .unwrap()
+            .is_none());
+    }
+
+    fn footer_payload_len(stream: &[u8]) -> u64 {
+        let trailing = &stream[stream.len() - FOOTER_FRAME_SIZE..];
+        RrdFooter::decode_trailing_frame(trailing).unwrap()
+    }
 }
@@ -6,6 +6,7 @@ use re_build_info::CrateVersion;
 use re_log_types::LogMsg;
 
 use crate::decoder::read_options;
+use crate::footer::{RrdFooter, FOOTER_FRAME_SIZE};
 use crate::Compression;
 use crate::FileHeader;
 use crate::MessageHeader;
@@ -41,7 +42,7 @@ pub struct StreamDecoder {
 
 ///
 /// ```text,ignore
-/// StreamHeader
+/// StreamHeader <---> Footer
 ///      |
 ///      v
 /// MessageHeader
@@ -73,6 +74,11 @@ enum State {
     /// to read it, otherwise the call to `decompress_into` or the
     /// MessagePack deserialization may block or even fail.
     Message(MessageHeader),
+
+    /// An optional [`RrdFooter`], found right after an end-of-stream marker.
+    ///
+    /// It is only useful for seekable readers, so we just skip over the given number of bytes.
+    Footer(u64),
 }
 
 impl StreamDecoder {
@@ -95,6 +101,12 @@ impl StreamDecoder {
         match self.state {
             State::StreamHeader => {
                 if let Some(header) = self.chunks.try_read(FileHeader::SIZE) {
+                    if let Some(payload_len) = RrdFooter::decode_frame(header) {
+                        // Not a new stream, but the footer of the previous one.
+                        self.state = State::Footer(payload_len + FOOTER_FRAME_SIZE as u64);
+                        return self.try_read();
+                    }
+
                     // header contains version and compression options
                     let (version, options) = read_options(self.version_policy, header)?;
                     self.version = Some(version);
@@ -130,6 +142,15 @@ impl StreamDecoder {
                                         .map_err(DecodeError::Lz4)?;
                                     &self.uncompressed
                                 }
+                                Compression::Zstd { .. } => {
+                                    self.uncompressed.resize(uncompressed_len as usize, 0);
+                                    crate::zstd_codec::decompress_to_buffer(
+                                        bytes,
+                                        &mut self.uncompressed,
+                                    )
+                                    .map_err(DecodeError::Zstd)?;
+                                    &self.uncompressed
+                                }
                             };
 
                             // read the message from the uncompressed bytes
@@ -156,6 +177,12 @@ impl StreamDecoder {
                     }
                 }
             }
+            State::Footer(len) => {
+                if self.chunks.try_read(len as usize).is_some() {
+                    self.state = State::StreamHeader;
+                    return self.try_read();
+                }
+            }
         }
 
         Ok(None)
@@ -407,6 +434,60 @@ mod tests {
         assert_eq!(input, decoded_messages);
     }
 
+    #[test]
+    fn stream_byte_chunks_zstd() {
+        let (input, data) = test_data(EncodingOptions::MSGPACK_ZSTD, 16);
+
+        let mut decoder = StreamDecoder::new(VersionPolicy::Error);
+
+        assert_message_incomplete!(decoder.try_read());
+
+        for chunk in data.chunks(1) {
+            decoder.push_chunk(chunk.to_vec());
+        }
+
+        let decoded_messages: Vec<_> = (0..16)
+            .map(|_| assert_message_ok!(decoder.try_read()))
+            .collect();
+
+        assert_eq!(input, decoded_messages);
+    }
+
+    #[test]
+    fn stream_byte_chunks_with_footer() {
+        let messages: Vec<_> = (0..16).map(|_| fake_log_msg()).collect();
+
+        let mut data = Vec::new();
+        let mut encoder = Encoder::new(
+            CrateVersion::LOCAL,
+            EncodingOptions::MSGPACK_COMPRESSED,
+            &mut data,
+        )
+        .unwrap()
+        .with_footer();
+        for message in &messages {
+            encoder.append(message).unwrap();
+        }
+        encoder.finish().unwrap();
+
+        // The footer must be skipped, including when followed by another stream.
+        let (input2, data2) = test_data(EncodingOptions::MSGPACK_COMPRESSED, 16);
+        let input = messages.into_iter().chain(input2).collect::<Vec<_>>();
+
+        let mut decoder = StreamDecoder::new(VersionPolicy::Error);
+
+        for chunk in data.chunks(1).chain(data2.chunks(1)) {
+            decoder.push_chunk(chunk.to_vec());
+        }
+
+        let decoded_messages: Vec<_> = (0..32)
+            .map(|_| assert_message_ok!(decoder.try_read()))
+            .collect();
+
+        assert_eq!(input, decoded_messages);
+        assert_message_incomplete!(decoder.try_read());
+    }
+
     #[test]
     fn stream_3x16_chunks() {
         let (input, data) = test_data(EncodingOptions::MSGPACK_COMPRESSED, 16);
@@ -2,6 +2,7 @@
 
 use crate::codec;
 use crate::codec::file::{self, encoder};
+use crate::footer::RrdFooter;
 use crate::FileHeader;
 use crate::MessageHeader;
 use crate::Serializer;
@@ -21,6 +22,9 @@ pub enum EncodeError {
     #[error("lz4 error: {0}")]
     Lz4(#[from] lz4_flex::block::CompressError),
 
+    #[error("zstd error: {0}")]
+    Zstd(std::io::Error),
+
     #[error("MsgPack error: {0}")]
     MsgPack(#[from] rmp_serde::encode::Error),
 
@@ -82,6 +86,19 @@ impl<W: std::io::Write> DroppableEncoder<W> {
         })
     }
 
+    /// See [`Encoder::with_footer`].
+    #[inline]
+    pub fn with_footer(mut self) -> Self {
+        self.encoder = self.encoder.with_footer();
+        self
+    }
+
+    /// See [`Encoder::num_bytes_written`].
+    #[inline]
+    pub fn num_bytes_written(&self) -> u64 {
+        self.encoder.num_bytes_written()
+    }
+
     /// Returns the size in bytes of the encoded data.
     #[inline]
     pub fn append(&mut self, message: &LogMsg) -> Result<u64, EncodeError> {
@@ -125,6 +142,12 @@ pub struct Encoder<W: std::io::Write> {
     write: W,
     uncompressed: Vec<u8>,
     compressed: Vec<u8>,
+
+    /// How many bytes have been written out since the start of the stream, headers included.
+    num_bytes_written: u64,
+
+    /// If set, an index of all appended messages is accumulated and written out on [`Self::finish`].
+    footer: Option<RrdFooter>,
 }
 
 impl<W: std::io::Write> Encoder<W> {
@@ -146,13 +169,52 @@ impl<W: std::io::Write> Encoder<W> {
             write,
             uncompressed: Vec::new(),
             compressed: Vec::new(),
+            num_bytes_written: FileHeader::SIZE as _,
+            footer: None,
         })
     }
 
+    /// Write an [`RrdFooter`] at the end of the stream, indexing every appended message.
+    ///
+    /// This allows seekable readers to fetch only the chunks they are interested in, see
+    /// [`crate::decoder::IndexedDecoder`].
+    /// Decoders that don't know about footers will still be able to read the stream as usual.
+    #[inline]
+    pub fn with_footer(mut self) -> Self {
+        self.footer = Some(RrdFooter::default());
+        self
+    }
+
+    /// How many bytes have been written out since the start of the stream, headers included.
+    ///
+    /// This is the byte offset at which the next appended message will start.
+    #[inline]
+    pub fn num_bytes_written(&self) -> u64 {
+        self.num_bytes_written
+    }
+
     /// Returns the size in bytes of the encoded data.
     pub fn append(&mut self, message: &LogMsg) -> Result<u64, EncodeError> {
         re_tracing::profile_function!();
 
+        let size_bytes = self.append_impl(message)?;
+
+        let byte_offset = self.num_bytes_written;
+        let byte_len = match self.serializer {
+            Serializer::MsgPack => MessageHeader::SIZE as u64 + size_bytes,
+            // The protobuf encoder accounts for its own header.
+            Serializer::Protobuf => size_bytes,
+        };
+        self.num_bytes_written += byte_len;
+
+        if let Some(footer) = &mut self.footer {
+            footer.push(byte_offset, byte_len, message)?;
+        }
+
+        Ok(size_bytes)
+    }
+
+    fn append_impl(&mut self, message: &LogMsg) -> Result<u64, EncodeError> {
         self.uncompressed.clear();
         match self.serializer {
             Serializer::Protobuf => {
@@ -198,6 +260,26 @@ impl<W: std::io::Write> Encoder<W> {
                             .map(|_| compressed_len as _)
                             .map_err(EncodeError::Write)
                     }
+
+                    Compression::Zstd { level } => {
+                        let max_len = crate::zstd_codec::compress_bound(self.uncompressed.len());
+                        self.compressed.resize(max_len, 0);
+                        let compressed_len = crate::zstd_codec::compress_to_buffer(
+                            &self.uncompressed,
+                            &mut self.compressed,
+                            level,
+                        )
+                        .map_err(EncodeError::Zstd)?;
+                        MessageHeader::Data {
+                            uncompressed_len: self.uncompressed.len() as u32,
+                            compressed_len: compressed_len as u32,
+                        }
+                        .encode(&mut self.write)?;
+                        self.write
+                            .write_all(&self.compressed[..compressed_len])
+                            .map(|_| compressed_len as _)
+                            .map_err(EncodeError::Write)
+                    }
                 }
             }
         }
@@ -210,15 +292,23 @@ impl<W: std::io::Write> Encoder<W> {
         match self.serializer {
             Serializer::MsgPack => {
                 MessageHeader::EndOfStream.encode(&mut self.write)?;
+                self.num_bytes_written += MessageHeader::SIZE as u64;
             }
             Serializer::Protobuf => {
-                file::MessageHeader {
+                let header = file::MessageHeader {
                     kind: file::MessageKind::End,
                     len: 0,
-                }
-                .encode(&mut self.write)?;
+                };
+                header.encode(&mut self.write)?;
+                self.num_bytes_written += std::mem::size_of_val(&header) as u64;
             }
         }
+
+        if let Some(mut footer) = self.footer.take() {
+            footer.stream_len = self.num_bytes_written;
+            footer.encode(&mut self.write)?;
+        }
+
         Ok(())
     }
 
@@ -24,13 +24,13 @@ pub enum FileSinkError {
     LogMsgEncode(#[from] crate::encoder::EncodeError),
 }
 
-enum Command {
+pub(crate) enum Command {
     Send(LogMsg),
     Flush(SyncSender<()>),
 }
 
 impl Command {
-    fn flush() -> (Self, Receiver<()>) {
+    pub(crate) fn flush() -> (Self, Receiver<()>) {
         let (tx, rx) = std::sync::mpsc::sync_channel(0); // oneshot
         (Self::Flush(tx), rx)
     }
@@ -61,11 +61,36 @@ impl FileSink {
     /// Start writing log messages to a file at the given path.
     pub fn new(path: impl Into<std::path::PathBuf>) -> Result<Self, FileSinkError> {
         // We always compress on disk
-        let encoding_options = crate::EncodingOptions::MSGPACK_COMPRESSED;
+        Self::new_with_options(path, crate::EncodingOptions::MSGPACK_COMPRESSED)
+    }
 
-        let (tx, rx) = std::sync::mpsc::channel();
+    /// Start writing log messages to a file at the given path, using the given encoding options.
+    ///
+    /// Use e.g. [`crate::EncodingOptions::MSGPACK_ZSTD`] for smaller files.
+    pub fn new_with_options(
+        path: impl Into<std::path::PathBuf>,
+        encoding_options: crate::EncodingOptions,
+    ) -> Result<Self, FileSinkError> {
+        Self::create(path.into(), encoding_options, false)
+    }
 
-        let path = path.into();
+    /// Like [`Self::new_with_options`], but also writes an [`crate::RrdFooter`] indexing all
+    /// chunks once the sink is closed.
+    ///
+    /// This allows for random access to the resulting file, see [`crate::decoder::IndexedDecoder`].
+    pub fn new_with_footer(
+        path: impl Into<std::path::PathBuf>,
+        encoding_options: crate::EncodingOptions,
+    ) -> Result<Self, FileSinkError> {
+        Self::create(path.into(), encoding_options, true)
+    }
+
+    fn create(
+        path: PathBuf,
+        encoding_options: crate::EncodingOptions,
+        with_footer: bool,
+    ) -> Result<Self, FileSinkError> {
+        let (tx, rx) = std::sync::mpsc::channel();
 
         re_log::debug!("Saving file to {path:?}…");
 
@@ -75,11 +100,14 @@ impl FileSink {
 
         let file = std::fs::File::create(&path)
             .map_err(|err| FileSinkError::CreateFile(path.clone(), err))?;
-        let encoder = crate::encoder::DroppableEncoder::new(
+        let mut encoder = crate::encoder::DroppableEncoder::new(
             re_build_info::CrateVersion::LOCAL,
             encoding_options,
             file,
         )?;
+        if with_footer {
+            encoder = encoder.with_footer();
+        }
         let join_handle = spawn_and_stream(Some(&path), encoder, rx)?;
 
         Ok(Self {
@@ -91,8 +119,13 @@ impl FileSink {
 
     /// Start writing log messages to standard output.
     pub fn stdout() -> Result<Self, FileSinkError> {
-        let encoding_options = crate::EncodingOptions::MSGPACK_COMPRESSED;
+        Self::stdout_with_options(crate::EncodingOptions::MSGPACK_COMPRESSED)
+    }
 
+    /// Start writing log messages to standard output, using the given encoding options.
+    pub fn stdout_with_options(
+        encoding_options: crate::EncodingOptions,
+    ) -> Result<Self, FileSinkError> {
         let (tx, rx) = std::sync::mpsc::channel();
 
         re_log::debug!("Writing to stdout…");
@@ -0,0 +1,263 @@
+//! An optional index appended at the end of `.rrd` files, allowing random access to chunks.
+//!
+//! The footer follows the end-of-stream marker, and is framed on both ends so that streaming
+//! decoders can skip it while seekable readers can find it from the end of the file:
+//!
+//! ```text,ignore
+//! FOOTER_MAGIC | payload_len: u64 (LE) | payload (MsgPack) | payload_len: u64 (LE) | FOOTER_MAGIC
+//! ```
+//!
+//! Decoders that predate the footer simply stop reading at the end-of-stream marker.
+//!
+//! Every stream of a concatenated `.rrd` file carries its own footer, with byte offsets relative
+//! to the start of that stream. [`RrdFooter::stream_len`] allows readers to walk the chain of
+//! footers backwards from the end of the file, see [`crate::decoder::read_footer`].
+
+use std::collections::BTreeMap;
+
+use re_log_types::{EntityPath, ResolvedEntityPathFilter, ResolvedTimeRange, StoreId, Timeline};
+
+// ----------------------------------------------------------------------------
+
+pub(crate) const FOOTER_MAGIC: &[u8; 4] = b"RRFI";
+
+/// Size of each of the two frames surrounding the footer payload: magic bytes + payload length.
+pub(crate) const FOOTER_FRAME_SIZE: usize = 12;
+
+/// Index of all the messages present in an `.rrd` file, stored at its very end.
+///
+/// See [`crate::encoder::Encoder::with_footer`] and [`crate::decoder::IndexedDecoder`].
+#[derive(Clone, Debug, Default, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct RrdFooter {
+    /// All the messages in the file, in the order they were written.
+    pub messages: Vec<MessageIndexEntry>,
+
+    /// Size in bytes of the stream indexed by this footer, from its `FileHeader` up to and
+    /// including its end-of-stream marker, i.e. the footer itself starts right after.
+    pub stream_len: u64,
+}
+
+/// Where to find a single message in an `.rrd` file, and what it contains.
+#[derive(Clone, Debug, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct MessageIndexEntry {
+    /// Offset of the message, in bytes, relative to the start of its stream (i.e. its `FileHeader`).
+    ///
+    /// Footers returned by [`crate::decoder::read_footer`] are rebased so that this is relative
+    /// to the start of the file instead, even for concatenated streams.
+    pub byte_offset: u64,
+
+    /// Total length of the message in bytes, including its header.
+    pub byte_len: u64,
+
+    /// The store this message belongs to.
+    pub store_id: StoreId,
+
+    /// Set if the message is an `ArrowMsg`, `None` for all other kinds of messages.
+    pub chunk: Option<ChunkIndexEntry>,
+}
+
+/// Summary of the contents of an `ArrowMsg`.
+#[derive(Clone, Debug, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct ChunkIndexEntry {
+    pub entity_path: EntityPath,
+
+    pub num_rows: u64,
+
+    /// Empty for static chunks.
+    pub time_ranges: BTreeMap<Timeline, ResolvedTimeRange>,
+}
+
+impl ChunkIndexEntry {
+    #[inline]
+    pub fn is_static(&self) -> bool {
+        self.time_ranges.is_empty()
+    }
+
+    /// Summarizes an `ArrowMsg` straight from its transport metadata, without deserializing it
+    /// into a [`re_chunk::Chunk`].
+    #[cfg(feature = "encoder")]
+    fn from_arrow_msg(msg: &re_log_types::ArrowMsg) -> re_chunk::ChunkResult<Self> {
+        use arrow2::{
+            array::PrimitiveArray as Arrow2PrimitiveArray, datatypes::DataType as Arrow2Datatype,
+        };
+        use re_log_types::TimeInt;
+
+        let transport = re_chunk::TransportChunk {
+            schema: msg.schema.clone(),
+            data: msg.chunk.clone(), // shallow
+        };
+
+        let mut time_ranges = BTreeMap::default();
+        for (field, column) in transport.timelines() {
+            // See also `Chunk::from_transport`.
+            let timeline = match column.data_type().to_logical_type() {
+                Arrow2Datatype::Int64 => Timeline::new_sequence(field.name.as_str()),
+                Arrow2Datatype::Timestamp(_, _) => Timeline::new_temporal(field.name.as_str()),
+                _ => {
+                    return Err(re_chunk::ChunkError::Malformed {
+                        reason: format!(
+                            "time column '{}' is not deserializable ({:?})",
+                            field.name,
+                            column.data_type()
+                        ),
+                    })
+                }
+            };
+
+            let times = column
+                .as_any()
+                .downcast_ref::<Arrow2PrimitiveArray<i64>>()
+                .ok_or_else(|| re_chunk::ChunkError::Malformed {
+                    reason: format!(
+                        "time column '{}' is not deserializable ({:?})",
+                        field.name,
+                        column.data_type()
+                    ),
+                })?;
+
+            let times = times.values().as_slice();
+            let (Some(min), Some(max)) = (times.iter().min(), times.iter().max()) else {
+                continue;
+            };
+            time_ranges.insert(
+                timeline,
+                ResolvedTimeRange::new(TimeInt::new_temporal(*min), TimeInt::new_temporal(*max)),
+            );
+        }
+
+        Ok(Self {
+            entity_path: transport.entity_path()?,
+            num_rows: transport.num_rows() as u64,
+            time_ranges,
+        })
+    }
+}
+
+/// Which chunks to fetch from an indexed `.rrd` file.
+///
+/// Non-chunk messages (e.g. `SetStoreInfo`) always match, so that the results can be loaded as-is.
+#[derive(Clone, Debug, Default)]
+pub struct ChunkIndexQuery {
+    /// Only keep chunks whose entity path matches this filter.
+    ///
+    /// `None` means all entities.
+    pub entity_path_filter: Option<ResolvedEntityPathFilter>,
+
+    /// Only keep chunks that have data on the given timeline, within the given range.
+    ///
+    /// Static chunks always match.
+    /// `None` means all times.
+    pub time_range: Option<(Timeline, ResolvedTimeRange)>,
+}
+
+impl ChunkIndexQuery {
+    pub fn matches(&self, entry: &MessageIndexEntry) -> bool {
+        let Some(chunk) = &entry.chunk else {
+            return true;
+        };
+
+        if let Some(filter) = &self.entity_path_filter {
+            if !filter.matches(&chunk.entity_path) {
+                return false;
+            }
+        }
+
+        if let Some((timeline, range)) = &self.time_range {
+            if !chunk.is_static() {
+                let Some(chunk_range) = chunk.time_ranges.get(timeline) else {
+                    return false;
+                };
+                if !chunk_range.intersects(*range) {
+                    return false;
+                }
+            }
+        }
+
+        true
+    }
+}
+
+impl RrdFooter {
+    /// All the messages that match the given query, in file order.
+    pub fn matching<'a>(
+        &'a self,
+        query: &'a ChunkIndexQuery,
+    ) -> impl Iterator<Item = &'a MessageIndexEntry> + 'a {
+        self.messages.iter().filter(|entry| query.matches(entry))
+    }
+
+    #[cfg(feature = "encoder")]
+    pub(crate) fn push(
+        &mut self,
+        byte_offset: u64,
+        byte_len: u64,
+        message: &re_log_types::LogMsg,
+    ) -> Result<(), crate::encoder::EncodeError> {
+        let chunk = match message {
+            re_log_types::LogMsg::ArrowMsg(_, arrow_msg) => {
+                Some(ChunkIndexEntry::from_arrow_msg(arrow_msg)?)
+            }
+            re_log_types::LogMsg::SetStoreInfo(_)
+            | re_log_types::LogMsg::BlueprintActivationCommand(_) => None,
+        };
+
+        self.messages.push(MessageIndexEntry {
+            byte_offset,
+            byte_len,
+            store_id: message.store_id().clone(),
+            chunk,
+        });
+
+        Ok(())
+    }
+
+    #[cfg(feature = "encoder")]
+    pub(crate) fn encode(
+        &self,
+        write: &mut impl std::io::Write,
+    ) -> Result<(), crate::encoder::EncodeError> {
+        let payload = rmp_serde::to_vec_named(self)?;
+        let payload_len = (payload.len() as u64).to_le_bytes();
+
+        write.write_all(FOOTER_MAGIC)?;
+        write.write_all(&payload_len)?;
+        write.write_all(&payload)?;
+        write.write_all(&payload_len)?;
+        write.write_all(FOOTER_MAGIC)?;
+
+        Ok(())
+    }
+
+    /// Parses the leading frame of a footer, returning the length of its payload.
+    ///
+    /// Returns `None` if `frame` is not the start of a footer.
+    #[cfg(feature = "decoder")]
+    pub(crate) fn decode_frame(frame: &[u8]) -> Option<u64> {
+        if frame.len() < FOOTER_FRAME_SIZE || &frame[..4] != FOOTER_MAGIC {
+            return None;
+        }
+
+        #[allow(clippy::unwrap_used)] // cannot fail
+        let payload_len = u64::from_le_bytes(frame[4..12].try_into().unwrap());
+        Some(payload_len)
+    }
+
+    /// Parses the trailing frame of a footer, returning the length of its payload.
+    ///
+    /// Returns `None` if `frame` is not the end of a footer.
+    #[cfg(feature = "decoder")]
+    pub(crate) fn decode_trailing_frame(frame: &[u8]) -> Option<u64> {
+        if frame.len() < FOOTER_FRAME_SIZE || &frame[8..12] != FOOTER_MAGIC {
+            return None;
+        }
+
+        #[allow(clippy::unwrap_used)] // cannot fail
+        let payload_len = u64::from_le_bytes(frame[0..8].try_into().unwrap());
+        Some(payload_len)
+    }
+
+    #[cfg(feature = "decoder")]
+    pub(crate) fn decode(payload: &[u8]) -> Result<Self, crate::decoder::DecodeError> {
+        Ok(rmp_serde::from_slice(payload)?)
+    }
+}
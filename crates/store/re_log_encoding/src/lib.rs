@@ -8,12 +8,22 @@ pub mod encoder;
 
 pub mod codec;
 
+#[cfg(any(feature = "encoder", feature = "decoder"))]
+pub mod footer;
+
 mod protobuf_conversions;
 
+#[cfg(any(feature = "encoder", feature = "decoder"))]
+mod zstd_codec;
+
 #[cfg(feature = "encoder")]
 #[cfg(not(target_arch = "wasm32"))]
 mod file_sink;
 
+#[cfg(feature = "encoder")]
+#[cfg(not(target_arch = "wasm32"))]
+mod rotating_file_sink;
+
 #[cfg(feature = "stream_from_http")]
 pub mod stream_rrd_from_http;
 
@@ -37,6 +47,19 @@ pub enum VersionPolicy {
 #[cfg(not(target_arch = "wasm32"))]
 pub use file_sink::{FileSink, FileSinkError};
 
+#[cfg(feature = "encoder")]
+#[cfg(not(target_arch = "wasm32"))]
+pub use rotating_file_sink::{RotatingFileSink, RotatingFileSinkOptions};
+
+#[cfg(any(feature = "encoder", feature = "decoder"))]
+pub use footer::{ChunkIndexEntry, ChunkIndexQuery, MessageIndexEntry, RrdFooter};
+
+#[cfg(feature = "encoder")]
+pub use protobuf_conversions::log_msg_to_proto;
+
+#[cfg(feature = "decoder")]
+pub use protobuf_conversions::log_msg_from_proto;
+
 // ----------------------------------------------------------------------------
 
 #[cfg(any(feature = "encoder", feature = "decoder"))]
@@ -49,12 +72,85 @@ const OLD_RRD_HEADERS: &[[u8; 4]] = &[*b"RRF0", *b"RRF1"];
 
 /// Compression format used.
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
-#[repr(u8)]
 pub enum Compression {
-    Off = 0,
+    Off,
 
     /// Very fast compression and decompression, but not very good compression ratio.
-    LZ4 = 1,
+    LZ4,
+
+    /// Slower compression, but much better compression ratio.
+    ///
+    /// Well suited for archival, e.g. recordings that are rarely re-read.
+    ///
+    /// The `level` is only used during encoding, it is not stored in the stream.
+    /// Encoding is only supported on native: the web can decode zstd-compressed data,
+    /// but not encode it.
+    /// Encoding is only supported on native: on the web, zstd-compressed data can be decoded but not encoded.
+    Zstd {
+        level: i32,
+    },
+}
+
+impl Compression {
+    /// The default zstd compression level, matching the one of the reference implementation.
+    pub const ZSTD_DEFAULT_LEVEL: i32 = 3;
+
+    /// [`Compression::Zstd`] with the default compression level.
+    pub const ZSTD: Self = Self::Zstd {
+        level: Self::ZSTD_DEFAULT_LEVEL,
+    };
+
+    /// The on-disk identifier of this compression format.
+    pub fn to_u8(self) -> u8 {
+        match self {
+            Self::Off => 0,
+            Self::LZ4 => 1,
+            Self::Zstd { .. } => 2,
+        }
+    }
+
+    pub fn from_u8(value: u8) -> Result<Self, OptionsError> {
+        match value {
+            0 => Ok(Self::Off),
+            1 => Ok(Self::LZ4),
+            2 => Ok(Self::ZSTD),
+            _ => Err(OptionsError::UnknownCompression(value)),
+        }
+    }
+}
+
+impl std::fmt::Display for Compression {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Off => f.write_str("off"),
+            Self::LZ4 => f.write_str("lz4"),
+            Self::Zstd { level } => write!(f, "zstd:{level}"),
+        }
+    }
+}
+
+impl std::str::FromStr for Compression {
+    type Err = OptionsError;
+
+    /// Parses `off`, `lz4`, `zstd` or `zstd:<level>` (e.g. `zstd:19`).
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim().to_lowercase();
+        match s.as_str() {
+            "off" | "none" => Ok(Self::Off),
+            "lz4" => Ok(Self::LZ4),
+            "zstd" => Ok(Self::ZSTD),
+            _ => {
+                if let Some(level) = s.strip_prefix("zstd:") {
+                    let level = level
+                        .parse()
+                        .map_err(|_err| OptionsError::InvalidCompressionName(s.clone()))?;
+                    Ok(Self::Zstd { level })
+                } else {
+                    Err(OptionsError::InvalidCompressionName(s))
+                }
+            }
+        }
+    }
 }
 
 /// How we serialize the data
@@ -84,15 +180,15 @@ impl EncodingOptions {
         compression: Compression::LZ4,
         serializer: Serializer::Protobuf,
     };
+    pub const MSGPACK_ZSTD: Self = Self {
+        compression: Compression::ZSTD,
+        serializer: Serializer::MsgPack,
+    };
 
     pub fn from_bytes(bytes: [u8; 4]) -> Result<Self, OptionsError> {
         match bytes {
             [compression, serializer, 0, 0] => {
-                let compression = match compression {
-                    0 => Compression::Off,
-                    1 => Compression::LZ4,
-                    _ => return Err(OptionsError::UnknownCompression(compression)),
-                };
+                let compression = Compression::from_u8(compression)?;
                 let serializer = match serializer {
                     1 => Serializer::MsgPack,
                     2 => Serializer::Protobuf,
@@ -109,7 +205,7 @@ impl EncodingOptions {
 
     pub fn to_bytes(self) -> [u8; 4] {
         [
-            self.compression as u8,
+            self.compression.to_u8(),
             self.serializer as u8,
             0, // reserved
             0, // reserved
@@ -127,6 +223,9 @@ pub enum OptionsError {
     #[error("Unknown compression: {0}")]
     UnknownCompression(u8),
 
+    #[error("Invalid compression: {0:?} (expected one of `off`, `lz4`, `zstd` or `zstd:<level>`)")]
+    InvalidCompressionName(String),
+
     #[error("Unknown serializer: {0}")]
     UnknownSerializer(u8),
 }
@@ -141,7 +240,6 @@ pub(crate) struct FileHeader {
 
 #[cfg(any(feature = "encoder", feature = "decoder"))]
 impl FileHeader {
-    #[cfg(feature = "decoder")]
     pub const SIZE: usize = 12;
 
     #[cfg(feature = "encoder")]
@@ -189,7 +287,6 @@ pub(crate) enum MessageHeader {
 
 #[cfg(any(feature = "encoder", feature = "decoder"))]
 impl MessageHeader {
-    #[cfg(feature = "decoder")]
     pub const SIZE: usize = 8;
 
     #[cfg(feature = "encoder")]
@@ -3,6 +3,7 @@ impl From<re_protos::log_msg::v0::Compression> for crate::Compression {
         match value {
             re_protos::log_msg::v0::Compression::None => Self::Off,
             re_protos::log_msg::v0::Compression::Lz4 => Self::LZ4,
+            re_protos::log_msg::v0::Compression::Zstd => Self::ZSTD,
         }
     }
 }
@@ -12,6 +13,170 @@ impl From<crate::Compression> for re_protos::log_msg::v0::Compression {
         match value {
             crate::Compression::Off => Self::None,
             crate::Compression::LZ4 => Self::Lz4,
+            crate::Compression::Zstd { .. } => Self::Zstd,
+        }
+    }
+}
+
+/// Converts an [`re_log_types::ArrowMsg`] into its protobuf representation, compressing the
+/// Arrow-IPC payload as specified.
+#[cfg(feature = "encoder")]
+pub(crate) fn arrow_msg_to_proto(
+    store_id: &re_log_types::StoreId,
+    arrow_msg: &re_log_types::ArrowMsg,
+    compression: crate::Compression,
+) -> Result<re_protos::log_msg::v0::ArrowMsg, crate::encoder::EncodeError> {
+    use re_protos::log_msg::v0::{Compression, Encoding};
+
+    let payload =
+        crate::codec::arrow::encode_arrow(&arrow_msg.schema, &arrow_msg.chunk, compression)?;
+
+    Ok(re_protos::log_msg::v0::ArrowMsg {
+        store_id: Some(store_id.clone().into()),
+        compression: Compression::from(compression) as i32,
+        uncompressed_size: payload.uncompressed_size as i32,
+        encoding: Encoding::ArrowIpc as i32,
+        payload: payload.data,
+    })
+}
+
+/// Converts a protobuf `ArrowMsg` back into a [`re_log_types::LogMsg`].
+#[cfg(feature = "decoder")]
+pub(crate) fn arrow_msg_from_proto(
+    arrow_msg: re_protos::log_msg::v0::ArrowMsg,
+) -> Result<re_log_types::LogMsg, crate::decoder::DecodeError> {
+    use re_protos::log_msg::v0::Encoding;
+
+    if arrow_msg.encoding() != Encoding::ArrowIpc {
+        return Err(crate::decoder::DecodeError::Codec(
+            crate::codec::CodecError::UnsupportedEncoding,
+        ));
+    }
+
+    let (schema, chunk) = crate::codec::arrow::decode_arrow(
+        &arrow_msg.payload,
+        arrow_msg.uncompressed_size as usize,
+        arrow_msg.compression().into(),
+    )?;
+
+    let store_id: re_log_types::StoreId = arrow_msg
+        .store_id
+        .ok_or_else(|| re_protos::missing_field!(re_protos::log_msg::v0::ArrowMsg, "store_id"))?
+        .into();
+
+    let chunk = re_chunk::Chunk::from_transport(&re_chunk::TransportChunk {
+        schema,
+        data: chunk,
+    })?;
+
+    Ok(re_log_types::LogMsg::ArrowMsg(
+        store_id,
+        chunk.to_arrow_msg()?,
+    ))
+}
+
+/// Converts a [`re_log_types::LogMsg`] into its protobuf representation, e.g. to send it over
+/// gRPC.
+///
+/// Arrow payloads are compressed as specified.
+#[cfg(feature = "encoder")]
+pub fn log_msg_to_proto(
+    message: re_log_types::LogMsg,
+    compression: crate::Compression,
+) -> Result<re_protos::log_msg::v0::LogMsg, crate::encoder::EncodeError> {
+    use re_protos::log_msg::v0::log_msg::Msg;
+
+    let msg = match message {
+        re_log_types::LogMsg::SetStoreInfo(set_store_info) => {
+            Msg::SetStoreInfo(set_store_info.into())
+        }
+        re_log_types::LogMsg::ArrowMsg(store_id, arrow_msg) => {
+            Msg::ArrowMsg(arrow_msg_to_proto(&store_id, &arrow_msg, compression)?)
+        }
+        re_log_types::LogMsg::BlueprintActivationCommand(blueprint_activation_command) => {
+            Msg::BlueprintActivationCommand(blueprint_activation_command.into())
+        }
+    };
+
+    Ok(re_protos::log_msg::v0::LogMsg { msg: Some(msg) })
+}
+
+/// Converts a protobuf `LogMsg`, e.g. received over gRPC, back into a [`re_log_types::LogMsg`].
+#[cfg(feature = "decoder")]
+pub fn log_msg_from_proto(
+    message: re_protos::log_msg::v0::LogMsg,
+) -> Result<re_log_types::LogMsg, crate::decoder::DecodeError> {
+    use re_protos::log_msg::v0::log_msg::Msg;
+
+    match message
+        .msg
+        .ok_or_else(|| re_protos::missing_field!(re_protos::log_msg::v0::LogMsg, "msg"))?
+    {
+        Msg::SetStoreInfo(set_store_info) => Ok(re_log_types::LogMsg::SetStoreInfo(
+            set_store_info.try_into()?,
+        )),
+        Msg::ArrowMsg(arrow_msg) => arrow_msg_from_proto(arrow_msg),
+        Msg::BlueprintActivationCommand(blueprint_activation_command) => {
+            Ok(re_log_types::LogMsg::BlueprintActivationCommand(
+                blueprint_activation_command.try_into()?,
+            ))
+        }
+    }
+}
+
+#[cfg(all(test, feature = "encoder", feature = "decoder"))]
+mod tests {
+    use re_chunk::{Chunk, RowId};
+    use re_log_types::{
+        example_components::MyPoint, ApplicationId, BlueprintActivationCommand, LogMsg,
+        SetStoreInfo, StoreId, StoreInfo, StoreKind, StoreSource, Time, Timeline,
+    };
+
+    use super::{log_msg_from_proto, log_msg_to_proto};
+
+    #[test]
+    fn log_msg_proto_roundtrip() {
+        let store_id = StoreId::random(StoreKind::Recording);
+
+        let chunk = Chunk::builder("points".into())
+            .with_component_batches(
+                RowId::new(),
+                [(Timeline::new_sequence("frame"), 1)],
+                [&[MyPoint::new(1.0, 2.0)] as _],
+            )
+            .build()
+            .unwrap();
+
+        let messages = vec![
+            LogMsg::SetStoreInfo(SetStoreInfo {
+                row_id: *RowId::new(),
+                info: StoreInfo {
+                    application_id: ApplicationId::from("test"),
+                    store_id: store_id.clone(),
+                    cloned_from: None,
+                    is_official_example: false,
+                    started: Time::now(),
+                    store_source: StoreSource::Unknown,
+                    store_version: Some(re_build_info::CrateVersion::LOCAL),
+                },
+            }),
+            LogMsg::ArrowMsg(store_id, chunk.to_arrow_msg().unwrap()),
+            LogMsg::BlueprintActivationCommand(BlueprintActivationCommand::make_active(
+                StoreId::random(StoreKind::Blueprint),
+            )),
+        ];
+
+        for compression in [
+            crate::Compression::Off,
+            crate::Compression::LZ4,
+            crate::Compression::ZSTD,
+            crate::Compression::Zstd { level: 19 },
+        ] {
+            for message in &messages {
+                let proto = log_msg_to_proto(message.clone(), compression).unwrap();
+                let decoded = log_msg_from_proto(proto).unwrap();
+                assert_eq!(message, &decoded);
+            }
         }
     }
 }
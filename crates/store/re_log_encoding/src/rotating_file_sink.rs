@@ -0,0 +1,797 @@
+use std::fmt;
+use std::{
+    collections::BTreeSet,
+    path::{Path, PathBuf},
+    sync::mpsc::{Receiver, Sender},
+    time::{Duration, Instant},
+};
+
+use parking_lot::Mutex;
+
+use re_chunk::{
+    Chunk, ChunkId, ComponentName, LatestAtQuery, RowId, TimeInt, Timeline, TransportChunk,
+};
+use re_log_types::{EntityPath, LogMsg, StoreId, StoreKind};
+
+use crate::file_sink::{Command, FileSinkError};
+
+/// Configures when a [`RotatingFileSink`] starts a new segment, and which old segments it deletes.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct RotatingFileSinkOptions {
+    /// Start a new segment once the current one has grown to at least this many bytes.
+    pub max_segment_bytes: Option<u64>,
+
+    /// Start a new segment once the current one has been open for at least this long.
+    pub max_segment_duration: Option<Duration>,
+
+    /// Delete the oldest segments so that at most this many are kept, including the current one.
+    pub max_segments: Option<usize>,
+
+    /// Delete the oldest segments so that all segments together take at most this many bytes.
+    ///
+    /// The current segment is never deleted, even if it is larger than this on its own.
+    pub max_total_bytes: Option<u64>,
+
+    pub encoding_options: crate::EncodingOptions,
+}
+
+impl Default for RotatingFileSinkOptions {
+    fn default() -> Self {
+        Self {
+            max_segment_bytes: None,
+            max_segment_duration: None,
+            max_segments: None,
+            max_total_bytes: None,
+
+            // We always compress on disk
+            encoding_options: crate::EncodingOptions::MSGPACK_COMPRESSED,
+        }
+    }
+}
+
+/// Stream log messages to a series of `.rrd` files, starting a new one whenever the current one
+/// gets too big or too old.
+///
+/// Every segment is self-contained: it starts with the `SetStoreInfo` of every store seen so far,
+/// followed by the latest static data, the latest value of every blueprint component and the
+/// latest activation command of every blueprint, so that each file can be opened on its own.
+///
+/// Segments are named `<path_prefix>-000001.rrd`, `<path_prefix>-000002.rrd`, etc.
+/// Numbering resumes after any existing segment with the same prefix, and those existing
+/// segments also count towards the retention limits of [`RotatingFileSinkOptions`].
+pub struct RotatingFileSink {
+    // None = quit
+    tx: Mutex<Sender<Option<Command>>>,
+    join_handle: Option<std::thread::JoinHandle<()>>,
+
+    /// Only used for diagnostics, not for access after `new()`.
+    path_prefix: PathBuf,
+}
+
+impl Drop for RotatingFileSink {
+    fn drop(&mut self) {
+        self.tx.lock().send(None).ok();
+        if let Some(join_handle) = self.join_handle.take() {
+            join_handle.join().ok();
+        }
+    }
+}
+
+impl RotatingFileSink {
+    /// Start writing log messages to segments named after the given path prefix.
+    ///
+    /// The first segment is created right away.
+    pub fn new(
+        path_prefix: impl Into<PathBuf>,
+        options: RotatingFileSinkOptions,
+    ) -> Result<Self, FileSinkError> {
+        let path_prefix = path_prefix.into();
+        let (tx, rx) = std::sync::mpsc::channel();
+
+        let writer = SegmentWriter::new(path_prefix.clone(), options)?;
+        re_log::debug!("Saving rotating segments to {:?}…", writer.segment.path);
+
+        let join_handle = std::thread::Builder::new()
+            .name("rotating_file_writer".into())
+            .spawn(move || writer.run(&rx))
+            .map_err(FileSinkError::SpawnThread)?;
+
+        Ok(Self {
+            tx: tx.into(),
+            join_handle: Some(join_handle),
+            path_prefix,
+        })
+    }
+
+    #[inline]
+    pub fn flush_blocking(&self) {
+        let (cmd, oneshot) = Command::flush();
+        self.tx.lock().send(Some(cmd)).ok();
+        oneshot.recv().ok();
+    }
+
+    #[inline]
+    pub fn send(&self, log_msg: LogMsg) {
+        self.tx.lock().send(Some(Command::Send(log_msg))).ok();
+    }
+}
+
+impl fmt::Debug for RotatingFileSink {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("RotatingFileSink")
+            .field("path_prefix", &self.path_prefix)
+            .finish_non_exhaustive()
+    }
+}
+
+// ----------------------------------------------------------------------------
+
+struct Segment {
+    path: PathBuf,
+    encoder: crate::encoder::DroppableEncoder<std::fs::File>,
+    opened_at: Instant,
+
+    /// Whether anything was written beyond the messages carried over from the previous segments.
+    has_new_data: bool,
+}
+
+/// A message that gets written again at the start of every new segment.
+struct RetainedMsg {
+    msg: LogMsg,
+
+    /// Identifies the newer messages that make this one redundant.
+    key: RetainedKey,
+}
+
+enum RetainedKey {
+    StoreInfo(StoreId),
+    Static(StaticKey),
+    BlueprintComponent(BlueprintKey),
+    ActivationCommand(StoreId),
+}
+
+impl RetainedKey {
+    /// Does the message with this key make the message with the `other` key redundant?
+    fn supersedes(&self, other: &Self) -> bool {
+        match (self, other) {
+            (Self::StoreInfo(store_id), Self::StoreInfo(other))
+            | (Self::ActivationCommand(store_id), Self::ActivationCommand(other)) => {
+                store_id == other
+            }
+            (Self::Static(key), Self::Static(other)) => key.supersedes(other),
+            (Self::BlueprintComponent(key), Self::BlueprintComponent(other)) => {
+                key.supersedes(other)
+            }
+            _ => false,
+        }
+    }
+}
+
+struct StaticKey {
+    store_id: StoreId,
+    entity_path: EntityPath,
+    components: BTreeSet<String>,
+}
+
+impl StaticKey {
+    /// Returns `None` if this is not a static chunk.
+    fn from_arrow_msg(store_id: &StoreId, arrow_msg: &re_log_types::ArrowMsg) -> Option<Self> {
+        let chunk = TransportChunk {
+            schema: arrow_msg.schema.clone(),
+            data: arrow_msg.chunk.clone(),
+        };
+
+        if chunk.num_timelines() != 0 {
+            return None;
+        }
+
+        Some(Self {
+            store_id: store_id.clone(),
+            entity_path: chunk.entity_path().ok()?,
+            components: chunk
+                .components()
+                .map(|(field, _)| field.name.clone())
+                .collect(),
+        })
+    }
+
+    /// Does the data with this key overwrite all of the data of `other`?
+    fn supersedes(&self, other: &Self) -> bool {
+        self.store_id == other.store_id
+            && self.entity_path == other.entity_path
+            && other.components.is_subset(&self.components)
+    }
+}
+
+/// A single-row, single-component blueprint chunk: the latest value of that component.
+struct BlueprintKey {
+    store_id: StoreId,
+    entity_path: EntityPath,
+    component_name: ComponentName,
+
+    /// Static data always wins, then the latest time, then the latest row.
+    index: (bool, TimeInt, RowId),
+}
+
+impl BlueprintKey {
+    /// Returns `None` if the chunk is empty.
+    fn from_latest_at_chunk(
+        store_id: &StoreId,
+        component_name: ComponentName,
+        chunk: &Chunk,
+        timeline: &Timeline,
+    ) -> Option<Self> {
+        let row_id = chunk.row_ids().next()?;
+        let time = if chunk.is_static() {
+            TimeInt::STATIC
+        } else {
+            TimeInt::new_temporal(*chunk.timelines().get(timeline)?.times_raw().first()?)
+        };
+
+        Some(Self {
+            store_id: store_id.clone(),
+            entity_path: chunk.entity_path().clone(),
+            component_name,
+            index: (chunk.is_static(), time, row_id),
+        })
+    }
+
+    /// Is this at least as recent a value of the same component as `other`?
+    fn supersedes(&self, other: &Self) -> bool {
+        self.store_id == other.store_id
+            && self.entity_path == other.entity_path
+            && self.component_name == other.component_name
+            && other.index <= self.index
+    }
+}
+
+/// Lives on the writer thread.
+struct SegmentWriter {
+    path_prefix: PathBuf,
+    options: RotatingFileSinkOptions,
+
+    segment: Segment,
+    next_index: u64,
+
+    /// Everything needed to make a new segment self-contained, in the order it was received.
+    retained: Vec<RetainedMsg>,
+}
+
+impl SegmentWriter {
+    fn new(path_prefix: PathBuf, options: RotatingFileSinkOptions) -> Result<Self, FileSinkError> {
+        let next_index = existing_segments(&path_prefix)
+            .last()
+            .map_or(1, |segment| segment.index + 1);
+
+        let segment = open_segment(&path_prefix, next_index, options.encoding_options)?;
+
+        let writer = Self {
+            path_prefix,
+            options,
+            segment,
+            next_index: next_index + 1,
+            retained: Vec::new(),
+        };
+        writer.enforce_retention();
+
+        Ok(writer)
+    }
+
+    fn run(mut self, rx: &Receiver<Option<Command>>) {
+        while let Ok(Some(cmd)) = rx.recv() {
+            match cmd {
+                Command::Send(log_msg) => {
+                    if let Err(err) = self.append(log_msg) {
+                        re_log::error!(
+                            "Failed to write log stream to {:?}: {err}",
+                            self.segment.path
+                        );
+                        return;
+                    }
+                }
+                Command::Flush(oneshot) => {
+                    re_log::trace!("Flushing…");
+                    if let Err(err) = self.segment.encoder.flush_blocking() {
+                        re_log::error!(
+                            "Failed to flush log stream to {:?}: {err}",
+                            self.segment.path
+                        );
+                        return;
+                    }
+                    drop(oneshot); // signals the oneshot
+                }
+            }
+        }
+
+        if let Err(err) = self.segment.encoder.finish() {
+            re_log::error!(
+                "Failed to end log stream for {:?}: {err}",
+                self.segment.path
+            );
+            return;
+        }
+        re_log::debug!("Log stream written to {:?}", self.segment.path);
+    }
+
+    fn append(&mut self, log_msg: LogMsg) -> Result<(), FileSinkError> {
+        if self.should_rotate() {
+            self.rotate()?;
+        }
+
+        self.segment.encoder.append(&log_msg)?;
+        self.segment.has_new_data = true;
+
+        self.retain(log_msg);
+
+        Ok(())
+    }
+
+    fn should_rotate(&self) -> bool {
+        // Never rotate a segment that only holds carried-over data, or we'd rotate forever if
+        // that alone exceeds the limits.
+        if !self.segment.has_new_data {
+            return false;
+        }
+
+        let too_big = self
+            .options
+            .max_segment_bytes
+            .map_or(false, |max| max <= self.segment.encoder.num_bytes_written());
+        let too_old = self
+            .options
+            .max_segment_duration
+            .map_or(false, |max| max <= self.segment.opened_at.elapsed());
+
+        too_big || too_old
+    }
+
+    fn rotate(&mut self) -> Result<(), FileSinkError> {
+        let segment = open_segment(
+            &self.path_prefix,
+            self.next_index,
+            self.options.encoding_options,
+        )?;
+        self.next_index += 1;
+
+        let mut previous = std::mem::replace(&mut self.segment, segment);
+        if let Err(err) = previous.encoder.finish() {
+            re_log::error!("Failed to end log stream for {:?}: {err}", previous.path);
+        } else {
+            re_log::debug!("Log stream written to {:?}", previous.path);
+        }
+        drop(previous);
+
+        re_log::debug!("Rotating to {:?}", self.segment.path);
+
+        for retained in &self.retained {
+            self.segment.encoder.append(&retained.msg)?;
+        }
+
+        self.enforce_retention();
+
+        Ok(())
+    }
+
+    /// Keeps track of what needs to be carried over to the next segments.
+    fn retain(&mut self, log_msg: LogMsg) {
+        match &log_msg {
+            LogMsg::SetStoreInfo(info) => {
+                let key = RetainedKey::StoreInfo(info.info.store_id.clone());
+
+                // Store infos are updated in place: they must come before the data of their store.
+                if let Some(existing) = self
+                    .retained
+                    .iter_mut()
+                    .find(|retained| key.supersedes(&retained.key))
+                {
+                    existing.msg = log_msg;
+                } else {
+                    self.retained.push(RetainedMsg { msg: log_msg, key });
+                }
+            }
+
+            LogMsg::ArrowMsg(store_id, arrow_msg) => {
+                if store_id.kind == StoreKind::Blueprint {
+                    self.retain_blueprint(store_id, arrow_msg);
+                } else if let Some(static_key) = StaticKey::from_arrow_msg(store_id, arrow_msg) {
+                    self.replace(RetainedKey::Static(static_key), log_msg);
+                }
+            }
+
+            LogMsg::BlueprintActivationCommand(cmd) => {
+                let key = RetainedKey::ActivationCommand(cmd.blueprint_id.clone());
+                self.replace(key, log_msg);
+            }
+        }
+    }
+
+    /// Only carries over the latest value of each blueprint component.
+    fn retain_blueprint(&mut self, store_id: &StoreId, arrow_msg: &re_log_types::ArrowMsg) {
+        let chunk = match Chunk::from_arrow_msg(arrow_msg) {
+            Ok(chunk) => chunk,
+            Err(err) => {
+                re_log::warn_once!(
+                    "Failed to decode a blueprint chunk, it won't be carried over to new segments: {err}"
+                );
+                return;
+            }
+        };
+
+        // Same as `re_viewer_context::blueprint_timeline`.
+        let timeline = Timeline::new_sequence("blueprint");
+        let query = LatestAtQuery::new(timeline, TimeInt::MAX);
+
+        for component_name in chunk.component_names() {
+            let latest = chunk
+                .latest_at(&query, component_name)
+                .component_sliced(component_name)
+                .with_id(ChunkId::new());
+
+            let Some(key) =
+                BlueprintKey::from_latest_at_chunk(store_id, component_name, &latest, &timeline)
+            else {
+                continue;
+            };
+
+            let is_outdated = self.retained.iter().any(|retained| {
+                matches!(&retained.key, RetainedKey::BlueprintComponent(existing)
+                    if existing.supersedes(&key))
+            });
+            if is_outdated {
+                continue;
+            }
+
+            match latest.to_arrow_msg() {
+                Ok(arrow_msg) => self.replace(
+                    RetainedKey::BlueprintComponent(key),
+                    LogMsg::ArrowMsg(store_id.clone(), arrow_msg),
+                ),
+                Err(err) => {
+                    re_log::warn_once!(
+                        "Failed to encode a blueprint chunk, it won't be carried over to new segments: {err}"
+                    );
+                }
+            }
+        }
+    }
+
+    /// Drops the retained messages made redundant by the new one, which goes last.
+    fn replace(&mut self, key: RetainedKey, msg: LogMsg) {
+        self.retained
+            .retain(|retained| !key.supersedes(&retained.key));
+        self.retained.push(RetainedMsg { msg, key });
+    }
+
+    /// Deletes the oldest segments according to the retention limits.
+    fn enforce_retention(&self) {
+        let RotatingFileSinkOptions {
+            max_segments,
+            max_total_bytes,
+            ..
+        } = self.options;
+
+        if max_segments.is_none() && max_total_bytes.is_none() {
+            return;
+        }
+
+        let segments = existing_segments(&self.path_prefix);
+        let mut num_segments = segments.len();
+        let mut total_bytes: u64 = segments.iter().map(|segment| segment.num_bytes).sum();
+
+        for segment in segments {
+            let too_many = max_segments.map_or(false, |max| max < num_segments);
+            let too_big = max_total_bytes.map_or(false, |max| max < total_bytes);
+            if !too_many && !too_big {
+                break;
+            }
+
+            if segment.path == self.segment.path {
+                continue;
+            }
+
+            match std::fs::remove_file(&segment.path) {
+                Ok(()) => {
+                    re_log::debug!("Deleted old segment {:?}", segment.path);
+                    num_segments -= 1;
+                    total_bytes -= segment.num_bytes;
+                }
+                Err(err) => {
+                    re_log::warn!("Failed to delete old segment {:?}: {err}", segment.path);
+                }
+            }
+        }
+    }
+}
+
+fn open_segment(
+    path_prefix: &Path,
+    index: u64,
+    encoding_options: crate::EncodingOptions,
+) -> Result<Segment, FileSinkError> {
+    let path = segment_path(path_prefix, index);
+
+    let file =
+        std::fs::File::create(&path).map_err(|err| FileSinkError::CreateFile(path.clone(), err))?;
+    let encoder = crate::encoder::DroppableEncoder::new(
+        re_build_info::CrateVersion::LOCAL,
+        encoding_options,
+        file,
+    )?;
+
+    Ok(Segment {
+        path,
+        encoder,
+        opened_at: Instant::now(),
+        has_new_data: false,
+    })
+}
+
+fn segment_path(path_prefix: &Path, index: u64) -> PathBuf {
+    let mut file_name = path_prefix.file_name().unwrap_or_default().to_owned();
+    file_name.push(format!("-{index:06}.rrd"));
+    path_prefix.with_file_name(file_name)
+}
+
+struct ExistingSegment {
+    index: u64,
+    path: PathBuf,
+    num_bytes: u64,
+}
+
+/// All the segments on disk with the given prefix, oldest first.
+fn existing_segments(path_prefix: &Path) -> Vec<ExistingSegment> {
+    let Some(prefix) = path_prefix.file_name().and_then(|name| name.to_str()) else {
+        return Vec::new();
+    };
+    let directory = match path_prefix.parent() {
+        Some(parent) if !parent.as_os_str().is_empty() => parent,
+        _ => Path::new("."),
+    };
+
+    let Ok(entries) = std::fs::read_dir(directory) else {
+        return Vec::new();
+    };
+
+    let mut segments: Vec<_> = entries
+        .filter_map(|entry| {
+            let entry = entry.ok()?;
+            let file_name = entry.file_name();
+            let index = file_name
+                .to_str()?
+                .strip_prefix(prefix)?
+                .strip_prefix('-')?
+                .strip_suffix(".rrd")?
+                .parse()
+                .ok()?;
+            Some(ExistingSegment {
+                index,
+                path: path_prefix.with_file_name(file_name),
+                num_bytes: entry.metadata().ok()?.len(),
+            })
+        })
+        .collect();
+
+    segments.sort_by_key(|segment| segment.index);
+    segments
+}
+
+// ----------------------------------------------------------------------------
+
+#[cfg(all(test, feature = "decoder"))]
+mod tests {
+    #![allow(clippy::unwrap_used)] // acceptable for tests
+
+    use re_build_info::CrateVersion;
+    use re_chunk::{Chunk, RowId};
+    use re_log_types::{
+        ApplicationId, SetStoreInfo, StoreInfo, StoreSource, Time, TimePoint, Timeline,
+    };
+
+    use super::*;
+
+    fn set_store_info(store_id: &StoreId) -> LogMsg {
+        LogMsg::SetStoreInfo(SetStoreInfo {
+            row_id: *RowId::new(),
+            info: StoreInfo {
+                application_id: ApplicationId("test".to_owned()),
+                store_id: store_id.clone(),
+                cloned_from: None,
+                is_official_example: true,
+                started: Time::now(),
+                store_source: StoreSource::RustSdk {
+                    rustc_version: String::new(),
+                    llvm_version: String::new(),
+                },
+                store_version: Some(CrateVersion::LOCAL),
+            },
+        })
+    }
+
+    fn points(store_id: &StoreId, timepoint: TimePoint) -> LogMsg {
+        LogMsg::ArrowMsg(
+            store_id.clone(),
+            Chunk::builder("points".into())
+                .with_archetype(
+                    RowId::new(),
+                    timepoint,
+                    &re_types::archetypes::Points3D::new([[1.0, 2.0, 3.0]]),
+                )
+                .build()
+                .unwrap()
+                .to_arrow_msg()
+                .unwrap(),
+        )
+    }
+
+    #[test]
+    fn rotation_and_retention() {
+        let directory = std::env::temp_dir().join(format!(
+            "rerun_rotating_file_sink_test_{}",
+            std::process::id()
+        ));
+        std::fs::create_dir_all(&directory).unwrap();
+        let path_prefix = directory.join("recording");
+
+        let store_id = StoreId::random(StoreKind::Recording);
+        let timeline = Timeline::new_sequence("frame");
+
+        {
+            let sink = RotatingFileSink::new(
+                &path_prefix,
+                RotatingFileSinkOptions {
+                    // Rotate on every new message.
+                    max_segment_bytes: Some(1),
+                    max_segments: Some(3),
+                    ..Default::default()
+                },
+            )
+            .unwrap();
+
+            sink.send(set_store_info(&store_id));
+            sink.send(points(&store_id, TimePoint::default()));
+            for frame in 0..5_i64 {
+                sink.send(points(
+                    &store_id,
+                    TimePoint::default().with(timeline, frame),
+                ));
+            }
+        }
+
+        let segments = existing_segments(&path_prefix);
+        assert_eq!(
+            segments
+                .iter()
+                .map(|segment| segment.index)
+                .collect::<Vec<_>>(),
+            vec![5, 6, 7]
+        );
+
+        // Every segment starts with the store info and the static data.
+        for segment in &segments {
+            let bytes = std::fs::read(&segment.path).unwrap();
+            let msgs = crate::decoder::decode_bytes(crate::VersionPolicy::Error, &bytes).unwrap();
+
+            assert_eq!(msgs.len(), 3);
+            assert!(matches!(&msgs[0], LogMsg::SetStoreInfo(_)));
+            assert!(matches!(&msgs[1], LogMsg::ArrowMsg(_, msg)
+                if StaticKey::from_arrow_msg(&store_id, msg).is_some()));
+            assert!(matches!(&msgs[2], LogMsg::ArrowMsg(_, msg)
+                if StaticKey::from_arrow_msg(&store_id, msg).is_none()));
+        }
+
+        std::fs::remove_dir_all(&directory).ok();
+    }
+
+    #[test]
+    fn blueprint_compaction() {
+        let directory = std::env::temp_dir().join(format!(
+            "rerun_rotating_file_sink_blueprint_test_{}",
+            std::process::id()
+        ));
+        std::fs::create_dir_all(&directory).unwrap();
+        let path_prefix = directory.join("recording");
+
+        let recording_id = StoreId::random(StoreKind::Recording);
+        let blueprint_id = StoreId::random(StoreKind::Blueprint);
+        let blueprint_timeline = Timeline::new_sequence("blueprint");
+        let activation = |make_active| {
+            LogMsg::BlueprintActivationCommand(re_log_types::BlueprintActivationCommand {
+                blueprint_id: blueprint_id.clone(),
+                make_active,
+                make_default: true,
+            })
+        };
+
+        {
+            let sink = RotatingFileSink::new(
+                &path_prefix,
+                RotatingFileSinkOptions {
+                    // Rotate on every new message.
+                    max_segment_bytes: Some(1),
+                    ..Default::default()
+                },
+            )
+            .unwrap();
+
+            sink.send(set_store_info(&recording_id));
+            sink.send(set_store_info(&blueprint_id));
+            sink.send(set_store_info(&blueprint_id));
+            for frame in [1, 2, 0] {
+                sink.send(points(
+                    &blueprint_id,
+                    TimePoint::default().with(blueprint_timeline, frame),
+                ));
+            }
+            sink.send(activation(false));
+            sink.send(activation(true));
+            sink.send(points(
+                &recording_id,
+                TimePoint::default().with(Timeline::new_sequence("frame"), 0),
+            ));
+        }
+
+        let segment = existing_segments(&path_prefix).pop().unwrap();
+        let bytes = std::fs::read(&segment.path).unwrap();
+        let msgs = crate::decoder::decode_bytes(crate::VersionPolicy::Error, &bytes).unwrap();
+
+        let store_infos: Vec<_> = msgs
+            .iter()
+            .filter_map(|msg| match msg {
+                LogMsg::SetStoreInfo(info) => Some(info.info.store_id.clone()),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(
+            store_infos,
+            vec![recording_id.clone(), blueprint_id.clone()]
+        );
+
+        let blueprint_chunks: Vec<_> = msgs
+            .iter()
+            .filter_map(|msg| match msg {
+                LogMsg::ArrowMsg(store_id, arrow_msg) if *store_id == blueprint_id => {
+                    Some(Chunk::from_arrow_msg(arrow_msg).unwrap())
+                }
+                _ => None,
+            })
+            .collect();
+        let num_components = match points(&blueprint_id, TimePoint::default()) {
+            LogMsg::ArrowMsg(_, arrow_msg) => Chunk::from_arrow_msg(&arrow_msg)
+                .unwrap()
+                .component_names()
+                .count(),
+            _ => unreachable!(),
+        };
+        assert_eq!(blueprint_chunks.len(), num_components);
+        for chunk in &blueprint_chunks {
+            // Only the latest value, even though an older one was sent last.
+            assert_eq!(chunk.num_rows(), 1);
+            assert_eq!(chunk.component_names().count(), 1);
+            assert_eq!(
+                chunk
+                    .timelines()
+                    .get(&blueprint_timeline)
+                    .unwrap()
+                    .times_raw(),
+                &[2]
+            );
+        }
+
+        let activations: Vec<_> = msgs
+            .iter()
+            .filter_map(|msg| match msg {
+                LogMsg::BlueprintActivationCommand(cmd) => Some(cmd.make_active),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(activations, vec![true]);
+
+        // The activation command comes after the blueprint it refers to.
+        assert!(matches!(
+            &msgs[msgs.len() - 2],
+            LogMsg::BlueprintActivationCommand(_)
+        ));
+
+        std::fs::remove_dir_all(&directory).ok();
+    }
+}
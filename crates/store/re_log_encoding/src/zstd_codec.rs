@@ -0,0 +1,94 @@
+//! Thin wrappers around `zstd`.
+//!
+//! `zstd` is C code, so it is only available on native: the web viewer would otherwise need a
+//! C toolchain targeting wasm just for that. On the web, decoding goes through the pure Rust
+//! `ruzstd` instead, so zstd-compressed data can still be read there. Encoding is native-only.
+
+#[cfg(not(target_arch = "wasm32"))]
+mod native {
+    #[cfg(feature = "encoder")]
+    pub(crate) fn compress(src: &[u8], level: i32) -> std::io::Result<Vec<u8>> {
+        zstd::bulk::compress(src, level)
+    }
+
+    #[cfg(feature = "encoder")]
+    pub(crate) fn compress_bound(src_len: usize) -> usize {
+        zstd::zstd_safe::compress_bound(src_len)
+    }
+
+    #[cfg(feature = "encoder")]
+    pub(crate) fn compress_to_buffer(
+        src: &[u8],
+        dst: &mut [u8],
+        level: i32,
+    ) -> std::io::Result<usize> {
+        zstd::bulk::compress_to_buffer(src, dst, level)
+    }
+
+    #[cfg(feature = "decoder")]
+    pub(crate) fn decompress_to_buffer(src: &[u8], dst: &mut [u8]) -> std::io::Result<usize> {
+        zstd::bulk::decompress_to_buffer(src, dst)
+    }
+}
+
+#[cfg(target_arch = "wasm32")]
+mod web {
+    #[cfg(feature = "encoder")]
+    fn unsupported() -> std::io::Error {
+        std::io::Error::new(
+            std::io::ErrorKind::Unsupported,
+            "zstd encoding is not supported on the web",
+        )
+    }
+
+    #[cfg(feature = "encoder")]
+    pub(crate) fn compress(_src: &[u8], _level: i32) -> std::io::Result<Vec<u8>> {
+        Err(unsupported())
+    }
+
+    #[cfg(feature = "encoder")]
+    pub(crate) fn compress_bound(src_len: usize) -> usize {
+        src_len
+    }
+
+    #[cfg(feature = "encoder")]
+    pub(crate) fn compress_to_buffer(
+        _src: &[u8],
+        _dst: &mut [u8],
+        _level: i32,
+    ) -> std::io::Result<usize> {
+        Err(unsupported())
+    }
+
+    #[cfg(feature = "decoder")]
+    pub(crate) fn decompress_to_buffer(src: &[u8], dst: &mut [u8]) -> std::io::Result<usize> {
+        use std::io::Read as _;
+
+        let mut decoder = ruzstd::StreamingDecoder::new(src)
+            .map_err(|err| std::io::Error::new(std::io::ErrorKind::InvalidData, err.to_string()))?;
+
+        let mut len = 0;
+        while len < dst.len() {
+            match decoder.read(&mut dst[len..])? {
+                0 => break,
+                n => len += n,
+            }
+        }
+
+        // Same as the native implementation: the output must fit in `dst`.
+        if decoder.read(&mut [0_u8])? != 0 {
+            return Err(std::io::Error::new(
+                std::io::ErrorKind::InvalidData,
+                "zstd: decompressed data is larger than the destination buffer",
+            ));
+        }
+
+        Ok(len)
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+pub(crate) use native::*;
+
+#[cfg(target_arch = "wasm32")]
+pub(crate) use web::*;
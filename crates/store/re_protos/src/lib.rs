@@ -29,6 +29,9 @@ mod v0 {
 
     #[path = "./rerun.remote_store.v0.rs"]
     pub mod rerun_remote_store_v0;
+
+    #[path = "./rerun.sdk_comms.v0.rs"]
+    pub mod rerun_sdk_comms_v0;
 }
 
 pub mod common {
@@ -50,6 +53,18 @@ pub mod remote_store {
     }
 }
 
+/// Generated types for the log message ingestion gRPC service API v0.
+pub mod sdk_comms {
+    /// The default port of the `MessageProxy` gRPC server of the viewer.
+    ///
+    /// Sits right next to the default TCP (9876) and WebSocket (9877) server ports.
+    pub const DEFAULT_SERVER_PORT: u16 = 9878;
+
+    pub mod v0 {
+        pub use crate::v0::rerun_sdk_comms_v0::*;
+    }
+}
+
 #[derive(Debug, thiserror::Error)]
 pub enum TypeConversionError {
     #[error("missing required field: {package_name}.{type_name}.{field_name}")]
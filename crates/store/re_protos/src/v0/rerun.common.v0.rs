@@ -136,10 +136,11 @@ pub struct Query {
     /// If set, this overrides both filtered_index_range and filtered_index_values.
     #[prost(message, optional, tag = "8")]
     pub using_index_values: ::core::option::Option<IndexValues>,
-    /// The component column used to filter out _rows_ from the view contents.
-    /// Only rows where this column contains non-null data be kept in the final dataset.
-    #[prost(message, optional, tag = "9")]
-    pub filtered_is_not_null: ::core::option::Option<ComponentColumnSelector>,
+    /// The component columns used to filter out _rows_ from the view contents.
+    /// Only rows where these columns contain non-null data will be kept in the final dataset,
+    /// as specified by filtered_is_not_null_mode.
+    #[prost(message, repeated, tag = "9")]
+    pub filtered_is_not_null: ::prost::alloc::vec::Vec<ComponentColumnSelector>,
     /// / The specific _columns_ to sample from the final view contents.
     /// / The order of the samples will be respected in the final result.
     /// /
@@ -149,6 +150,10 @@ pub struct Query {
     /// Specifies how null values should be filled in the returned dataframe.
     #[prost(enumeration = "SparseFillStrategy", tag = "11")]
     pub sparse_fill_strategy: i32,
+    /// Whether all or any of the filtered_is_not_null columns must contain non-null data for a row
+    /// to be kept.
+    #[prost(enumeration = "FilterIsNotNullMode", tag = "12")]
+    pub filtered_is_not_null_mode: i32,
 }
 impl ::prost::Name for Query {
     const NAME: &'static str = "Query";
@@ -514,6 +519,33 @@ impl SparseFillStrategy {
         }
     }
 }
+/// Specifies how multiple filtered_is_not_null columns are combined.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, ::prost::Enumeration)]
+#[repr(i32)]
+pub enum FilterIsNotNullMode {
+    All = 0,
+    Any = 1,
+}
+impl FilterIsNotNullMode {
+    /// String value of the enum field names used in the ProtoBuf definition.
+    ///
+    /// The values are not transformed in any way and thus are considered stable
+    /// (if the ProtoBuf definition does not change) and safe for programmatic use.
+    pub fn as_str_name(&self) -> &'static str {
+        match self {
+            Self::All => "ALL",
+            Self::Any => "ANY",
+        }
+    }
+    /// Creates an enum from field names used in the ProtoBuf definition.
+    pub fn from_str_name(value: &str) -> ::core::option::Option<Self> {
+        match value {
+            "ALL" => Some(Self::All),
+            "ANY" => Some(Self::Any),
+            _ => None,
+        }
+    }
+}
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, ::prost::Enumeration)]
 #[repr(i32)]
 pub enum StoreKind {
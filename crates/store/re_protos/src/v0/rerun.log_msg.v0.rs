@@ -1,4 +1,35 @@
 // This file is @generated by prost-build.
+/// Corresponds to `LogMsg`. Used to send messages over gRPC.
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct LogMsg {
+    #[prost(oneof = "log_msg::Msg", tags = "1, 2, 3")]
+    pub msg: ::core::option::Option<log_msg::Msg>,
+}
+/// Nested message and enum types in `LogMsg`.
+pub mod log_msg {
+    #[derive(Clone, PartialEq, ::prost::Oneof)]
+    pub enum Msg {
+        /// A message that contains a new store info.
+        #[prost(message, tag = "1")]
+        SetStoreInfo(super::SetStoreInfo),
+        /// A message that contains an Arrow-IPC encoded message.
+        #[prost(message, tag = "2")]
+        ArrowMsg(super::ArrowMsg),
+        /// A message that contains a blueprint activation command.
+        #[prost(message, tag = "3")]
+        BlueprintActivationCommand(super::BlueprintActivationCommand),
+    }
+}
+impl ::prost::Name for LogMsg {
+    const NAME: &'static str = "LogMsg";
+    const PACKAGE: &'static str = "rerun.log_msg.v0";
+    fn full_name() -> ::prost::alloc::string::String {
+        "rerun.log_msg.v0.LogMsg".into()
+    }
+    fn type_url() -> ::prost::alloc::string::String {
+        "/rerun.log_msg.v0.LogMsg".into()
+    }
+}
 /// Corresponds to `LogMsg::SetStoreInfo`. Used to identify a recording.
 #[derive(Clone, PartialEq, ::prost::Message)]
 pub struct SetStoreInfo {
@@ -228,6 +259,8 @@ pub enum Compression {
     None = 0,
     /// LZ4 block compression.
     Lz4 = 1,
+    /// Zstandard compression.
+    Zstd = 2,
 }
 impl Compression {
     /// String value of the enum field names used in the ProtoBuf definition.
@@ -238,6 +271,7 @@ impl Compression {
         match self {
             Self::None => "NONE",
             Self::Lz4 => "LZ4",
+            Self::Zstd => "ZSTD",
         }
     }
     /// Creates an enum from field names used in the ProtoBuf definition.
@@ -245,6 +279,7 @@ impl Compression {
         match value {
             "NONE" => Some(Self::None),
             "LZ4" => Some(Self::Lz4),
+            "ZSTD" => Some(Self::Zstd),
             _ => None,
         }
     }
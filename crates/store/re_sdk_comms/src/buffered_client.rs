@@ -1,9 +1,11 @@
-use std::{fmt, net::SocketAddr, thread::JoinHandle};
+use std::{fmt, net::SocketAddr, thread::JoinHandle, time::Instant};
 
 use crossbeam::channel::{select, Receiver, Sender};
 
 use re_log_types::LogMsg;
 
+use crate::spool::{Spool, SpoolOptions};
+
 #[derive(Debug, PartialEq, Eq)]
 struct FlushedMsg;
 
@@ -57,7 +59,50 @@ impl Client {
     /// flush before potentially dropping data. Note: Passing `None` here can
     /// cause a call to `flush` to block indefinitely if a connection cannot be
     /// established.
+    ///
+    /// The auth token to present to the server, if any, is read from the
+    /// [`crate::ENV_AUTH_TOKEN`] environment variable.
     pub fn new(addr: SocketAddr, flush_timeout: Option<std::time::Duration>) -> Self {
+        Self::new_with_auth_token(addr, flush_timeout, crate::default_auth_token())
+    }
+
+    /// Connect via TCP to this log server, presenting the given `auth_token` during the handshake.
+    ///
+    /// See [`Self::new`] for details.
+    pub fn new_with_auth_token(
+        addr: SocketAddr,
+        flush_timeout: Option<std::time::Duration>,
+        auth_token: Option<String>,
+    ) -> Self {
+        Self::new_impl(addr, flush_timeout, auth_token, None)
+    }
+
+    /// Connect via TCP to this log server, spooling messages to disk while it is unreachable.
+    ///
+    /// Rather than blocking or dropping messages while disconnected, they are appended to the
+    /// bounded spool file described by `spool_options`. The client keeps trying to reconnect with
+    /// an exponential back-off, and once connected replays the spooled messages in order before
+    /// sending any new one.
+    ///
+    /// A flush waits at most `flush_timeout` for the spool to be replayed: whatever is left stays
+    /// on disk and will be replayed later.
+    ///
+    /// Falls back to [`Self::new_with_auth_token`] if the spool file cannot be created.
+    pub fn new_with_spool(
+        addr: SocketAddr,
+        flush_timeout: Option<std::time::Duration>,
+        auth_token: Option<String>,
+        spool_options: SpoolOptions,
+    ) -> Self {
+        Self::new_impl(addr, flush_timeout, auth_token, Some(spool_options))
+    }
+
+    fn new_impl(
+        addr: SocketAddr,
+        flush_timeout: Option<std::time::Duration>,
+        auth_token: Option<String>,
+        spool_options: Option<SpoolOptions>,
+    ) -> Self {
         re_log::debug!("Connecting to remote {addr}…");
 
         // TODO(emilk): keep track of how much memory is in each pipe
@@ -83,7 +128,37 @@ impl Client {
         let send_join = std::thread::Builder::new()
             .name("tcp_sender".into())
             .spawn(move || {
-                tcp_sender(addr, flush_timeout, &packet_rx, &send_quit_rx, &flushed_tx);
+                let spool = spool_options.and_then(|spool_options| {
+                    let path = spool_options.path.clone();
+                    Spool::open(spool_options)
+                        .map_err(|err| {
+                            re_log::error!(
+                                "Failed to create spool at {path:?}, messages will not be spooled: {err}"
+                            );
+                        })
+                        .ok()
+                });
+
+                if let Some(spool) = spool {
+                    spooling_tcp_sender(
+                        addr,
+                        flush_timeout,
+                        auth_token,
+                        spool,
+                        &packet_rx,
+                        &send_quit_rx,
+                        &flushed_tx,
+                    );
+                } else {
+                    tcp_sender(
+                        addr,
+                        flush_timeout,
+                        auth_token,
+                        &packet_rx,
+                        &send_quit_rx,
+                        &flushed_tx,
+                    );
+                }
             })
             .expect("Failed to spawn thread");
 
@@ -212,11 +287,12 @@ fn msg_encode(
 fn tcp_sender(
     addr: SocketAddr,
     flush_timeout: Option<std::time::Duration>,
+    auth_token: Option<String>,
     packet_rx: &Receiver<PacketMsg>,
     quit_rx: &Receiver<InterruptMsg>,
     flushed_tx: &Sender<FlushedMsg>,
 ) {
-    let mut tcp_client = crate::tcp_client::TcpClient::new(addr, flush_timeout);
+    let mut tcp_client = crate::tcp_client::TcpClient::new(addr, flush_timeout, auth_token);
     // Once this flag has been set, we will drop all messages if the tcp_client is
     // no longer connected.
     let mut drop_if_disconnected = false;
@@ -325,3 +401,167 @@ fn send_until_success(
         None
     }
 }
+
+/// Like [`tcp_sender`], but spools packets to disk instead of blocking while disconnected.
+fn spooling_tcp_sender(
+    addr: SocketAddr,
+    flush_timeout: Option<std::time::Duration>,
+    auth_token: Option<String>,
+    mut spool: Spool,
+    packet_rx: &Receiver<PacketMsg>,
+    quit_rx: &Receiver<InterruptMsg>,
+    flushed_tx: &Sender<FlushedMsg>,
+) {
+    const MIN_RECONNECT_DELAY: std::time::Duration = std::time::Duration::from_millis(100);
+    const MAX_RECONNECT_DELAY: std::time::Duration = std::time::Duration::from_secs(3);
+
+    let mut tcp_client = crate::tcp_client::TcpClient::new(addr, flush_timeout, auth_token);
+    // Once this flag has been set, flushing will no longer wait for the spool to be replayed.
+    let mut drop_if_disconnected = false;
+
+    let mut reconnect_delay = MIN_RECONNECT_DELAY;
+    let mut next_reconnect = Instant::now();
+
+    loop {
+        // Only wake up to reconnect while we have something to replay.
+        let reconnect = if spool.is_empty() {
+            crossbeam::channel::never()
+        } else {
+            crossbeam::channel::at(next_reconnect)
+        };
+
+        select! {
+            recv(packet_rx) -> packet_msg => {
+                let Ok(packet_msg) = packet_msg else {
+                    re_log::debug!("Shutting down tcp_sender thread: packet_rx channel has closed");
+                    return; // channel has closed
+                };
+
+                match packet_msg {
+                    PacketMsg::Packet(packet) => {
+                        // Preserve ordering: nothing new goes out before the spool has been replayed.
+                        if !spool.is_empty() {
+                            spool_packet(&mut spool, &packet);
+                        } else if let Err(err) = tcp_client.send(&packet) {
+                            re_log::info!(
+                                "Lost connection to {addr} ({err}): spooling messages to {:?}",
+                                spool.options().path
+                            );
+                            spool_packet(&mut spool, &packet);
+                            reconnect_delay = MIN_RECONNECT_DELAY;
+                            next_reconnect = Instant::now() + reconnect_delay;
+                        }
+                    }
+                    PacketMsg::Flush => {
+                        let deadline = flush_timeout.map(|timeout| Instant::now() + timeout);
+
+                        while !replay_spool(&mut tcp_client, &mut spool) {
+                            if drop_if_disconnected
+                                || deadline.map_or(false, |deadline| deadline <= Instant::now())
+                            {
+                                re_log::warn!(
+                                    "Timed out while flushing: {} message(s) remain in the spool at {:?}",
+                                    spool.num_packets(),
+                                    spool.options().path
+                                );
+                                break;
+                            }
+
+                            select! {
+                                recv(quit_rx) -> quit_msg => match quit_msg {
+                                    Ok(InterruptMsg::DropIfDisconnected) => {
+                                        drop_if_disconnected = true;
+                                    }
+                                    Ok(InterruptMsg::Quit) | Err(_) => {
+                                        re_log::debug!("Shutting down tcp_sender thread while flushing");
+                                        return;
+                                    }
+                                },
+                                default(MIN_RECONNECT_DELAY) => {}
+                            }
+                        }
+
+                        if spool.is_empty() {
+                            tcp_client.flush();
+                        }
+                        flushed_tx
+                            .send(FlushedMsg)
+                            .expect("Main thread should still be alive");
+                    }
+                }
+            },
+            recv(reconnect) -> _ => {
+                if !replay_spool(&mut tcp_client, &mut spool) {
+                    reconnect_delay = (reconnect_delay * 2).min(MAX_RECONNECT_DELAY);
+                    next_reconnect = Instant::now() + reconnect_delay;
+                }
+            },
+            recv(quit_rx) -> quit_msg => { match quit_msg {
+                // The spooled messages are on disk, so there is nothing to drop.
+                Ok(InterruptMsg::DropIfDisconnected) => {
+                    drop_if_disconnected = true;
+                }
+                Ok(InterruptMsg::Quit) => {
+                    re_log::debug!("Shutting down tcp_sender thread: received Quit message");
+                    return;
+                }
+                Err(_) => {
+                    re_log::debug!("Shutting down tcp_sender thread: quit_rx channel has closed");
+                    return;
+                }
+            }}
+        }
+    }
+}
+
+fn spool_packet(spool: &mut Spool, packet: &[u8]) {
+    if let Err(err) = spool.push(packet) {
+        re_log::error_once!(
+            "Failed to write to the spool at {:?}, dropping messages: {err}",
+            spool.options().path
+        );
+    }
+}
+
+/// Sends all the spooled packets, oldest first.
+///
+/// Returns `true` once the spool is empty, `false` if the connection failed along the way.
+fn replay_spool(tcp_client: &mut crate::tcp_client::TcpClient, spool: &mut Spool) -> bool {
+    if spool.is_empty() {
+        return true;
+    }
+
+    let num_dropped = spool.num_dropped();
+
+    loop {
+        let packet = match spool.front() {
+            Ok(Some(packet)) => packet,
+            Ok(None) => break,
+            Err(err) => {
+                re_log::error!(
+                    "Failed to read from the spool at {:?}, dropping {} message(s): {err}",
+                    spool.options().path,
+                    spool.num_packets()
+                );
+                spool.clear();
+                return true;
+            }
+        };
+
+        if let Err(err) = tcp_client.send(&packet) {
+            re_log::debug!("Failed to replay spooled message: {err}");
+            return false;
+        }
+        spool.pop_front();
+    }
+
+    if 0 < num_dropped {
+        re_log::warn!(
+            "Replayed the spool, but {num_dropped} message(s) were dropped because it was full"
+        );
+    } else {
+        re_log::info!("Replayed the spool");
+    }
+
+    true
+}
@@ -0,0 +1,243 @@
+use std::net::TcpListener;
+
+use re_log_types::LogMsg;
+use re_protos::log_msg::v0::LogMsg as LogMsgProto;
+use re_protos::sdk_comms::v0::{
+    message_proxy_server::{MessageProxy, MessageProxyServer},
+    WriteMessagesResponse,
+};
+use re_smart_channel::{Receiver, Sender};
+use tonic::{Request, Response, Status, Streaming};
+
+use crate::server::{CongestionManager, ServerError, ServerOptions};
+
+/// The gRPC metadata key holding the auth token, as `Bearer <token>`.
+const AUTHORIZATION_METADATA_KEY: &str = "authorization";
+
+/// Listen to multiple SDK:s streaming log messages to us over gRPC, using the
+/// `rerun.sdk_comms.v0.MessageProxy/WriteMessages` RPC.
+///
+/// ``` no_run
+/// # use re_sdk_comms::{serve_grpc, ServerOptions, DEFAULT_GRPC_SERVER_PORT};
+/// fn main() {
+///     let log_msg_rx = serve_grpc("0.0.0.0", DEFAULT_GRPC_SERVER_PORT, ServerOptions::default()).unwrap();
+/// }
+/// ```
+///
+/// This is the gRPC counterpart of [`crate::serve`]: both can run side by side.
+/// Internally spawns a thread running a small async runtime, which serves all connected clients.
+pub fn serve_grpc(
+    bind_ip: &str,
+    port: u16,
+    options: ServerOptions,
+) -> Result<Receiver<LogMsg>, ServerError> {
+    let (tx, rx) = re_smart_channel::smart_channel(
+        // NOTE: We don't know until we start actually accepting clients!
+        re_smart_channel::SmartMessageSource::Unknown,
+        re_smart_channel::SmartChannelSource::GrpcServer { port },
+    );
+
+    // Bind right away, so that errors are reported to the caller rather than in the background.
+    let bind_addr = format!("{bind_ip}:{port}");
+    let listener = TcpListener::bind(&bind_addr)
+        .and_then(|listener| listener.set_nonblocking(true).map(|()| listener))
+        .map_err(|err| ServerError::TcpBindError {
+            bind_addr: bind_addr.clone(),
+            err,
+        })?;
+
+    let quiet = options.quiet;
+
+    let runtime = tokio::runtime::Builder::new_current_thread()
+        .enable_all()
+        .build()?;
+
+    std::thread::Builder::new()
+        .name("rerun_sdk_comms: grpc server".to_owned())
+        .spawn(move || {
+            runtime.block_on(async move {
+                let listener = match tokio::net::TcpListener::from_std(listener) {
+                    Ok(listener) => listener,
+                    Err(err) => {
+                        re_log::error!("Failed to start gRPC server: {err}");
+                        return;
+                    }
+                };
+
+                let service = MessageProxyService { tx, options };
+                let result = tonic::transport::Server::builder()
+                    .add_service(
+                        // TODO(#8411): figure out the right size for this
+                        MessageProxyServer::new(service).max_decoding_message_size(usize::MAX),
+                    )
+                    .serve_with_incoming(tokio_stream::wrappers::TcpListenerStream::new(listener))
+                    .await;
+
+                if let Err(err) = result {
+                    re_log::error!("gRPC server stopped: {err}");
+                }
+            });
+        })?;
+
+    if quiet {
+        re_log::debug!(
+            "Hosting a SDK server over gRPC at {bind_addr}. Connect with the Rerun logging SDK."
+        );
+    } else {
+        re_log::info!(
+            "Hosting a SDK server over gRPC at {bind_addr}. Connect with the Rerun logging SDK."
+        );
+    }
+
+    Ok(rx)
+}
+
+/// Forwards all the messages of all the incoming streams into a [`Sender`].
+struct MessageProxyService {
+    tx: Sender<LogMsg>,
+    options: ServerOptions,
+}
+
+#[tonic::async_trait]
+impl MessageProxy for MessageProxyService {
+    async fn write_messages(
+        &self,
+        request: Request<Streaming<LogMsgProto>>,
+    ) -> Result<Response<WriteMessagesResponse>, Status> {
+        let addr = request.remote_addr();
+        let addr_string = addr.map_or_else(|| "(unknown ip)".to_owned(), |addr| addr.to_string());
+
+        if let Some(expected) = &self.options.auth_token {
+            let is_authorized = request
+                .metadata()
+                .get(AUTHORIZATION_METADATA_KEY)
+                .and_then(|value| value.to_str().ok())
+                .and_then(|value| value.strip_prefix("Bearer "))
+                .is_some_and(|presented| re_auth::tokens_match(presented, expected));
+            if !is_authorized {
+                re_log::warn!("Rejected unauthenticated SDK client at {addr_string}");
+                return Err(Status::unauthenticated("missing or invalid auth token"));
+            }
+        }
+
+        let tx = self
+            .tx
+            .clone_as(re_smart_channel::SmartMessageSource::GrpcClient { addr });
+
+        if self.options.quiet {
+            re_log::debug!("New SDK client connected over gRPC from: {addr_string}");
+        } else {
+            re_log::info!("New SDK client connected over gRPC from: {addr_string}");
+        }
+
+        match forward_messages(request.into_inner(), &tx, self.options.max_latency_sec).await {
+            Ok(()) => {
+                // Client gracefully closed the stream.
+                tx.quit(None).ok(); // best-effort at this point
+                Ok(Response::new(WriteMessagesResponse {}))
+            }
+            Err(status) => {
+                re_log::warn_once!("Closing gRPC stream of client at {addr_string}: {status}");
+                tx.quit(Some(Box::new(status.clone()))).ok(); // best-effort at this point
+                Err(status)
+            }
+        }
+    }
+}
+
+async fn forward_messages(
+    mut stream: Streaming<LogMsgProto>,
+    tx: &Sender<LogMsg>,
+    max_latency_sec: f32,
+) -> Result<(), Status> {
+    let mut congestion_manager = CongestionManager::new(max_latency_sec);
+
+    while let Some(msg) = stream.message().await? {
+        let msg = re_log_encoding::log_msg_from_proto(msg)
+            .map_err(|err| Status::invalid_argument(format!("invalid log message: {err}")))?;
+
+        congestion_manager.register_latency(tx.latency_sec());
+
+        if congestion_manager.should_send(&msg) {
+            tx.send(msg)
+                .map_err(|_err| Status::unavailable("the viewer is shutting down"))?;
+        } else {
+            re_log::warn_once!(
+                "Input latency is over the max ({max_latency_sec} s) - dropping packets."
+            );
+        }
+    }
+
+    Ok(())
+}
+
+// ----------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use re_protos::sdk_comms::v0::message_proxy_client::MessageProxyClient;
+
+    use super::*;
+
+    /// Opens and immediately closes a `WriteMessages` stream, presenting `client_metadata` as the
+    /// `authorization` metadata, to a server expecting `server_auth_token`.
+    fn write_messages(
+        server_auth_token: Option<&str>,
+        client_metadata: Option<&str>,
+    ) -> Result<(), Status> {
+        // Find a free port: the server binds it again right away.
+        let port = TcpListener::bind("127.0.0.1:0")
+            .unwrap()
+            .local_addr()
+            .unwrap()
+            .port();
+        let _rx = serve_grpc(
+            "127.0.0.1",
+            port,
+            ServerOptions {
+                quiet: true,
+                auth_token: server_auth_token.map(ToOwned::to_owned),
+                ..Default::default()
+            },
+        )
+        .unwrap();
+
+        let runtime = tokio::runtime::Builder::new_current_thread()
+            .enable_all()
+            .build()
+            .unwrap();
+
+        runtime.block_on(async move {
+            let mut client = MessageProxyClient::connect(format!("http://127.0.0.1:{port}"))
+                .await
+                .unwrap();
+
+            let mut request = Request::new(tokio_stream::iter(Vec::<LogMsgProto>::new()));
+            if let Some(value) = client_metadata {
+                request
+                    .metadata_mut()
+                    .insert(AUTHORIZATION_METADATA_KEY, value.parse().unwrap());
+            }
+
+            client.write_messages(request).await.map(|_response| ())
+        })
+    }
+
+    #[test]
+    fn auth_token_metadata() {
+        assert!(write_messages(None, None).is_ok());
+        assert!(write_messages(None, Some("Bearer whatever")).is_ok());
+
+        assert!(write_messages(Some("secret"), Some("Bearer secret")).is_ok());
+
+        for metadata in [
+            None,
+            Some("Bearer Secret"),
+            Some("secret"),
+            Some("Basic secret"),
+        ] {
+            let status = write_messages(Some("secret"), metadata).unwrap_err();
+            assert_eq!(tonic::Code::Unauthenticated, status.code(), "{metadata:?}");
+        }
+    }
+}
@@ -1,4 +1,4 @@
-//! TCP communications between a Rerun logging SDK and server/viewer.
+//! TCP and gRPC communications between a Rerun logging SDK and server/viewer.
 //!
 //! ## Feature flags
 #![doc = document_features::document_features!()]
@@ -11,7 +11,14 @@ pub(crate) mod tcp_client;
 mod buffered_client;
 
 #[cfg(feature = "client")]
-pub use {buffered_client::Client, tcp_client::ClientError};
+mod spool;
+
+#[cfg(feature = "client")]
+pub use {
+    buffered_client::Client,
+    spool::{SpoolOptions, SpoolOverflowPolicy},
+    tcp_client::ClientError,
+};
 
 #[cfg(feature = "server")]
 mod server;
@@ -19,6 +26,12 @@ mod server;
 #[cfg(feature = "server")]
 pub use server::{serve, ServerError, ServerOptions};
 
+#[cfg(feature = "grpc_server")]
+mod grpc_server;
+
+#[cfg(feature = "grpc_server")]
+pub use grpc_server::serve_grpc;
+
 /// Server connection error.
 ///
 /// This can only occur when using the `server` feature,
@@ -30,6 +43,9 @@ pub enum ConnectionError {
     #[error("An unknown client tried to connect")]
     UnknownClient,
 
+    #[error("The client did not present a valid auth token")]
+    Unauthorized,
+
     #[error(transparent)]
     VersionError(#[from] VersionError),
 
@@ -65,11 +81,42 @@ pub const PROTOCOL_VERSION_0: u16 = 0;
 /// Added [`PROTOCOL_HEADER`]. Introduced for Rerun 0.16.
 pub const PROTOCOL_VERSION_1: u16 = 1;
 
+/// Added an auth token after the [`PROTOCOL_HEADER`]: its length as a little-endian `u32`,
+/// followed by its UTF-8 bytes. Introduced for Rerun 0.22.
+///
+/// The server replies with a single byte, [`PROTOCOL_AUTH_ACCEPTED`] or [`PROTOCOL_AUTH_REJECTED`],
+/// before closing the connection in the latter case.
+///
+/// Clients only use this version when they have a token to present.
+pub const PROTOCOL_VERSION_2: u16 = 2;
+
+/// Sent by the server once it has accepted the auth token of a [`PROTOCOL_VERSION_2`] client.
+pub const PROTOCOL_AUTH_ACCEPTED: u8 = 1;
+
+/// Sent by the server when it rejects the auth token of a [`PROTOCOL_VERSION_2`] client.
+pub const PROTOCOL_AUTH_REJECTED: u8 = 0;
+
 /// Comes after version.
 pub const PROTOCOL_HEADER: &str = "rerun";
 
+/// Environment variable holding the shared secret that SDKs present to the server by default.
+///
+/// See also `ServerOptions::auth_token`.
+pub const ENV_AUTH_TOKEN: &str = "RERUN_AUTH_TOKEN";
+
+/// The auth token SDKs present to the server by default, read from [`ENV_AUTH_TOKEN`].
+pub fn default_auth_token() -> Option<String> {
+    std::env::var(ENV_AUTH_TOKEN)
+        .ok()
+        .filter(|auth_token| !auth_token.is_empty())
+}
+
 pub const DEFAULT_SERVER_PORT: u16 = 9876;
 
+/// The default port of the gRPC log message server, see `serve_grpc`.
+#[cfg(feature = "grpc_server")]
+pub const DEFAULT_GRPC_SERVER_PORT: u16 = re_protos::sdk_comms::DEFAULT_SERVER_PORT;
+
 /// The default address of a Rerun TCP server which an SDK connects to.
 pub fn default_server_addr() -> std::net::SocketAddr {
     std::net::SocketAddr::from(([127, 0, 0, 1], DEFAULT_SERVER_PORT))
@@ -1,5 +1,5 @@
 use std::{
-    io::{ErrorKind, Read as _},
+    io::{ErrorKind, Read as _, Write as _},
     net::{TcpListener, TcpStream},
     time::Instant,
 };
@@ -23,7 +23,7 @@ pub enum ServerError {
     FailedToSpawnThread(#[from] std::io::Error),
 }
 
-#[derive(Clone, Copy, Debug, PartialEq)]
+#[derive(Clone, Debug, PartialEq)]
 pub struct ServerOptions {
     /// If the latency in the [`LogMsg`] channel is greater than this,
     /// then start dropping messages in order to keep up.
@@ -31,6 +31,15 @@ pub struct ServerOptions {
 
     /// Turns `info`-level logs into `debug`-level logs.
     pub quiet: bool,
+
+    /// If set, clients must present this shared secret during the handshake.
+    ///
+    /// Clients that don't are disconnected right away, after being told so if they use
+    /// [`crate::PROTOCOL_VERSION_2`].
+    /// SDKs present the token found in the [`crate::ENV_AUTH_TOKEN`] environment variable by default.
+    ///
+    /// Note that because of this token, [`ServerOptions`] is no longer `Copy` as of Rerun 0.22.
+    pub auth_token: Option<String>,
 }
 
 impl Default for ServerOptions {
@@ -38,10 +47,14 @@ impl Default for ServerOptions {
         Self {
             max_latency_sec: f32::INFINITY,
             quiet: false,
+            auth_token: None,
         }
     }
 }
 
+/// The longest auth token we accept, so that a bogus length can't make us allocate a lot.
+const MAX_AUTH_TOKEN_LEN: u32 = 4096;
+
 /// Listen to multiple SDK:s connecting to us over TCP.
 ///
 /// ``` no_run
@@ -71,13 +84,15 @@ pub fn serve(
         err,
     })?;
 
+    let quiet = options.quiet;
+
     std::thread::Builder::new()
         .name("rerun_sdk_comms: listener".to_owned())
         .spawn(move || {
-            listen_for_new_clients(&listener, options, &tx);
+            listen_for_new_clients(&listener, &options, &tx);
         })?;
 
-    if options.quiet {
+    if quiet {
         re_log::debug!(
             "Hosting a SDK server over TCP at {bind_addr}. Connect with the Rerun logging SDK."
         );
@@ -90,7 +105,7 @@ pub fn serve(
     Ok(rx)
 }
 
-fn listen_for_new_clients(listener: &TcpListener, options: ServerOptions, tx: &Sender<LogMsg>) {
+fn listen_for_new_clients(listener: &TcpListener, options: &ServerOptions, tx: &Sender<LogMsg>) {
     // TODO(emilk): some way of aborting this loop
     #[allow(clippy::infinite_loop)]
     loop {
@@ -98,11 +113,12 @@ fn listen_for_new_clients(listener: &TcpListener, options: ServerOptions, tx: &S
             Ok((stream, _)) => {
                 let addr = stream.peer_addr().ok();
                 let tx = tx.clone_as(re_smart_channel::SmartMessageSource::TcpClient { addr });
+                let options = options.clone();
 
                 std::thread::Builder::new()
                     .name("rerun_sdk_comms: client".to_owned())
                     .spawn(move || {
-                        spawn_client(stream, &tx, options, addr);
+                        spawn_client(stream, &tx, &options, addr);
                     })
                     .ok();
             }
@@ -143,7 +159,7 @@ fn listen_for_new_clients(listener: &TcpListener, options: ServerOptions, tx: &S
 fn spawn_client(
     stream: TcpStream,
     tx: &Sender<LogMsg>,
-    options: ServerOptions,
+    options: &ServerOptions,
     peer_addr: Option<std::net::SocketAddr>,
 ) {
     let addr_string = peer_addr.map_or_else(|| "(unknown ip)".to_owned(), |addr| addr.to_string());
@@ -157,7 +173,10 @@ fn spawn_client(
             }
         }
 
-        if matches!(&err, ConnectionError::UnknownClient) {
+        if matches!(&err, ConnectionError::Unauthorized) {
+            // Not `warn_once`: every single rejected connection is worth knowing about.
+            re_log::warn!("Rejected unauthenticated SDK client at {addr_string}");
+        } else if matches!(&err, ConnectionError::UnknownClient) {
             // An unknown client that probably stumbled onto the wrong port.
             // Don't log as an error (https://github.com/rerun-io/rerun/issues/5883).
             re_log::debug!(
@@ -176,7 +195,7 @@ fn run_client(
     mut stream: TcpStream,
     addr_string: &str,
     tx: &Sender<LogMsg>,
-    options: ServerOptions,
+    options: &ServerOptions,
 ) -> Result<(), ConnectionError> {
     #![allow(clippy::read_zero_byte_vec)] // false positive: https://github.com/rust-lang/rust-clippy/issues/9274
 
@@ -198,30 +217,58 @@ fn run_client(
             return Err(ConnectionError::UnknownClient);
         }
 
-        if options.quiet {
-            re_log::debug!("New SDK client connected from: {addr_string}");
-        } else {
-            re_log::info!("New SDK client connected from: {addr_string}");
+        // Version 1 is still supported: it's what clients without an auth token use.
+        let server_version = crate::PROTOCOL_VERSION_2;
+        if server_version < client_version {
+            return Err(ConnectionError::VersionError(VersionError::ClientIsNewer {
+                client_version,
+                server_version,
+            }));
         }
+    };
 
-        let server_version = crate::PROTOCOL_VERSION_1;
-        match client_version.cmp(&server_version) {
-            std::cmp::Ordering::Less => {
-                return Err(ConnectionError::VersionError(VersionError::ClientIsOlder {
-                    client_version,
-                    server_version,
-                }));
-            }
-            std::cmp::Ordering::Equal => {}
-            std::cmp::Ordering::Greater => {
-                return Err(ConnectionError::VersionError(VersionError::ClientIsNewer {
-                    client_version,
-                    server_version,
-                }));
-            }
+    // The auth token was added in version 2
+    let client_auth_token = if crate::PROTOCOL_VERSION_2 <= client_version {
+        let mut auth_token_len = [0_u8; 4];
+        stream.read_exact(&mut auth_token_len)?;
+        let auth_token_len = u32::from_le_bytes(auth_token_len);
+        if MAX_AUTH_TOKEN_LEN < auth_token_len {
+            return Err(ConnectionError::Unauthorized);
         }
+
+        let mut auth_token = vec![0_u8; auth_token_len as usize];
+        stream.read_exact(&mut auth_token)?;
+        Some(String::from_utf8(auth_token).map_err(|_err| ConnectionError::Unauthorized)?)
+    } else {
+        None
     };
 
+    let is_authorized = options.auth_token.as_ref().map_or(true, |expected| {
+        client_auth_token
+            .as_deref()
+            .is_some_and(|presented| re_auth::tokens_match(presented, expected))
+    });
+
+    // Version 2 clients wait for our verdict, so that they can report a rejected token.
+    if crate::PROTOCOL_VERSION_2 <= client_version {
+        let response = if is_authorized {
+            crate::PROTOCOL_AUTH_ACCEPTED
+        } else {
+            crate::PROTOCOL_AUTH_REJECTED
+        };
+        stream.write_all(&[response])?;
+    }
+
+    if !is_authorized {
+        return Err(ConnectionError::Unauthorized);
+    }
+
+    if options.quiet {
+        re_log::debug!("New SDK client connected from: {addr_string}");
+    } else {
+        re_log::info!("New SDK client connected from: {addr_string}");
+    }
+
     let mut congestion_manager = CongestionManager::new(options.max_latency_sec);
 
     let mut packet = Vec::new();
@@ -255,7 +302,7 @@ fn run_client(
 // ----------------------------------------------------------------------------
 
 /// Decides how many messages to drop so that we achieve a desired maximum latency.
-struct CongestionManager {
+pub(crate) struct CongestionManager {
     throttling: Throttling,
     rng: rand::rngs::SmallRng,
     timeline_histories: ahash::HashMap<TimelineName, TimelineThrottling>,
@@ -410,3 +457,93 @@ impl Throttling {
         }
     }
 }
+
+// ----------------------------------------------------------------------------
+
+#[cfg(all(test, feature = "client"))]
+mod tests {
+    use std::time::Duration;
+
+    use re_smart_channel::SmartMessagePayload;
+
+    use super::*;
+
+    /// Starts a server expecting `auth_token`, returning its port and the messages it receives.
+    fn start_server(auth_token: Option<&str>) -> (u16, Receiver<LogMsg>) {
+        // Find a free port: the server binds it again right away.
+        let port = TcpListener::bind("127.0.0.1:0")
+            .unwrap()
+            .local_addr()
+            .unwrap()
+            .port();
+        let rx = serve(
+            "127.0.0.1",
+            port,
+            ServerOptions {
+                quiet: true,
+                auth_token: auth_token.map(ToOwned::to_owned),
+                ..Default::default()
+            },
+        )
+        .unwrap();
+
+        (port, rx)
+    }
+
+    /// Performs a handshake presenting `client_auth_token` with a server expecting
+    /// `server_auth_token`, then hangs up.
+    ///
+    /// Returns whether the server accepted the client.
+    fn handshake(server_auth_token: Option<&str>, client_auth_token: Option<&str>) -> bool {
+        let (port, rx) = start_server(server_auth_token);
+
+        let mut stream = TcpStream::connect(("127.0.0.1", port)).unwrap();
+        crate::tcp_client::write_handshake(&mut stream, client_auth_token).unwrap();
+
+        // Clients presenting a token are told whether it was accepted.
+        let client_verdict =
+            client_auth_token.map(|_| crate::tcp_client::read_auth_response(&mut stream).unwrap());
+        drop(stream);
+
+        // Accepted clients that hang up quit gracefully, rejected ones with an error.
+        let is_accepted = match rx.recv_timeout(Duration::from_secs(10)).unwrap().payload {
+            SmartMessagePayload::Quit(err) => err.is_none(),
+            SmartMessagePayload::Msg(_) | SmartMessagePayload::Flush { .. } => {
+                panic!("expected the client to quit")
+            }
+        };
+
+        if let Some(client_verdict) = client_verdict {
+            assert_eq!(is_accepted, client_verdict);
+        }
+
+        is_accepted
+    }
+
+    #[test]
+    fn auth_token_handshake() {
+        // No token expected: both protocol versions are welcome.
+        assert!(handshake(None, None));
+        assert!(handshake(None, Some("whatever")));
+
+        assert!(handshake(Some("secret"), Some("secret")));
+        assert!(!handshake(Some("secret"), Some("Secret")));
+        assert!(!handshake(Some("secret"), Some("")));
+        assert!(!handshake(Some("secret"), None));
+    }
+
+    #[test]
+    fn client_unauthorized() {
+        let (port, _rx) = start_server(Some("secret"));
+        let addr = std::net::SocketAddr::from(([127, 0, 0, 1], port));
+
+        let mut client = crate::tcp_client::TcpClient::new(addr, None, Some("Secret".to_owned()));
+        assert!(matches!(
+            client.connect(),
+            Err(crate::ClientError::Unauthorized { .. })
+        ));
+
+        let mut client = crate::tcp_client::TcpClient::new(addr, None, Some("secret".to_owned()));
+        client.connect().unwrap();
+    }
+}
@@ -0,0 +1,520 @@
+use std::{
+    collections::VecDeque,
+    fs::File,
+    io::{Read as _, Seek as _, SeekFrom, Write as _},
+    path::PathBuf,
+};
+
+/// What to do with new messages when the spool is full.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum SpoolOverflowPolicy {
+    /// Drop the oldest spooled messages to make room for the new ones.
+    ///
+    /// The viewer will see the most recent data once it is reachable again.
+    #[default]
+    DropOldest,
+
+    /// Drop new messages until the spool has been replayed.
+    ///
+    /// The viewer will see the data from right after the connection was lost.
+    DropNewest,
+}
+
+/// Configures the on-disk spool of a [`crate::Client`].
+///
+/// While the server is unreachable, the encoded messages are appended to a local file instead of
+/// being kept in memory. Once the connection is back, they are replayed in order before any new
+/// message is sent.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SpoolOptions {
+    /// The file holding the unsent messages.
+    ///
+    /// Messages left in it by a previous run are replayed first. It is removed once the client
+    /// shuts down if all messages could be sent.
+    pub path: PathBuf,
+
+    /// The maximum size of the spool, in bytes.
+    pub max_bytes: u64,
+
+    /// What to do with new messages once [`Self::max_bytes`] has been reached.
+    pub overflow_policy: SpoolOverflowPolicy,
+}
+
+impl SpoolOptions {
+    /// The default maximum size of the spool: 1 GiB.
+    pub const DEFAULT_MAX_BYTES: u64 = 1024 * 1024 * 1024;
+
+    /// Spool to the given file, with the default size limit and overflow policy.
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self {
+            path: path.into(),
+            max_bytes: Self::DEFAULT_MAX_BYTES,
+            overflow_policy: SpoolOverflowPolicy::default(),
+        }
+    }
+}
+
+/// The file starts with the position of the oldest unsent packet (little-endian `u64`), so that
+/// packets that were already sent aren't replayed again after a restart.
+const HEADER_SIZE: u64 = std::mem::size_of::<u64>() as u64;
+
+/// Each packet is stored as its length (little-endian `u32`) followed by its bytes.
+const LENGTH_PREFIX_SIZE: u64 = std::mem::size_of::<u32>() as u64;
+
+/// A bounded FIFO of encoded packets, stored in a file.
+///
+/// Packets are appended at the end of the file and consumed from the front. The consumed prefix
+/// of the file is reclaimed when the spool is emptied, or compacted away when it grows too large.
+///
+/// The packets found in the file when it is opened are kept, so nothing is lost if the process
+/// exits before the spool could be replayed.
+pub(crate) struct Spool {
+    options: SpoolOptions,
+    file: File,
+
+    /// Where the oldest unsent packet starts in the file.
+    read_pos: u64,
+
+    /// Where the next packet will be written in the file.
+    write_pos: u64,
+
+    /// The sizes of the unsent packets, oldest first, excluding their length prefix.
+    packet_sizes: VecDeque<u64>,
+
+    /// The number of packets dropped because the spool was full, since it was last empty.
+    num_dropped: u64,
+}
+
+impl Spool {
+    pub fn open(options: SpoolOptions) -> std::io::Result<Self> {
+        if let Some(parent) = options.path.parent() {
+            if !parent.as_os_str().is_empty() {
+                std::fs::create_dir_all(parent)?;
+            }
+        }
+
+        let file = File::options()
+            .read(true)
+            .write(true)
+            .create(true)
+            .truncate(false)
+            .open(&options.path)?;
+
+        let mut spool = Self {
+            options,
+            file,
+            read_pos: HEADER_SIZE,
+            write_pos: HEADER_SIZE,
+            packet_sizes: VecDeque::new(),
+            num_dropped: 0,
+        };
+        spool.load()?;
+
+        Ok(spool)
+    }
+
+    /// Picks up the packets left unsent by a previous run, if any.
+    fn load(&mut self) -> std::io::Result<()> {
+        let file_len = self.file.metadata()?.len();
+        if file_len <= HEADER_SIZE {
+            self.clear();
+            return Ok(());
+        }
+
+        let mut header = [0; HEADER_SIZE as usize];
+        self.file.seek(SeekFrom::Start(0))?;
+        self.file.read_exact(&mut header)?;
+        let read_pos = u64::from_le_bytes(header);
+
+        if read_pos < HEADER_SIZE || file_len < read_pos {
+            re_log::warn!(
+                "Discarding the spool at {:?}: its header is corrupt",
+                self.options.path
+            );
+            self.clear();
+            return Ok(());
+        }
+
+        let mut packet_sizes = VecDeque::new();
+        let mut pos = read_pos;
+        {
+            let mut reader = std::io::BufReader::new(&self.file);
+            reader.seek(SeekFrom::Start(pos))?;
+
+            while LENGTH_PREFIX_SIZE <= file_len - pos {
+                let mut length = [0; LENGTH_PREFIX_SIZE as usize];
+                reader.read_exact(&mut length)?;
+                let size = u32::from_le_bytes(length) as u64;
+
+                if file_len - pos - LENGTH_PREFIX_SIZE < size {
+                    break;
+                }
+
+                reader.seek_relative(size as i64)?;
+                pos += LENGTH_PREFIX_SIZE + size;
+                packet_sizes.push_back(size);
+            }
+        }
+
+        if pos < file_len {
+            // The previous run was interrupted while writing the last packet.
+            re_log::warn!(
+                "Discarding a truncated message at the end of the spool at {:?}",
+                self.options.path
+            );
+        }
+
+        if packet_sizes.is_empty() {
+            self.clear();
+            return Ok(());
+        }
+
+        re_log::info!(
+            "Found {} unsent message(s) ({} bytes) in the spool at {:?}: they will be sent first",
+            packet_sizes.len(),
+            pos - read_pos,
+            self.options.path
+        );
+
+        self.read_pos = read_pos;
+        self.write_pos = pos;
+        self.packet_sizes = packet_sizes;
+        self.file.set_len(pos)
+    }
+
+    #[inline]
+    pub fn options(&self) -> &SpoolOptions {
+        &self.options
+    }
+
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.packet_sizes.is_empty()
+    }
+
+    #[inline]
+    pub fn num_packets(&self) -> usize {
+        self.packet_sizes.len()
+    }
+
+    /// The number of bytes taken by the unsent packets.
+    #[inline]
+    pub fn num_bytes(&self) -> u64 {
+        self.write_pos - self.read_pos
+    }
+
+    /// The number of packets dropped because the spool was full, since it was last empty.
+    #[inline]
+    pub fn num_dropped(&self) -> u64 {
+        self.num_dropped
+    }
+
+    /// Appends a packet, dropping data according to the [`SpoolOverflowPolicy`] if full.
+    pub fn push(&mut self, packet: &[u8]) -> std::io::Result<()> {
+        let entry_size = LENGTH_PREFIX_SIZE + packet.len() as u64;
+
+        if self.options.max_bytes < entry_size {
+            re_log::warn_once!(
+                "Dropping a message of {} bytes: it doesn't fit in the spool ({} bytes max)",
+                packet.len(),
+                self.options.max_bytes
+            );
+            self.num_dropped += 1;
+            return Ok(());
+        }
+
+        while self.options.max_bytes < self.num_bytes() + entry_size {
+            match self.options.overflow_policy {
+                SpoolOverflowPolicy::DropOldest => {
+                    re_log::warn_once!("Spool is full: dropping the oldest messages");
+                    self.pop_front();
+                    self.num_dropped += 1;
+                }
+                SpoolOverflowPolicy::DropNewest => {
+                    re_log::warn_once!("Spool is full: dropping new messages");
+                    self.num_dropped += 1;
+                    return Ok(());
+                }
+            }
+        }
+
+        // Keep the file bounded when we keep dropping the oldest messages without ever emptying
+        // the spool.
+        if self.options.max_bytes <= self.read_pos - HEADER_SIZE {
+            self.compact()?;
+        }
+
+        self.file.seek(SeekFrom::Start(self.write_pos))?;
+        self.file.write_all(&(packet.len() as u32).to_le_bytes())?;
+        self.file.write_all(packet)?;
+
+        self.write_pos += entry_size;
+        self.packet_sizes.push_back(packet.len() as u64);
+
+        Ok(())
+    }
+
+    /// Reads the oldest unsent packet, without removing it.
+    pub fn front(&mut self) -> std::io::Result<Option<Vec<u8>>> {
+        let Some(&size) = self.packet_sizes.front() else {
+            return Ok(None);
+        };
+
+        let mut packet = vec![0; size as usize];
+        self.file
+            .seek(SeekFrom::Start(self.read_pos + LENGTH_PREFIX_SIZE))?;
+        self.file.read_exact(&mut packet)?;
+
+        Ok(Some(packet))
+    }
+
+    /// Removes the oldest unsent packet, if any.
+    pub fn pop_front(&mut self) {
+        let Some(size) = self.packet_sizes.pop_front() else {
+            return;
+        };
+
+        self.read_pos += LENGTH_PREFIX_SIZE + size;
+
+        if self.packet_sizes.is_empty() {
+            self.clear();
+        } else if let Err(err) = self.write_header() {
+            re_log::warn_once!(
+                "Failed to update the spool at {:?}, sent messages may be replayed after a restart: {err}",
+                self.options.path
+            );
+        }
+    }
+
+    /// Removes all the unsent packets.
+    pub fn clear(&mut self) {
+        self.packet_sizes.clear();
+        self.read_pos = HEADER_SIZE;
+        self.write_pos = HEADER_SIZE;
+        self.num_dropped = 0;
+
+        // Best-effort: we only ever read what we've written since, so stale bytes are harmless.
+        self.file.set_len(HEADER_SIZE).ok();
+        self.write_header().ok();
+    }
+
+    fn write_header(&mut self) -> std::io::Result<()> {
+        self.file.seek(SeekFrom::Start(0))?;
+        self.file.write_all(&self.read_pos.to_le_bytes())
+    }
+
+    /// Moves the unsent packets to the start of the file.
+    fn compact(&mut self) -> std::io::Result<()> {
+        re_log::trace!(
+            "Compacting spool at {:?}: reclaiming {} bytes",
+            self.options.path,
+            self.read_pos - HEADER_SIZE
+        );
+
+        // The destination is always behind the source, so copying front-to-back is safe.
+        let mut buffer = vec![0; 64 * 1024];
+        let mut src = self.read_pos;
+        let mut dst = HEADER_SIZE;
+        while src < self.write_pos {
+            let len = buffer.len().min((self.write_pos - src) as usize);
+
+            self.file.seek(SeekFrom::Start(src))?;
+            self.file.read_exact(&mut buffer[..len])?;
+            self.file.seek(SeekFrom::Start(dst))?;
+            self.file.write_all(&buffer[..len])?;
+
+            src += len as u64;
+            dst += len as u64;
+        }
+
+        self.write_pos = dst;
+        self.read_pos = HEADER_SIZE;
+        self.write_header()?;
+        self.file.set_len(self.write_pos)
+    }
+}
+
+impl Drop for Spool {
+    fn drop(&mut self) {
+        if self.is_empty() {
+            std::fs::remove_file(&self.options.path).ok();
+        } else {
+            re_log::warn!(
+                "Shutting down with {} unsent message(s) ({} bytes) left in the spool at {:?}: they will be sent by the next client using it",
+                self.num_packets(),
+                self.num_bytes(),
+                self.options.path
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Write as _;
+
+    use super::*;
+
+    fn options(
+        dir: &tempfile::TempDir,
+        max_bytes: u64,
+        policy: SpoolOverflowPolicy,
+    ) -> SpoolOptions {
+        SpoolOptions {
+            path: dir.path().join("spool.bin"),
+            max_bytes,
+            overflow_policy: policy,
+        }
+    }
+
+    fn drain(spool: &mut Spool) -> Vec<Vec<u8>> {
+        let mut packets = Vec::new();
+        while let Some(packet) = spool.front().unwrap() {
+            packets.push(packet);
+            spool.pop_front();
+        }
+        packets
+    }
+
+    #[test]
+    fn fifo() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut spool = Spool::open(options(&dir, 1024, SpoolOverflowPolicy::DropOldest)).unwrap();
+
+        spool.push(b"one").unwrap();
+        spool.push(b"").unwrap();
+        spool.push(b"three").unwrap();
+        assert_eq!(spool.num_packets(), 3);
+        assert_eq!(spool.num_bytes(), 3 * LENGTH_PREFIX_SIZE + 8);
+
+        // Peeking doesn't consume.
+        assert_eq!(spool.front().unwrap().as_deref(), Some(&b"one"[..]));
+        assert_eq!(spool.front().unwrap().as_deref(), Some(&b"one"[..]));
+
+        assert_eq!(
+            drain(&mut spool),
+            vec![b"one".to_vec(), vec![], b"three".to_vec()]
+        );
+        assert!(spool.is_empty());
+        assert_eq!(spool.front().unwrap(), None);
+
+        // The file is reclaimed once empty.
+        let path = spool.options().path.clone();
+        assert_eq!(std::fs::metadata(&path).unwrap().len(), HEADER_SIZE);
+
+        // …and removed on shutdown.
+        drop(spool);
+        assert!(!path.exists());
+    }
+
+    #[test]
+    fn reopen() {
+        let dir = tempfile::tempdir().unwrap();
+        let options = options(&dir, 1024, SpoolOverflowPolicy::DropOldest);
+
+        {
+            let mut spool = Spool::open(options.clone()).unwrap();
+            spool.push(b"sent").unwrap();
+            spool.push(b"unsent 1").unwrap();
+            spool.push(b"unsent 2").unwrap();
+            spool.pop_front();
+        }
+
+        // Simulate a crash in the middle of writing a packet.
+        {
+            let mut file = File::options().append(true).open(&options.path).unwrap();
+            file.write_all(&100_u32.to_le_bytes()).unwrap();
+            file.write_all(b"trunc").unwrap();
+        }
+
+        let mut spool = Spool::open(options.clone()).unwrap();
+        assert_eq!(spool.num_packets(), 2);
+
+        // New packets go after the ones we found, not after the truncated one.
+        spool.push(b"new").unwrap();
+        assert_eq!(
+            drain(&mut spool),
+            vec![b"unsent 1".to_vec(), b"unsent 2".to_vec(), b"new".to_vec()]
+        );
+    }
+
+    #[test]
+    fn reopen_corrupt() {
+        let dir = tempfile::tempdir().unwrap();
+        let options = options(&dir, 1024, SpoolOverflowPolicy::DropOldest);
+        std::fs::write(&options.path, u64::MAX.to_le_bytes()).unwrap();
+        File::options()
+            .append(true)
+            .open(&options.path)
+            .unwrap()
+            .write_all(b"garbage")
+            .unwrap();
+
+        let mut spool = Spool::open(options).unwrap();
+        assert!(spool.is_empty());
+
+        spool.push(b"fresh").unwrap();
+        assert_eq!(drain(&mut spool), vec![b"fresh".to_vec()]);
+    }
+
+    #[test]
+    fn drop_oldest() {
+        let dir = tempfile::tempdir().unwrap();
+        let max_bytes = 2 * (LENGTH_PREFIX_SIZE + 4);
+        let mut spool =
+            Spool::open(options(&dir, max_bytes, SpoolOverflowPolicy::DropOldest)).unwrap();
+
+        for packet in [b"aaaa", b"bbbb", b"cccc", b"dddd"] {
+            spool.push(packet).unwrap();
+        }
+        assert_eq!(spool.num_dropped(), 2);
+        assert!(spool.num_bytes() <= max_bytes);
+
+        // A packet that can never fit is dropped on its own.
+        spool.push(&[0; 64]).unwrap();
+        assert_eq!(spool.num_dropped(), 3);
+
+        assert_eq!(drain(&mut spool), vec![b"cccc".to_vec(), b"dddd".to_vec()]);
+        assert_eq!(spool.num_dropped(), 0);
+    }
+
+    #[test]
+    fn drop_newest() {
+        let dir = tempfile::tempdir().unwrap();
+        let max_bytes = 2 * (LENGTH_PREFIX_SIZE + 4);
+        let mut spool =
+            Spool::open(options(&dir, max_bytes, SpoolOverflowPolicy::DropNewest)).unwrap();
+
+        for packet in [b"aaaa", b"bbbb", b"cccc", b"dddd"] {
+            spool.push(packet).unwrap();
+        }
+        assert_eq!(spool.num_dropped(), 2);
+        assert_eq!(drain(&mut spool), vec![b"aaaa".to_vec(), b"bbbb".to_vec()]);
+    }
+
+    #[test]
+    fn compaction() {
+        let dir = tempfile::tempdir().unwrap();
+        let entry_size = LENGTH_PREFIX_SIZE + 8;
+        let max_bytes = 4 * entry_size;
+        let mut spool =
+            Spool::open(options(&dir, max_bytes, SpoolOverflowPolicy::DropOldest)).unwrap();
+
+        // Never let the spool empty out, so that only compaction can reclaim the consumed prefix.
+        spool.push(&0_u64.to_le_bytes()).unwrap();
+        for i in 1..100_u64 {
+            spool.push(&i.to_le_bytes()).unwrap();
+            assert_eq!(spool.front().unwrap(), Some((i - 1).to_le_bytes().to_vec()));
+            spool.pop_front();
+
+            let file_len = std::fs::metadata(&spool.options().path).unwrap().len();
+            assert!(
+                file_len <= HEADER_SIZE + 2 * max_bytes,
+                "file_len: {file_len}"
+            );
+        }
+
+        assert_eq!(spool.num_dropped(), 0);
+        assert_eq!(drain(&mut spool), vec![99_u64.to_le_bytes().to_vec()]);
+    }
+}
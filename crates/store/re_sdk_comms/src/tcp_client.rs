@@ -1,5 +1,5 @@
 use std::{
-    io::Write,
+    io::{Read as _, Write},
     net::{SocketAddr, TcpStream},
     time::{Duration, Instant},
 };
@@ -17,6 +17,9 @@ pub enum ClientError {
         addr: SocketAddr,
         err: std::io::Error,
     },
+
+    #[error("Rerun server at {addr:?} rejected our auth token")]
+    Unauthorized { addr: SocketAddr },
 }
 
 /// State of the [`TcpStream`]
@@ -59,6 +62,39 @@ impl TcpStreamState {
     }
 }
 
+/// Writes the protocol version and header, followed by the auth token if any.
+///
+/// We only use [`crate::PROTOCOL_VERSION_2`] when we have a token to present, so that we can still
+/// talk to older servers otherwise.
+pub(crate) fn write_handshake(
+    stream: &mut TcpStream,
+    auth_token: Option<&str>,
+) -> std::io::Result<()> {
+    if let Some(auth_token) = auth_token {
+        stream.write_all(&crate::PROTOCOL_VERSION_2.to_le_bytes())?;
+        stream.write_all(crate::PROTOCOL_HEADER.as_bytes())?;
+        stream.write_all(&(auth_token.len() as u32).to_le_bytes())?;
+        stream.write_all(auth_token.as_bytes())
+    } else {
+        stream.write_all(&crate::PROTOCOL_VERSION_1.to_le_bytes())?;
+        stream.write_all(crate::PROTOCOL_HEADER.as_bytes())
+    }
+}
+
+/// Waits for the server to accept or reject the auth token sent by [`write_handshake`].
+///
+/// Returns whether the token was accepted.
+pub(crate) fn read_auth_response(stream: &mut TcpStream) -> std::io::Result<bool> {
+    // Don't hang forever on a server that never answers.
+    stream.set_read_timeout(Some(Duration::from_secs(5)))?;
+    let mut response = [0_u8; 1];
+    let res = stream.read_exact(&mut response);
+    stream.set_read_timeout(None)?;
+    res?;
+
+    Ok(response[0] == crate::PROTOCOL_AUTH_ACCEPTED)
+}
+
 /// Connect to a rerun server and send log messages.
 ///
 /// Blocking connection.
@@ -66,14 +102,22 @@ pub struct TcpClient {
     addr: SocketAddr,
     stream_state: TcpStreamState,
     flush_timeout: Option<Duration>,
+
+    /// Presented to the server during the handshake, if set.
+    auth_token: Option<String>,
 }
 
 impl TcpClient {
-    pub fn new(addr: SocketAddr, flush_timeout: Option<Duration>) -> Self {
+    pub fn new(
+        addr: SocketAddr,
+        flush_timeout: Option<Duration>,
+        auth_token: Option<String>,
+    ) -> Self {
         Self {
             addr,
             stream_state: TcpStreamState::reset(),
             flush_timeout,
+            auth_token,
         }
     }
 
@@ -93,21 +137,37 @@ impl TcpClient {
                     Ok(mut stream) => {
                         re_log::debug!("Connected to {:?}.", self.addr);
 
-                        if let Err(err) = stream
-                            .write(&crate::PROTOCOL_VERSION_1.to_le_bytes())
-                            .and_then(|_| stream.write(crate::PROTOCOL_HEADER.as_bytes()))
-                        {
-                            self.stream_state = TcpStreamState::Pending {
-                                start_time,
-                                num_attempts: num_attempts + 1,
-                            };
-                            Err(ClientError::Send {
-                                addr: self.addr,
-                                err,
-                            })
-                        } else {
-                            self.stream_state = TcpStreamState::Connected(stream);
-                            Ok(())
+                        let auth_token = self.auth_token.as_deref();
+                        let res = write_handshake(&mut stream, auth_token).and_then(|()| {
+                            if auth_token.is_some() {
+                                read_auth_response(&mut stream)
+                            } else {
+                                Ok(true) // Protocol version 1: the server doesn't answer.
+                            }
+                        });
+
+                        match res {
+                            Ok(true) => {
+                                self.stream_state = TcpStreamState::Connected(stream);
+                                Ok(())
+                            }
+                            Ok(false) => {
+                                self.stream_state = TcpStreamState::Pending {
+                                    start_time,
+                                    num_attempts: num_attempts + 1,
+                                };
+                                Err(ClientError::Unauthorized { addr: self.addr })
+                            }
+                            Err(err) => {
+                                self.stream_state = TcpStreamState::Pending {
+                                    start_time,
+                                    num_attempts: num_attempts + 1,
+                                };
+                                Err(ClientError::Send {
+                                    addr: self.addr,
+                                    err,
+                                })
+                            }
                         }
                     }
                     Err(err) => {
@@ -126,22 +186,19 @@ impl TcpClient {
     }
 
     /// Blocks until it is sent.
+    ///
+    /// If the connection breaks midway through, the server discards the incomplete packet along
+    /// with the connection, so the packet can safely be sent again once reconnected.
     pub fn send(&mut self, packet: &[u8]) -> Result<(), ClientError> {
-        use std::io::Write as _;
-
         self.connect()?;
 
         if let TcpStreamState::Connected(stream) = &mut self.stream_state {
             re_log::trace!("Sending a packet of size {}…", packet.len());
-            if let Err(err) = stream.write(&(packet.len() as u32).to_le_bytes()) {
-                self.stream_state = TcpStreamState::reset();
-                return Err(ClientError::Send {
-                    addr: self.addr,
-                    err,
-                });
-            }
 
-            if let Err(err) = stream.write(packet) {
+            if let Err(err) = stream
+                .write_all(&(packet.len() as u32).to_le_bytes())
+                .and_then(|()| stream.write_all(packet))
+            {
                 self.stream_state = TcpStreamState::reset();
                 return Err(ClientError::Send {
                     addr: self.addr,
@@ -31,12 +31,25 @@ pub struct DataframeQuery {
     /// Note: will be unset as soon as `timeline` is changed.
     pub filter_by_range: Option<crate::blueprint::components::FilterByRange>,
 
-    /// If provided, only show rows which contains a logged event for the specified component.
-    pub filter_is_not_null: Option<crate::blueprint::components::FilterIsNotNull>,
+    /// If provided, only show rows which contains a logged event for the specified components.
+    ///
+    /// See `filter_is_not_null_mode` for how multiple columns are combined.
+    pub filter_is_not_null: Option<Vec<crate::blueprint::components::FilterIsNotNull>>,
+
+    /// Whether rows must contain all of the `filter_is_not_null` columns, or any of them.
+    ///
+    /// Defaults to all.
+    pub filter_is_not_null_mode: Option<crate::blueprint::components::FilterIsNotNullMode>,
 
     /// Should empty cells be filled with latest-at queries?
     pub apply_latest_at: Option<crate::blueprint::components::ApplyLatestAt>,
 
+    /// Should the latest-at queries only consider data within the filtered range?
+    ///
+    /// If false (the default), empty cells may be filled with values logged before the start of the range.
+    /// Has no effect unless `apply_latest_at` is true.
+    pub latest_at_within_range: Option<crate::blueprint::components::LatestAtWithinRange>,
+
     /// Selected columns. If unset, all columns are selected.
     pub select: Option<crate::blueprint::components::SelectedColumns>,
 }
@@ -53,7 +66,7 @@ static RECOMMENDED_COMPONENTS: once_cell::sync::Lazy<[ComponentDescriptor; 1usiz
         }]
     });
 
-static OPTIONAL_COMPONENTS: once_cell::sync::Lazy<[ComponentDescriptor; 5usize]> =
+static OPTIONAL_COMPONENTS: once_cell::sync::Lazy<[ComponentDescriptor; 7usize]> =
     once_cell::sync::Lazy::new(|| {
         [
             ComponentDescriptor {
@@ -71,11 +84,21 @@ static OPTIONAL_COMPONENTS: once_cell::sync::Lazy<[ComponentDescriptor; 5usize]>
                 component_name: "rerun.blueprint.components.FilterIsNotNull".into(),
                 archetype_field_name: Some("filter_is_not_null".into()),
             },
+            ComponentDescriptor {
+                archetype_name: Some("rerun.blueprint.archetypes.DataframeQuery".into()),
+                component_name: "rerun.blueprint.components.FilterIsNotNullMode".into(),
+                archetype_field_name: Some("filter_is_not_null_mode".into()),
+            },
             ComponentDescriptor {
                 archetype_name: Some("rerun.blueprint.archetypes.DataframeQuery".into()),
                 component_name: "rerun.blueprint.components.ApplyLatestAt".into(),
                 archetype_field_name: Some("apply_latest_at".into()),
             },
+            ComponentDescriptor {
+                archetype_name: Some("rerun.blueprint.archetypes.DataframeQuery".into()),
+                component_name: "rerun.blueprint.components.LatestAtWithinRange".into(),
+                archetype_field_name: Some("latest_at_within_range".into()),
+            },
             ComponentDescriptor {
                 archetype_name: Some("rerun.blueprint.archetypes.DataframeQuery".into()),
                 component_name: "rerun.blueprint.components.SelectedColumns".into(),
@@ -84,7 +107,7 @@ static OPTIONAL_COMPONENTS: once_cell::sync::Lazy<[ComponentDescriptor; 5usize]>
         ]
     });
 
-static ALL_COMPONENTS: once_cell::sync::Lazy<[ComponentDescriptor; 6usize]> =
+static ALL_COMPONENTS: once_cell::sync::Lazy<[ComponentDescriptor; 8usize]> =
     once_cell::sync::Lazy::new(|| {
         [
             ComponentDescriptor {
@@ -107,11 +130,21 @@ static ALL_COMPONENTS: once_cell::sync::Lazy<[ComponentDescriptor; 6usize]> =
                 component_name: "rerun.blueprint.components.FilterIsNotNull".into(),
                 archetype_field_name: Some("filter_is_not_null".into()),
             },
+            ComponentDescriptor {
+                archetype_name: Some("rerun.blueprint.archetypes.DataframeQuery".into()),
+                component_name: "rerun.blueprint.components.FilterIsNotNullMode".into(),
+                archetype_field_name: Some("filter_is_not_null_mode".into()),
+            },
             ComponentDescriptor {
                 archetype_name: Some("rerun.blueprint.archetypes.DataframeQuery".into()),
                 component_name: "rerun.blueprint.components.ApplyLatestAt".into(),
                 archetype_field_name: Some("apply_latest_at".into()),
             },
+            ComponentDescriptor {
+                archetype_name: Some("rerun.blueprint.archetypes.DataframeQuery".into()),
+                component_name: "rerun.blueprint.components.LatestAtWithinRange".into(),
+                archetype_field_name: Some("latest_at_within_range".into()),
+            },
             ComponentDescriptor {
                 archetype_name: Some("rerun.blueprint.archetypes.DataframeQuery".into()),
                 component_name: "rerun.blueprint.components.SelectedColumns".into(),
@@ -121,8 +154,8 @@ static ALL_COMPONENTS: once_cell::sync::Lazy<[ComponentDescriptor; 6usize]> =
     });
 
 impl DataframeQuery {
-    /// The total number of components in the archetype: 0 required, 1 recommended, 5 optional
-    pub const NUM_COMPONENTS: usize = 6usize;
+    /// The total number of components in the archetype: 0 required, 1 recommended, 7 optional
+    pub const NUM_COMPONENTS: usize = 8usize;
 }
 
 /// Indicator component for the [`DataframeQuery`] [`::re_types_core::Archetype`]
@@ -197,16 +230,31 @@ impl ::re_types_core::Archetype for DataframeQuery {
             } else {
                 None
             };
-        let filter_is_not_null =
-            if let Some(array) = arrays_by_name.get("rerun.blueprint.components.FilterIsNotNull") {
+        let filter_is_not_null = if let Some(array) =
+            arrays_by_name.get("rerun.blueprint.components.FilterIsNotNull")
+        {
+            Some({
                 <crate::blueprint::components::FilterIsNotNull>::from_arrow_opt(&**array)
                     .with_context("rerun.blueprint.archetypes.DataframeQuery#filter_is_not_null")?
                     .into_iter()
-                    .next()
-                    .flatten()
-            } else {
-                None
-            };
+                    .map(|v| v.ok_or_else(DeserializationError::missing_data))
+                    .collect::<DeserializationResult<Vec<_>>>()
+                    .with_context("rerun.blueprint.archetypes.DataframeQuery#filter_is_not_null")?
+            })
+        } else {
+            None
+        };
+        let filter_is_not_null_mode = if let Some(array) =
+            arrays_by_name.get("rerun.blueprint.components.FilterIsNotNullMode")
+        {
+            <crate::blueprint::components::FilterIsNotNullMode>::from_arrow_opt(&**array)
+                .with_context("rerun.blueprint.archetypes.DataframeQuery#filter_is_not_null_mode")?
+                .into_iter()
+                .next()
+                .flatten()
+        } else {
+            None
+        };
         let apply_latest_at =
             if let Some(array) = arrays_by_name.get("rerun.blueprint.components.ApplyLatestAt") {
                 <crate::blueprint::components::ApplyLatestAt>::from_arrow_opt(&**array)
@@ -217,6 +265,17 @@ impl ::re_types_core::Archetype for DataframeQuery {
             } else {
                 None
             };
+        let latest_at_within_range = if let Some(array) =
+            arrays_by_name.get("rerun.blueprint.components.LatestAtWithinRange")
+        {
+            <crate::blueprint::components::LatestAtWithinRange>::from_arrow_opt(&**array)
+                .with_context("rerun.blueprint.archetypes.DataframeQuery#latest_at_within_range")?
+                .into_iter()
+                .next()
+                .flatten()
+        } else {
+            None
+        };
         let select =
             if let Some(array) = arrays_by_name.get("rerun.blueprint.components.SelectedColumns") {
                 <crate::blueprint::components::SelectedColumns>::from_arrow_opt(&**array)
@@ -231,7 +290,9 @@ impl ::re_types_core::Archetype for DataframeQuery {
             timeline,
             filter_by_range,
             filter_is_not_null,
+            filter_is_not_null_mode,
             apply_latest_at,
+            latest_at_within_range,
             select,
         })
     }
@@ -270,7 +331,7 @@ impl ::re_types_core::AsComponents for DataframeQuery {
             (self
                 .filter_is_not_null
                 .as_ref()
-                .map(|comp| (comp as &dyn ComponentBatch)))
+                .map(|comp_batch| (comp_batch as &dyn ComponentBatch)))
             .map(|batch| ::re_types_core::ComponentBatchCowWithDescriptor {
                 batch: batch.into(),
                 descriptor_override: Some(ComponentDescriptor {
@@ -279,6 +340,18 @@ impl ::re_types_core::AsComponents for DataframeQuery {
                     component_name: ("rerun.blueprint.components.FilterIsNotNull").into(),
                 }),
             }),
+            (self
+                .filter_is_not_null_mode
+                .as_ref()
+                .map(|comp| (comp as &dyn ComponentBatch)))
+            .map(|batch| ::re_types_core::ComponentBatchCowWithDescriptor {
+                batch: batch.into(),
+                descriptor_override: Some(ComponentDescriptor {
+                    archetype_name: Some("rerun.blueprint.archetypes.DataframeQuery".into()),
+                    archetype_field_name: Some(("filter_is_not_null_mode").into()),
+                    component_name: ("rerun.blueprint.components.FilterIsNotNullMode").into(),
+                }),
+            }),
             (self
                 .apply_latest_at
                 .as_ref()
@@ -291,6 +364,18 @@ impl ::re_types_core::AsComponents for DataframeQuery {
                     component_name: ("rerun.blueprint.components.ApplyLatestAt").into(),
                 }),
             }),
+            (self
+                .latest_at_within_range
+                .as_ref()
+                .map(|comp| (comp as &dyn ComponentBatch)))
+            .map(|batch| ::re_types_core::ComponentBatchCowWithDescriptor {
+                batch: batch.into(),
+                descriptor_override: Some(ComponentDescriptor {
+                    archetype_name: Some("rerun.blueprint.archetypes.DataframeQuery".into()),
+                    archetype_field_name: Some(("latest_at_within_range").into()),
+                    component_name: ("rerun.blueprint.components.LatestAtWithinRange").into(),
+                }),
+            }),
             (self
                 .select
                 .as_ref()
@@ -320,7 +405,9 @@ impl DataframeQuery {
             timeline: None,
             filter_by_range: None,
             filter_is_not_null: None,
+            filter_is_not_null_mode: None,
             apply_latest_at: None,
+            latest_at_within_range: None,
             select: None,
         }
     }
@@ -349,13 +436,29 @@ impl DataframeQuery {
         self
     }
 
-    /// If provided, only show rows which contains a logged event for the specified component.
+    /// If provided, only show rows which contains a logged event for the specified components.
+    ///
+    /// See `filter_is_not_null_mode` for how multiple columns are combined.
     #[inline]
     pub fn with_filter_is_not_null(
         mut self,
-        filter_is_not_null: impl Into<crate::blueprint::components::FilterIsNotNull>,
+        filter_is_not_null: impl IntoIterator<
+            Item = impl Into<crate::blueprint::components::FilterIsNotNull>,
+        >,
     ) -> Self {
-        self.filter_is_not_null = Some(filter_is_not_null.into());
+        self.filter_is_not_null = Some(filter_is_not_null.into_iter().map(Into::into).collect());
+        self
+    }
+
+    /// Whether rows must contain all of the `filter_is_not_null` columns, or any of them.
+    ///
+    /// Defaults to all.
+    #[inline]
+    pub fn with_filter_is_not_null_mode(
+        mut self,
+        filter_is_not_null_mode: impl Into<crate::blueprint::components::FilterIsNotNullMode>,
+    ) -> Self {
+        self.filter_is_not_null_mode = Some(filter_is_not_null_mode.into());
         self
     }
 
@@ -369,6 +472,19 @@ impl DataframeQuery {
         self
     }
 
+    /// Should the latest-at queries only consider data within the filtered range?
+    ///
+    /// If false (the default), empty cells may be filled with values logged before the start of the range.
+    /// Has no effect unless `apply_latest_at` is true.
+    #[inline]
+    pub fn with_latest_at_within_range(
+        mut self,
+        latest_at_within_range: impl Into<crate::blueprint::components::LatestAtWithinRange>,
+    ) -> Self {
+        self.latest_at_within_range = Some(latest_at_within_range.into());
+        self
+    }
+
     /// Selected columns. If unset, all columns are selected.
     #[inline]
     pub fn with_select(
@@ -386,7 +502,9 @@ impl ::re_byte_size::SizeBytes for DataframeQuery {
         self.timeline.heap_size_bytes()
             + self.filter_by_range.heap_size_bytes()
             + self.filter_is_not_null.heap_size_bytes()
+            + self.filter_is_not_null_mode.heap_size_bytes()
             + self.apply_latest_at.heap_size_bytes()
+            + self.latest_at_within_range.heap_size_bytes()
             + self.select.heap_size_bytes()
     }
 
@@ -394,8 +512,10 @@ impl ::re_byte_size::SizeBytes for DataframeQuery {
     fn is_pod() -> bool {
         <Option<crate::blueprint::components::TimelineName>>::is_pod()
             && <Option<crate::blueprint::components::FilterByRange>>::is_pod()
-            && <Option<crate::blueprint::components::FilterIsNotNull>>::is_pod()
+            && <Option<Vec<crate::blueprint::components::FilterIsNotNull>>>::is_pod()
+            && <Option<crate::blueprint::components::FilterIsNotNullMode>>::is_pod()
             && <Option<crate::blueprint::components::ApplyLatestAt>>::is_pod()
+            && <Option<crate::blueprint::components::LatestAtWithinRange>>::is_pod()
             && <Option<crate::blueprint::components::SelectedColumns>>::is_pod()
     }
 }
@@ -18,6 +18,7 @@ mod filter_by_range;
 mod filter_by_range_ext;
 mod filter_is_not_null;
 mod filter_is_not_null_ext;
+mod filter_is_not_null_mode;
 mod force_distance;
 mod force_iterations;
 mod force_strength;
@@ -27,6 +28,7 @@ mod grid_spacing_ext;
 mod included_content;
 mod interactive;
 mod interactive_ext;
+mod latest_at_within_range;
 mod lock_range_during_zoom;
 mod map_provider;
 mod near_clip_plane;
@@ -69,6 +71,7 @@ pub use self::corner2d::Corner2D;
 pub use self::enabled::Enabled;
 pub use self::filter_by_range::FilterByRange;
 pub use self::filter_is_not_null::FilterIsNotNull;
+pub use self::filter_is_not_null_mode::FilterIsNotNullMode;
 pub use self::force_distance::ForceDistance;
 pub use self::force_iterations::ForceIterations;
 pub use self::force_strength::ForceStrength;
@@ -76,6 +79,7 @@ pub use self::grid_columns::GridColumns;
 pub use self::grid_spacing::GridSpacing;
 pub use self::included_content::IncludedContent;
 pub use self::interactive::Interactive;
+pub use self::latest_at_within_range::LatestAtWithinRange;
 pub use self::lock_range_during_zoom::LockRangeDuringZoom;
 pub use self::map_provider::MapProvider;
 pub use self::near_clip_plane::NearClipPlane;
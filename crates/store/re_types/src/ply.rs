@@ -0,0 +1,310 @@
+//! Parsing of `.ply` files.
+//!
+//! Shared by [`Points3D::from_file_contents`] & co and by the `.ply` data loader, which also
+//! supports meshes and extra vertex properties.
+//!
+//! [`Points3D::from_file_contents`]: crate::archetypes::Points3D::from_file_contents
+
+use ply_rs::ply::{DefaultElement, Property, PropertyType};
+
+use crate::components::{Color, Radius, Texcoord2D, Text, Vector3D};
+
+// NOTE: Empirical evidence points to these being de-facto standard…
+const PROP_X: &str = "x";
+const PROP_Y: &str = "y";
+const PROP_Z: &str = "z";
+const PROPS_NORMAL: [&str; 3] = ["nx", "ny", "nz"];
+const PROPS_COLOR: [&str; 3] = ["red", "green", "blue"];
+const PROP_ALPHA: &str = "alpha";
+const PROP_RADIUS: &str = "radius";
+const PROP_LABEL: &str = "label";
+const PROPS_TEXCOORD: [[&str; 2]; 3] = [["u", "v"], ["s", "t"], ["texture_u", "texture_v"]];
+const PROPS_FACE_INDICES: [&str; 2] = ["vertex_indices", "vertex_index"];
+
+/// The vertices, and optionally faces, of a `.ply` file.
+///
+/// All per-vertex attributes have one value per vertex.
+#[derive(Clone, Debug)]
+pub struct PlyData {
+    /// Missing coordinates default to zero.
+    pub positions: Vec<[f32; 3]>,
+
+    /// Whether the vertices have a `z` property, i.e. whether they are 3D.
+    pub has_z: bool,
+
+    /// From the `red`, `green`, `blue` & `alpha` properties.
+    pub colors: Option<Vec<Color>>,
+
+    /// From the `radius` property. Missing radii default to `1.0`.
+    pub radii: Option<Vec<Radius>>,
+
+    /// From the `label` property, either a string or a number (e.g. a class id).
+    ///
+    /// Missing labels default to `"undef"`.
+    pub labels: Option<Vec<Text>>,
+
+    /// From the `nx`, `ny` & `nz` properties.
+    pub normals: Option<Vec<Vector3D>>,
+
+    /// From the `u` & `v` properties, or one of their common aliases.
+    pub texcoords: Option<Vec<Texcoord2D>>,
+
+    /// The faces of the file turned into triangles, if it has any.
+    pub triangles: Option<Vec<[u32; 3]>>,
+
+    /// Any other scalar vertex property (e.g. `intensity` or `confidence`), in file order.
+    pub extra_scalars: Vec<(String, Vec<Option<f64>>)>,
+}
+
+impl PlyData {
+    /// Parses a `.ply` file, which must at least have `x` & `y` vertex properties.
+    pub fn read(mut reader: impl std::io::BufRead) -> anyhow::Result<Self> {
+        re_tracing::profile_function!();
+
+        let parser = ply_rs::parser::Parser::<DefaultElement>::new();
+        let ply = {
+            re_tracing::profile_scope!("read_ply");
+            parser
+                .read_ply(&mut reader)
+                .map_err(|err| anyhow::anyhow!("Failed to parse .ply file: {err}"))?
+        };
+
+        let vertex_props = ply
+            .header
+            .elements
+            .get("vertex")
+            .map(|element| {
+                element
+                    .properties
+                    .values()
+                    .map(|prop| (prop.name.as_str(), &prop.data_type))
+                    .collect::<Vec<_>>()
+            })
+            .unwrap_or_default();
+        let has_prop = |name: &str| vertex_props.iter().any(|(prop, _)| *prop == name);
+        let has_props = |names: &[&str]| names.iter().all(|name| has_prop(name));
+
+        if !has_props(&[PROP_X, PROP_Y]) {
+            anyhow::bail!("The .ply file has no vertex positions (`x` & `y`)");
+        }
+
+        let no_elements = Vec::new();
+        let vertices = ply.payload.get("vertex").unwrap_or(&no_elements);
+
+        for key in ply.payload.keys() {
+            if key != "vertex" && key != "face" {
+                re_log::warn!("Ignoring {key:?} in .ply file");
+            }
+        }
+
+        let scalars = |name: &str| {
+            vertices
+                .iter()
+                .map(|vertex| vertex.get(name).and_then(f32))
+                .collect::<Vec<_>>()
+        };
+        let mut known_props = vec![PROP_X, PROP_Y, PROP_Z];
+
+        let has_z = has_prop(PROP_Z);
+        let positions = izip_xyz(scalars(PROP_X), scalars(PROP_Y), scalars(PROP_Z));
+
+        let colors = has_props(&PROPS_COLOR).then(|| {
+            known_props.extend(PROPS_COLOR);
+            known_props.push(PROP_ALPHA);
+            vertices
+                .iter()
+                .map(|vertex| {
+                    let [r, g, b] =
+                        PROPS_COLOR.map(|name| vertex.get(name).and_then(u8).unwrap_or(0));
+                    let a = vertex.get(PROP_ALPHA).and_then(u8).unwrap_or(255);
+                    Color::new((r, g, b, a))
+                })
+                .collect::<Vec<_>>()
+        });
+
+        let radii = has_prop(PROP_RADIUS).then(|| {
+            known_props.push(PROP_RADIUS);
+            scalars(PROP_RADIUS)
+                .into_iter()
+                .map(|radius| Radius::from(radius.unwrap_or(1.0)))
+                .collect::<Vec<_>>()
+        });
+
+        let labels = has_prop(PROP_LABEL).then(|| {
+            known_props.push(PROP_LABEL);
+            vertices
+                .iter()
+                .map(|vertex| {
+                    let label = vertex.get(PROP_LABEL).and_then(label);
+                    Text(label.unwrap_or_else(|| "undef".to_owned()).into())
+                })
+                .collect::<Vec<_>>()
+        });
+
+        let normals = has_props(&PROPS_NORMAL).then(|| {
+            known_props.extend(PROPS_NORMAL);
+            let [nx, ny, nz] = PROPS_NORMAL.map(scalars);
+            izip_xyz(nx, ny, nz)
+                .into_iter()
+                .map(Vector3D::from)
+                .collect::<Vec<_>>()
+        });
+
+        let texcoords = PROPS_TEXCOORD
+            .into_iter()
+            .find(|names| has_props(names))
+            .map(|[u, v]| {
+                known_props.extend([u, v]);
+                itertools::izip!(scalars(u), scalars(v))
+                    .map(|(u, v)| Texcoord2D::from([u.unwrap_or(0.0), v.unwrap_or(0.0)]))
+                    .collect::<Vec<_>>()
+            });
+
+        let triangles = ply
+            .payload
+            .get("face")
+            .filter(|faces| !faces.is_empty())
+            .map(|faces| triangulate(faces.as_slice()));
+
+        // Everything else is kept as-is, so that it can show up in the UI.
+        let mut extra_scalars = Vec::new();
+        for (name, data_type) in &vertex_props {
+            if known_props.contains(name) {
+                continue;
+            }
+
+            match data_type {
+                PropertyType::Scalar(_) => {
+                    let values = vertices
+                        .iter()
+                        .map(|vertex| vertex.get(*name).and_then(f64))
+                        .collect::<Vec<_>>();
+                    extra_scalars.push(((*name).to_owned(), values));
+                }
+                PropertyType::List(_, _) => {
+                    re_log::warn!("Ignoring vertex list property {name:?} in .ply file");
+                }
+            }
+        }
+
+        Ok(Self {
+            positions,
+            has_z,
+            colors,
+            radii,
+            labels,
+            normals,
+            texcoords,
+            triangles,
+            extra_scalars,
+        })
+    }
+}
+
+/// Missing coordinates default to zero, e.g. when turning 2D vertices into 3D positions.
+fn izip_xyz(x: Vec<Option<f32>>, y: Vec<Option<f32>>, z: Vec<Option<f32>>) -> Vec<[f32; 3]> {
+    // `z` may be missing altogether: pad it rather than truncating the other coordinates.
+    let z = z.into_iter().chain(std::iter::repeat(None));
+    itertools::izip!(x, y, z)
+        .map(|(x, y, z)| [x, y, z].map(|c| c.unwrap_or(0.0)))
+        .collect()
+}
+
+/// Turns the polygonal faces of a `.ply` file into triangles, as fans around their first vertex.
+fn triangulate(faces: &[DefaultElement]) -> Vec<[u32; 3]> {
+    re_tracing::profile_function!();
+
+    let mut triangles = Vec::with_capacity(faces.len());
+    let mut num_ignored = 0;
+
+    for face in faces {
+        let Some(indices) = PROPS_FACE_INDICES
+            .iter()
+            .find_map(|name| face.get(*name).and_then(indices))
+        else {
+            num_ignored += 1;
+            continue;
+        };
+
+        if let Some((&first, rest)) = indices.split_first() {
+            triangles.extend(rest.windows(2).map(|window| [first, window[0], window[1]]));
+        }
+    }
+
+    if 0 < num_ignored {
+        re_log::warn!("Ignored {num_ignored} face(s) without vertex indices in .ply file");
+    }
+
+    triangles
+}
+
+fn f32(prop: &Property) -> Option<f32> {
+    f64(prop).map(|v| v as f32)
+}
+
+fn f64(prop: &Property) -> Option<f64> {
+    match *prop {
+        Property::Char(v) => Some(v as f64),
+        Property::UChar(v) => Some(v as f64),
+        Property::Short(v) => Some(v as f64),
+        Property::UShort(v) => Some(v as f64),
+        Property::Int(v) => Some(v as f64),
+        Property::UInt(v) => Some(v as f64),
+        Property::Float(v) => Some(v as f64),
+        Property::Double(v) => Some(v),
+        Property::ListChar(_)
+        | Property::ListUChar(_)
+        | Property::ListShort(_)
+        | Property::ListUShort(_)
+        | Property::ListInt(_)
+        | Property::ListUInt(_)
+        | Property::ListFloat(_)
+        | Property::ListDouble(_) => None,
+    }
+}
+
+/// Floating point colors are expected to be in the `[0, 1]` range.
+fn u8(prop: &Property) -> Option<u8> {
+    match *prop {
+        Property::Float(v) => Some((v * 255.0) as u8),
+        Property::Double(v) => Some((v * 255.0) as u8),
+        _ => f64(prop).map(|v| v as u8),
+    }
+}
+
+/// Labels are either strings (i.e. lists of chars), or plain numbers such as class ids.
+fn label(prop: &Property) -> Option<String> {
+    match prop {
+        Property::ListUChar(chars) => Some(String::from_utf8_lossy(chars).into_owned()),
+        Property::ListChar(chars) => {
+            let chars = chars.iter().map(|&c| c as u8).collect::<Vec<_>>();
+            Some(String::from_utf8_lossy(&chars).into_owned())
+        }
+        Property::Char(v) => Some(v.to_string()),
+        Property::UChar(v) => Some(v.to_string()),
+        Property::Short(v) => Some(v.to_string()),
+        Property::UShort(v) => Some(v.to_string()),
+        Property::Int(v) => Some(v.to_string()),
+        Property::UInt(v) => Some(v.to_string()),
+        Property::Float(v) => Some(v.to_string()),
+        Property::Double(v) => Some(v.to_string()),
+        Property::ListShort(_)
+        | Property::ListUShort(_)
+        | Property::ListInt(_)
+        | Property::ListUInt(_)
+        | Property::ListFloat(_)
+        | Property::ListDouble(_) => None,
+    }
+}
+
+fn indices(prop: &Property) -> Option<Vec<u32>> {
+    match prop {
+        Property::ListChar(v) => Some(v.iter().map(|&i| i as u32).collect()),
+        Property::ListUChar(v) => Some(v.iter().map(|&i| i as u32).collect()),
+        Property::ListShort(v) => Some(v.iter().map(|&i| i as u32).collect()),
+        Property::ListUShort(v) => Some(v.iter().map(|&i| i as u32).collect()),
+        Property::ListInt(v) => Some(v.iter().map(|&i| i as u32).collect()),
+        Property::ListUInt(v) => Some(v.clone()),
+        _ => None,
+    }
+}
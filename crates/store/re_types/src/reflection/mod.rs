@@ -129,6 +129,14 @@ fn generate_component_reflection() -> Result<ComponentReflectionMap, Serializati
                 datatype: FilterIsNotNull::arrow_datatype(),
             },
         ),
+        (
+            <FilterIsNotNullMode as Component>::name(),
+            ComponentReflection {
+                docstring_md: "How multiple \"filter is not null\" columns are combined in the dataframe view.",
+                custom_placeholder: Some(FilterIsNotNullMode::default().to_arrow()?),
+                datatype: FilterIsNotNullMode::arrow_datatype(),
+            },
+        ),
         (
             <ForceDistance as Component>::name(),
             ComponentReflection {
@@ -185,6 +193,14 @@ fn generate_component_reflection() -> Result<ComponentReflectionMap, Serializati
                 datatype: Interactive::arrow_datatype(),
             },
         ),
+        (
+            <LatestAtWithinRange as Component>::name(),
+            ComponentReflection {
+                docstring_md: "Whether the latest-at queries filling empty cells in a dataframe should only consider data within the filtered range.",
+                custom_placeholder: Some(LatestAtWithinRange::default().to_arrow()?),
+                datatype: LatestAtWithinRange::arrow_datatype(),
+            },
+        ),
         (
             <LockRangeDuringZoom as Component>::name(),
             ComponentReflection {
@@ -1988,12 +2004,22 @@ fn generate_archetype_reflection() -> ArchetypeReflectionMap {
                     "filter_is_not_null", display_name : "Filter is not null",
                     component_name : "rerun.blueprint.components.FilterIsNotNull".into(),
                     docstring_md :
-                    "If provided, only show rows which contains a logged event for the specified component.",
+                    "If provided, only show rows which contains a logged event for the specified components.\n\nSee `filter_is_not_null_mode` for how multiple columns are combined.",
+                    is_required : false, }, ArchetypeFieldReflection { name :
+                    "filter_is_not_null_mode", display_name : "Filter is not null mode",
+                    component_name : "rerun.blueprint.components.FilterIsNotNullMode"
+                    .into(), docstring_md :
+                    "Whether rows must contain all of the `filter_is_not_null` columns, or any of them.\n\nDefaults to all.",
                     is_required : false, }, ArchetypeFieldReflection { name :
                     "apply_latest_at", display_name : "Apply latest at", component_name :
                     "rerun.blueprint.components.ApplyLatestAt".into(), docstring_md :
                     "Should empty cells be filled with latest-at queries?", is_required :
-                    false, }, ArchetypeFieldReflection { name : "select", display_name :
+                    false, }, ArchetypeFieldReflection { name : "latest_at_within_range",
+                    display_name : "Latest at within range", component_name :
+                    "rerun.blueprint.components.LatestAtWithinRange".into(), docstring_md
+                    :
+                    "Should the latest-at queries only consider data within the filtered range?\n\nIf false (the default), empty cells may be filled with values logged before the start of the range.\nHas no effect unless `apply_latest_at` is true.",
+                    is_required : false, }, ArchetypeFieldReflection { name : "select", display_name :
                     "Select", component_name :
                     "rerun.blueprint.components.SelectedColumns".into(), docstring_md :
                     "Selected columns. If unset, all columns are selected.", is_required
@@ -51,3 +51,34 @@ fn roundtrip() {
     let deserialized = Points3D::from_arrow(serialized).unwrap();
     similar_asserts::assert_eq!(expected, deserialized);
 }
+
+#[test]
+fn from_file_contents() {
+    let contents = "ply
+format ascii 1.0
+element vertex 2
+property float x
+property float y
+property float z
+property uchar red
+property uchar green
+property uchar blue
+property float radius
+property int label
+property float intensity
+end_header
+1 2 3 255 0 0 0.5 7 1
+4 5 6 0 0 255 2 42 0
+";
+
+    let expected = Points3D::new([(1.0, 2.0, 3.0), (4.0, 5.0, 6.0)])
+        .with_colors([0xFF0000FF, 0x0000FFFF])
+        .with_radii([0.5, 2.0])
+        .with_labels(["7", "42"]);
+
+    // Unknown properties, e.g. `intensity`, are ignored.
+    let arch = Points3D::from_file_contents(contents.as_bytes()).unwrap();
+    similar_asserts::assert_eq!(expected, arch);
+
+    assert!(Points3D::from_file_contents(b"not a ply file").is_err());
+}
@@ -19,12 +19,14 @@ pub fn viewer_to_server(
         ..ewebsock::Options::default()
     };
 
+    let redacted_url = crate::redact_auth_token(&url);
+
     ewebsock::ws_receive(
-        url.clone(),
+        url,
         options,
         Box::new(move |event: WsEvent| match event {
             WsEvent::Opened => {
-                re_log::info!("Connection to {url} established");
+                re_log::info!("Connection to {redacted_url} established");
                 ControlFlow::Continue(())
             }
             WsEvent::Message(message) => match message {
@@ -51,7 +53,7 @@ pub fn viewer_to_server(
                 ControlFlow::Break(())
             }
             WsEvent::Closed => {
-                re_log::info!("Connection to {url} closed.");
+                re_log::info!("Connection to {redacted_url} closed.");
                 ControlFlow::Break(())
             }
         }),
@@ -17,12 +17,25 @@ pub use client::viewer_to_server;
 #[cfg(feature = "server")]
 mod server;
 #[cfg(feature = "server")]
-pub use server::RerunServer;
+pub use server::{RerunServer, SnapshotTimeWindow};
 
 use re_log_types::LogMsg;
 
 pub const DEFAULT_WS_SERVER_PORT: u16 = 9877;
 
+/// Name of the url query parameter holding the auth token, e.g. `ws://localhost:9877?token=…`.
+///
+/// Only needed when the server was started with an auth token, see `RerunServer::new`.
+/// Since it is part of a url, the token should only contain url-safe characters.
+pub const AUTH_TOKEN_QUERY_PARAM: &str = "token";
+
+/// Redacts the value of the [`AUTH_TOKEN_QUERY_PARAM`] in `url`, so that it can be logged.
+///
+/// Also applies to ws urls nested in a web viewer url, e.g. `http://localhost:9090?url=ws://…`.
+pub fn redact_auth_token(url: &str) -> String {
+    re_auth::redact_query_param(url, AUTH_TOKEN_QUERY_PARAM)
+}
+
 #[cfg(feature = "tls")]
 pub const PROTOCOL: &str = "wss";
 
@@ -1,95 +1,348 @@
 //! The server is a pub-sub architecture.
 //!
 //! Each incoming log message is stored, and sent to any connected client.
-//! Each connecting client is first sent the history of stored log messages.
+//! Each connecting client is first sent a compacted snapshot of the stored data: all the static
+//! data, and optionally only the most recent temporal data (see [`SnapshotTimeWindow`]).
+//!
+//! Every client is fed from its own queue and thread, so that a slow client cannot hold back the
+//! others. A client that falls too far behind gets a decimated stream until it has caught up:
+//! only the latest pending chunk is kept for each entity and set of components, and sent at a
+//! capped rate. A client that can't even keep up with the data that is never decimated is
+//! eventually disconnected.
 //!
 //! In the future thing will be changed to a protocol where the clients can query
 //! for specific data based on e.g. time.
 
 use std::{
-    collections::VecDeque,
+    collections::{BTreeSet, HashMap, VecDeque},
     net::{TcpListener, TcpStream},
     sync::{
         atomic::{AtomicBool, AtomicU64, Ordering},
         Arc,
     },
+    time::{Duration, Instant},
 };
 
-use parking_lot::Mutex;
+use parking_lot::{Condvar, Mutex};
 use polling::{Event, Poller};
-use tungstenite::WebSocket;
+use tungstenite::{
+    handshake::server::{ErrorResponse, Request, Response},
+    http::StatusCode,
+    WebSocket,
+};
 
-use re_log_types::LogMsg;
+use re_chunk::Chunk;
+use re_chunk_store::{
+    ChunkStore, ChunkStoreConfig, GarbageCollectionOptions, GarbageCollectionTarget,
+};
+use re_log_types::{ArrowMsg, EntityPath, LogMsg, StoreId, StoreKind, TimelineName};
 use re_memory::MemoryLimit;
 use re_smart_channel::ReceiveSet;
 
-use crate::{server_url, RerunServerError, RerunServerPort};
+use crate::{server_url, RerunServerError, RerunServerPort, AUTH_TOKEN_QUERY_PARAM};
+
+/// A client is considered to be lagging once it hasn't been able to receive a message for this long.
+///
+/// It then gets a decimated stream until it has caught up, see [`ClientQueue`].
+const MAX_CLIENT_LATENCY: Duration = Duration::from_secs(1);
+
+/// A lagging client gets the latest chunk of each entity at most 10 times per second.
+const MIN_DECIMATED_INTERVAL: Duration = Duration::from_millis(100);
 
-struct MessageQueue {
+/// A client is disconnected once the messages waiting to be sent to it (excluding the snapshot it
+/// got when joining) take this many bytes.
+const MAX_CLIENT_BACKLOG_BYTES: usize = 512 * 1024 * 1024;
+
+/// An encoded message, shared by the history and all the client queues.
+type EncodedMsg = Arc<Vec<u8>>;
+
+/// How often the [`SnapshotTimeWindow`] is enforced on the history.
+const TIME_WINDOW_GC_INTERVAL: Duration = Duration::from_secs(1);
+
+/// When over the memory limit, drop at least this fraction of the history, so that we don't have
+/// to garbage collect again on the very next message.
+const MIN_MEMORY_GC_FRACTION: f64 = 0.1;
+
+/// Limits the snapshot sent to clients joining late to the most recent data on a timeline.
+///
+/// Static data, and data not logged on [`Self::timeline`], is always part of the snapshot.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct SnapshotTimeWindow {
+    /// The name of the timeline the window applies to, whatever its type.
+    pub timeline: TimelineName,
+
+    /// How far back from the latest time on [`Self::timeline`] to keep data.
+    ///
+    /// In the native unit of the timeline: nanoseconds for temporal timelines, ticks for sequences.
+    pub time_span: i64,
+}
+
+/// What the server keeps around for clients joining late.
+///
+/// Rather than every message received so far, the data is kept in one [`ChunkStore`] per
+/// recording or blueprint, which compacts small chunks together. A new client is sent a snapshot
+/// of these stores, which is much smaller than the full stream of a long session.
+struct History {
     server_memory_limit: MemoryLimit,
-    messages: VecDeque<Vec<u8>>,
+    time_window: Option<SnapshotTimeWindow>,
+    last_time_window_gc: Instant,
 
-    /// Never garbage collected.
-    messages_static: VecDeque<Vec<u8>>,
+    /// `SetStoreInfo` messages, sent before any data.
+    store_infos: Vec<EncodedMsg>,
+
+    /// One store per recording and blueprint, in order of appearance.
+    stores: Vec<ChunkStore>,
+
+    /// Other non-data messages (e.g. blueprint activation), sent after all the data.
+    commands: Vec<EncodedMsg>,
 }
 
-impl MessageQueue {
-    pub fn new(server_memory_limit: MemoryLimit) -> Self {
+impl History {
+    fn new(server_memory_limit: MemoryLimit, time_window: Option<SnapshotTimeWindow>) -> Self {
         Self {
             server_memory_limit,
-            messages: Default::default(),
-            messages_static: Default::default(),
+            time_window,
+            last_time_window_gc: Instant::now(),
+            store_infos: Vec::new(),
+            stores: Vec::new(),
+            commands: Vec::new(),
         }
     }
 
-    pub fn push(&mut self, msg: Vec<u8>) {
-        self.gc_if_using_too_much_ram();
-        self.messages.push_back(msg);
+    fn push(&mut self, msg: &LogMsg, encoded: &EncodedMsg) {
+        match msg {
+            LogMsg::SetStoreInfo(_) => self.store_infos.push(encoded.clone()),
+            LogMsg::ArrowMsg(store_id, arrow_msg) => {
+                self.insert_chunk(store_id, arrow_msg);
+                self.gc_if_needed();
+            }
+            LogMsg::BlueprintActivationCommand(_) => self.commands.push(encoded.clone()),
+        }
     }
 
-    /// Messages pushed using this method will stay around indefinitely.
-    ///
-    /// Useful e.g. for `SetStoreInfo` messages, so that clients late to the party actually get a
-    /// chance of receiving them.
-    pub fn push_static(&mut self, msg: Vec<u8>) {
-        self.gc_if_using_too_much_ram();
-        self.messages_static.push_back(msg);
+    fn insert_chunk(&mut self, store_id: &StoreId, arrow_msg: &ArrowMsg) {
+        re_tracing::profile_function!();
+
+        let chunk = match Chunk::from_arrow_msg(arrow_msg) {
+            Ok(chunk) => Arc::new(chunk),
+            Err(err) => {
+                re_log::warn_once!(
+                    "Failed to decode chunk: clients connecting later will not receive it: {err}"
+                );
+                return;
+            }
+        };
+
+        let store = if let Some(index) = self.stores.iter().position(|s| s.id() == *store_id) {
+            &mut self.stores[index]
+        } else {
+            let config = ChunkStoreConfig {
+                // Nobody is subscribed to the history.
+                enable_changelog: false,
+                ..ChunkStoreConfig::DEFAULT
+            };
+            self.stores.push(ChunkStore::new(store_id.clone(), config));
+            self.stores.last_mut().unwrap()
+        };
+
+        if let Err(err) = store.insert_chunk(&chunk) {
+            re_log::warn_once!(
+                "Failed to store chunk: clients connecting later will not receive it: {err}"
+            );
+        }
     }
 
-    fn gc_if_using_too_much_ram(&mut self) {
+    fn gc_if_needed(&mut self) {
         re_tracing::profile_function!();
 
+        if let Some(time_window) = self.time_window {
+            if TIME_WINDOW_GC_INTERVAL < self.last_time_window_gc.elapsed() {
+                self.last_time_window_gc = Instant::now();
+                self.gc_recordings(|store| {
+                    let timeline = store
+                        .all_timelines()
+                        .into_iter()
+                        .find(|timeline| *timeline.name() == time_window.timeline)?;
+                    Some(GarbageCollectionTarget::KeepLatestTimeSpan {
+                        timeline,
+                        time_span: time_window.time_span,
+                    })
+                });
+            }
+        }
+
         if let Some(max_bytes) = self.server_memory_limit.max_bytes {
             let max_bytes = max_bytes as u64;
-            let bytes_used = self.messages.iter().map(|m| m.len() as u64).sum::<u64>();
+            let bytes_used = self
+                .stores
+                .iter()
+                .map(|store| store.stats().total().total_size_bytes)
+                .sum::<u64>();
 
             if max_bytes < bytes_used {
-                re_tracing::profile_scope!("Drop messages");
                 re_log::info_once!(
-                    "Memory limit ({}) exceeded. Dropping old log messages from the server. Clients connecting after this will not see the full history.",
+                    "Memory limit ({}) exceeded. Dropping old data from the server. Clients connecting after this will not see the full history.",
                     re_format::format_bytes(max_bytes as _)
                 );
 
-                let bytes_to_free = bytes_used - max_bytes;
+                let fraction = (bytes_used - max_bytes) as f64 / bytes_used as f64;
+                self.gc_recordings(|_| {
+                    Some(GarbageCollectionTarget::DropAtLeastFraction(
+                        fraction.max(MIN_MEMORY_GC_FRACTION),
+                    ))
+                });
+            }
+        }
+    }
+
+    /// Blueprints are small and only make sense as a whole: they are never garbage collected.
+    ///
+    /// Recordings for which `target` returns `None` are left untouched.
+    fn gc_recordings(&mut self, target: impl Fn(&ChunkStore) -> Option<GarbageCollectionTarget>) {
+        for store in &mut self.stores {
+            if store.id().kind != StoreKind::Recording {
+                continue;
+            }
+            let Some(target) = target(store) else {
+                continue;
+            };
+
+            let options = GarbageCollectionOptions {
+                target,
+                time_budget: Duration::MAX,
+                // Always keep the latest state of everything.
+                protect_latest: 1,
+                protected_time_ranges: Default::default(),
+            };
+
+            let (events, _) = store.gc(&options);
+            re_log::trace!(
+                "Dropped {} chunk(s) from the history of {} ({})",
+                events.len(),
+                store.id(),
+                options.target
+            );
+        }
+    }
+
+    /// Everything a client joining now needs to catch up.
+    fn snapshot(&self) -> HistorySnapshot {
+        re_tracing::profile_function!();
 
-                let mut bytes_dropped = 0;
-                let mut messages_dropped = 0;
+        HistorySnapshot {
+            store_infos: self.store_infos.clone(),
+            chunks: self
+                .stores
+                .iter()
+                .flat_map(|store| {
+                    let store_id = store.id();
+                    store
+                        .iter_chunks()
+                        .map(move |chunk| (store_id.clone(), chunk.clone()))
+                })
+                .collect(),
+            commands: self.commands.clone(),
+        }
+    }
+}
 
-                while bytes_dropped < bytes_to_free {
-                    if let Some(msg) = self.messages.pop_front() {
-                        bytes_dropped += msg.len() as u64;
-                        messages_dropped += 1;
-                    } else {
-                        break;
-                    }
-                }
+/// The contents of the [`History`] at some point in time.
+///
+/// Taking one is cheap, as the chunks are shared rather than copied: the expensive part is
+/// [`Self::encode`], which doesn't need the history anymore.
+struct HistorySnapshot {
+    store_infos: Vec<EncodedMsg>,
+    chunks: Vec<(StoreId, Arc<Chunk>)>,
+    commands: Vec<EncodedMsg>,
+}
 
-                re_log::trace!(
-                    "Dropped {} bytes in {messages_dropped} message(s)",
-                    re_format::format_bytes(bytes_dropped as _)
-                );
+impl HistorySnapshot {
+    /// The messages to send to a new client, in order.
+    fn encode(self) -> Vec<EncodedMsg> {
+        re_tracing::profile_function!();
+
+        let Self {
+            store_infos,
+            chunks,
+            commands,
+        } = self;
+
+        let mut messages = store_infos;
+
+        for (store_id, chunk) in chunks {
+            match chunk.to_arrow_msg() {
+                Ok(arrow_msg) => {
+                    let msg = LogMsg::ArrowMsg(store_id, arrow_msg);
+                    messages.push(Arc::new(crate::encode_log_msg(&msg)));
+                }
+                Err(err) => {
+                    re_log::warn_once!("Failed to encode chunk for new client: {err}");
+                }
             }
         }
+
+        messages.extend(commands);
+
+        messages
+    }
+}
+
+/// The [`History`], fed by its own thread so that decoding, storing and garbage collecting the
+/// data never holds back the broadcasting.
+struct SharedHistory {
+    state: Mutex<SharedHistoryState>,
+    condvar: Condvar,
+}
+
+struct SharedHistoryState {
+    history: History,
+
+    /// The sequence number of the last broadcast message stored in [`Self::history`],
+    /// see [`ReceiveSetBroadcasterInnerState::last_seq`].
+    last_seq: u64,
+
+    /// Set once no more messages will be stored.
+    is_closed: bool,
+}
+
+impl SharedHistory {
+    fn new(history: History) -> Self {
+        Self {
+            state: Mutex::new(SharedHistoryState {
+                history,
+                last_seq: 0,
+                is_closed: false,
+            }),
+            condvar: Condvar::new(),
+        }
+    }
+
+    /// Stores the broadcast messages until the broadcaster is gone.
+    fn thread_func(&self, rx: &std::sync::mpsc::Receiver<(u64, LogMsg, EncodedMsg)>) {
+        while let Ok((seq, msg, encoded)) = rx.recv() {
+            let mut state = self.state.lock();
+            state.history.push(&msg, &encoded);
+            state.last_seq = seq;
+            drop(state);
+            self.condvar.notify_all();
+        }
+        self.close();
+    }
+
+    fn close(&self) {
+        self.state.lock().is_closed = true;
+        self.condvar.notify_all();
+    }
+
+    /// Waits for the history to include broadcast message `seq`, then takes a snapshot of it.
+    ///
+    /// Also returns the sequence number of the last message in the snapshot, which may be past `seq`.
+    fn snapshot(&self, seq: u64) -> (HistorySnapshot, u64) {
+        let mut state = self.state.lock();
+        while state.last_seq < seq && !state.is_closed {
+            self.condvar.wait(&mut state);
+        }
+        (state.history.snapshot(), state.last_seq)
     }
 }
 
@@ -99,6 +352,9 @@ impl MessageQueue {
 pub struct RerunServer {
     local_addr: std::net::SocketAddr,
 
+    /// If set, clients must present this token in the query of the url they connect to.
+    auth_token: Option<String>,
+
     listener_join_handle: Option<std::thread::JoinHandle<()>>,
     poller: Arc<Poller>,
     shutdown_flag: Arc<AtomicBool>,
@@ -114,12 +370,21 @@ impl RerunServer {
     /// A `bind_ip` of `"0.0.0.0"` is a good default.
     /// A port of 0 will let the OS choose a free port.
     ///
+    /// Clients connecting late first receive a snapshot of the data received so far, bounded by
+    /// `server_memory_limit` and, if set, `snapshot_time_window`.
+    ///
+    /// If `auth_token` is set, clients must connect to a url with a matching `token` query
+    /// parameter, e.g. `ws://localhost:9877?token=<auth_token>`, as returned by [`Self::server_url`].
+    /// Other connections are rejected.
+    ///
     /// Once created, the server will immediately start listening for connections.
     pub fn new(
         rerun_rx: ReceiveSet<LogMsg>,
         bind_ip: &str,
         port: RerunServerPort,
         server_memory_limit: MemoryLimit,
+        snapshot_time_window: Option<SnapshotTimeWindow>,
+        auth_token: Option<String>,
     ) -> Result<Self, RerunServerError> {
         let bind_addr = format!("{bind_ip}:{port}");
 
@@ -145,6 +410,7 @@ impl RerunServer {
         let poller_copy = poller.clone();
         let shutdown_flag_copy = shutdown_flag.clone();
         let num_clients_copy = num_accepted_clients.clone();
+        let auth_token_copy = auth_token.clone();
 
         let listener_join_handle = std::thread::Builder::new()
             .name("rerun_ws_server: listener".to_owned())
@@ -152,14 +418,20 @@ impl RerunServer {
                 Self::listen_thread_func(
                     &poller,
                     &listener_socket,
-                    &ReceiveSetBroadcaster::new(rerun_rx, server_memory_limit),
+                    &ReceiveSetBroadcaster::new(
+                        rerun_rx,
+                        server_memory_limit,
+                        snapshot_time_window,
+                    ),
                     &shutdown_flag,
                     &num_accepted_clients,
+                    auth_token.as_deref(),
                 );
             })?;
 
         let slf = Self {
             local_addr,
+            auth_token: auth_token_copy,
             poller: poller_copy,
             listener_join_handle: Some(listener_join_handle),
             shutdown_flag: shutdown_flag_copy,
@@ -168,15 +440,27 @@ impl RerunServer {
 
         re_log::info!(
             "Hosting a WebSocket server on {wsurl}. You can connect to this with a native viewer (`rerun {wsurl}`) or the web viewer (with `?url={wsurl}`).",
-            wsurl=slf.server_url()
+            wsurl=slf.redacted_server_url()
         );
 
         Ok(slf)
     }
 
-    /// Contains the `ws://` or `wss://` prefix.
+    /// Contains the `ws://` or `wss://` prefix, as well as the auth token, if any.
+    ///
+    /// Never log this, see [`Self::redacted_server_url`].
     pub fn server_url(&self) -> String {
-        server_url(&self.local_addr)
+        let url = server_url(&self.local_addr);
+        if let Some(auth_token) = &self.auth_token {
+            format!("{url}?{AUTH_TOKEN_QUERY_PARAM}={auth_token}")
+        } else {
+            url
+        }
+    }
+
+    /// Same as [`Self::server_url`], but with the auth token redacted: use this one for logging.
+    pub fn redacted_server_url(&self) -> String {
+        crate::redact_auth_token(&self.server_url())
     }
 
     /// Total count; never decreasing.
@@ -199,6 +483,7 @@ impl RerunServer {
         message_broadcaster: &ReceiveSetBroadcaster,
         shutdown_flag: &AtomicBool,
         num_accepted_clients: &AtomicU64,
+        auth_token: Option<&str>,
     ) {
         // Each socket in `poll::Poller` needs a "name".
         // Doesn't matter much what we're using here, as long as it's not used for something else
@@ -232,6 +517,7 @@ impl RerunServer {
                         poller,
                         listener_poll_key,
                         num_accepted_clients,
+                        auth_token,
                     );
                 }
             }
@@ -248,6 +534,7 @@ impl RerunServer {
         poller: &Poller,
         listener_poll_key: usize,
         num_accepted_clients: &AtomicU64,
+        auth_token: Option<&str>,
     ) {
         match listener_socket.accept() {
             Ok((tcp_stream, _)) => {
@@ -258,11 +545,36 @@ impl RerunServer {
 
                 re_log::debug!("New WebSocket connection from {address:?}");
 
-                match tungstenite::accept(tcp_stream) {
+                let check_auth_token = |request: &Request, response: Response| {
+                    if let Some(expected) = auth_token {
+                        let is_authorized = request
+                            .uri()
+                            .query()
+                            .and_then(|query| query_param(query, AUTH_TOKEN_QUERY_PARAM))
+                            .is_some_and(|presented| re_auth::tokens_match(presented, expected));
+                        if !is_authorized {
+                            let mut response = ErrorResponse::new(Some(
+                                "Missing or invalid auth token".to_owned(),
+                            ));
+                            *response.status_mut() = StatusCode::UNAUTHORIZED;
+                            return Err(response);
+                        }
+                    }
+                    Ok(response)
+                };
+
+                match tungstenite::accept_hdr(tcp_stream, check_auth_token) {
                     Ok(ws_stream) => {
                         message_broadcaster.add_client(ws_stream);
                         num_accepted_clients.fetch_add(1, Ordering::Relaxed);
                     }
+                    Err(tungstenite::HandshakeError::Failure(tungstenite::Error::Http(
+                        response,
+                    ))) if response.status() == StatusCode::UNAUTHORIZED => {
+                        re_log::warn!(
+                            "Rejected unauthenticated WebSocket connection from {address:?}"
+                        );
+                    }
                     Err(err) => {
                         re_log::warn!("Error accepting WebSocket connection: {err}");
                     }
@@ -302,43 +614,451 @@ impl Drop for RerunServer {
         let num_accepted_clients = self.num_accepted_clients.load(Ordering::Relaxed);
         re_log::info!(
             "Shutting down Rerun server on {} after serving {num_accepted_clients} client(s)",
-            self.server_url()
+            self.redacted_server_url()
         );
         self.stop_listener();
     }
 }
 
-/// Broadcasts messages to all connected clients and stores a history of messages to resend to new clients.
+/// Finds the value of `key` in a url query string, e.g. `a=1&token=foo`.
+fn query_param<'a>(query: &'a str, key: &str) -> Option<&'a str> {
+    query.split('&').find_map(|pair| {
+        let (k, v) = pair.split_once('=')?;
+        (k == key).then_some(v)
+    })
+}
+
+/// Identifies the data that a newer chunk supersedes when a client is lagging behind.
+#[derive(Clone, PartialEq, Eq, Hash)]
+struct DecimationKey {
+    store_id: StoreId,
+    entity_path: EntityPath,
+    components: BTreeSet<String>,
+}
+
+impl DecimationKey {
+    /// Only temporal chunks of recordings may be decimated: everything else is always sent.
+    fn from_log_msg(msg: &LogMsg) -> Option<Self> {
+        let LogMsg::ArrowMsg(store_id, arrow_msg) = msg else {
+            return None;
+        };
+        if store_id.kind != StoreKind::Recording {
+            return None;
+        }
+
+        let chunk = re_chunk::TransportChunk {
+            schema: arrow_msg.schema.clone(),
+            data: arrow_msg.chunk.clone(),
+        };
+        if chunk.num_timelines() == 0 {
+            return None; // static data
+        }
+
+        Some(Self {
+            store_id: store_id.clone(),
+            entity_path: chunk.entity_path().ok()?,
+            components: chunk
+                .components()
+                .map(|(field, _)| field.name.clone())
+                .collect(),
+        })
+    }
+}
+
+struct QueuedMsg {
+    msg: EncodedMsg,
+
+    /// `None` if this message must not be dropped.
+    decimation_key: Option<Arc<DecimationKey>>,
+
+    queued_at: Instant,
+
+    /// Counted towards [`ClientQueueLimits::max_backlog_bytes`]: zero for the snapshot.
+    num_backlog_bytes: usize,
+}
+
+/// See [`ClientQueue`].
+#[derive(Clone, Copy, Debug)]
+struct ClientQueueLimits {
+    /// The client is considered to be lagging once its oldest message has been waiting this long.
+    max_latency: Duration,
+
+    /// While lagging, a client gets at most one message per [`DecimationKey`] this often.
+    min_decimated_interval: Duration,
+
+    /// The client is disconnected once the messages queued for it since it joined take this
+    /// many bytes, which can only happen if it can't even keep up with the non-decimatable data.
+    max_backlog_bytes: usize,
+}
+
+impl Default for ClientQueueLimits {
+    fn default() -> Self {
+        Self {
+            max_latency: MAX_CLIENT_LATENCY,
+            min_decimated_interval: MIN_DECIMATED_INTERVAL,
+            max_backlog_bytes: MAX_CLIENT_BACKLOG_BYTES,
+        }
+    }
+}
+
+struct ClientQueueState {
+    messages: VecDeque<QueuedMsg>,
+
+    /// The sequence number of the front of [`Self::messages`]: every queued message gets the
+    /// next one, so that a message can be found from its sequence number alone.
+    front_seq: u64,
+
+    /// The sequence number of the newest message in [`Self::messages`] for each key.
+    pending: HashMap<Arc<DecimationKey>, u64>,
+
+    /// While lagging: when a message was last queued for each key.
+    last_queued: HashMap<Arc<DecimationKey>, Instant>,
+
+    /// While lagging: the latest message for each key that came too soon after the previous one.
+    held: HashMap<Arc<DecimationKey>, EncodedMsg>,
+
+    /// When to move the [`Self::held`] messages into the queue, in order.
+    held_until: VecDeque<(Instant, Arc<DecimationKey>)>,
+
+    /// The sum of [`QueuedMsg::num_backlog_bytes`].
+    num_backlog_bytes: usize,
+
+    /// Set when the oldest message has been waiting for more than
+    /// [`ClientQueueLimits::max_latency`], and cleared once the queue has been drained.
+    is_lagging: bool,
+
+    /// Messages dropped since the client started lagging.
+    num_dropped: u64,
+
+    /// While the snapshot for a new client is being taken: the messages broadcast in the
+    /// meantime, along with their sequence numbers, see [`ClientQueue::start_snapshot`].
+    joining: Option<Vec<(u64, EncodedMsg, Option<Arc<DecimationKey>>)>>,
+
+    /// Set once the client disconnected or the server is shutting down.
+    is_closed: bool,
+}
+
+impl ClientQueueState {
+    fn enqueue(
+        &mut self,
+        msg: EncodedMsg,
+        decimation_key: Option<Arc<DecimationKey>>,
+        num_backlog_bytes: usize,
+    ) {
+        let now = Instant::now();
+
+        if let Some(decimation_key) = &decimation_key {
+            let seq = self.front_seq + self.messages.len() as u64;
+            self.pending.insert(decimation_key.clone(), seq);
+            if self.is_lagging {
+                self.last_queued.insert(decimation_key.clone(), now);
+            }
+        }
+
+        self.num_backlog_bytes += num_backlog_bytes;
+        self.messages.push_back(QueuedMsg {
+            msg,
+            decimation_key,
+            queued_at: now,
+            num_backlog_bytes,
+        });
+    }
+
+    fn dequeue(&mut self) -> Option<QueuedMsg> {
+        let queued = self.messages.pop_front()?;
+
+        if let Some(decimation_key) = &queued.decimation_key {
+            if self.pending.get(decimation_key) == Some(&self.front_seq) {
+                self.pending.remove(decimation_key);
+            }
+        }
+        self.front_seq += 1;
+        self.num_backlog_bytes -= queued.num_backlog_bytes;
+
+        Some(queued)
+    }
+
+    /// Moves the held messages that have waited long enough into the queue.
+    ///
+    /// Returns when the next one is due, if any.
+    fn release_held(&mut self) -> Option<Instant> {
+        while let Some((until, _)) = self.held_until.front() {
+            if Instant::now() < *until {
+                return Some(*until);
+            }
+
+            let Some((_, decimation_key)) = self.held_until.pop_front() else {
+                break;
+            };
+            if let Some(msg) = self.held.remove(&decimation_key) {
+                let num_bytes = msg.len();
+                self.enqueue(msg, Some(decimation_key), num_bytes);
+            }
+        }
+
+        None
+    }
+}
+
+/// The messages waiting to be sent to a single client, by its own thread.
+///
+/// While the client is lagging, its stream is decimated: a new chunk replaces any pending chunk
+/// with the same [`DecimationKey`], and it gets at most one chunk per key every
+/// [`ClientQueueLimits::min_decimated_interval`]. The client thus gets the latest value of each
+/// entity at a capped rate, rather than falling further and further behind.
+struct ClientQueue {
+    state: Mutex<ClientQueueState>,
+    condvar: Condvar,
+    limits: ClientQueueLimits,
+}
+
+impl ClientQueue {
+    fn new(limits: ClientQueueLimits) -> Self {
+        Self {
+            state: Mutex::new(ClientQueueState {
+                messages: VecDeque::new(),
+                front_seq: 0,
+                pending: HashMap::default(),
+                last_queued: HashMap::default(),
+                held: HashMap::default(),
+                held_until: VecDeque::new(),
+                num_backlog_bytes: 0,
+                is_lagging: false,
+                num_dropped: 0,
+                joining: None,
+                is_closed: false,
+            }),
+            condvar: Condvar::new(),
+            limits,
+        }
+    }
+
+    /// Holds back the broadcast messages until [`Self::finish_snapshot`] is called.
+    fn start_snapshot(&self) {
+        self.state.lock().joining = Some(Vec::new());
+    }
+
+    /// Queues the snapshot sent to new clients, which is never decimated, followed by the
+    /// messages broadcast since [`Self::start_snapshot`] that it doesn't include.
+    ///
+    /// `snapshot_seq` is the sequence number of the last broadcast message in the snapshot.
+    fn finish_snapshot(&self, snapshot: Vec<EncodedMsg>, snapshot_seq: u64) {
+        let mut state = self.state.lock();
+
+        for msg in snapshot {
+            state.enqueue(msg, None, 0);
+        }
+
+        for (seq, msg, decimation_key) in state.joining.take().unwrap_or_default() {
+            if snapshot_seq < seq && !self.push_locked(&mut state, msg, decimation_key) {
+                return;
+            }
+        }
+
+        self.condvar.notify_one();
+    }
+
+    /// Pushes broadcast message number `seq`.
+    ///
+    /// Returns `false` if the client is gone.
+    fn push_broadcast(
+        &self,
+        seq: u64,
+        msg: EncodedMsg,
+        decimation_key: Option<Arc<DecimationKey>>,
+    ) -> bool {
+        let mut state = self.state.lock();
+        if state.is_closed {
+            return false;
+        }
+        if let Some(joining) = &mut state.joining {
+            joining.push((seq, msg, decimation_key));
+            return true;
+        }
+        self.push_locked(&mut state, msg, decimation_key)
+    }
+
+    /// Returns `false` if the client is gone.
+    fn push_locked(
+        &self,
+        state: &mut ClientQueueState,
+        msg: EncodedMsg,
+        decimation_key: Option<Arc<DecimationKey>>,
+    ) -> bool {
+        if state.is_closed {
+            return false;
+        }
+
+        if !state.is_lagging
+            && state
+                .messages
+                .front()
+                .is_some_and(|oldest| self.limits.max_latency < oldest.queued_at.elapsed())
+        {
+            re_log::debug!("Web socket client is lagging behind: decimating its stream");
+            state.is_lagging = true;
+        }
+
+        if state.is_lagging {
+            if let Some(decimation_key) = &decimation_key {
+                if let Some(&seq) = state.pending.get(decimation_key) {
+                    let pending = &mut state.messages[(seq - state.front_seq) as usize];
+
+                    // Keep the original timestamp, so that we keep measuring how late we are.
+                    state.num_backlog_bytes -= pending.num_backlog_bytes;
+                    state.num_backlog_bytes += msg.len();
+                    pending.num_backlog_bytes = msg.len();
+                    pending.msg = msg;
+                    state.num_dropped += 1;
+                    return true;
+                }
+
+                if let Some(held) = state.held.get_mut(decimation_key) {
+                    *held = msg;
+                    state.num_dropped += 1;
+                    return true;
+                }
+
+                if let Some(last_queued) = state.last_queued.get(decimation_key) {
+                    let until = *last_queued + self.limits.min_decimated_interval;
+                    if Instant::now() < until {
+                        let index = state
+                            .held_until
+                            .partition_point(|(other_until, _)| *other_until <= until);
+                        state
+                            .held_until
+                            .insert(index, (until, decimation_key.clone()));
+                        state.held.insert(decimation_key.clone(), msg);
+                        self.condvar.notify_one();
+                        return true;
+                    }
+                }
+            }
+        }
+
+        let num_bytes = msg.len();
+        state.enqueue(msg, decimation_key, num_bytes);
+
+        if self.limits.max_backlog_bytes < state.num_backlog_bytes {
+            re_log::warn!(
+                "Disconnecting a web socket client that is too far behind: {} are waiting to be sent to it",
+                re_format::format_bytes(state.num_backlog_bytes as _)
+            );
+            state.is_closed = true;
+            self.condvar.notify_all();
+            return false;
+        }
+
+        self.condvar.notify_one();
+
+        true
+    }
+
+    /// Blocks until there is a message to send, or returns `None` if the client is gone.
+    fn pop(&self) -> Option<EncodedMsg> {
+        let mut state = self.state.lock();
+        loop {
+            if state.is_closed {
+                return None;
+            }
+
+            let next_release = state.release_held();
+
+            if let Some(queued) = state.dequeue() {
+                if state.is_lagging && state.messages.is_empty() && state.held.is_empty() {
+                    re_log::debug!(
+                        "Web socket client caught up after dropping {} message(s)",
+                        state.num_dropped
+                    );
+                    state.is_lagging = false;
+                    state.num_dropped = 0;
+                    state.last_queued.clear();
+                }
+                return Some(queued.msg);
+            }
+
+            if let Some(next_release) = next_release {
+                self.condvar.wait_until(&mut state, next_release);
+            } else {
+                self.condvar.wait(&mut state);
+            }
+        }
+    }
+
+    fn close(&self) {
+        self.state.lock().is_closed = true;
+        self.condvar.notify_all();
+    }
+
+    /// Sends the queued messages to the client until either side closes.
+    fn send_thread_func(&self, mut client: WebSocket<TcpStream>) {
+        while let Some(msg) = self.pop() {
+            if let Err(err) = client.send(tungstenite::Message::Binary((*msg).clone())) {
+                re_log::warn!("Error sending message to web socket client: {err}");
+                break;
+            }
+        }
+        self.close();
+    }
+}
+
+/// Broadcasts messages to all connected clients and stores a history of the data to send to new clients.
 ///
 /// This starts a thread which will close when the underlying `ReceiveSet` gets a quit message or looses all its connections.
+/// The history is maintained by a second thread, so that it never holds back the broadcasting.
 struct ReceiveSetBroadcaster {
     inner: Arc<Mutex<ReceiveSetBroadcasterInnerState>>,
+    history: Arc<SharedHistory>,
     shutdown_on_next_recv: Arc<AtomicBool>,
 }
 
 /// Inner state of the [`ReceiveSetBroadcaster`], protected by a mutex.
 struct ReceiveSetBroadcasterInnerState {
-    /// Don't allow adding to the history while adding/removing clients.
-    /// This way, no messages history is lost!
-    history: MessageQueue,
-    clients: Vec<WebSocket<TcpStream>>,
+    clients: Vec<Arc<ClientQueue>>,
+
+    /// The sequence number of the last broadcast message, starting at 1.
+    ///
+    /// Comparing it with [`SharedHistoryState::last_seq`] tells which messages a new client gets
+    /// as part of its snapshot, so that no message is lost or sent twice.
+    last_seq: u64,
 }
 
 impl ReceiveSetBroadcaster {
-    pub fn new(log_rx: ReceiveSet<LogMsg>, server_memory_limit: MemoryLimit) -> Self {
+    pub fn new(
+        log_rx: ReceiveSet<LogMsg>,
+        server_memory_limit: MemoryLimit,
+        snapshot_time_window: Option<SnapshotTimeWindow>,
+    ) -> Self {
         let inner = Arc::new(Mutex::new(ReceiveSetBroadcasterInnerState {
-            history: MessageQueue::new(server_memory_limit),
             clients: Vec::new(),
+            last_seq: 0,
         }));
+        let history = Arc::new(SharedHistory::new(History::new(
+            server_memory_limit,
+            snapshot_time_window,
+        )));
         let shutdown = Arc::new(AtomicBool::new(false));
 
+        let (history_tx, history_rx) = std::sync::mpsc::channel();
+        let history_copy = history.clone();
+        if let Err(err) = std::thread::Builder::new()
+            .name("rerun_ws_server: history".to_owned())
+            .spawn(move || history_copy.thread_func(&history_rx))
+        {
+            re_log::error!(
+                "Failed to spawn thread for storing the history of websocket messages: {err}"
+            );
+            // New clients will only get the messages broadcast after they connected.
+            history.close();
+        }
+
         let inner_copy = inner.clone();
         let shutdown_copy = shutdown.clone();
 
         if let Err(err) = std::thread::Builder::new()
             .name("rerun_ws_server: broadcaster".to_owned())
             .spawn(move || {
-                Self::broadcast_thread_func(&log_rx, &inner, &shutdown);
+                Self::broadcast_thread_func(&log_rx, &inner, &history_tx, &shutdown);
             })
         {
             re_log::error!(
@@ -348,6 +1068,7 @@ impl ReceiveSetBroadcaster {
 
         Self {
             inner: inner_copy,
+            history,
             shutdown_on_next_recv: shutdown_copy,
         }
     }
@@ -355,6 +1076,7 @@ impl ReceiveSetBroadcaster {
     fn broadcast_thread_func(
         log_rx: &ReceiveSet<LogMsg>,
         inner: &Mutex<ReceiveSetBroadcasterInnerState>,
+        history_tx: &std::sync::mpsc::Sender<(u64, LogMsg, EncodedMsg)>,
         shutdown: &AtomicBool,
     ) {
         while let Ok(msg) = log_rx.recv() {
@@ -365,26 +1087,21 @@ impl ReceiveSetBroadcaster {
 
             match msg.payload {
                 re_smart_channel::SmartMessagePayload::Msg(data) => {
-                    let msg = crate::encode_log_msg(&data);
-                    let mut inner = inner.lock();
-
-                    // TODO(andreas): Should this be a parallel-for?
-                    inner.clients.retain_mut(|client| {
-                        if let Err(err) = client.send(tungstenite::Message::Binary(msg.clone())) {
-                            re_log::warn!("Error sending message to web socket client: {err}");
-                            false
-                        } else {
-                            true
-                        }
-                    });
-
-                    let msg_is_data = matches!(data, LogMsg::ArrowMsg(_, _));
-                    if msg_is_data {
-                        inner.history.push(msg);
-                    } else {
-                        // Keep non-data commands around for clients late to the party.
-                        inner.history.push_static(msg);
-                    }
+                    let msg = Arc::new(crate::encode_log_msg(&data));
+                    let decimation_key = DecimationKey::from_log_msg(&data).map(Arc::new);
+
+                    let seq = {
+                        let mut inner = inner.lock();
+                        inner.last_seq += 1;
+                        let seq = inner.last_seq;
+                        inner.clients.retain(|client| {
+                            client.push_broadcast(seq, msg.clone(), decimation_key.clone())
+                        });
+                        seq
+                    };
+
+                    // Only fails if the history thread is gone, which has already been reported.
+                    history_tx.send((seq, data, msg)).ok();
                 }
 
                 re_smart_channel::SmartMessagePayload::Flush { on_flush_done } => {
@@ -407,28 +1124,36 @@ impl ReceiveSetBroadcaster {
         }
     }
 
-    /// Adds a websocket client to the broadcaster and replies all message history so far to it.
-    pub fn add_client(&self, mut client: WebSocket<TcpStream>) {
-        // TODO(andreas): While it's great that we don't loose any messages while adding clients,
-        // the problem with this is that now we won't be able to keep the other clients fed, until this one is done!
-        // Meaning that if a new one connects, we stall the old connections until we have sent all messages to this one.
-        let mut inner = self.inner.lock();
+    /// Adds a websocket client to the broadcaster and sends it a snapshot of the history so far.
+    pub fn add_client(&self, client: WebSocket<TcpStream>) {
+        let queue = Arc::new(ClientQueue::new(ClientQueueLimits::default()));
 
-        for msg in &inner.history.messages_static {
-            if let Err(err) = client.send(tungstenite::Message::Binary(msg.clone())) {
-                re_log::warn!("Error sending static message to web socket client: {err}");
-                return;
-            }
-        }
+        // The messages broadcast while the snapshot is being taken are held back, and only
+        // queued after it if it doesn't include them.
+        queue.start_snapshot();
 
-        for msg in &inner.history.messages {
-            if let Err(err) = client.send(tungstenite::Message::Binary(msg.clone())) {
-                re_log::warn!("Error sending message to web socket client: {err}");
-                return;
-            }
+        let queue_copy = queue.clone();
+        if let Err(err) = std::thread::Builder::new()
+            .name("rerun_ws_server: client".to_owned())
+            .spawn(move || queue_copy.send_thread_func(client))
+        {
+            re_log::error!(
+                "Failed to spawn thread for sending messages to web socket client: {err}"
+            );
+            return;
         }
 
-        inner.clients.push(client);
+        let last_seq = {
+            let mut inner = self.inner.lock();
+            inner.clients.push(queue.clone());
+            inner.last_seq
+        };
+
+        // The client missed everything up to `last_seq`: the snapshot must include it.
+        let (snapshot, snapshot_seq) = self.history.snapshot(last_seq);
+
+        // The snapshot is never decimated: it's what late joiners need to catch up.
+        queue.finish_snapshot(snapshot.encode(), snapshot_seq);
     }
 }
 
@@ -439,6 +1164,375 @@ impl Drop for ReceiveSetBroadcaster {
         // neither closing nor sending any more messages.
         self.shutdown_on_next_recv
             .store(true, std::sync::atomic::Ordering::Release);
-        self.inner.lock().clients.clear();
+        for client in self.inner.lock().clients.drain(..) {
+            client.close();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn connect(url: &str) -> Result<(), StatusCode> {
+        match tungstenite::connect(url) {
+            Ok(_) => Ok(()),
+            Err(tungstenite::Error::Http(response)) => Err(response.status()),
+            Err(err) => panic!("unexpected error: {err}"),
+        }
+    }
+
+    #[test]
+    fn auth_token() {
+        let server = RerunServer::new(
+            ReceiveSet::new(vec![]),
+            "127.0.0.1",
+            RerunServerPort(0),
+            MemoryLimit::UNLIMITED,
+            None,
+            Some("secret".to_owned()),
+        )
+        .unwrap();
+
+        let url = server.server_url();
+        assert!(url.ends_with("?token=secret"), "{url}");
+        assert!(!server.redacted_server_url().contains("secret"));
+
+        assert_eq!(Ok(()), connect(&url));
+
+        let url = server_url(&server.local_addr);
+        assert_eq!(Err(StatusCode::UNAUTHORIZED), connect(&url));
+        assert_eq!(
+            Err(StatusCode::UNAUTHORIZED),
+            connect(&format!("{url}?token=Secret"))
+        );
+        assert_eq!(
+            Err(StatusCode::UNAUTHORIZED),
+            connect(&format!("{url}?tokens=secret"))
+        );
+        assert_eq!(Ok(()), connect(&format!("{url}?foo=bar&token=secret")));
+    }
+
+    #[test]
+    fn no_auth_token() {
+        let server = RerunServer::new(
+            ReceiveSet::new(vec![]),
+            "127.0.0.1",
+            RerunServerPort(0),
+            MemoryLimit::UNLIMITED,
+            None,
+            None,
+        )
+        .unwrap();
+
+        assert_eq!(Ok(()), connect(&server.server_url()));
+        assert_eq!(
+            Ok(()),
+            connect(&format!("{}?token=whatever", server.server_url()))
+        );
+    }
+
+    fn decimation_key(entity_path: &str) -> Option<Arc<DecimationKey>> {
+        Some(Arc::new(DecimationKey {
+            store_id: StoreId::from_string(StoreKind::Recording, "test".to_owned()),
+            entity_path: entity_path.into(),
+            components: BTreeSet::from(["positions".to_owned()]),
+        }))
+    }
+
+    fn encoded(bytes: &[u8]) -> EncodedMsg {
+        Arc::new(bytes.to_vec())
+    }
+
+    impl ClientQueue {
+        fn push(&self, msg: EncodedMsg, decimation_key: Option<Arc<DecimationKey>>) -> bool {
+            self.push_locked(&mut self.state.lock(), msg, decimation_key)
+        }
+    }
+
+    fn lagging_queue(min_decimated_interval: Duration, max_backlog_bytes: usize) -> ClientQueue {
+        ClientQueue::new(ClientQueueLimits {
+            max_latency: Duration::ZERO,
+            min_decimated_interval,
+            max_backlog_bytes,
+        })
+    }
+
+    #[test]
+    fn client_queue_keeps_everything_while_keeping_up() {
+        let queue = ClientQueue::new(ClientQueueLimits::default());
+
+        for bytes in [b"a1", b"a2", b"a3"] {
+            assert!(queue.push(encoded(bytes), decimation_key("a")));
+        }
+
+        for bytes in [b"a1", b"a2", b"a3"] {
+            assert_eq!(queue.pop().unwrap().as_slice(), bytes);
+        }
+    }
+
+    #[test]
+    fn client_queue_decimation() {
+        let queue = lagging_queue(Duration::ZERO, usize::MAX);
+
+        queue.push(encoded(b"a1"), decimation_key("a"));
+        queue.push(encoded(b"b1"), decimation_key("b"));
+        assert_eq!(queue.pop().unwrap().as_slice(), b"a1");
+
+        std::thread::sleep(Duration::from_millis(2));
+
+        // Now lagging: replaces the pending message in place…
+        queue.push(encoded(b"b2"), decimation_key("b"));
+        // …but never drops messages without a key…
+        queue.push(encoded(b"static"), None);
+        queue.push(encoded(b"b3"), decimation_key("b"));
+        // …nor messages for other keys.
+        queue.push(encoded(b"c1"), decimation_key("c"));
+
+        assert_eq!(queue.state.lock().num_dropped, 2);
+        assert_eq!(queue.pop().unwrap().as_slice(), b"b3");
+        assert_eq!(queue.pop().unwrap().as_slice(), b"static");
+        assert_eq!(queue.pop().unwrap().as_slice(), b"c1");
+
+        // Caught up.
+        let state = queue.state.lock();
+        assert!(!state.is_lagging);
+        assert!(state.pending.is_empty());
+        assert_eq!(state.num_backlog_bytes, 0);
+    }
+
+    #[test]
+    fn client_queue_rate_cap() {
+        let interval = Duration::from_millis(50);
+        let queue = lagging_queue(interval, usize::MAX);
+
+        queue.push(encoded(b"x"), None);
+        std::thread::sleep(Duration::from_millis(2));
+        let a1_queued_at = Instant::now();
+        queue.push(encoded(b"a1"), decimation_key("a"));
+        queue.push(encoded(b"y"), None);
+
+        assert_eq!(queue.pop().unwrap().as_slice(), b"x");
+        assert_eq!(queue.pop().unwrap().as_slice(), b"a1");
+
+        // Too soon after `a1`: held back, and superseded while waiting.
+        queue.push(encoded(b"a2"), decimation_key("a"));
+        queue.push(encoded(b"a3"), decimation_key("a"));
+
+        assert_eq!(queue.pop().unwrap().as_slice(), b"y");
+        assert_eq!(queue.pop().unwrap().as_slice(), b"a3");
+        assert!(interval <= a1_queued_at.elapsed());
+
+        let state = queue.state.lock();
+        assert!(!state.is_lagging);
+        assert!(state.held.is_empty());
+    }
+
+    #[test]
+    fn client_queue_backlog_limit() {
+        let queue = lagging_queue(Duration::ZERO, 10);
+
+        // The snapshot doesn't count.
+        queue.finish_snapshot(vec![encoded(&[0; 100])], 0);
+        assert!(queue.push(encoded(&[0; 6]), None));
+
+        // Non-decimatable messages can't be dropped, so the client has to go.
+        assert!(!queue.push(encoded(&[0; 6]), None));
+        assert!(queue.pop().is_none());
+        assert!(!queue.push(encoded(&[0; 1]), None));
+    }
+
+    #[test]
+    fn client_queue_joining() {
+        let queue = ClientQueue::new(ClientQueueLimits::default());
+        queue.start_snapshot();
+
+        for (seq, bytes) in [(1, b"a1"), (2, b"a2"), (3, b"a3")] {
+            assert!(queue.push_broadcast(seq, encoded(bytes), decimation_key("a")));
+        }
+
+        // The snapshot already includes the first two messages: only the third one follows it.
+        queue.finish_snapshot(vec![encoded(b"snapshot")], 2);
+        assert!(queue.push_broadcast(4, encoded(b"a4"), decimation_key("a")));
+
+        for bytes in [b"snapshot".as_slice(), b"a3".as_slice(), b"a4".as_slice()] {
+            assert_eq!(queue.pop().unwrap().as_slice(), bytes);
+        }
+        assert!(queue.state.lock().messages.is_empty());
+    }
+
+    fn store_info_msg(store_id: &StoreId) -> LogMsg {
+        LogMsg::SetStoreInfo(re_log_types::SetStoreInfo {
+            row_id: *re_chunk::RowId::new(),
+            info: re_log_types::StoreInfo {
+                application_id: re_log_types::ApplicationId("test".to_owned()),
+                store_id: store_id.clone(),
+                cloned_from: None,
+                is_official_example: true,
+                started: re_log_types::Time::now(),
+                store_source: re_log_types::StoreSource::Unknown,
+                store_version: None,
+            },
+        })
+    }
+
+    /// One row per frame in `frames`, in a single chunk.
+    fn points_msg(store_id: &StoreId, frames: std::ops::Range<i64>) -> LogMsg {
+        let mut builder = Chunk::builder("points".into());
+        for frame in frames {
+            builder = builder.with_component_batches(
+                re_chunk::RowId::new(),
+                [(re_log_types::Timeline::new_sequence("frame"), frame)],
+                [&[re_log_types::example_components::MyPoint::new(1.0, 2.0)] as _],
+            );
+        }
+        LogMsg::ArrowMsg(
+            store_id.clone(),
+            builder.build().unwrap().to_arrow_msg().unwrap(),
+        )
+    }
+
+    fn static_msg(store_id: &StoreId) -> LogMsg {
+        let chunk = Chunk::builder("label".into())
+            .with_component_batches(
+                re_chunk::RowId::new(),
+                re_log_types::TimePoint::default(),
+                [&[re_log_types::example_components::MyLabel(
+                    "static".to_owned(),
+                )] as _],
+            )
+            .build()
+            .unwrap();
+        LogMsg::ArrowMsg(store_id.clone(), chunk.to_arrow_msg().unwrap())
+    }
+
+    fn push(history: &mut History, msg: &LogMsg) {
+        history.push(msg, &Arc::new(crate::encode_log_msg(msg)));
+    }
+
+    /// The snapshot, decoded: the chunks are decoded as well.
+    fn decoded_snapshot(history: &History) -> Vec<LogMsg> {
+        history
+            .snapshot()
+            .encode()
+            .iter()
+            .map(|msg| crate::decode_log_msg(msg).unwrap())
+            .collect()
+    }
+
+    fn frames(msgs: &[LogMsg]) -> Vec<i64> {
+        let mut frames: Vec<i64> = msgs
+            .iter()
+            .filter_map(|msg| match msg {
+                LogMsg::ArrowMsg(_, arrow_msg) => Some(Chunk::from_arrow_msg(arrow_msg).unwrap()),
+                _ => None,
+            })
+            .filter_map(|chunk| {
+                let times = chunk
+                    .timelines()
+                    .get(&re_log_types::Timeline::new_sequence("frame"))?
+                    .times_raw()
+                    .to_vec();
+                Some(times)
+            })
+            .flatten()
+            .collect();
+        frames.sort_unstable();
+        frames
+    }
+
+    #[test]
+    fn history_snapshot() {
+        let recording_id = StoreId::from_string(StoreKind::Recording, "recording".to_owned());
+        let blueprint_id = StoreId::from_string(StoreKind::Blueprint, "blueprint".to_owned());
+
+        let mut history = History::new(MemoryLimit::UNLIMITED, None);
+        assert!(history.snapshot().encode().is_empty());
+
+        push(&mut history, &store_info_msg(&recording_id));
+        push(&mut history, &points_msg(&recording_id, 0..10));
+        push(
+            &mut history,
+            &LogMsg::BlueprintActivationCommand(re_log_types::BlueprintActivationCommand {
+                blueprint_id: blueprint_id.clone(),
+                make_active: true,
+                make_default: true,
+            }),
+        );
+        push(&mut history, &store_info_msg(&blueprint_id));
+        push(&mut history, &static_msg(&blueprint_id));
+        push(&mut history, &points_msg(&recording_id, 10..20));
+
+        let msgs = decoded_snapshot(&history);
+
+        // Store infos first, then the data, then the commands, whatever the order they came in.
+        assert!(
+            matches!(&msgs[0], LogMsg::SetStoreInfo(info) if info.info.store_id == recording_id)
+        );
+        assert!(
+            matches!(&msgs[1], LogMsg::SetStoreInfo(info) if info.info.store_id == blueprint_id)
+        );
+        assert!(matches!(
+            msgs.last(),
+            Some(LogMsg::BlueprintActivationCommand(_))
+        ));
+        assert!(msgs[2..msgs.len() - 1]
+            .iter()
+            .all(|msg| matches!(msg, LogMsg::ArrowMsg(..))));
+
+        // Nothing is lost, even though the chunks may have been compacted together.
+        assert_eq!(frames(&msgs), (0..20).collect::<Vec<_>>());
+        assert!(msgs
+            .iter()
+            .any(|msg| matches!(msg, LogMsg::ArrowMsg(store_id, _) if *store_id == blueprint_id)));
+    }
+
+    #[test]
+    fn history_time_window() {
+        let recording_id = StoreId::from_string(StoreKind::Recording, "recording".to_owned());
+
+        let mut history = History::new(
+            MemoryLimit::UNLIMITED,
+            Some(SnapshotTimeWindow {
+                // The timeline is found by name, whatever its type.
+                timeline: "frame".into(),
+                time_span: 10,
+            }),
+        );
+
+        // Too many rows for any two of these chunks to be compacted together.
+        let num_rows = ChunkStoreConfig::DEFAULT.chunk_max_rows as i64;
+
+        let msgs = [
+            store_info_msg(&recording_id),
+            static_msg(&recording_id),
+            points_msg(&recording_id, 0..num_rows),
+            points_msg(&recording_id, 1000..1000 + num_rows),
+        ];
+        let last_msg = points_msg(&recording_id, 2000..2000 + num_rows);
+
+        // Not enforced more often than every `TIME_WINDOW_GC_INTERVAL`.
+        history.last_time_window_gc = Instant::now();
+        for msg in &msgs {
+            push(&mut history, msg);
+        }
+        assert_eq!(
+            frames(&decoded_snapshot(&history)).len(),
+            2 * num_rows as usize
+        );
+
+        history.last_time_window_gc = Instant::now()
+            .checked_sub(2 * TIME_WINDOW_GC_INTERVAL)
+            .unwrap();
+        push(&mut history, &last_msg);
+
+        let msgs = decoded_snapshot(&history);
+
+        // Only the chunk overlapping the window is left, along with the static data.
+        assert_eq!(frames(&msgs), (2000..2000 + num_rows).collect::<Vec<_>>());
+        assert!(msgs.iter().any(|msg| match msg {
+            LogMsg::ArrowMsg(_, arrow_msg) => Chunk::from_arrow_msg(arrow_msg).unwrap().is_static(),
+            _ => false,
+        }));
     }
 }
@@ -0,0 +1,52 @@
+//! Aligning the clocks of several processes logging to the same recording.
+//!
+//! Each process has its own clock, so their `log_time` timelines (or any other timeline derived
+//! from a local clock) don't exactly agree. To fix this after the fact, every process can log a
+//! sync marker with [`crate::RecordingStream::log_sync_marker`] when a shared event happens (e.g. a
+//! hardware trigger, or a message received by all of them). `rerun rrd merge --sync-marker` then
+//! shifts the timelines of each input so that their markers line up.
+
+use re_chunk::Chunk;
+use re_log_types::{EntityPath, TimeInt, Timeline, TimelineName};
+use re_types_core::ComponentName;
+
+/// The entity under which sync markers are logged.
+pub const SYNC_MARKER_ENTITY_PATH: &str = "/__sync_markers";
+
+/// The component holding the name of a sync marker, as a UTF-8 string.
+pub const SYNC_MARKER_COMPONENT: &str = "rerun.sdk.SyncMarker";
+
+/// The entity under which sync markers are logged, see [`SYNC_MARKER_ENTITY_PATH`].
+pub fn sync_marker_entity_path() -> EntityPath {
+    EntityPath::parse_forgiving(SYNC_MARKER_ENTITY_PATH)
+}
+
+/// Returns all the sync markers found in `chunk`, with their time on the given timeline.
+///
+/// Returns nothing if the chunk doesn't hold sync markers, or isn't on that timeline.
+pub fn sync_markers(chunk: &Chunk, timeline: &TimelineName) -> Vec<(String, Timeline, TimeInt)> {
+    if *chunk.entity_path() != sync_marker_entity_path() {
+        return Vec::new();
+    }
+
+    let Some(timeline) = chunk
+        .timelines()
+        .keys()
+        .find(|candidate| candidate.name() == timeline)
+        .copied()
+    else {
+        return Vec::new();
+    };
+
+    let component_name = ComponentName::from(SYNC_MARKER_COMPONENT);
+    itertools::izip!(
+        chunk.iter_component_indices(&timeline, &component_name),
+        chunk.iter_slices::<String>(component_name),
+    )
+    .flat_map(|((time, _row_id), names)| {
+        names
+            .into_iter()
+            .map(move |name| (name.as_str().to_owned(), timeline, time))
+    })
+    .collect()
+}
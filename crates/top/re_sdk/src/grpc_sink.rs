@@ -0,0 +1,385 @@
+use std::time::Duration;
+
+use re_log_encoding::Compression;
+use re_log_types::LogMsg;
+use re_protos::sdk_comms::v0::message_proxy_client::MessageProxyClient;
+use tokio::sync::mpsc;
+use tokio_stream::wrappers::ReceiverStream;
+
+use crate::sink::LogSink;
+
+// ---
+
+/// The default address of a Rerun `MessageProxy` gRPC server which an SDK connects to.
+pub fn default_grpc_server_addr() -> std::net::SocketAddr {
+    std::net::SocketAddr::from(([127, 0, 0, 1], re_protos::sdk_comms::DEFAULT_SERVER_PORT))
+}
+
+/// How many encoded messages can be queued up in the gRPC stream before we wait for the transport.
+const STREAM_CHANNEL_CAPACITY: usize = 256;
+
+/// How long to wait before trying to connect again after a failed attempt.
+///
+/// Doubles after every consecutive failure, up to [`MAX_RECONNECT_DELAY`].
+const MIN_RECONNECT_DELAY: Duration = Duration::from_millis(500);
+
+/// See [`MIN_RECONNECT_DELAY`].
+const MAX_RECONNECT_DELAY: Duration = Duration::from_secs(10);
+
+enum Cmd {
+    LogMsg(LogMsg),
+
+    /// Answered once all the messages sent before it have been handed over to the transport,
+    /// or the connection broke.
+    Flush(crossbeam::channel::Sender<()>),
+}
+
+/// Stream log messages to a Rerun `MessageProxy` gRPC server, e.g. a viewer started with
+/// `--grpc-port`.
+///
+/// This is an alternative to the [`crate::sink::TcpSink`]: the wire protocol is described by the
+/// `rerun.sdk_comms.v0` protobuf package, so that any language with gRPC support can implement it.
+pub struct GrpcSink {
+    cmd_tx: Option<mpsc::UnboundedSender<Cmd>>,
+
+    /// Signaled when the background thread is done, i.e. the stream has been closed.
+    done_rx: crossbeam::channel::Receiver<()>,
+
+    flush_timeout: Option<Duration>,
+}
+
+impl GrpcSink {
+    /// Connect to the given address in a background thread.
+    /// Retries until successful, and reconnects whenever the connection breaks.
+    ///
+    /// `flush_timeout` is the minimum time the [`GrpcSink`] will wait during a flush
+    /// before potentially dropping data. Note: Passing `None` here can cause a
+    /// call to `flush` to block indefinitely if a connection cannot be established.
+    ///
+    /// The auth token to present to the server, if any, is read from the
+    /// [`re_sdk_comms::ENV_AUTH_TOKEN`] environment variable.
+    pub fn new(addr: std::net::SocketAddr, flush_timeout: Option<Duration>) -> Self {
+        Self::new_with_auth_token(addr, flush_timeout, re_sdk_comms::default_auth_token())
+    }
+
+    /// Connect to the given address in a background thread, presenting the given `auth_token`.
+    ///
+    /// See [`Self::new`] for details.
+    pub fn new_with_auth_token(
+        addr: std::net::SocketAddr,
+        flush_timeout: Option<Duration>,
+        auth_token: Option<String>,
+    ) -> Self {
+        let (cmd_tx, cmd_rx) = mpsc::unbounded_channel();
+        let (done_tx, done_rx) = crossbeam::channel::bounded(1);
+
+        std::thread::Builder::new()
+            .name("grpc_sink".to_owned())
+            .spawn(move || {
+                match tokio::runtime::Builder::new_current_thread()
+                    .enable_all()
+                    .build()
+                {
+                    Ok(runtime) => runtime.block_on(stream_messages(addr, auth_token, cmd_rx)),
+                    Err(err) => re_log::error!("Failed to start gRPC sink runtime: {err}"),
+                }
+                done_tx.send(()).ok();
+            })
+            .expect("Failed to spawn thread");
+
+        Self {
+            cmd_tx: Some(cmd_tx),
+            done_rx,
+            flush_timeout,
+        }
+    }
+}
+
+impl Drop for GrpcSink {
+    fn drop(&mut self) {
+        // Closing the channel ends the stream, which in turn completes the gRPC call.
+        self.cmd_tx = None;
+
+        let done = if let Some(timeout) = self.flush_timeout {
+            self.done_rx.recv_timeout(timeout).is_ok()
+        } else {
+            self.done_rx.recv().is_ok()
+        };
+        if !done {
+            re_log::warn!("Dropping gRPC sink before all data could be sent");
+        }
+    }
+}
+
+impl LogSink for GrpcSink {
+    #[inline]
+    fn send(&self, msg: LogMsg) {
+        if let Some(cmd_tx) = &self.cmd_tx {
+            if cmd_tx.send(Cmd::LogMsg(msg)).is_err() {
+                re_log::warn_once!("gRPC sink is closed, dropping log messages");
+            }
+        }
+    }
+
+    fn flush_blocking(&self) {
+        let Some(cmd_tx) = &self.cmd_tx else {
+            return;
+        };
+
+        let (flushed_tx, flushed_rx) = crossbeam::channel::bounded(1);
+        if cmd_tx.send(Cmd::Flush(flushed_tx)).is_err() {
+            return;
+        }
+
+        let flushed = if let Some(timeout) = self.flush_timeout {
+            flushed_rx.recv_timeout(timeout).is_ok()
+        } else {
+            flushed_rx.recv().is_ok()
+        };
+        if !flushed {
+            re_log::warn!("Timed out while flushing the gRPC sink");
+        }
+    }
+}
+
+impl std::fmt::Debug for GrpcSink {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("GrpcSink")
+            .field("flush_timeout", &self.flush_timeout)
+            .finish_non_exhaustive()
+    }
+}
+
+/// Runs until `cmd_rx` is closed and all the messages have been sent.
+///
+/// Reconnects with an exponential backoff whenever the connection breaks. Messages that were in
+/// flight at that point are lost.
+async fn stream_messages(
+    addr: std::net::SocketAddr,
+    auth_token: Option<String>,
+    mut cmd_rx: mpsc::UnboundedReceiver<Cmd>,
+) {
+    let mut metadata = tonic::metadata::MetadataMap::new();
+    if let Some(auth_token) = auth_token {
+        match format!("Bearer {auth_token}").parse() {
+            Ok(value) => {
+                metadata.insert("authorization", value);
+            }
+            Err(err) => {
+                re_log::error!("Invalid auth token: {err}");
+                return;
+            }
+        }
+    }
+
+    let endpoint = match tonic::transport::Endpoint::new(format!("http://{addr}")) {
+        Ok(endpoint) => endpoint,
+        Err(err) => {
+            re_log::error!("Invalid gRPC server address {addr}: {err}");
+            return;
+        }
+    };
+
+    let mut reconnect_delay = MIN_RECONNECT_DELAY;
+    loop {
+        let client = loop {
+            match endpoint.connect().await {
+                Ok(channel) => {
+                    // TODO(#8411): figure out the right size for this
+                    break MessageProxyClient::new(channel).max_encoding_message_size(usize::MAX);
+                }
+                Err(err) => {
+                    re_log::debug!("Failed to connect to gRPC server at {addr}: {err}");
+                    if cmd_rx.is_closed() {
+                        re_log::warn!(
+                            "gRPC sink dropped before it could connect to {addr}, dropping {} message(s)",
+                            cmd_rx.len()
+                        );
+                        return;
+                    }
+                    tokio::time::sleep(reconnect_delay).await;
+                    reconnect_delay = (reconnect_delay * 2).min(MAX_RECONNECT_DELAY);
+                }
+            }
+        };
+
+        re_log::debug!("Connected to gRPC server at {addr}");
+        let connected_at = tokio::time::Instant::now();
+
+        let status = match write_messages(client, metadata.clone(), &mut cmd_rx).await {
+            Ok(()) => {
+                re_log::debug!("Closed gRPC stream to {addr}");
+                return;
+            }
+            Err(status) => status,
+        };
+
+        if matches!(
+            status.code(),
+            tonic::Code::Unauthenticated
+                | tonic::Code::PermissionDenied
+                | tonic::Code::InvalidArgument
+                | tonic::Code::Unimplemented
+        ) {
+            // Trying again won't change anything.
+            re_log::error!(
+                "gRPC stream to {addr} was rejected, dropping all further log messages: {status}"
+            );
+            return;
+        }
+
+        re_log::error!("gRPC stream to {addr} failed, reconnecting: {status}");
+
+        // Only back off further if the connection keeps breaking right away.
+        if connected_at.elapsed() > MAX_RECONNECT_DELAY {
+            reconnect_delay = MIN_RECONNECT_DELAY;
+        } else {
+            tokio::time::sleep(reconnect_delay).await;
+            reconnect_delay = (reconnect_delay * 2).min(MAX_RECONNECT_DELAY);
+        }
+    }
+}
+
+/// Streams messages over a single `WriteMessages` call.
+///
+/// Returns `Ok` once `cmd_rx` is closed and the call completed, or the reason the call failed.
+async fn write_messages(
+    mut client: MessageProxyClient<tonic::transport::Channel>,
+    metadata: tonic::metadata::MetadataMap,
+    cmd_rx: &mut mpsc::UnboundedReceiver<Cmd>,
+) -> Result<(), tonic::Status> {
+    use tokio_stream::StreamExt as _;
+
+    let (msg_tx, msg_rx) = mpsc::channel(STREAM_CHANNEL_CAPACITY);
+
+    // Counts the messages that tonic pulled out of the stream, i.e. handed over to the transport.
+    let (num_written_tx, mut num_written_rx) = tokio::sync::watch::channel(0_u64);
+    let stream = ReceiverStream::new(msg_rx).map(move |msg| {
+        num_written_tx.send_modify(|num_written| *num_written += 1);
+        msg
+    });
+
+    let request = tonic::Request::from_parts(metadata, tonic::Extensions::default(), stream);
+    let call = tokio::spawn(async move { client.write_messages(request).await });
+
+    let mut num_sent = 0_u64;
+    while let Some(cmd) = cmd_rx.recv().await {
+        match cmd {
+            Cmd::LogMsg(msg) => {
+                let msg = match re_log_encoding::log_msg_to_proto(msg, Compression::LZ4) {
+                    Ok(msg) => msg,
+                    Err(err) => {
+                        re_log::error!("Failed to encode log message: {err}");
+                        continue;
+                    }
+                };
+                if msg_tx.send(msg).await.is_err() {
+                    break; // the call ended, we'll report why below
+                }
+                num_sent += 1;
+            }
+
+            Cmd::Flush(flushed_tx) => {
+                // Errors out if the call ended, since that drops the stream: nothing to wait for then.
+                num_written_rx
+                    .wait_for(|num_written| *num_written >= num_sent)
+                    .await
+                    .ok();
+                flushed_tx.send(()).ok();
+            }
+        }
+    }
+
+    // Ends the stream.
+    drop(msg_tx);
+
+    match call.await {
+        Ok(result) => result.map(|_response| ()),
+        Err(err) => Err(tonic::Status::internal(format!(
+            "gRPC call panicked: {err}"
+        ))),
+    }
+}
+
+// ---
+
+#[cfg(test)]
+mod tests {
+    use re_chunk::{Chunk, RowId};
+    use re_log_types::{
+        example_components::MyPoint, SetStoreInfo, StoreId, StoreInfo, StoreKind, StoreSource,
+        Time, Timeline,
+    };
+    use re_smart_channel::SmartMessagePayload;
+
+    use super::*;
+
+    #[test]
+    fn sink_to_server() {
+        // Find a free port: the server binds it again right away.
+        let port = std::net::TcpListener::bind("127.0.0.1:0")
+            .unwrap()
+            .local_addr()
+            .unwrap()
+            .port();
+        let rx = re_sdk_comms::serve_grpc(
+            "127.0.0.1",
+            port,
+            re_sdk_comms::ServerOptions {
+                quiet: true,
+                ..Default::default()
+            },
+        )
+        .unwrap();
+
+        let store_id = StoreId::random(StoreKind::Recording);
+        let mut messages = vec![LogMsg::SetStoreInfo(SetStoreInfo {
+            row_id: *RowId::new(),
+            info: StoreInfo {
+                application_id: "test".into(),
+                store_id: store_id.clone(),
+                cloned_from: None,
+                is_official_example: false,
+                started: Time::now(),
+                store_source: StoreSource::Unknown,
+                store_version: Some(re_build_info::CrateVersion::LOCAL),
+            },
+        })];
+        for frame in 0..10 {
+            let chunk = Chunk::builder("points".into())
+                .with_component_batches(
+                    RowId::new(),
+                    [(Timeline::new_sequence("frame"), frame)],
+                    [&[MyPoint::new(frame as f32, 0.0)] as _],
+                )
+                .build()
+                .unwrap();
+            messages.push(LogMsg::ArrowMsg(
+                store_id.clone(),
+                chunk.to_arrow_msg().unwrap(),
+            ));
+        }
+
+        let sink = GrpcSink::new_with_auth_token(
+            std::net::SocketAddr::from(([127, 0, 0, 1], port)),
+            Some(Duration::from_secs(10)),
+            None,
+        );
+        for msg in &messages {
+            sink.send(msg.clone());
+        }
+        sink.flush_blocking();
+        drop(sink);
+
+        let mut received = Vec::new();
+        while received.len() < messages.len() {
+            let msg = rx.recv_timeout(Duration::from_secs(10)).unwrap();
+            match msg.payload {
+                SmartMessagePayload::Msg(msg) => received.push(msg),
+                SmartMessagePayload::Flush { on_flush_done } => on_flush_done(),
+                SmartMessagePayload::Quit(err) => panic!("client quit early: {err:?}"),
+            }
+        }
+        assert_eq!(messages, received);
+    }
+}
@@ -23,6 +23,12 @@ mod log_sink;
 mod recording_stream;
 mod spawn;
 
+#[cfg(feature = "grpc")]
+mod grpc_sink;
+
+#[cfg(not(target_arch = "wasm32"))]
+mod replay;
+
 // -------------
 // Public items:
 
@@ -35,6 +41,12 @@ pub use self::recording_stream::{
 
 pub use re_sdk_comms::{default_flush_timeout, default_server_addr};
 
+#[cfg(feature = "grpc")]
+pub use self::grpc_sink::default_grpc_server_addr;
+
+#[cfg(not(target_arch = "wasm32"))]
+pub use self::replay::{replay_rrd, ReplayError, ReplayOptions};
+
 pub use re_log_types::{
     entity_path, ApplicationId, EntityPath, EntityPathPart, Instance, StoreId, StoreKind,
 };
@@ -55,6 +67,18 @@ impl crate::sink::LogSink for re_log_encoding::FileSink {
     }
 }
 
+#[cfg(not(target_arch = "wasm32"))]
+impl crate::sink::LogSink for re_log_encoding::RotatingFileSink {
+    fn send(&self, msg: re_log_types::LogMsg) {
+        Self::send(self, msg);
+    }
+
+    #[inline]
+    fn flush_blocking(&self) {
+        Self::flush_blocking(self);
+    }
+}
+
 // ---------------
 // Public modules:
 
@@ -67,11 +91,16 @@ pub mod sink {
         BinaryStreamSink, BinaryStreamSinkError, BinaryStreamStorage,
     };
     pub use crate::log_sink::{
-        BufferedSink, CallbackSink, LogSink, MemorySink, MemorySinkStorage, TcpSink,
+        BufferedSink, CallbackSink, LogSink, MemorySink, MemorySinkStorage, MultiSink, TcpSink,
     };
 
+    pub use re_sdk_comms::{SpoolOptions, SpoolOverflowPolicy};
+
     #[cfg(not(target_arch = "wasm32"))]
-    pub use re_log_encoding::{FileSink, FileSinkError};
+    pub use re_log_encoding::{FileSink, FileSinkError, RotatingFileSink, RotatingFileSinkOptions};
+
+    #[cfg(feature = "grpc")]
+    pub use crate::grpc_sink::GrpcSink;
 }
 
 /// Things directly related to logging.
@@ -106,6 +135,8 @@ pub use re_data_loader::{DataLoader, DataLoaderError, DataLoaderSettings, Loaded
 #[cfg(feature = "web_viewer")]
 pub mod web_viewer;
 
+pub mod clock_sync;
+
 /// Re-exports of other crates.
 pub mod external {
     pub use re_log;
@@ -119,6 +150,9 @@ pub mod external {
 
     #[cfg(feature = "data_loaders")]
     pub use re_data_loader;
+
+    #[cfg(feature = "grpc")]
+    pub use re_protos;
 }
 
 // -----
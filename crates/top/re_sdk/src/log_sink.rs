@@ -343,12 +343,54 @@ impl TcpSink {
     /// `flush_timeout` is the minimum time the [`TcpSink`] will wait during a flush
     /// before potentially dropping data. Note: Passing `None` here can cause a
     /// call to `flush` to block indefinitely if a connection cannot be established.
+    ///
+    /// The auth token to present to the server, if any, is read from the
+    /// [`re_sdk_comms::ENV_AUTH_TOKEN`] environment variable.
     #[inline]
     pub fn new(addr: std::net::SocketAddr, flush_timeout: Option<std::time::Duration>) -> Self {
         Self {
             client: re_sdk_comms::Client::new(addr, flush_timeout),
         }
     }
+
+    /// Connect to the given address in a background thread, presenting the given `auth_token`
+    /// to the server during the handshake.
+    ///
+    /// See [`Self::new`] for details.
+    #[inline]
+    pub fn new_with_auth_token(
+        addr: std::net::SocketAddr,
+        flush_timeout: Option<std::time::Duration>,
+        auth_token: Option<String>,
+    ) -> Self {
+        Self {
+            client: re_sdk_comms::Client::new_with_auth_token(addr, flush_timeout, auth_token),
+        }
+    }
+
+    /// Connect to the given address in a background thread, spooling messages to disk while
+    /// the server is unreachable.
+    ///
+    /// The spooled messages are replayed in order once the connection is back, see
+    /// [`re_sdk_comms::Client::new_with_spool`] for details.
+    ///
+    /// The auth token to present to the server, if any, is read from the
+    /// [`re_sdk_comms::ENV_AUTH_TOKEN`] environment variable.
+    #[inline]
+    pub fn new_with_spool(
+        addr: std::net::SocketAddr,
+        flush_timeout: Option<std::time::Duration>,
+        spool_options: re_sdk_comms::SpoolOptions,
+    ) -> Self {
+        Self {
+            client: re_sdk_comms::Client::new_with_spool(
+                addr,
+                flush_timeout,
+                re_sdk_comms::default_auth_token(),
+                spool_options,
+            ),
+        }
+    }
 }
 
 impl LogSink for TcpSink {
@@ -367,3 +409,103 @@ impl LogSink for TcpSink {
         self.client.drop_if_disconnected();
     }
 }
+
+// ----------------------------------------------------------------------------
+
+/// Fans log messages out to several sinks at once, e.g. to stream live to a viewer while also
+/// saving to an `.rrd` file.
+///
+/// Every message is sent to every sink, in order.
+/// Sinks are flushed independently of one another, so that a sink that is slow to flush (e.g. a
+/// [`TcpSink`] with a broken connection) does not hold back the others, and only delays the
+/// overall flush by its own flush timeout.
+pub struct MultiSink {
+    sinks: Vec<Box<dyn LogSink>>,
+}
+
+impl MultiSink {
+    /// Send all log messages to all of these sinks.
+    #[inline]
+    pub fn new(sinks: Vec<Box<dyn LogSink>>) -> Self {
+        Self { sinks }
+    }
+
+    /// Also send all log messages to this sink.
+    #[inline]
+    pub fn with(mut self, sink: impl LogSink) -> Self {
+        self.sinks.push(Box::new(sink));
+        self
+    }
+}
+
+impl Drop for MultiSink {
+    fn drop(&mut self) {
+        // Some sinks block on drop until their data has been sent: don't make the others wait.
+        #[cfg(not(target_arch = "wasm32"))]
+        std::thread::scope(|scope| {
+            for sink in self.sinks.drain(..) {
+                scope.spawn(move || drop(sink));
+            }
+        });
+    }
+}
+
+impl LogSink for MultiSink {
+    #[inline]
+    fn send(&self, msg: LogMsg) {
+        let Some((last, rest)) = self.sinks.split_last() else {
+            return;
+        };
+        for sink in rest {
+            sink.send(msg.clone());
+        }
+        last.send(msg);
+    }
+
+    #[inline]
+    fn send_all(&self, messages: Vec<LogMsg>) {
+        let Some((last, rest)) = self.sinks.split_last() else {
+            return;
+        };
+        for sink in rest {
+            sink.send_all(messages.clone());
+        }
+        last.send_all(messages);
+    }
+
+    fn flush_blocking(&self) {
+        // Flush all sinks concurrently, so they don't wait on each other.
+        #[cfg(not(target_arch = "wasm32"))]
+        if 1 < self.sinks.len() {
+            std::thread::scope(|scope| {
+                for sink in &self.sinks {
+                    let spawned = std::thread::Builder::new()
+                        .name("multi_sink_flush".to_owned())
+                        .spawn_scoped(scope, || sink.flush_blocking());
+                    if let Err(err) = spawned {
+                        re_log::warn_once!("Failed to spawn flush thread: {err}");
+                        sink.flush_blocking();
+                    }
+                }
+            });
+            return;
+        }
+
+        for sink in &self.sinks {
+            sink.flush_blocking();
+        }
+    }
+
+    #[inline]
+    fn drop_if_disconnected(&self) {
+        for sink in &self.sinks {
+            sink.drop_if_disconnected();
+        }
+    }
+}
+
+impl fmt::Debug for MultiSink {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "MultiSink {{ {} sinks }}", self.sinks.len())
+    }
+}
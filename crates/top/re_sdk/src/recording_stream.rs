@@ -116,6 +116,8 @@ pub struct RecordingStreamBuilder {
 
     batcher_config: Option<ChunkBatcherConfig>,
 
+    tcp_spool: Option<crate::sink::SpoolOptions>,
+
     is_official_example: bool,
 }
 
@@ -146,6 +148,7 @@ impl RecordingStreamBuilder {
             enabled: None,
 
             batcher_config: None,
+            tcp_spool: None,
             is_official_example,
         }
     }
@@ -215,6 +218,29 @@ impl RecordingStreamBuilder {
         self
     }
 
+    /// Spool messages to disk while the TCP connection is down, rather than blocking or dropping
+    /// them, and replay them in order once it is back.
+    ///
+    /// Only affects [`Self::connect_tcp`] and [`Self::connect_tcp_opts`].
+    /// See [`crate::sink::SpoolOptions`] for the size limit and overflow policy.
+    ///
+    /// ```no_run
+    /// # use re_sdk::{sink::{SpoolOptions, SpoolOverflowPolicy}, RecordingStreamBuilder};
+    /// let rec = RecordingStreamBuilder::new("rerun_example_app")
+    ///     .tcp_spool(SpoolOptions {
+    ///         max_bytes: 100 * 1024 * 1024,
+    ///         overflow_policy: SpoolOverflowPolicy::DropOldest,
+    ///         ..SpoolOptions::new("/tmp/rerun_spool.bin")
+    ///     })
+    ///     .connect_tcp()?;
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    #[inline]
+    pub fn tcp_spool(mut self, spool_options: crate::sink::SpoolOptions) -> Self {
+        self.tcp_spool = Some(spool_options);
+        self
+    }
+
     #[doc(hidden)]
     #[inline]
     pub fn store_source(mut self, store_source: StoreSource) -> Self {
@@ -370,6 +396,135 @@ impl RecordingStreamBuilder {
     /// # Ok::<(), Box<dyn std::error::Error>>(())
     /// ```
     pub fn connect_tcp_opts(
+        mut self,
+        addr: std::net::SocketAddr,
+        flush_timeout: Option<std::time::Duration>,
+    ) -> RecordingStreamResult<RecordingStream> {
+        let tcp_spool = self.tcp_spool.take();
+        let (enabled, store_info, batcher_config) = self.into_args();
+        if enabled {
+            let sink = Self::tcp_sink(addr, flush_timeout, tcp_spool);
+            RecordingStream::new(store_info, batcher_config, Box::new(sink))
+        } else {
+            re_log::debug!("Rerun disabled - call to connect() ignored");
+            Ok(RecordingStream::disabled())
+        }
+    }
+
+    /// Creates a new [`RecordingStream`] that is pre-configured to both stream the data through
+    /// to a remote Rerun instance and save it to an RRD file on disk.
+    ///
+    /// See also [`Self::connect_and_save_opts`] if you wish to configure the TCP connection.
+    ///
+    /// ## Example
+    ///
+    /// ```no_run
+    /// let rec = re_sdk::RecordingStreamBuilder::new("rerun_example_app")
+    ///     .connect_and_save("my_recording.rrd")?;
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn connect_and_save(
+        self,
+        path: impl Into<std::path::PathBuf>,
+    ) -> RecordingStreamResult<RecordingStream> {
+        self.connect_and_save_opts(
+            crate::default_server_addr(),
+            crate::default_flush_timeout(),
+            path,
+        )
+    }
+
+    /// Creates a new [`RecordingStream`] that is pre-configured to both stream the data through
+    /// to a remote Rerun instance and save it to an RRD file on disk.
+    ///
+    /// The data is fanned out using a [`MultiSink`][`crate::sink::MultiSink`]: the file keeps
+    /// being written to even if the TCP connection is broken.
+    ///
+    /// `flush_timeout` is the minimum time the [`TcpSink`][`crate::log_sink::TcpSink`] will
+    /// wait during a flush before potentially dropping data. Note: Passing `None` here can cause a
+    /// call to `flush` to block indefinitely if a connection cannot be established.
+    ///
+    /// ## Example
+    ///
+    /// ```no_run
+    /// let rec = re_sdk::RecordingStreamBuilder::new("rerun_example_app").connect_and_save_opts(
+    ///     re_sdk::default_server_addr(),
+    ///     re_sdk::default_flush_timeout(),
+    ///     "my_recording.rrd",
+    /// )?;
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn connect_and_save_opts(
+        mut self,
+        addr: std::net::SocketAddr,
+        flush_timeout: Option<std::time::Duration>,
+        path: impl Into<std::path::PathBuf>,
+    ) -> RecordingStreamResult<RecordingStream> {
+        let tcp_spool = self.tcp_spool.take();
+        let (enabled, store_info, batcher_config) = self.into_args();
+        if enabled {
+            let sink = crate::sink::MultiSink::new(vec![
+                Box::new(Self::tcp_sink(addr, flush_timeout, tcp_spool)),
+                Box::new(crate::sink::FileSink::new(path)?),
+            ]);
+            RecordingStream::new(store_info, batcher_config, Box::new(sink))
+        } else {
+            re_log::debug!("Rerun disabled - call to connect_and_save() ignored");
+            Ok(RecordingStream::disabled())
+        }
+    }
+
+    fn tcp_sink(
+        addr: std::net::SocketAddr,
+        flush_timeout: Option<std::time::Duration>,
+        tcp_spool: Option<crate::sink::SpoolOptions>,
+    ) -> crate::log_sink::TcpSink {
+        if let Some(spool_options) = tcp_spool {
+            crate::log_sink::TcpSink::new_with_spool(addr, flush_timeout, spool_options)
+        } else {
+            crate::log_sink::TcpSink::new(addr, flush_timeout)
+        }
+    }
+
+    /// Creates a new [`RecordingStream`] that is pre-configured to stream the data through to a
+    /// remote Rerun instance over gRPC.
+    ///
+    /// See also [`Self::connect_grpc_opts`] if you wish to configure the connection.
+    ///
+    /// ## Example
+    ///
+    /// ```no_run
+    /// let rec = re_sdk::RecordingStreamBuilder::new("rerun_example_app").connect_grpc()?;
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    #[cfg(feature = "grpc")]
+    pub fn connect_grpc(self) -> RecordingStreamResult<RecordingStream> {
+        self.connect_grpc_opts(
+            crate::default_grpc_server_addr(),
+            crate::default_flush_timeout(),
+        )
+    }
+
+    /// Creates a new [`RecordingStream`] that is pre-configured to stream the data through to a
+    /// remote Rerun instance over gRPC.
+    ///
+    /// `flush_timeout` is the minimum time the [`GrpcSink`][`crate::sink::GrpcSink`] will
+    /// wait during a flush before potentially dropping data. Note: Passing `None` here can cause a
+    /// call to `flush` to block indefinitely if a connection cannot be established.
+    ///
+    /// ## Example
+    ///
+    /// ```no_run
+    /// let rec = re_sdk::RecordingStreamBuilder::new("rerun_example_app").connect_grpc_opts(
+    ///     re_sdk::default_grpc_server_addr(),
+    ///     re_sdk::default_flush_timeout(),
+    /// )?;
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    #[cfg(feature = "grpc")]
+    pub fn connect_grpc_opts(
         self,
         addr: std::net::SocketAddr,
         flush_timeout: Option<std::time::Duration>,
@@ -379,10 +534,10 @@ impl RecordingStreamBuilder {
             RecordingStream::new(
                 store_info,
                 batcher_config,
-                Box::new(crate::log_sink::TcpSink::new(addr, flush_timeout)),
+                Box::new(crate::sink::GrpcSink::new(addr, flush_timeout)),
             )
         } else {
-            re_log::debug!("Rerun disabled - call to connect() ignored");
+            re_log::debug!("Rerun disabled - call to connect_grpc() ignored");
             Ok(RecordingStream::disabled())
         }
     }
@@ -419,6 +574,47 @@ impl RecordingStreamBuilder {
         }
     }
 
+    /// Creates a new [`RecordingStream`] that is pre-configured to stream the data through to a
+    /// series of RRD files on disk, starting a new one whenever the current one gets too big or
+    /// too old.
+    ///
+    /// Every file is self-contained, and old files can be deleted automatically: see
+    /// [`crate::sink::RotatingFileSink`] and [`crate::sink::RotatingFileSinkOptions`].
+    ///
+    /// ## Example
+    ///
+    /// ```no_run
+    /// # use re_sdk::sink::RotatingFileSinkOptions;
+    /// let rec = re_sdk::RecordingStreamBuilder::new("rerun_example_app").save_rotating(
+    ///     "recordings/my_recording",
+    ///     RotatingFileSinkOptions {
+    ///         max_segment_duration: Some(std::time::Duration::from_secs(3600)),
+    ///         max_total_bytes: Some(100 * 1024 * 1024 * 1024),
+    ///         ..Default::default()
+    ///     },
+    /// )?;
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn save_rotating(
+        self,
+        path_prefix: impl Into<std::path::PathBuf>,
+        options: crate::sink::RotatingFileSinkOptions,
+    ) -> RecordingStreamResult<RecordingStream> {
+        let (enabled, store_info, batcher_config) = self.into_args();
+
+        if enabled {
+            RecordingStream::new(
+                store_info,
+                batcher_config,
+                Box::new(crate::sink::RotatingFileSink::new(path_prefix, options)?),
+            )
+        } else {
+            re_log::debug!("Rerun disabled - call to save_rotating() ignored");
+            Ok(RecordingStream::disabled())
+        }
+    }
+
     /// Creates a new [`RecordingStream`] that is pre-configured to stream the data through to stdout.
     ///
     /// If there isn't any listener at the other end of the pipe, the [`RecordingStream`] will
@@ -633,6 +829,7 @@ impl RecordingStreamBuilder {
             default_enabled: _,
             enabled: _,
             batcher_config,
+            tcp_spool: _,
             is_official_example,
         } = self;
 
@@ -1198,6 +1395,36 @@ impl RecordingStream {
         Ok(())
     }
 
+    /// Logs a sync marker called `name`, timestamped using the [`RecordingStream`]'s internal
+    /// clock.
+    ///
+    /// When several processes log to the same recording, each with its own clock, have all of
+    /// them log the same marker when a shared event happens: `rerun rrd merge --sync-marker <name>`
+    /// will then shift the timelines of each of their recordings so that the markers line up.
+    ///
+    /// See [`crate::clock_sync`] for more information.
+    pub fn log_sync_marker(&self, name: &str) {
+        if !self.is_enabled() {
+            return; // silently drop the message
+        }
+
+        let row_id = RowId::new();
+        let components = std::iter::once((
+            re_types_core::ComponentDescriptor::new(crate::clock_sync::SYNC_MARKER_COMPONENT),
+            Box::new(arrow2::array::Utf8Array::<i32>::from_slice([name]))
+                as Box<dyn arrow2::array::Array>,
+        ))
+        .collect();
+
+        let row = PendingRow {
+            row_id,
+            // NOTE: The `RecordingStream` will inject the current time using its internal clock.
+            timepoint: TimePoint::default(),
+            components,
+        };
+        self.record_row(crate::clock_sync::sync_marker_entity_path(), row, true);
+    }
+
     /// Logs the file at the given `path` using all [`re_data_loader::DataLoader`]s available.
     ///
     /// A single `path` might be handled by more than one loader.
@@ -1658,6 +1885,16 @@ impl RecordingStream {
         }
     }
 
+    /// Swaps the underlying sink for a [`crate::sink::MultiSink`] that sends all future data to
+    /// all of the given sinks.
+    ///
+    /// This is a convenience wrapper for [`Self::set_sink`] that upholds the same guarantees in
+    /// terms of data durability and ordering.
+    /// See [`Self::set_sink`] for more information.
+    pub fn set_sinks(&self, sinks: Vec<Box<dyn LogSink>>) {
+        self.set_sink(Box::new(crate::sink::MultiSink::new(sinks)));
+    }
+
     /// Initiates a flush of the pipeline and returns immediately.
     ///
     /// This does **not** wait for the flush to propagate (see [`Self::flush_blocking`]).
@@ -1761,6 +1998,48 @@ impl RecordingStream {
         self.set_sink(Box::new(sink));
     }
 
+    /// Swaps the underlying sink for a [`crate::sink::GrpcSink`] sink pre-configured to use
+    /// the default gRPC address.
+    ///
+    /// See also [`Self::connect_grpc_opts`] if you wish to configure the connection.
+    ///
+    /// This is a convenience wrapper for [`Self::set_sink`] that upholds the same guarantees in
+    /// terms of data durability and ordering.
+    /// See [`Self::set_sink`] for more information.
+    #[cfg(feature = "grpc")]
+    pub fn connect_grpc(&self) {
+        self.connect_grpc_opts(
+            crate::default_grpc_server_addr(),
+            crate::default_flush_timeout(),
+        );
+    }
+
+    /// Swaps the underlying sink for a [`crate::sink::GrpcSink`] sink pre-configured to use
+    /// the specified address.
+    ///
+    /// `flush_timeout` is the minimum time the [`GrpcSink`][`crate::sink::GrpcSink`] will
+    /// wait during a flush before potentially dropping data. Note: Passing `None` here can cause a
+    /// call to `flush` to block indefinitely if a connection cannot be established.
+    ///
+    /// This is a convenience wrapper for [`Self::set_sink`] that upholds the same guarantees in
+    /// terms of data durability and ordering.
+    /// See [`Self::set_sink`] for more information.
+    #[cfg(feature = "grpc")]
+    pub fn connect_grpc_opts(
+        &self,
+        addr: std::net::SocketAddr,
+        flush_timeout: Option<std::time::Duration>,
+    ) {
+        if forced_sink_path().is_some() {
+            re_log::debug!("Ignored setting new GrpcSink since {ENV_FORCE_SAVE} is set");
+            return;
+        }
+
+        let sink = crate::sink::GrpcSink::new(addr, flush_timeout);
+
+        self.set_sink(Box::new(sink));
+    }
+
     /// Spawns a new Rerun Viewer process from an executable available in PATH, then swaps the
     /// underlying sink for a [`crate::log_sink::TcpSink`] sink pre-configured to send data to that
     /// new process.
@@ -2517,6 +2796,66 @@ mod tests {
         assert!(msgs.pop().is_none());
     }
 
+    #[test]
+    fn multi_sink() {
+        let rec = RecordingStreamBuilder::new("rerun_example_multi_sink")
+            .enabled(true)
+            .batcher_config(ChunkBatcherConfig::ALWAYS)
+            .buffered()
+            .unwrap();
+
+        let sink1 = crate::sink::MemorySink::new(rec.clone());
+        let storage1 = sink1.buffer();
+        let sink2 = crate::sink::MemorySink::new(rec.clone());
+        let storage2 = sink2.buffer();
+        rec.set_sinks(vec![Box::new(sink1), Box::new(sink2)]);
+
+        let rows = example_rows(false);
+        for row in rows.clone() {
+            rec.record_row("a".into(), row, false);
+        }
+
+        let msgs1 = storage1.take();
+        let msgs2 = storage2.take();
+
+        // Both sinks got everything, in the same order.
+        assert!(msgs1
+            .iter()
+            .any(|msg| matches!(msg, LogMsg::ArrowMsg(_, _))));
+        similar_asserts::assert_eq!(msgs1, msgs2);
+    }
+
+    #[test]
+    fn sync_marker() {
+        let (rec, storage) = RecordingStreamBuilder::new("rerun_example_sync_marker")
+            .enabled(true)
+            .batcher_config(ChunkBatcherConfig::ALWAYS)
+            .memory()
+            .unwrap();
+
+        rec.set_time_sequence("frame", 42);
+        rec.log_sync_marker("start");
+
+        let markers = storage
+            .take()
+            .iter()
+            .filter_map(|msg| match msg {
+                LogMsg::ArrowMsg(_, arrow_msg) => Some(Chunk::from_arrow_msg(arrow_msg).unwrap()),
+                _ => None,
+            })
+            .flat_map(|chunk| crate::clock_sync::sync_markers(&chunk, &"frame".into()))
+            .collect::<Vec<_>>();
+
+        similar_asserts::assert_eq!(
+            markers,
+            vec![(
+                "start".to_owned(),
+                Timeline::new_sequence("frame"),
+                TimeInt::new_temporal(42)
+            )]
+        );
+    }
+
     #[test]
     fn test_set_thread_local() {
         // Regression-test for https://github.com/rerun-io/rerun/issues/2889
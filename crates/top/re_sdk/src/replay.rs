@@ -0,0 +1,426 @@
+use std::{
+    path::{Path, PathBuf},
+    time::{Duration, Instant},
+};
+
+use re_chunk::{Chunk, ChunkId, RowId};
+use re_log_encoding::{
+    decoder::{DecodeError, Decoder},
+    VersionPolicy,
+};
+use re_log_types::{ArrowMsg, LogMsg, TimeType, TimelineName};
+
+use crate::sink::LogSink;
+
+/// Errors that can occur when replaying an .rrd file with [`replay_rrd`].
+#[derive(thiserror::Error, Debug)]
+pub enum ReplayError {
+    /// The file could not be opened.
+    #[error("Failed to open {path:?}: {err}")]
+    Open {
+        /// The file being replayed.
+        path: PathBuf,
+
+        /// Inner error explaining why the file could not be opened.
+        err: std::io::Error,
+    },
+
+    /// The contents of the file could not be decoded.
+    #[error(transparent)]
+    Decode(#[from] DecodeError),
+
+    /// The chunks could not be given new ids when looping.
+    #[error(transparent)]
+    Chunk(#[from] re_chunk::ChunkError),
+}
+
+/// Options for [`replay_rrd`].
+#[derive(Clone, Debug, PartialEq)]
+pub struct ReplayOptions {
+    /// The timeline whose time deltas are reproduced, e.g. `log_time`.
+    ///
+    /// Data that isn't on this timeline (e.g. static data) is sent as soon as it is read.
+    pub timeline: TimelineName,
+
+    /// How much faster than the original pace to replay, e.g. `2.0` for twice as fast.
+    pub speed: f64,
+
+    /// How many ticks of a sequence timeline correspond to one second of the original pace.
+    ///
+    /// Ignored for temporal timelines, whose times are in nanoseconds.
+    pub ticks_per_second: f64,
+
+    /// Where to start the replay, relative to the first time found on [`Self::timeline`].
+    ///
+    /// In the native unit of the timeline: nanoseconds for temporal timelines, ticks for sequences.
+    /// Data before that point is sent as fast as possible, so that the viewer starts from the same
+    /// state as if the whole recording had been replayed.
+    pub start_offset: i64,
+
+    /// Start over once the end of the file is reached, until the process is stopped.
+    ///
+    /// Every pass after the first one sends the chunks with new ids, so that the viewer ingests
+    /// them again rather than ignoring them as duplicates. Their times on [`Self::timeline`] are
+    /// shifted by the time span of the file on every pass, so that each pass shows up after the
+    /// previous one. Other timelines are left untouched.
+    pub looping: bool,
+}
+
+impl Default for ReplayOptions {
+    fn default() -> Self {
+        Self {
+            timeline: "log_time".into(),
+            speed: 1.0,
+            ticks_per_second: 1.0,
+            start_offset: 0,
+            looping: false,
+        }
+    }
+}
+
+/// Reads the .rrd file at `path` and re-sends its messages to `sink` at their original pace.
+///
+/// The pace is reconstructed from the latest time of each chunk on [`ReplayOptions::timeline`],
+/// which is roughly when it was sent when it was first logged. Recordings that have been
+/// compacted (e.g. with `rerun rrd compact`) will replay in coarser bursts.
+///
+/// Messages are always sent in file order, so the pace is only reproduced if the chunks are sorted
+/// by time on [`ReplayOptions::timeline`], as they are in a recording that was saved while being
+/// logged. A chunk that is older than one sent before it is sent right away.
+///
+/// Blocks until the whole file has been replayed and flushed, or forever when
+/// [`ReplayOptions::looping`] is set.
+///
+/// ## Example
+///
+/// ```no_run
+/// let sink = re_sdk::sink::TcpSink::new(
+///     re_sdk::default_server_addr(),
+///     re_sdk::default_flush_timeout(),
+/// );
+/// re_sdk::replay_rrd("recording.rrd", &sink, &re_sdk::ReplayOptions::default())?;
+/// # Ok::<(), Box<dyn std::error::Error>>(())
+/// ```
+pub fn replay_rrd(
+    path: impl AsRef<Path>,
+    sink: &dyn LogSink,
+    options: &ReplayOptions,
+) -> Result<(), ReplayError> {
+    let path = path.as_ref();
+
+    let mut time_offset = 0_i64;
+    for pass in 0.. {
+        let stats = replay_pass(path, sink, options, pass, time_offset)?;
+        sink.flush_blocking();
+
+        if !options.looping {
+            break;
+        }
+        if stats.num_sent == 0 {
+            re_log::warn!("Nothing to replay in {path:?}: not looping");
+            break;
+        }
+        if let Some(time_span) = stats.time_span() {
+            time_offset = time_offset.saturating_add(time_span);
+        }
+    }
+
+    Ok(())
+}
+
+/// What a single pass over the file sent.
+#[derive(Debug, Default)]
+struct PassStats {
+    num_sent: usize,
+
+    /// The original times covered on [`ReplayOptions::timeline`], if any.
+    ///
+    /// Only tracked when looping.
+    time_range: Option<(i64, i64)>,
+}
+
+impl PassStats {
+    /// How much to shift the times of the next pass by, so that it starts right after this one.
+    fn time_span(&self) -> Option<i64> {
+        let (min, max) = self.time_range?;
+        Some(max.saturating_sub(min).saturating_add(1))
+    }
+}
+
+/// Replays the whole file once, shifting the times on [`ReplayOptions::timeline`] by `time_offset`.
+fn replay_pass(
+    path: &Path,
+    sink: &dyn LogSink,
+    options: &ReplayOptions,
+    pass: usize,
+    time_offset: i64,
+) -> Result<PassStats, ReplayError> {
+    re_log::debug!("Replaying {path:?} (pass #{pass})");
+
+    let file = std::fs::File::open(path).map_err(|err| ReplayError::Open {
+        path: path.to_owned(),
+        err,
+    })?;
+    let decoder = Decoder::new(VersionPolicy::Warn, std::io::BufReader::new(file))?;
+
+    let mut clock = ReplayClock::new(options);
+    let mut stats = PassStats::default();
+
+    for msg in decoder {
+        let mut msg = msg?;
+
+        if let LogMsg::ArrowMsg(store_id, arrow_msg) = &msg {
+            clock.wait_for(arrow_msg);
+
+            if options.looping {
+                let mut chunk = Chunk::from_arrow_msg(arrow_msg)?;
+
+                let time_column = chunk
+                    .timelines()
+                    .values()
+                    .find(|time_column| *time_column.timeline().name() == options.timeline)
+                    .map(|time_column| (*time_column.timeline(), time_column.time_range()));
+
+                if let Some((timeline, time_range)) = time_column {
+                    let (min, max) = (time_range.min().as_i64(), time_range.max().as_i64());
+                    stats.time_range = Some(
+                        stats
+                            .time_range
+                            .map_or((min, max), |(a, b)| (a.min(min), b.max(max))),
+                    );
+
+                    chunk = chunk.time_shifted(&timeline, time_offset);
+                }
+
+                if 0 < pass {
+                    let chunk = chunk.clone_as(ChunkId::new(), RowId::new());
+                    msg = LogMsg::ArrowMsg(store_id.clone(), chunk.to_arrow_msg()?);
+                }
+            }
+        }
+
+        sink.send(msg);
+        stats.num_sent += 1;
+    }
+
+    Ok(stats)
+}
+
+/// Maps times on the replayed timeline to wall-clock instants.
+struct ReplayClock<'a> {
+    options: &'a ReplayOptions,
+
+    /// The time on the timeline that corresponds to `start`, once known.
+    start_time: Option<i64>,
+    start: Instant,
+}
+
+impl<'a> ReplayClock<'a> {
+    fn new(options: &'a ReplayOptions) -> Self {
+        Self {
+            options,
+            start_time: None,
+            start: Instant::now(),
+        }
+    }
+
+    /// Sleeps until it's time to send this chunk.
+    ///
+    /// Returns immediately for chunks that are not on the timeline, or that are late.
+    fn wait_for(&mut self, arrow_msg: &ArrowMsg) {
+        let Some((timeline, time)) = arrow_msg
+            .timepoint_max
+            .iter()
+            .find(|(timeline, _)| *timeline.name() == self.options.timeline)
+        else {
+            return;
+        };
+        let time = time.as_i64();
+
+        let start_time = *self.start_time.get_or_insert_with(|| {
+            self.start = Instant::now();
+            time.saturating_add(self.options.start_offset)
+        });
+
+        let Some(delta) = time.checked_sub(start_time).filter(|delta| 0 < *delta) else {
+            return;
+        };
+
+        let secs = match timeline.typ() {
+            TimeType::Time => delta as f64 * 1e-9,
+            TimeType::Sequence => delta as f64 / self.options.ticks_per_second,
+        } / self.options.speed;
+
+        if let Ok(offset) = Duration::try_from_secs_f64(secs) {
+            let deadline = self.start + offset;
+            let now = Instant::now();
+            if now < deadline {
+                std::thread::sleep(deadline - now);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use parking_lot::Mutex;
+
+    use re_log_types::{example_components::MyPoint, StoreId, StoreKind, TimePoint, Timeline};
+
+    use super::*;
+
+    fn points(store_id: &StoreId, timepoint: impl Into<TimePoint>) -> LogMsg {
+        let chunk = Chunk::builder("points".into())
+            .with_component_batches(RowId::new(), timepoint, [&[MyPoint::new(1.0, 2.0)] as _])
+            .build()
+            .unwrap();
+        LogMsg::ArrowMsg(store_id.clone(), chunk.to_arrow_msg().unwrap())
+    }
+
+    /// A static chunk, followed by one chunk per frame.
+    fn write_rrd(dir: &tempfile::TempDir, frames: &[i64]) -> PathBuf {
+        let store_id = StoreId::random(StoreKind::Recording);
+        let msgs = std::iter::once(points(&store_id, TimePoint::default())).chain(
+            frames
+                .iter()
+                .map(|&frame| points(&store_id, [(Timeline::new_sequence("frame"), frame)])),
+        );
+
+        let path = dir.path().join("recording.rrd");
+        let bytes = re_log_encoding::encoder::encode_as_bytes_local(msgs.map(Ok)).unwrap();
+        std::fs::write(&path, bytes).unwrap();
+        path
+    }
+
+    /// Records when each message was sent.
+    fn recording_sink() -> (
+        crate::sink::CallbackSink,
+        Arc<Mutex<Vec<(Instant, LogMsg)>>>,
+    ) {
+        let sent = Arc::new(Mutex::new(Vec::new()));
+        let sent_copy = sent.clone();
+        let sink = crate::sink::CallbackSink::new(move |msgs| {
+            let now = Instant::now();
+            sent_copy
+                .lock()
+                .extend(msgs.iter().map(|msg| (now, msg.clone())));
+        });
+        (sink, sent)
+    }
+
+    fn chunks(sent: &[(Instant, LogMsg)]) -> Vec<Chunk> {
+        sent.iter()
+            .filter_map(|(_, msg)| match msg {
+                LogMsg::ArrowMsg(_, arrow_msg) => Some(Chunk::from_arrow_msg(arrow_msg).unwrap()),
+                _ => None,
+            })
+            .collect()
+    }
+
+    fn frames(chunk: &Chunk) -> Vec<i64> {
+        chunk
+            .timelines()
+            .get(&Timeline::new_sequence("frame"))
+            .map_or_else(Vec::new, |time_column| time_column.times_raw().to_vec())
+    }
+
+    /// How long it took to replay 200 frames at 1000 frames per second.
+    fn replay_duration(speed: f64) -> Duration {
+        let dir = tempfile::tempdir().unwrap();
+        let path = write_rrd(&dir, &[0, 100, 200]);
+        let (sink, sent) = recording_sink();
+
+        let options = ReplayOptions {
+            timeline: "frame".into(),
+            speed,
+            ticks_per_second: 1000.0,
+            ..Default::default()
+        };
+        replay_rrd(&path, &sink, &options).unwrap();
+
+        let sent = sent.lock();
+        assert_eq!(sent.len(), 4);
+        sent[3].0 - sent[0].0
+    }
+
+    #[test]
+    fn pacing() {
+        // Sleeping may take longer than asked for, but never less.
+        let duration = replay_duration(1.0);
+        assert!(Duration::from_millis(190) <= duration, "{duration:?}");
+    }
+
+    #[test]
+    fn speed() {
+        let duration = replay_duration(4.0);
+        assert!(Duration::from_millis(45) <= duration, "{duration:?}");
+        assert!(duration < Duration::from_millis(190), "{duration:?}");
+    }
+
+    #[test]
+    fn start_offset() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = write_rrd(&dir, &[0, 100, 200]);
+        let (sink, sent) = recording_sink();
+
+        let options = ReplayOptions {
+            timeline: "frame".into(),
+            ticks_per_second: 1000.0,
+            start_offset: 200,
+            ..Default::default()
+        };
+        replay_rrd(&path, &sink, &options).unwrap();
+
+        // Everything up to the start is sent right away.
+        let sent = sent.lock();
+        assert!(sent[3].0 - sent[0].0 < Duration::from_millis(190));
+    }
+
+    #[test]
+    fn looping() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = write_rrd(&dir, &[0, 10, 20]);
+        let (sink, sent) = recording_sink();
+
+        let options = ReplayOptions {
+            timeline: "frame".into(),
+            speed: 1e9,
+            looping: true,
+            ..Default::default()
+        };
+
+        let stats = replay_pass(&path, &sink, &options, 0, 0).unwrap();
+        assert_eq!(stats.num_sent, 4);
+        assert_eq!(stats.time_range, Some((0, 20)));
+        let time_span = stats.time_span().unwrap();
+        assert_eq!(time_span, 21);
+
+        let stats = replay_pass(&path, &sink, &options, 1, time_span).unwrap();
+        assert_eq!(stats.num_sent, 4);
+        // The original times, not the shifted ones.
+        assert_eq!(stats.time_range, Some((0, 20)));
+
+        let chunks = chunks(&sent.lock());
+        let (first_pass, second_pass) = chunks.split_at(4);
+
+        assert_eq!(
+            first_pass.iter().map(frames).collect::<Vec<_>>(),
+            vec![vec![], vec![0], vec![10], vec![20]]
+        );
+        assert_eq!(
+            second_pass.iter().map(frames).collect::<Vec<_>>(),
+            vec![vec![], vec![21], vec![31], vec![41]]
+        );
+
+        // Every chunk of the second pass is new to the viewer, including the static one.
+        for (first, second) in first_pass.iter().zip(second_pass) {
+            assert_ne!(first.id(), second.id());
+            assert_ne!(
+                first.row_ids().collect::<Vec<_>>(),
+                second.row_ids().collect::<Vec<_>>()
+            );
+        }
+    }
+}
@@ -40,6 +40,7 @@ impl WebViewerSink {
         web_port: WebViewerServerPort,
         ws_port: RerunServerPort,
         server_memory_limit: re_memory::MemoryLimit,
+        auth_token: Option<String>,
     ) -> Result<Self, WebViewerSinkError> {
         // TODO(cmc): the sources here probably don't make much sense…
         let (rerun_tx, rerun_rx) = re_smart_channel::smart_channel(
@@ -52,6 +53,8 @@ impl WebViewerSink {
             bind_ip,
             ws_port,
             server_memory_limit,
+            None,
+            auth_token,
         )?;
         let webviewer_server = WebViewerServer::new(bind_ip, web_port)?;
 
@@ -59,7 +62,10 @@ impl WebViewerSink {
         let ws_server_url = rerun_server.server_url();
         let viewer_url = format!("{http_web_viewer_url}?url={ws_server_url}");
 
-        re_log::info!("Hosting a web-viewer at {viewer_url}");
+        re_log::info!(
+            "Hosting a web-viewer at {}",
+            re_ws_comms::redact_auth_token(&viewer_url)
+        );
         if open_browser {
             webbrowser::open(&viewer_url).ok();
         }
@@ -200,7 +206,10 @@ impl WebViewerConfig {
             append_argument(format!("video_decoder={video_decoder}"));
         }
 
-        re_log::info!("Hosting a web-viewer at {viewer_url}");
+        re_log::info!(
+            "Hosting a web-viewer at {}",
+            re_ws_comms::redact_auth_token(&viewer_url)
+        );
         if open_browser {
             webbrowser::open(&viewer_url).ok();
         }
@@ -221,6 +230,9 @@ impl WebViewerConfig {
 /// NOTE: you can not connect one `Session` to another.
 ///
 /// This function returns immediately.
+///
+/// Viewers must present the auth token found in the [`re_sdk_comms::ENV_AUTH_TOKEN`] environment
+/// variable, if any, see [`new_sink_with_auth_token`].
 #[must_use = "the sink must be kept around to keep the servers running"]
 pub fn new_sink(
     open_browser: bool,
@@ -228,6 +240,28 @@ pub fn new_sink(
     web_port: WebViewerServerPort,
     ws_port: RerunServerPort,
     server_memory_limit: re_memory::MemoryLimit,
+) -> Result<Box<dyn crate::sink::LogSink>, WebViewerSinkError> {
+    new_sink_with_auth_token(
+        open_browser,
+        bind_ip,
+        web_port,
+        ws_port,
+        server_memory_limit,
+        re_sdk_comms::default_auth_token(),
+    )
+}
+
+/// Same as [`new_sink`], but viewers must present the given `auth_token` instead.
+///
+/// The url of the opened web viewer includes it.
+#[must_use = "the sink must be kept around to keep the servers running"]
+pub fn new_sink_with_auth_token(
+    open_browser: bool,
+    bind_ip: &str,
+    web_port: WebViewerServerPort,
+    ws_port: RerunServerPort,
+    server_memory_limit: re_memory::MemoryLimit,
+    auth_token: Option<String>,
 ) -> Result<Box<dyn crate::sink::LogSink>, WebViewerSinkError> {
     Ok(Box::new(WebViewerSink::new(
         open_browser,
@@ -235,5 +269,6 @@ pub fn new_sink(
         web_port,
         ws_port,
         server_memory_limit,
+        auth_token,
     )?))
 }
@@ -18,6 +18,9 @@ use re_ws_comms::RerunServerPort;
 #[cfg(feature = "analytics")]
 use crate::commands::AnalyticsCommands;
 
+#[cfg(feature = "grpc_server")]
+use crate::commands::ServeGrpcCommand;
+
 // ---
 
 const LONG_ABOUT: &str = r#"
@@ -30,10 +33,14 @@ The Rerun command-line interface:
 // Place the important help _last_, to make it most visible in the terminal.
 const ENVIRONMENT_VARIABLES_AND_EXAMPLES: &str = r#"
 Environment variables:
+    RERUN_AUTH_TOKEN          Shared secret required from clients of the TCP, gRPC and WebSocket servers,
+                              and presented by the logging SDKs. See `--auth-token`.
     RERUN_CHUNK_MAX_BYTES     Maximum chunk size threshold for the compactor.
     RERUN_CHUNK_MAX_ROWS      Maximum chunk row count threshold for the compactor (sorted chunks).
     RERUN_CHUNK_MAX_ROWS_IF_UNSORTED
                               Maximum chunk row count threshold for the compactor (unsorted chunks).
+    RERUN_CHUNK_SPILL_DIR     Directory where the viewer writes the data it evicts from memory, instead of
+                              dropping it. Evicted data is read back from disk when needed.
     RERUN_SHADER_PATH         The search path for shader/shader-imports. Only available in developer builds.
     RERUN_TRACK_ALLOCATIONS   Track memory allocations to diagnose memory leaks in the viewer.
                               WARNING: slows down the viewer by a lot!
@@ -65,6 +72,9 @@ Examples:
     Connect to a Rerun Server:
         rerun ws://localhost:9877
 
+    Connect to a Rerun Server that requires an auth token:
+        rerun ws://localhost:9877?token=my-secret
+
     Listen for incoming TCP connections from the logging SDK and stream the results to disk:
         rerun --save new_recording.rrd
 "#;
@@ -83,6 +93,17 @@ struct Args {
     #[command(subcommand)]
     command: Option<Command>,
 
+    /// Require this shared secret from everything connecting to our servers.
+    ///
+    /// SDKs present it during the TCP or gRPC handshake: they read it from the `RERUN_AUTH_TOKEN`
+    /// environment variable. Viewers connecting over WebSocket present it in the url,
+    /// e.g. `ws://localhost:9877?token=<TOKEN>`. Unauthenticated connections are rejected and logged.
+    ///
+    /// Defaults to the value of `RERUN_AUTH_TOKEN`, if set.
+    #[cfg(feature = "server")]
+    #[clap(long)]
+    auth_token: Option<String>,
+
     /// What bind address IP to use.
     #[clap(long, default_value = "0.0.0.0")]
     bind: String,
@@ -106,16 +127,48 @@ Example: `16GB` or `50%` (of system total)."
     )]
     memory_limit: String,
 
+    #[clap(
+        long,
+        long_help = r"Only keep the most recent data on the timeline specified by `--time-limit-timeline`.
+Anything older than this, relative to the latest time on that timeline, is dropped, even if `--memory-limit` hasn't been reached.
+The latest value of each component is always kept.
+Example: `30s` or `5min` for temporal timelines, `1000` for sequence timelines."
+    )]
+    time_limit: Option<String>,
+
+    #[clap(
+        long,
+        default_value = "log_time",
+        long_help = r"The timeline that `--time-limit` applies to."
+    )]
+    time_limit_timeline: String,
+
     #[clap(
         long,
         default_value = "25%",
         long_help = r"An upper limit on how much memory the WebSocket server (`--serve-web`) should use.
-The server buffers log messages for the benefit of late-arriving viewers.
+The server keeps a compacted copy of the data for the benefit of late-arriving viewers.
 When this limit is reached, Rerun will drop the oldest data.
 Example: `16GB` or `50%` (of system total)."
     )]
     server_memory_limit: String,
 
+    #[clap(
+        long,
+        long_help = r"Only send the most recent data on the timeline specified by `--server-time-window-timeline` to late-arriving viewers of the WebSocket server (`--serve-web`).
+Anything older than this, relative to the latest time on that timeline, is dropped from the server.
+Static data and the latest value of each component are always sent.
+Example: `30s` or `5min` for temporal timelines, `1000` for sequence timelines."
+    )]
+    server_time_window: Option<String>,
+
+    #[clap(
+        long,
+        default_value = "log_time",
+        long_help = r"The timeline that `--server-time-window` applies to."
+    )]
+    server_time_window_timeline: String,
+
     #[clap(
         long,
         default_value_t = true,
@@ -132,6 +185,14 @@ When persisted, the state will be stored at the following locations:
     #[clap(long, default_value_t = re_sdk_comms::DEFAULT_SERVER_PORT)]
     port: u16,
 
+    /// What port do we listen to for SDKs streaming log messages over gRPC.
+    ///
+    /// The gRPC server only starts if this is set, and runs alongside the TCP one.
+    /// SDKs connect to it with `connect_grpc()`, which defaults to port 9878.
+    #[cfg(feature = "grpc_sdk_comms")]
+    #[clap(long)]
+    grpc_port: Option<u16>,
+
     /// Start with the puffin profiler running.
     #[clap(long)]
     profile: bool,
@@ -188,6 +249,7 @@ When persisted, the state will be stored at the following locations:
 - A path to a Rerun .rrd recording
 - A path to a Rerun .rbl blueprint
 - An HTTP(S) URL to an .rrd or .rbl file to load
+- An HTTP(S) or file:// URL to any other file that Rerun can load
 - A path to an image or mesh, or any other file that Rerun can load (see https://www.rerun.io/docs/reference/data-loaders/overview)
 
 If no arguments are given, a server will be hosted which a Rerun SDK can connect to.")]
@@ -277,6 +339,14 @@ If no arguments are given, a server will be hosted which a Rerun SDK can connect
 }
 
 impl Args {
+    /// The shared secret required from clients of our servers, if any.
+    #[cfg(feature = "server")]
+    fn auth_token(&self) -> Option<String> {
+        self.auth_token
+            .clone()
+            .or_else(re_sdk_comms::default_auth_token)
+    }
+
     fn generate_markdown_manual() -> String {
         let mut out = String::new();
 
@@ -435,6 +505,7 @@ impl Args {
             // > - A path to a Rerun .rrd recording
             // > - A path to a Rerun .rbl blueprint
             // > - An HTTP(S) URL to an .rrd or .rbl file to load
+            // > - An HTTP(S) or file:// URL to any other file that Rerun can load
             // > - A path to an image or mesh, or any other file that Rerun can load (see https://www.rerun.io/docs/reference/data-loaders/overview)
             // >
             // > If no arguments are given, a server will be hosted which a Rerun SDK can connect to.
@@ -515,6 +586,13 @@ enum Command {
     #[cfg(feature = "native_viewer")]
     Reset,
 
+    /// Serve a directory of .rrd files over gRPC, using the Rerun Data Platform protocol.
+    ///
+    /// Example: `rerun serve-grpc ./recordings`, then open `rerun://127.0.0.1:51234/catalog` in the Viewer.
+    #[cfg(feature = "grpc_server")]
+    #[command(name = "serve-grpc")]
+    ServeGrpc(ServeGrpcCommand),
+
     /// Generates the Rerun CLI manual (markdown).
     ///
     /// Example: `rerun man > docs/content/reference/cli.md`
@@ -581,6 +659,9 @@ where
             #[cfg(feature = "native_viewer")]
             Command::Reset => re_viewer::reset_viewer_persistence(),
 
+            #[cfg(feature = "grpc_server")]
+            Command::ServeGrpc(serve_grpc) => serve_grpc.run(),
+
             Command::Manual => {
                 let man = Args::generate_markdown_manual();
                 let web_header = unindent::unindent(
@@ -647,6 +728,17 @@ fn run_impl(
             hide_welcome_screen: args.hide_welcome_screen,
             memory_limit: re_memory::MemoryLimit::parse(&args.memory_limit)
                 .map_err(|err| anyhow::format_err!("Bad --memory-limit: {err}"))?,
+            time_limit: args
+                .time_limit
+                .as_deref()
+                .map(|time_limit| -> anyhow::Result<_> {
+                    Ok(re_viewer::TimeLimit {
+                        timeline: args.time_limit_timeline.as_str().into(),
+                        time_span: crate::commands::parse_time_value(time_limit)
+                            .map_err(|err| anyhow::format_err!("Bad --time-limit: {err}"))?,
+                    })
+                })
+                .transpose()?,
             persist_state: args.persist_state,
             is_in_notebook: false,
             screenshot_to_path_then_quit: args.screenshot_to.clone(),
@@ -720,10 +812,18 @@ fn run_impl(
                 let server_options = re_sdk_comms::ServerOptions {
                     max_latency_sec: parse_max_latency(args.drop_at_latency.as_ref()),
                     quiet: false,
+                    auth_token: args.auth_token(),
                 };
                 let tcp_listener: Receiver<LogMsg> =
-                    re_sdk_comms::serve(&args.bind, args.port, server_options)?;
+                    re_sdk_comms::serve(&args.bind, args.port, server_options.clone())?;
                 rxs.push(tcp_listener);
+
+                #[cfg(feature = "grpc_sdk_comms")]
+                if let Some(grpc_port) = args.grpc_port {
+                    let grpc_listener: Receiver<LogMsg> =
+                        re_sdk_comms::serve_grpc(&args.bind, grpc_port, server_options)?;
+                    rxs.push(grpc_listener);
+                }
             }
         }
 
@@ -768,6 +868,18 @@ fn run_impl(
         {
             let server_memory_limit = re_memory::MemoryLimit::parse(&args.server_memory_limit)
                 .map_err(|err| anyhow::format_err!("Bad --server-memory-limit: {err}"))?;
+            let snapshot_time_window = args
+                .server_time_window
+                .as_deref()
+                .map(|time_window| -> anyhow::Result<_> {
+                    Ok(re_ws_comms::SnapshotTimeWindow {
+                        timeline: args.server_time_window_timeline.as_str().into(),
+                        time_span: crate::commands::parse_time_value(time_window).map_err(
+                            |err| anyhow::format_err!("Bad --server-time-window: {err}"),
+                        )?,
+                    })
+                })
+                .transpose()?;
 
             // This is the server which the web viewer will talk to:
             let _ws_server = re_ws_comms::RerunServer::new(
@@ -775,6 +887,8 @@ fn run_impl(
                 &args.bind,
                 args.ws_server_port,
                 server_memory_limit,
+                snapshot_time_window,
+                args.auth_token(),
             )?;
 
             #[cfg(feature = "web_viewer")]
@@ -809,7 +923,11 @@ fn run_impl(
         let addr = std::net::SocketAddr::new(re_sdk::default_server_addr().ip(), args.port);
         re_log::info!(%addr, "Another viewer is already running, streaming data to it.");
 
-        let sink = re_sdk::sink::TcpSink::new(addr, re_sdk::default_flush_timeout());
+        let sink = re_sdk::sink::TcpSink::new_with_auth_token(
+            addr,
+            re_sdk::default_flush_timeout(),
+            args.auth_token(),
+        );
 
         for rx in rxs {
             while rx.is_connected() {
@@ -25,6 +25,9 @@ mod entrypoint;
 mod rrd;
 mod stdio;
 
+#[cfg(feature = "grpc_server")]
+mod serve_grpc;
+
 #[cfg(feature = "analytics")]
 mod analytics;
 
@@ -32,5 +35,112 @@ pub use self::entrypoint::run;
 pub use self::rrd::RrdCommands;
 pub use self::stdio::read_rrd_streams_from_file_or_stdin;
 
+#[cfg(feature = "grpc_server")]
+pub use self::serve_grpc::ServeGrpcCommand;
+
 #[cfg(feature = "analytics")]
 pub(crate) use self::analytics::AnalyticsCommands;
+
+// ---
+
+/// Parses either a plain integer, or a duration with a unit suffix into nanoseconds.
+///
+/// Shared by all the CLI arguments that take a time in the native unit of a timeline, i.e. either
+/// a sequence number or nanoseconds. Times such as `--from`/`--to` are relative to the first time
+/// on the timeline, see [`relative_time_range`].
+pub(crate) fn parse_time_value(value: &str) -> anyhow::Result<i64> {
+    use anyhow::Context as _;
+
+    let value = value.trim();
+
+    if let Some(int) = re_format::parse_i64(value.strip_prefix('#').unwrap_or(value)) {
+        return Ok(int);
+    }
+
+    // NOTE: longest suffixes first, so that e.g. `ms` isn't mistaken for `s`.
+    const UNITS: &[(&str, f64)] = &[
+        ("min", 60e9),
+        ("ns", 1.0),
+        ("us", 1e3),
+        ("ms", 1e6),
+        ("s", 1e9),
+        ("h", 3600e9),
+    ];
+
+    for (suffix, nanos_per_unit) in UNITS {
+        if let Some(number) = value.strip_suffix(suffix) {
+            let number: f64 = number
+                .trim()
+                .parse()
+                .with_context(|| format!("invalid time value: {value:?}"))?;
+            return Ok((number * nanos_per_unit).round() as i64);
+        }
+    }
+
+    anyhow::bail!("invalid time value: {value:?}")
+}
+
+/// Resolves the `--from`/`--to` offsets of a command against the first time on a timeline.
+///
+/// Like `rrd replay --start`, these offsets are relative to `first_time`, so that e.g.
+/// `--from 3min --to 5min` means minutes 3 to 5 of the recording, not since the epoch.
+/// Missing bounds leave the range open on that side.
+pub(crate) fn relative_time_range(
+    first_time: Option<re_log_types::TimeInt>,
+    from: Option<i64>,
+    to: Option<i64>,
+) -> re_log_types::ResolvedTimeRange {
+    use re_log_types::TimeInt;
+
+    let first_time = first_time.map_or(0, TimeInt::as_i64);
+    let resolve = |offset: i64| TimeInt::new_temporal(first_time.saturating_add(offset));
+
+    re_log_types::ResolvedTimeRange::new(
+        from.map_or(TimeInt::MIN, resolve),
+        to.map_or(TimeInt::MAX, resolve),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use re_log_types::{ResolvedTimeRange, TimeInt};
+
+    use super::*;
+
+    #[test]
+    fn time_values() {
+        assert_eq!(42, parse_time_value("42").unwrap());
+        assert_eq!(42, parse_time_value("#42").unwrap());
+        assert_eq!(-3, parse_time_value(" -3 ").unwrap());
+        assert_eq!(1_500_000_000, parse_time_value("1.5s").unwrap());
+        assert_eq!(10_000_000, parse_time_value("10ms").unwrap());
+        assert_eq!(180_000_000_000, parse_time_value("3min").unwrap());
+        assert_eq!(2 * 3_600_000_000_000, parse_time_value("2h").unwrap());
+        assert!(parse_time_value("").is_err());
+        assert!(parse_time_value("3 parsecs").is_err());
+    }
+
+    #[test]
+    fn relative_time_ranges() {
+        let first_time = Some(TimeInt::new_temporal(1_000));
+
+        assert_eq!(
+            ResolvedTimeRange::new(1_010, 1_020),
+            relative_time_range(first_time, Some(10), Some(20))
+        );
+        assert_eq!(
+            ResolvedTimeRange::new(TimeInt::MIN, TimeInt::new_temporal(1_020)),
+            relative_time_range(first_time, None, Some(20))
+        );
+        assert_eq!(
+            ResolvedTimeRange::EVERYTHING,
+            relative_time_range(first_time, None, None)
+        );
+
+        // No data on the timeline.
+        assert_eq!(
+            ResolvedTimeRange::new(10, 20),
+            relative_time_range(None, Some(10), Some(20))
+        );
+    }
+}
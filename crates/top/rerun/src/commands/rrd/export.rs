@@ -0,0 +1,211 @@
+use std::path::PathBuf;
+
+use anyhow::Context as _;
+
+use re_chunk_store::{ChunkStoreConfig, ColumnDescriptor};
+use re_dataframe::{
+    external::re_query::StorageEngineLike as _, EntityPathFilter, ExportFormat, QueryEngine,
+    QueryExpression, SparseFillStrategy, StoreKind,
+};
+
+use super::split::suffixed_path;
+use crate::commands::{parse_time_value, relative_time_range};
+
+// ---
+
+#[derive(Debug, Clone, clap::Parser)]
+pub struct ExportCommand {
+    /// Path to the .rrd file to read from.
+    path_to_input_rrd: String,
+
+    /// Path to write to.
+    ///
+    /// If the input contains more than one recording, the ID of each recording is appended to the
+    /// file name: `out.parquet` becomes `out_<recording_id>.parquet`, etc.
+    #[arg(short = 'o', long = "output", value_name = "dst.(parquet|arrow)")]
+    path_to_output: String,
+
+    /// Output format: `parquet` or `arrow` (Arrow IPC file format).
+    ///
+    /// Guessed from the extension of the output path if unspecified.
+    #[clap(long)]
+    format: Option<ExportFormat>,
+
+    /// Name of the timeline to use as index, e.g. `log_time` or `frame`.
+    ///
+    /// There will be one row for every distinct time on that timeline.
+    /// If unspecified, only static data is exported.
+    #[clap(long)]
+    index: Option<String>,
+
+    /// Start of the index range to export (inclusive), relative to the first time on the
+    /// timeline, in its native unit.
+    ///
+    /// This is either a sequence number, or a duration in nanoseconds.
+    /// Durations can also use one of the `ns`, `us`, `ms`, `s`, `min` or `h` suffixes, e.g. `3min`.
+    ///
+    /// Same as `rrd replay --start`: `--from 3min --to 5min` exports minutes 3 to 5 of each
+    /// recording.
+    #[clap(long, requires = "index")]
+    from: Option<String>,
+
+    /// End of the index range to export (inclusive), relative to the first time on the timeline,
+    /// in its native unit.
+    ///
+    /// Same format as `--from`.
+    #[clap(long, requires = "index")]
+    to: Option<String>,
+
+    /// Include/exclude rules for the entities to export, using the same syntax as entity path
+    /// filters in blueprints, e.g. `--entity-filter "+ /world/** - /world/camera/**"`.
+    ///
+    /// Can be specified more than once, in which case all rules are combined.
+    /// Exports all entities if unspecified.
+    #[clap(long = "entity-filter")]
+    entity_filter_rules: Vec<String>,
+
+    /// How to fill null values: `none`, `latest-at-global` or `latest-at-view`.
+    #[clap(long, default_value = "none", value_parser = parse_sparse_fill_strategy)]
+    sparse_fill: SparseFillStrategy,
+}
+
+impl ExportCommand {
+    pub fn run(&self) -> anyhow::Result<()> {
+        let Self {
+            path_to_input_rrd,
+            path_to_output,
+            format,
+            index,
+            from,
+            to,
+            entity_filter_rules,
+            sparse_fill,
+        } = self;
+
+        let path_to_output = PathBuf::from(path_to_output);
+        let format = if let Some(format) = format {
+            *format
+        } else {
+            ExportFormat::from_path(&path_to_output).with_context(|| {
+                format!("couldn't guess export format from {path_to_output:?}, use `--format`")
+            })?
+        };
+
+        let entity_path_filter = if entity_filter_rules.is_empty() {
+            EntityPathFilter::all()
+        } else {
+            EntityPathFilter::parse_forgiving(&entity_filter_rules.join("\n"))
+        };
+
+        let from = from
+            .as_deref()
+            .map(parse_time_value)
+            .transpose()
+            .context("bad `--from`")?;
+        let to = to
+            .as_deref()
+            .map(parse_time_value)
+            .transpose()
+            .context("bad `--to`")?;
+
+        let now = std::time::Instant::now();
+        re_log::info!(
+            src = ?path_to_input_rrd,
+            %format,
+            ?index,
+            ?from,
+            ?to,
+            ?entity_filter_rules,
+            %sparse_fill,
+            "export started"
+        );
+
+        // TODO(cmc): might want to make this configurable at some point.
+        let version_policy = re_log_encoding::VersionPolicy::Warn;
+        let engines = QueryEngine::from_rrd_filepath(
+            &ChunkStoreConfig::DEFAULT,
+            path_to_input_rrd,
+            version_policy,
+        )
+        .with_context(|| format!("couldn't load {path_to_input_rrd:?}"))?;
+
+        let engines = engines
+            .into_iter()
+            .filter(|(store_id, _engine)| store_id.kind == StoreKind::Recording)
+            .collect::<Vec<_>>();
+        let is_single_recording = engines.len() == 1;
+
+        for (store_id, engine) in engines {
+            let filtered_index = index
+                .as_deref()
+                .map(|index| {
+                    engine
+                        .schema()
+                        .into_iter()
+                        .find_map(|column| match column {
+                            ColumnDescriptor::Time(descr)
+                                if descr.timeline.name().as_str() == index =>
+                            {
+                                Some(descr.timeline)
+                            }
+                            _ => None,
+                        })
+                        .with_context(|| {
+                            format!("recording {store_id} has no timeline named {index:?}")
+                        })
+                })
+                .transpose()?;
+
+            let query = QueryExpression {
+                view_contents: Some(
+                    engine
+                        .iter_entity_paths_sorted(&entity_path_filter)
+                        .map(|entity_path| (entity_path, None))
+                        .collect(),
+                ),
+                filtered_index,
+                filtered_index_range: filtered_index.map(|timeline| {
+                    let first_time = engine
+                        .engine
+                        .with(|store, _cache| store.time_range(&timeline))
+                        .map(|time_range| time_range.min());
+                    relative_time_range(first_time, from, to)
+                }),
+                sparse_fill_strategy: sparse_fill.clone(),
+                ..Default::default()
+            };
+
+            let path = if is_single_recording {
+                path_to_output.clone()
+            } else {
+                suffixed_path(&path_to_output, &store_id.to_string())
+            };
+
+            let num_rows = engine
+                .query(query)
+                .export_to_file(format, &path)
+                .with_context(|| format!("couldn't export recording {store_id} to {path:?}"))?;
+
+            re_log::info!(recording = %store_id, dst = ?path, num_rows, "exported recording");
+        }
+
+        re_log::info!(
+            time = ?now.elapsed(),
+            src = ?path_to_input_rrd,
+            "export finished"
+        );
+
+        Ok(())
+    }
+}
+
+fn parse_sparse_fill_strategy(value: &str) -> Result<SparseFillStrategy, String> {
+    match value {
+        "none" => Ok(SparseFillStrategy::None),
+        "latest-at-global" => Ok(SparseFillStrategy::LatestAtGlobal),
+        "latest-at-view" => Ok(SparseFillStrategy::LatestAtView),
+        _ => Err(format!(
+            "unknown sparse fill strategy {value:?}, expected one of: none, latest-at-global, latest-at-view"
+        )),
+    }
+}
@@ -27,6 +27,24 @@ pub struct FilterCommand {
     #[clap(long = "drop-entity")]
     dropped_entity_paths: Vec<String>,
 
+    /// Include/exclude rules for entity paths, using the same syntax as entity path filters in
+    /// blueprints, e.g. `--entity-filter "+ /world/** - /world/camera/**"`.
+    ///
+    /// Can be specified more than once, in which case all rules are combined.
+    /// If all the rules are exclusions, everything else is included.
+    #[clap(long = "entity-filter")]
+    entity_filter_rules: Vec<String>,
+
+    /// Names of the components to be kept, e.g. `rerun.components.Position3D` or `Position3D`.
+    ///
+    /// If specified, all other components are filtered out.
+    #[clap(long = "keep-component")]
+    kept_components: Vec<String>,
+
+    /// Names of the components to be filtered out, e.g. `rerun.components.Blob` or `Blob`.
+    #[clap(long = "drop-component")]
+    dropped_components: Vec<String>,
+
     /// If set, will try to proceed even in the face of IO and/or decoding errors in the input data.
     #[clap(long = "continue-on-error", default_value_t = false)]
     continue_on_error: bool,
@@ -39,6 +57,9 @@ impl FilterCommand {
             path_to_output_rrd,
             dropped_timelines,
             dropped_entity_paths,
+            entity_filter_rules,
+            kept_components,
+            dropped_components,
             continue_on_error,
         } = self;
 
@@ -51,13 +72,23 @@ impl FilterCommand {
         }
 
         let now = std::time::Instant::now();
-        re_log::info!(srcs = ?path_to_input_rrds, ?dropped_timelines, "filter started");
+        re_log::info!(
+            srcs = ?path_to_input_rrds,
+            ?dropped_timelines,
+            ?dropped_entity_paths,
+            ?entity_filter_rules,
+            ?kept_components,
+            ?dropped_components,
+            "filter started"
+        );
 
         let dropped_timelines: HashSet<_> = dropped_timelines.iter().collect();
         let dropped_entity_paths: HashSet<EntityPath> = dropped_entity_paths
             .iter()
             .map(|s| EntityPath::parse_forgiving(s))
             .collect();
+        let entity_path_filter = parse_entity_path_filter(entity_filter_rules);
+        let component_filter = ComponentFilter::new(kept_components, dropped_components);
 
         // TODO(cmc): might want to make this configurable at some point.
         let version_policy = re_log_encoding::VersionPolicy::Warn;
@@ -106,22 +137,36 @@ impl FilterCommand {
                 Ok(msg) => {
                     let msg = match msg {
                         re_log_types::LogMsg::ArrowMsg(store_id, mut msg) => {
-                            if !should_keep_entity_path(&dropped_entity_paths, &msg.schema) {
+                            if !should_keep_entity_path(
+                                &dropped_entity_paths,
+                                entity_path_filter.as_ref(),
+                                &msg.schema,
+                            ) {
                                 None
                             } else {
+                                let has_components =
+                                    |fields: &[ArrowField]| fields.iter().any(is_component);
+                                let had_components = has_components(&msg.schema.fields);
+
                                 let (fields, columns): (Vec<_>, Vec<_>) =
                                     itertools::izip!(msg.schema.fields.iter(), msg.chunk.iter())
                                         .filter(|(field, _col)| {
                                             should_keep_timeline(&dropped_timelines, field)
+                                                && component_filter.should_keep(field)
                                         })
                                         .map(|(field, col)| (field.clone(), col.clone()))
                                         .unzip();
 
-                                msg.schema.fields = fields;
-                                msg.chunk =
-                                    re_log_types::external::arrow2::chunk::Chunk::new(columns);
+                                if had_components && !has_components(&fields) {
+                                    // All components were filtered out: there's nothing left to keep.
+                                    None
+                                } else {
+                                    msg.schema.fields = fields;
+                                    msg.chunk =
+                                        re_log_types::external::arrow2::chunk::Chunk::new(columns);
 
-                                Some(re_log_types::LogMsg::ArrowMsg(store_id, msg))
+                                    Some(re_log_types::LogMsg::ArrowMsg(store_id, msg))
+                                }
                             }
                         }
 
@@ -185,9 +230,12 @@ impl FilterCommand {
 
 // ---
 
+use re_log_types::{
+    EntityPathFilter, EntityPathRule, EntityPathSubs, ResolvedEntityPathFilter, RuleEffect,
+};
 use re_sdk::{
     external::arrow2::{datatypes::Field as ArrowField, datatypes::Schema as Arrow2Schema},
-    EntityPath,
+    ComponentName, EntityPath,
 };
 
 fn should_keep_timeline(dropped_timelines: &HashSet<&String>, field: &ArrowField) -> bool {
@@ -204,6 +252,7 @@ fn should_keep_timeline(dropped_timelines: &HashSet<&String>, field: &ArrowField
 
 fn should_keep_entity_path(
     dropped_entity_paths: &HashSet<EntityPath>,
+    entity_path_filter: Option<&ResolvedEntityPathFilter>,
     schema: &Arrow2Schema,
 ) -> bool {
     let Some(entity_path) = schema
@@ -215,6 +264,177 @@ fn should_keep_entity_path(
     };
 
     let is_dropped = dropped_entity_paths.contains(&entity_path);
+    let is_filtered_out = entity_path_filter.is_some_and(|filter| !filter.matches(&entity_path));
+
+    !is_dropped && !is_filtered_out
+}
+
+/// Parses and resolves all the `--entity-filter` rules into a single filter.
+///
+/// Returns `None` if there are no rules.
+fn parse_entity_path_filter(rules: &[String]) -> Option<ResolvedEntityPathFilter> {
+    if rules.is_empty() {
+        return None;
+    }
+
+    let mut filter = EntityPathFilter::parse_forgiving(&rules.join("\n"));
+
+    // Filters exclude anything that isn't explicitly included, which is rarely what one wants when
+    // only specifying exclusions on the command line.
+    if filter
+        .rules()
+        .all(|(_rule, effect)| *effect == RuleEffect::Exclude)
+    {
+        filter.add_rule(RuleEffect::Include, EntityPathRule::new("/**"));
+    }
 
-    !is_dropped
+    Some(filter.resolve_forgiving(&EntityPathSubs::empty()))
+}
+
+fn is_component(field: &ArrowField) -> bool {
+    field
+        .metadata
+        .get(TransportChunk::FIELD_METADATA_KEY_KIND)
+        .map(|s| s.as_str())
+        == Some(TransportChunk::FIELD_METADATA_VALUE_KIND_DATA)
+}
+
+/// Decides which component columns to keep, based on their full or short names.
+struct ComponentFilter {
+    kept: HashSet<String>,
+    dropped: HashSet<String>,
+}
+
+impl ComponentFilter {
+    fn new(kept: &[String], dropped: &[String]) -> Self {
+        Self {
+            kept: kept.iter().cloned().collect(),
+            dropped: dropped.iter().cloned().collect(),
+        }
+    }
+
+    /// Non-component columns are always kept.
+    fn should_keep(&self, field: &ArrowField) -> bool {
+        if !is_component(field) {
+            return true;
+        }
+
+        let component_name = ComponentName::from(field.name.as_str());
+        let matches = |names: &HashSet<String>| {
+            names.contains(component_name.as_str()) || names.contains(component_name.short_name())
+        };
+
+        let is_kept = self.kept.is_empty() || matches(&self.kept);
+        let is_dropped = matches(&self.dropped);
+
+        is_kept && !is_dropped
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+
+    use re_log_types::LogMsg;
+
+    use super::*;
+    use crate::commands::rrd::{
+        compare::CompareCommand,
+        test_util::{fake_recording, path_to_string, write_rrd},
+    };
+
+    fn filter_command(input: &Path, output: &Path) -> FilterCommand {
+        FilterCommand {
+            path_to_input_rrds: vec![path_to_string(input)],
+            path_to_output_rrd: Some(path_to_string(output)),
+            dropped_timelines: Vec::new(),
+            dropped_entity_paths: Vec::new(),
+            entity_filter_rules: Vec::new(),
+            kept_components: Vec::new(),
+            dropped_components: Vec::new(),
+            continue_on_error: false,
+        }
+    }
+
+    fn compare(path_to_rrd1: &Path, path_to_rrd2: &Path) -> anyhow::Result<()> {
+        use clap::Parser as _;
+
+        CompareCommand::parse_from([
+            "compare".to_owned(),
+            path_to_string(path_to_rrd1),
+            path_to_string(path_to_rrd2),
+        ])
+        .run()
+    }
+
+    #[test]
+    fn filtered_output_compares_equal() {
+        let dir = tempfile::tempdir().unwrap();
+
+        let messages = fake_recording("filter", 4, 8);
+        let input = dir.path().join("input.rrd");
+        write_rrd(&input, &messages);
+
+        // The same recording, minus the `/static` entity and its only component.
+        let expected = dir.path().join("expected.rrd");
+        write_rrd(
+            &expected,
+            &messages
+                .iter()
+                .filter(|msg| {
+                    let LogMsg::ArrowMsg(_, arrow_msg) = msg else {
+                        return true;
+                    };
+                    arrow_msg
+                        .schema
+                        .metadata
+                        .get(TransportChunk::CHUNK_METADATA_KEY_ENTITY_PATH)
+                        .map(String::as_str)
+                        != Some("/static")
+                })
+                .cloned()
+                .collect::<Vec<_>>(),
+        );
+
+        // Filters that don't match anything leave the data untouched.
+        let output = dir.path().join("untouched.rrd");
+        FilterCommand {
+            dropped_timelines: vec!["nothing".to_owned()],
+            entity_filter_rules: vec!["- /nothing/**".to_owned()],
+            dropped_components: vec!["Nothing".to_owned()],
+            ..filter_command(&input, &output)
+        }
+        .run()
+        .unwrap();
+        compare(&input, &output).unwrap();
+
+        let output = dir.path().join("dropped_entity.rrd");
+        FilterCommand {
+            dropped_entity_paths: vec!["/static".to_owned()],
+            ..filter_command(&input, &output)
+        }
+        .run()
+        .unwrap();
+        compare(&output, &expected).unwrap();
+        assert!(compare(&output, &input).is_err());
+
+        let output = dir.path().join("filtered_entity.rrd");
+        FilterCommand {
+            entity_filter_rules: vec!["+ /points/**".to_owned()],
+            ..filter_command(&input, &output)
+        }
+        .run()
+        .unwrap();
+        compare(&output, &expected).unwrap();
+
+        // Chunks left without any component are dropped altogether.
+        let output = dir.path().join("dropped_component.rrd");
+        FilterCommand {
+            dropped_components: vec!["Color".to_owned()],
+            ..filter_command(&input, &output)
+        }
+        .run()
+        .unwrap();
+        compare(&output, &expected).unwrap();
+    }
 }
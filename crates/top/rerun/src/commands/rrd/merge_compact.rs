@@ -1,14 +1,18 @@
-use std::io::{IsTerminal, Write};
+use std::{
+    collections::HashMap,
+    io::{IsTerminal, Write},
+};
 
 use anyhow::Context as _;
-use itertools::Either;
+use itertools::{Either, Itertools as _};
 
+use re_chunk::TransportChunk;
 use re_chunk_store::ChunkStoreConfig;
 use re_entity_db::EntityDb;
-use re_log_types::StoreId;
-use re_sdk::StoreKind;
+use re_log_types::{LogMsg, StoreId, TimeInt, TimelineName};
+use re_sdk::{clock_sync, log::Chunk, StoreKind};
 
-use crate::commands::read_rrd_streams_from_file_or_stdin;
+use crate::commands::{parse_time_value, read_rrd_streams_from_file_or_stdin};
 
 // ---
 
@@ -21,6 +25,30 @@ pub struct MergeCommand {
     #[arg(short = 'o', long = "output", value_name = "dst.(rrd|rbl)")]
     path_to_output_rrd: Option<String>,
 
+    /// Shifts the times of one of the inputs on `--sync-timeline`, to correct for the clock offset
+    /// of the process that logged it, e.g. `--clock-offset robot.rrd=-1.5s`.
+    ///
+    /// The offset is in the native unit of the timeline: either a sequence number, or a duration
+    /// in nanoseconds. Durations can also use one of the `ns`, `us`, `ms`, `s`, `min` or `h`
+    /// suffixes.
+    ///
+    /// Can be specified more than once, for different inputs.
+    #[clap(long = "clock-offset", value_name = "PATH=OFFSET")]
+    clock_offsets: Vec<String>,
+
+    /// Name of a sync marker to align the inputs on, as logged by each of them with
+    /// `RecordingStream::log_sync_marker`.
+    ///
+    /// The times of every input on `--sync-timeline` are shifted so that the first occurrence of
+    /// the marker happens at the same time in all of them, using the first input that contains
+    /// the marker as reference. Inputs without the marker are only shifted by their `--clock-offset`, if any.
+    #[clap(long = "sync-marker")]
+    sync_marker: Option<String>,
+
+    /// The timeline that `--clock-offset` and `--sync-marker` apply to.
+    #[clap(long = "sync-timeline", default_value = "log_time")]
+    sync_timeline: String,
+
     /// If set, will try to proceed even in the face of IO and/or decoding errors in the input data.
     #[clap(long = "continue-on-error", default_value_t = false)]
     continue_on_error: bool,
@@ -31,6 +59,9 @@ impl MergeCommand {
         let Self {
             path_to_input_rrds,
             path_to_output_rrd,
+            clock_offsets,
+            sync_marker,
+            sync_timeline,
             continue_on_error,
         } = self;
 
@@ -47,15 +78,53 @@ impl MergeCommand {
         // (e.g. by recompacting it differently), so make sure to disable all these features.
         let store_config = ChunkStoreConfig::ALL_DISABLED;
 
+        let clock_sync = if clock_offsets.is_empty() && sync_marker.is_none() {
+            None
+        } else {
+            let offsets = clock_offsets
+                .iter()
+                .map(|clock_offset| -> anyhow::Result<_> {
+                    let (path, offset) = clock_offset.rsplit_once('=').with_context(|| {
+                        format!("bad `--clock-offset` {clock_offset:?}: expected PATH=OFFSET")
+                    })?;
+                    anyhow::ensure!(
+                        path_to_input_rrds.iter().any(|input| input == path),
+                        "bad `--clock-offset` {clock_offset:?}: {path:?} is not one of the inputs"
+                    );
+                    Ok((path.to_owned(), parse_time_value(offset)?))
+                })
+                .collect::<anyhow::Result<_>>()?;
+
+            Some(ClockSync {
+                timeline: sync_timeline.as_str().into(),
+                offsets,
+                marker: sync_marker.clone(),
+            })
+        };
+
         merge_and_compact(
             *continue_on_error,
             &store_config,
+            re_log_encoding::EncodingOptions::MSGPACK_COMPRESSED,
             path_to_input_rrds,
             path_to_output_rrd.as_ref(),
+            clock_sync.as_ref(),
         )
     }
 }
 
+/// How to align the clocks of the different inputs of a merge.
+struct ClockSync {
+    /// The timeline to shift.
+    timeline: TimelineName,
+
+    /// Explicit offsets, per input path.
+    offsets: HashMap<String, i64>,
+
+    /// The sync marker to align the inputs on, if any.
+    marker: Option<String>,
+}
+
 // ---
 
 #[derive(Debug, Clone, clap::Parser)]
@@ -87,6 +156,13 @@ pub struct CompactCommand {
     #[arg(long = "max-rows-if-unsorted")]
     max_rows_if_unsorted: Option<u64>,
 
+    /// Which compression to use for the output: `off`, `lz4`, `zstd` or `zstd:<level>`.
+    ///
+    /// `zstd` is much slower than `lz4` but yields smaller files, which makes it a good fit for
+    /// archival.
+    #[arg(long = "compression", default_value_t = re_log_encoding::Compression::LZ4)]
+    compression: re_log_encoding::Compression,
+
     /// If set, will try to proceed even in the face of IO and/or decoding errors in the input data.
     #[clap(long = "continue-on-error", default_value_t = false)]
     continue_on_error: bool,
@@ -100,6 +176,7 @@ impl CompactCommand {
             max_bytes,
             max_rows,
             max_rows_if_unsorted,
+            compression,
             continue_on_error,
         } = self;
 
@@ -125,11 +202,18 @@ impl CompactCommand {
             store_config.chunk_max_rows_if_unsorted = *max_rows_if_unsorted;
         }
 
+        let encoding_options = re_log_encoding::EncodingOptions {
+            compression: *compression,
+            serializer: re_log_encoding::Serializer::MsgPack,
+        };
+
         merge_and_compact(
             *continue_on_error,
             &store_config,
+            encoding_options,
             path_to_input_rrds,
             path_to_output_rrd.as_ref(),
+            None,
         )
     }
 }
@@ -137,8 +221,10 @@ impl CompactCommand {
 fn merge_and_compact(
     continue_on_error: bool,
     store_config: &ChunkStoreConfig,
+    encoding_options: re_log_encoding::EncodingOptions,
     path_to_input_rrds: &[String],
     path_to_output_rrd: Option<&String>,
+    clock_sync: Option<&ClockSync>,
 ) -> anyhow::Result<()> {
     let file_size_to_string = |size: Option<u64>| {
         size.map_or_else(
@@ -152,18 +238,17 @@ fn merge_and_compact(
         max_rows = %re_format::format_uint(store_config.chunk_max_rows),
         max_rows_if_unsorted = %re_format::format_uint(store_config.chunk_max_rows_if_unsorted),
         max_bytes = %re_format::format_bytes(store_config.chunk_max_bytes as _),
+        compression = %encoding_options.compression,
         srcs = ?path_to_input_rrds,
         "merge/compaction started"
     );
 
     // TODO(cmc): might want to make this configurable at some point.
     let version_policy = re_log_encoding::VersionPolicy::Warn;
-    let (rx, rx_size_bytes) =
-        read_rrd_streams_from_file_or_stdin(version_policy, path_to_input_rrds);
 
     let mut entity_dbs: std::collections::HashMap<StoreId, EntityDb> = Default::default();
 
-    for res in rx {
+    let mut add_msg = |res: anyhow::Result<LogMsg>| -> anyhow::Result<()> {
         let mut is_success = true;
 
         match res {
@@ -194,7 +279,25 @@ fn merge_and_compact(
                 "one or more IO and/or decoding failures in the input stream (check logs)"
             )
         }
-    }
+
+        Ok(())
+    };
+
+    let rrds_in_size = if let Some(clock_sync) = clock_sync {
+        let (msgs, rrds_in_size) =
+            read_with_clock_sync(version_policy, clock_sync, path_to_input_rrds)?;
+        for res in msgs {
+            add_msg(res)?;
+        }
+        rrds_in_size
+    } else {
+        let (rx, rx_size_bytes) =
+            read_rrd_streams_from_file_or_stdin(version_policy, path_to_input_rrds);
+        for res in rx {
+            add_msg(res)?;
+        }
+        rx_size_bytes.recv().ok()
+    };
 
     let mut rrd_out = if let Some(path) = path_to_output_rrd {
         Either::Left(std::io::BufWriter::new(
@@ -214,8 +317,6 @@ fn merge_and_compact(
         .filter(|entity_db| entity_db.store_kind() == StoreKind::Recording)
         .flat_map(|entity_db| entity_db.to_messages(None /* time selection */));
 
-    // TODO(cmc): encoding options should match the original.
-    let encoding_options = re_log_encoding::EncodingOptions::MSGPACK_COMPRESSED;
     let version = entity_dbs
         .values()
         .next()
@@ -234,7 +335,6 @@ fn merge_and_compact(
 
     rrd_out.flush().context("couldn't flush output")?;
 
-    let rrds_in_size = rx_size_bytes.recv().ok();
     let size_reduction = if let (Some(rrds_in_size), rrd_out_size) = (rrds_in_size, rrd_out_size) {
         format!(
             "-{:3.3}%",
@@ -255,3 +355,364 @@ fn merge_and_compact(
 
     Ok(())
 }
+
+/// Reads each input on its own, and shifts its times on [`ClockSync::timeline`] as configured.
+///
+/// Also returns the total size of the inputs, if known.
+fn read_with_clock_sync(
+    version_policy: re_log_encoding::VersionPolicy,
+    clock_sync: &ClockSync,
+    path_to_input_rrds: &[String],
+) -> anyhow::Result<(Vec<anyhow::Result<LogMsg>>, Option<u64>)> {
+    anyhow::ensure!(
+        !path_to_input_rrds.is_empty(),
+        "clock offsets and sync markers can only be applied to files, not to standard input"
+    );
+
+    // The sync markers can be anywhere in the inputs: read everything before shifting anything.
+    let mut rrds_in_size = Some(0);
+    let inputs = path_to_input_rrds
+        .iter()
+        .map(|path| {
+            let (rx, rx_size_bytes) =
+                read_rrd_streams_from_file_or_stdin(version_policy, std::slice::from_ref(path));
+            let msgs = rx.into_iter().collect_vec();
+            rrds_in_size = rrds_in_size
+                .zip(rx_size_bytes.recv().ok())
+                .map(|(total, size)| total + size);
+
+            let marker_time = clock_sync
+                .marker
+                .as_deref()
+                .and_then(|marker| find_sync_marker(&msgs, marker, &clock_sync.timeline));
+
+            (path, msgs, marker_time)
+        })
+        .collect_vec();
+
+    let clock_offset = |path: &str| clock_sync.offsets.get(path).copied().unwrap_or(0);
+
+    // The first input with the marker is the reference: its marker stays where its own clock
+    // offset puts it, and all other markers are moved there.
+    let reference_time = inputs.iter().find_map(|(path, _, marker_time)| {
+        marker_time.map(|time| time.as_i64().saturating_add(clock_offset(path)))
+    });
+    if let (Some(marker), None) = (&clock_sync.marker, reference_time) {
+        re_log::warn!(
+            marker,
+            timeline = %clock_sync.timeline,
+            "sync marker not found in any of the inputs"
+        );
+    }
+
+    let mut shifted_msgs = Vec::new();
+    for (path, msgs, marker_time) in inputs {
+        let offset = match (reference_time, marker_time) {
+            (Some(reference_time), Some(marker_time)) => {
+                reference_time.saturating_sub(marker_time.as_i64())
+            }
+            _ => {
+                if reference_time.is_some() {
+                    re_log::warn!(src = ?path, "sync marker not found: only applying clock offset");
+                }
+                clock_offset(path)
+            }
+        };
+
+        re_log::info!(src = ?path, timeline = %clock_sync.timeline, offset, "shifting input");
+
+        shifted_msgs.extend(msgs.into_iter().map(|res| {
+            res.and_then(|msg| {
+                shift_msg(msg, &clock_sync.timeline, offset)
+                    .with_context(|| format!("couldn't shift chunk from {path:?}"))
+            })
+        }));
+    }
+
+    Ok((shifted_msgs, rrds_in_size))
+}
+
+/// Returns the time of the first occurrence of the sync `marker` on `timeline`, if any.
+fn find_sync_marker(
+    msgs: &[anyhow::Result<LogMsg>],
+    marker: &str,
+    timeline: &TimelineName,
+) -> Option<TimeInt> {
+    let marker_entity_path = clock_sync::sync_marker_entity_path();
+
+    msgs.iter()
+        .filter_map(|res| match res {
+            Ok(LogMsg::ArrowMsg(_, arrow_msg)) => Some(arrow_msg),
+            _ => None,
+        })
+        .filter(|arrow_msg| {
+            // Avoid decoding chunks that cannot possibly contain markers.
+            let transport = TransportChunk {
+                schema: arrow_msg.schema.clone(),
+                data: arrow_msg.chunk.clone(),
+            };
+            transport.entity_path().ok().as_ref() == Some(&marker_entity_path)
+        })
+        .filter_map(|arrow_msg| Chunk::from_arrow_msg(arrow_msg).ok())
+        .flat_map(|chunk| clock_sync::sync_markers(&chunk, timeline))
+        .filter(|(name, _, _)| name == marker)
+        .map(|(_, _, time)| time)
+        .min()
+}
+
+/// Shifts the times of a data message on `timeline` by `offset`.
+fn shift_msg(msg: LogMsg, timeline: &TimelineName, offset: i64) -> anyhow::Result<LogMsg> {
+    let LogMsg::ArrowMsg(store_id, arrow_msg) = &msg else {
+        return Ok(msg);
+    };
+
+    let is_on_timeline = arrow_msg
+        .timepoint_max
+        .iter()
+        .any(|(candidate, _)| candidate.name() == timeline);
+    if offset == 0 || !is_on_timeline {
+        return Ok(msg);
+    }
+
+    let chunk = Chunk::from_arrow_msg(arrow_msg)?;
+    let Some(timeline) = chunk
+        .timelines()
+        .keys()
+        .find(|candidate| candidate.name() == timeline)
+        .copied()
+    else {
+        return Ok(msg);
+    };
+
+    let chunk = chunk.time_shifted(&timeline, offset);
+
+    Ok(LogMsg::ArrowMsg(store_id.clone(), chunk.to_arrow_msg()?))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+
+    use re_chunk::{external::arrow2, RowId};
+    use re_log_types::{EntityPath, Timeline};
+    use re_sdk::ComponentDescriptor;
+
+    use super::*;
+    use crate::commands::rrd::test_util::{fake_recording, path_to_string, read_rrd, write_rrd};
+
+    const MS: i64 = 1_000_000;
+
+    fn store_id(messages: &[LogMsg]) -> StoreId {
+        messages[0].store_id().clone()
+    }
+
+    /// Appends a chunk holding the sync `marker` at `time` on `log_time`.
+    fn with_sync_marker(mut messages: Vec<LogMsg>, marker: &str, time: i64) -> Vec<LogMsg> {
+        let chunk = Chunk::builder(clock_sync::sync_marker_entity_path())
+            .with_row_arrow2(
+                RowId::new(),
+                [(Timeline::log_time(), time)],
+                [(
+                    ComponentDescriptor::new(clock_sync::SYNC_MARKER_COMPONENT),
+                    Box::new(arrow2::array::Utf8Array::<i32>::from_slice([marker]))
+                        as Box<dyn arrow2::array::Array>,
+                )],
+            )
+            .build()
+            .unwrap();
+
+        messages.push(LogMsg::ArrowMsg(
+            store_id(&messages),
+            chunk.to_arrow_msg().unwrap(),
+        ));
+        messages
+    }
+
+    fn merge(
+        inputs: &[&Path],
+        output: &Path,
+        clock_offsets: &[String],
+        sync_marker: Option<&str>,
+    ) -> anyhow::Result<Vec<LogMsg>> {
+        MergeCommand {
+            path_to_input_rrds: inputs.iter().map(|path| path_to_string(path)).collect(),
+            path_to_output_rrd: Some(path_to_string(output)),
+            clock_offsets: clock_offsets.to_vec(),
+            sync_marker: sync_marker.map(ToOwned::to_owned),
+            sync_timeline: "log_time".to_owned(),
+            continue_on_error: false,
+        }
+        .run()?;
+
+        Ok(read_rrd(output))
+    }
+
+    /// All the times on `timeline` of the data logged to `entity_path` in `store_id`, sorted.
+    fn times(
+        messages: &[LogMsg],
+        store_id: &StoreId,
+        entity_path: &EntityPath,
+        timeline: &Timeline,
+    ) -> Vec<i64> {
+        messages
+            .iter()
+            .filter_map(|msg| match msg {
+                LogMsg::ArrowMsg(id, arrow_msg) if id == store_id => {
+                    Some(Chunk::from_arrow_msg(arrow_msg).unwrap())
+                }
+                _ => None,
+            })
+            .filter(|chunk| chunk.entity_path() == entity_path)
+            .flat_map(|chunk| {
+                chunk
+                    .timelines()
+                    .get(timeline)
+                    .map(|time_column| time_column.times_raw().to_vec())
+                    .unwrap_or_default()
+            })
+            .sorted()
+            .collect()
+    }
+
+    /// Two recordings, each with points at 0, 1, 2, 3 and 4ms on `log_time` (and frames 0 to 4).
+    fn two_inputs(dir: &Path) -> (Vec<LogMsg>, Vec<LogMsg>, [std::path::PathBuf; 2]) {
+        let paths = [dir.join("a.rrd"), dir.join("b.rrd")];
+        (fake_recording("a", 1, 5), fake_recording("b", 1, 5), paths)
+    }
+
+    #[test]
+    fn merge_clock_offset() {
+        let dir = tempfile::tempdir().unwrap();
+        let (a, b, [path_a, path_b]) = two_inputs(dir.path());
+        write_rrd(&path_a, &a);
+        write_rrd(&path_b, &b);
+
+        let merged = merge(
+            &[&path_a, &path_b],
+            &dir.path().join("merged.rrd"),
+            &[format!("{}=5ms", path_to_string(&path_b))],
+            None,
+        )
+        .unwrap();
+
+        let points = EntityPath::from("points");
+        assert_eq!(
+            vec![0, MS, 2 * MS, 3 * MS, 4 * MS],
+            times(&merged, &store_id(&a), &points, &Timeline::log_time())
+        );
+        assert_eq!(
+            vec![5 * MS, 6 * MS, 7 * MS, 8 * MS, 9 * MS],
+            times(&merged, &store_id(&b), &points, &Timeline::log_time())
+        );
+
+        // Other timelines are left alone.
+        assert_eq!(
+            vec![0, 1, 2, 3, 4],
+            times(
+                &merged,
+                &store_id(&b),
+                &points,
+                &Timeline::new_sequence("frame")
+            )
+        );
+    }
+
+    #[test]
+    fn merge_sync_marker() {
+        let dir = tempfile::tempdir().unwrap();
+        let (a, b, [path_a, path_b]) = two_inputs(dir.path());
+        let a = with_sync_marker(a, "trigger", 2 * MS);
+        let b = with_sync_marker(with_sync_marker(b, "other", 0), "trigger", 3 * MS);
+        write_rrd(&path_a, &a);
+        write_rrd(&path_b, &b);
+
+        let merged = merge(
+            &[&path_a, &path_b],
+            &dir.path().join("merged.rrd"),
+            &[],
+            Some("trigger"),
+        )
+        .unwrap();
+
+        // `a` is the reference, `b` is moved 1ms back so that both triggers line up.
+        let points = EntityPath::from("points");
+        assert_eq!(
+            vec![0, MS, 2 * MS, 3 * MS, 4 * MS],
+            times(&merged, &store_id(&a), &points, &Timeline::log_time())
+        );
+        assert_eq!(
+            vec![-MS, 0, MS, 2 * MS, 3 * MS],
+            times(&merged, &store_id(&b), &points, &Timeline::log_time())
+        );
+
+        let markers = clock_sync::sync_marker_entity_path();
+        assert_eq!(
+            vec![2 * MS],
+            times(&merged, &store_id(&a), &markers, &Timeline::log_time())
+        );
+        assert_eq!(
+            vec![-MS, 2 * MS],
+            times(&merged, &store_id(&b), &markers, &Timeline::log_time())
+        );
+    }
+
+    #[test]
+    fn merge_sync_marker_with_clock_offset() {
+        let dir = tempfile::tempdir().unwrap();
+        let (a, b, [path_a, path_b]) = two_inputs(dir.path());
+        let a = with_sync_marker(a, "trigger", 2 * MS);
+        let b = with_sync_marker(b, "trigger", 3 * MS);
+        write_rrd(&path_a, &a);
+        write_rrd(&path_b, &b);
+
+        // The clock offset of the reference moves everything, the one of `b` is superseded by
+        // its marker.
+        let merged = merge(
+            &[&path_a, &path_b],
+            &dir.path().join("merged.rrd"),
+            &[
+                format!("{}=10ms", path_to_string(&path_a)),
+                format!("{}=100ms", path_to_string(&path_b)),
+            ],
+            Some("trigger"),
+        )
+        .unwrap();
+
+        let points = EntityPath::from("points");
+        assert_eq!(
+            vec![10 * MS, 11 * MS, 12 * MS, 13 * MS, 14 * MS],
+            times(&merged, &store_id(&a), &points, &Timeline::log_time())
+        );
+        assert_eq!(
+            vec![9 * MS, 10 * MS, 11 * MS, 12 * MS, 13 * MS],
+            times(&merged, &store_id(&b), &points, &Timeline::log_time())
+        );
+
+        // A missing marker falls back to the clock offset.
+        let merged = merge(
+            &[&path_a, &path_b],
+            &dir.path().join("merged.rrd"),
+            &[format!("{}=100ms", path_to_string(&path_b))],
+            Some("missing"),
+        )
+        .unwrap();
+        assert_eq!(
+            vec![100 * MS, 101 * MS, 102 * MS, 103 * MS, 104 * MS],
+            times(&merged, &store_id(&b), &points, &Timeline::log_time())
+        );
+    }
+
+    #[test]
+    fn merge_bad_clock_offset() {
+        let dir = tempfile::tempdir().unwrap();
+        let (a, _, [path_a, path_b]) = two_inputs(dir.path());
+        write_rrd(&path_a, &a);
+
+        let output = dir.path().join("merged.rrd");
+        let not_an_input = [format!("{}=5ms", path_to_string(&path_b))];
+        assert!(merge(&[&path_a], &output, &not_an_input, None).is_err());
+
+        let no_offset = [path_to_string(&path_a)];
+        assert!(merge(&[&path_a], &output, &no_offset, None).is_err());
+    }
+}
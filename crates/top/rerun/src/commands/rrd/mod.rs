@@ -1,12 +1,23 @@
 mod compare;
+mod export;
 mod filter;
 mod merge_compact;
 mod print;
+mod replay;
+mod split;
+mod stats;
+
+#[cfg(test)]
+mod test_util;
 
 use self::compare::CompareCommand;
+use self::export::ExportCommand;
 use self::filter::FilterCommand;
 use self::merge_compact::{CompactCommand, MergeCommand};
 use self::print::PrintCommand;
+use self::replay::ReplayCommand;
+use self::split::SplitCommand;
+use self::stats::StatsCommand;
 
 // ---
 
@@ -29,6 +40,15 @@ pub enum RrdCommands {
     /// Example: `rerun rrd print /my/recordings/*.rrd`
     Print(PrintCommand),
 
+    /// Summarizes the contents of one or more .rrd/.rbl files/streams.
+    ///
+    /// Reports chunk counts, row counts, sizes and timeline coverage, per entity and per component.
+    ///
+    /// Reads from standard input if no paths are specified.
+    ///
+    /// Example: `rerun rrd stats --json /my/recordings/*.rrd`
+    Stats(StatsCommand),
+
     /// Compacts the contents of one or more .rrd/.rbl files/streams and writes the result standard output.
     ///
     /// Reads from standard input if no paths are specified.
@@ -45,6 +65,8 @@ pub enum RrdCommands {
     /// * `RERUN_CHUNK_MAX_ROWS=4096 RERUN_CHUNK_MAX_BYTES=1048576 rerun rrd compact /my/recordings/*.rrd -o output.rrd`
     ///
     /// * `rerun rrd compact --max-rows 4096 --max-bytes=1048576 /my/recordings/*.rrd > output.rrd`
+    ///
+    /// * `rerun rrd compact --compression zstd:19 /my/recordings/*.rrd -o archive.rrd`
     Compact(CompactCommand),
 
     /// Merges the contents of multiple .rrd/.rbl files/streams, and writes the result to standard output.
@@ -53,7 +75,14 @@ pub enum RrdCommands {
     ///
     /// This will not affect the chunking of the data in any way.
     ///
-    /// Example: `rerun merge /my/recordings/*.rrd > output.rrd`
+    /// When the inputs were logged by different processes, their clocks can be aligned with
+    /// `--clock-offset` and/or `--sync-marker`.
+    ///
+    /// Examples:
+    ///
+    /// * `rerun merge /my/recordings/*.rrd > output.rrd`
+    ///
+    /// * `rerun rrd merge --sync-marker start robot.rrd camera.rrd --clock-offset lidar.rrd=-1.5s lidar.rrd -o output.rrd`
     Merge(MergeCommand),
 
     /// Filters out data from .rrd/.rbl files/streams, and writes the result to standard output.
@@ -62,8 +91,48 @@ pub enum RrdCommands {
     ///
     /// This will not affect the chunking of the data in any way.
     ///
-    /// Example: `rerun filter --drop-timeline log_tick /my/recordings/*.rrd > output.rrd`
+    /// Examples:
+    ///
+    /// * `rerun rrd filter --drop-timeline log_tick /my/recordings/*.rrd > output.rrd`
+    ///
+    /// * `rerun rrd filter --entity-filter "+ /world/** - /world/camera/**" --drop-component Blob /my/recording.rrd > output.rrd`
     Filter(FilterCommand),
+
+    /// Splits .rrd/.rbl files/streams by time range and/or size, and writes the result(s) to disk.
+    ///
+    /// Reads from standard input if no paths are specified.
+    ///
+    /// Store metadata and static data are kept in every output file, so that each of them can be
+    /// opened on its own.
+    ///
+    /// Examples:
+    ///
+    /// * `rerun rrd split --timeline sim_time --from 3min --to 5min /my/recording.rrd -o excerpt.rrd`
+    ///
+    /// * `rerun rrd split --max-bytes 1000000000 /my/recording.rrd -o part.rrd`
+    Split(SplitCommand),
+
+    /// Runs a dataframe query over an .rrd file, and writes the results to Parquet or Arrow IPC files.
+    ///
+    /// There is one output file per recording in the input.
+    ///
+    /// Examples:
+    ///
+    /// * `rerun rrd export --index frame --sparse-fill latest-at-global /my/recording.rrd -o frames.parquet`
+    ///
+    /// * `rerun rrd export --index log_time --from 10s --to 20s --entity-filter "+ /world/**" /my/recording.rrd -o excerpt.arrow`
+    Export(ExportCommand),
+
+    /// Replays an .rrd file to a viewer at its original pace, as if it was being logged live.
+    ///
+    /// The pace is reconstructed from the times of the chunks on the chosen timeline.
+    ///
+    /// Examples:
+    ///
+    /// * `rerun rrd replay /my/recording.rrd`
+    ///
+    /// * `rerun rrd replay --timeline frame --ticks-per-second 30 --speed 2 --loop /my/recording.rrd`
+    Replay(ReplayCommand),
 }
 
 impl RrdCommands {
@@ -76,9 +145,13 @@ impl RrdCommands {
                     .with_context(|| format!("current directory {:?}", std::env::current_dir()))
             }
             Self::Print(print_command) => print_command.run(),
+            Self::Stats(stats_command) => stats_command.run(),
             Self::Compact(compact_command) => compact_command.run(),
             Self::Merge(merge_command) => merge_command.run(),
             Self::Filter(drop_command) => drop_command.run(),
+            Self::Split(split_command) => split_command.run(),
+            Self::Export(export_command) => export_command.run(),
+            Self::Replay(replay_command) => replay_command.run(),
         }
     }
 }
@@ -0,0 +1,92 @@
+use anyhow::Context as _;
+
+use crate::commands::parse_time_value;
+
+// ---
+
+#[derive(Debug, Clone, clap::Parser)]
+pub struct ReplayCommand {
+    /// Path to read from.
+    path_to_input_rrd: String,
+
+    /// Address of the viewer (or `rerun` server) to send the data to.
+    #[clap(long, default_value_t = re_sdk::default_server_addr())]
+    addr: std::net::SocketAddr,
+
+    /// Name of the timeline used to pace the replay, e.g. `log_time` or `frame`.
+    ///
+    /// Data that isn't on this timeline (e.g. static data) is sent right away.
+    #[clap(long, default_value = "log_time")]
+    timeline: String,
+
+    /// How much faster than the original pace to replay, e.g. `2` for twice as fast.
+    #[clap(long, default_value_t = 1.0)]
+    speed: f64,
+
+    /// How many ticks of a sequence timeline correspond to one second of the original pace.
+    ///
+    /// Ignored for temporal timelines.
+    #[clap(long, default_value_t = 1.0)]
+    ticks_per_second: f64,
+
+    /// Where to start the replay, relative to the first time on the timeline, in its native unit.
+    ///
+    /// This is either a sequence number, or a duration in nanoseconds.
+    /// Durations can also use one of the `ns`, `us`, `ms`, `s`, `min` or `h` suffixes, e.g. `3min`.
+    ///
+    /// Data before that point is sent as fast as possible.
+    #[clap(long)]
+    start: Option<String>,
+
+    /// If set, start over once the end of the file is reached, until interrupted.
+    #[clap(long = "loop", default_value_t = false)]
+    looping: bool,
+}
+
+impl ReplayCommand {
+    pub fn run(&self) -> anyhow::Result<()> {
+        let Self {
+            path_to_input_rrd,
+            addr,
+            timeline,
+            speed,
+            ticks_per_second,
+            start,
+            looping,
+        } = self;
+
+        anyhow::ensure!(0.0 < *speed, "`--speed` must be positive");
+        anyhow::ensure!(
+            0.0 < *ticks_per_second,
+            "`--ticks-per-second` must be positive"
+        );
+
+        let options = re_sdk::ReplayOptions {
+            timeline: timeline.as_str().into(),
+            speed: *speed,
+            ticks_per_second: *ticks_per_second,
+            start_offset: start
+                .as_deref()
+                .map(parse_time_value)
+                .transpose()
+                .context("bad `--start`")?
+                .unwrap_or(0),
+            looping: *looping,
+        };
+
+        let now = std::time::Instant::now();
+        re_log::info!(src = ?path_to_input_rrd, addr = %addr, options = ?options, "replay started");
+
+        let sink = re_sdk::sink::TcpSink::new(*addr, re_sdk::default_flush_timeout());
+        re_sdk::replay_rrd(path_to_input_rrd, &sink, &options)?;
+
+        re_log::info!(
+            time = ?now.elapsed(),
+            src = ?path_to_input_rrd,
+            addr = %addr,
+            "replay finished"
+        );
+
+        Ok(())
+    }
+}
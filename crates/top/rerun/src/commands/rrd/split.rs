@@ -0,0 +1,575 @@
+use std::path::{Path, PathBuf};
+
+use anyhow::Context as _;
+
+use re_build_info::CrateVersion;
+use re_log_types::{LogMsg, ResolvedTimeRange, StoreKind, TimeInt};
+use re_sdk::log::Chunk;
+
+use crate::commands::{parse_time_value, read_rrd_streams_from_file_or_stdin, relative_time_range};
+
+// ---
+
+#[derive(Debug, Clone, clap::Parser)]
+pub struct SplitCommand {
+    /// Paths to read from. Reads from standard input if none are specified.
+    path_to_input_rrds: Vec<String>,
+
+    /// Path to write to.
+    ///
+    /// When splitting by size, this is used as a template: `out.rrd` becomes `out_0000.rrd`,
+    /// `out_0001.rrd`, etc.
+    #[arg(short = 'o', long = "output", value_name = "dst.(rrd|rbl)")]
+    path_to_output_rrd: String,
+
+    /// Name of the timeline used to slice the data, e.g. `log_time` or `frame`.
+    ///
+    /// Temporal data that isn't present on this timeline is dropped.
+    #[clap(long)]
+    timeline: Option<String>,
+
+    /// Start of the time range to keep (inclusive), relative to the first time on the timeline, in
+    /// its native unit.
+    ///
+    /// This is either a sequence number, or a duration in nanoseconds.
+    /// Durations can also use one of the `ns`, `us`, `ms`, `s`, `min` or `h` suffixes, e.g. `3min`.
+    ///
+    /// Same as `rrd replay --start`: `--from 3min --to 5min` keeps minutes 3 to 5 of the recording.
+    #[clap(long, requires = "timeline")]
+    from: Option<String>,
+
+    /// End of the time range to keep (inclusive), relative to the first time on the timeline, in
+    /// its native unit.
+    ///
+    /// Same format as `--from`.
+    #[clap(long, requires = "timeline")]
+    to: Option<String>,
+
+    /// If set, all timelines other than `--timeline` are removed from the output.
+    #[clap(long, default_value_t = false, requires = "timeline")]
+    drop_other_timelines: bool,
+
+    /// Start a new output file whenever the current one exceeds this many bytes.
+    ///
+    /// Static data and store metadata seen so far are written at the start of every file, so that
+    /// each of them can be opened on its own.
+    #[clap(long = "max-bytes")]
+    max_bytes: Option<u64>,
+
+    /// If set, will try to proceed even in the face of IO and/or decoding errors in the input data.
+    #[clap(long = "continue-on-error", default_value_t = false)]
+    continue_on_error: bool,
+}
+
+impl SplitCommand {
+    pub fn run(&self) -> anyhow::Result<()> {
+        let Self {
+            path_to_input_rrds,
+            path_to_output_rrd,
+            timeline,
+            from,
+            to,
+            drop_other_timelines,
+            max_bytes,
+            continue_on_error,
+        } = self;
+
+        anyhow::ensure!(
+            timeline.is_some() || max_bytes.is_some(),
+            "nothing to split on: specify a time range (`--timeline`) and/or a size (`--max-bytes`)"
+        );
+
+        let from = from
+            .as_deref()
+            .map(parse_time_value)
+            .transpose()
+            .context("bad `--from`")?;
+        let to = to
+            .as_deref()
+            .map(parse_time_value)
+            .transpose()
+            .context("bad `--to`")?;
+
+        let now = std::time::Instant::now();
+        re_log::info!(
+            srcs = ?path_to_input_rrds,
+            ?timeline,
+            ?from,
+            ?to,
+            ?max_bytes,
+            "split started"
+        );
+
+        // TODO(cmc): might want to make this configurable at some point.
+        let version_policy = re_log_encoding::VersionPolicy::Warn;
+        let read_input = || {
+            let (rx, _) = read_rrd_streams_from_file_or_stdin(version_policy, path_to_input_rrds);
+            rx.into_iter()
+        };
+
+        let mut input: Box<dyn Iterator<Item = anyhow::Result<LogMsg>>> = Box::new(read_input());
+
+        // `--from` & `--to` are relative to the first time on the timeline, which takes a first
+        // pass over the data to find out. Standard input can only be read once: buffer it instead.
+        let mut first_time = None;
+        if let Some(timeline) = timeline
+            .as_deref()
+            .filter(|_| from.is_some() || to.is_some())
+        {
+            if path_to_input_rrds.is_empty() {
+                let msgs = input.collect::<Vec<_>>();
+                first_time = msgs
+                    .iter()
+                    .filter_map(|res| min_time(timeline, res.as_ref().ok()?))
+                    .min();
+                input = Box::new(msgs.into_iter());
+            } else {
+                first_time = input.filter_map(|res| min_time(timeline, &res.ok()?)).min();
+                input = Box::new(read_input());
+            }
+        }
+
+        let time_range = relative_time_range(first_time, from, to);
+        re_log::debug!(?first_time, ?time_range, "resolved time range");
+
+        let mut writer = SplitWriter::new(PathBuf::from(path_to_output_rrd), *max_bytes);
+
+        for res in input {
+            let mut is_success = true;
+
+            match res {
+                Ok(msg) => {
+                    let res = if let Some(timeline) = timeline {
+                        slice_msg(timeline, time_range, *drop_other_timelines, msg)
+                    } else {
+                        Ok(Some(msg))
+                    };
+
+                    match res {
+                        Ok(Some(msg)) => writer.append(msg)?,
+                        Ok(None) => {}
+                        Err(err) => {
+                            re_log::error!(err = re_error::format(err));
+                            is_success = false;
+                        }
+                    }
+                }
+
+                Err(err) => {
+                    re_log::error!(err = re_error::format(err));
+                    is_success = false;
+                }
+            }
+
+            if !*continue_on_error && !is_success {
+                anyhow::bail!(
+                    "one or more IO and/or decoding failures in the input stream (check logs)"
+                )
+            }
+        }
+
+        let paths = writer.finish()?;
+
+        re_log::info!(
+            time = ?now.elapsed(),
+            srcs = ?path_to_input_rrds,
+            dsts = ?paths,
+            "split finished"
+        );
+
+        Ok(())
+    }
+}
+
+/// The earliest time of the data in `msg` on `timeline_name`, if any.
+///
+/// Blueprints and static data have no time.
+fn min_time(timeline_name: &str, msg: &LogMsg) -> Option<TimeInt> {
+    let LogMsg::ArrowMsg(store_id, arrow_msg) = msg else {
+        return None;
+    };
+
+    if store_id.kind == StoreKind::Blueprint {
+        return None;
+    }
+
+    let chunk = Chunk::from_arrow_msg(arrow_msg).ok()?;
+    chunk
+        .timelines()
+        .iter()
+        .find(|(timeline, _)| timeline.name().as_str() == timeline_name)
+        .map(|(_, time_column)| time_column.time_range().min())
+}
+
+/// Keeps only the rows of `msg` that fall within `time_range` on `timeline`.
+///
+/// Store metadata, blueprints and static data are always kept as-is.
+fn slice_msg(
+    timeline_name: &str,
+    time_range: ResolvedTimeRange,
+    drop_other_timelines: bool,
+    msg: LogMsg,
+) -> anyhow::Result<Option<LogMsg>> {
+    let LogMsg::ArrowMsg(store_id, arrow_msg) = msg else {
+        return Ok(Some(msg));
+    };
+
+    if store_id.kind == StoreKind::Blueprint {
+        return Ok(Some(LogMsg::ArrowMsg(store_id, arrow_msg)));
+    }
+
+    let chunk = Chunk::from_arrow_msg(&arrow_msg).context("skipped corrupt chunk")?;
+    if chunk.is_static() {
+        return Ok(Some(LogMsg::ArrowMsg(store_id, arrow_msg)));
+    }
+
+    let Some(timeline) = chunk
+        .timelines()
+        .keys()
+        .find(|timeline| timeline.name().as_str() == timeline_name)
+        .copied()
+    else {
+        return Ok(None);
+    };
+
+    let chunk = chunk.sorted_by_timeline_if_unsorted(&timeline);
+    let Some(time_column) = chunk.timelines().get(&timeline) else {
+        return Ok(None);
+    };
+
+    let times = time_column.times_raw();
+    let start = times.partition_point(|&t| t < time_range.min().as_i64());
+    let end = times.partition_point(|&t| t <= time_range.max().as_i64());
+    if start >= end {
+        return Ok(None);
+    }
+
+    let mut chunk = chunk.row_sliced(start, end - start);
+    if drop_other_timelines {
+        chunk = chunk.timeline_sliced(timeline);
+    }
+
+    Ok(Some(LogMsg::ArrowMsg(store_id, chunk.to_arrow_msg()?)))
+}
+
+// ---
+
+/// Writes messages to one or more output files, starting a new one whenever `max_bytes` is exceeded.
+struct SplitWriter {
+    path_template: PathBuf,
+    max_bytes: Option<u64>,
+
+    /// Store metadata and static data seen so far, re-emitted at the start of every new file.
+    preamble: Vec<LogMsg>,
+
+    encoder: Option<re_log_encoding::encoder::DroppableEncoder<std::io::BufWriter<std::fs::File>>>,
+
+    /// Number of bytes written to the current file so far.
+    size_bytes: u64,
+
+    /// Whether the current file contains anything beyond the preamble.
+    has_temporal_data: bool,
+
+    paths: Vec<PathBuf>,
+}
+
+impl SplitWriter {
+    fn new(path_template: PathBuf, max_bytes: Option<u64>) -> Self {
+        Self {
+            path_template,
+            max_bytes,
+            preamble: Vec::new(),
+            encoder: None,
+            size_bytes: 0,
+            has_temporal_data: false,
+            paths: Vec::new(),
+        }
+    }
+
+    fn append(&mut self, msg: LogMsg) -> anyhow::Result<()> {
+        let is_preamble = match &msg {
+            LogMsg::SetStoreInfo(_) | LogMsg::BlueprintActivationCommand(_) => true,
+            LogMsg::ArrowMsg(store_id, arrow_msg) => {
+                store_id.kind == StoreKind::Blueprint
+                    || Chunk::from_arrow_msg(arrow_msg)
+                        .context("skipped corrupt chunk")?
+                        .is_static()
+            }
+        };
+
+        let is_full = self
+            .max_bytes
+            .is_some_and(|max_bytes| self.size_bytes >= max_bytes);
+
+        if self.encoder.is_none() || (!is_preamble && is_full && self.has_temporal_data) {
+            self.start_new_file()?;
+        }
+
+        if is_preamble {
+            self.preamble.push(msg.clone());
+        } else {
+            self.has_temporal_data = true;
+        }
+
+        if let Some(encoder) = &mut self.encoder {
+            self.size_bytes += encoder.append(&msg).context("encoding failure")?;
+        }
+
+        Ok(())
+    }
+
+    fn start_new_file(&mut self) -> anyhow::Result<()> {
+        self.finish_current_file()?;
+
+        let path = if self.max_bytes.is_some() {
+            numbered_path(&self.path_template, self.paths.len())
+        } else {
+            self.path_template.clone()
+        };
+
+        let file = std::fs::File::create(&path).with_context(|| format!("{path:?}"))?;
+
+        // TODO(cmc): encoding options & version should match the original.
+        let version = CrateVersion::LOCAL;
+        let options = re_log_encoding::EncodingOptions::MSGPACK_COMPRESSED;
+        let mut encoder = re_log_encoding::encoder::DroppableEncoder::new(
+            version,
+            options,
+            std::io::BufWriter::new(file),
+        )
+        .context("couldn't init encoder")?;
+
+        self.size_bytes = 0;
+        for msg in &self.preamble {
+            self.size_bytes += encoder.append(msg).context("encoding failure")?;
+        }
+
+        self.encoder = Some(encoder);
+        self.has_temporal_data = false;
+        self.paths.push(path);
+
+        Ok(())
+    }
+
+    fn finish_current_file(&mut self) -> anyhow::Result<()> {
+        if let Some(mut encoder) = self.encoder.take() {
+            encoder.finish().context("couldn't finish output")?;
+            encoder.flush_blocking().context("couldn't flush output")?;
+        }
+        Ok(())
+    }
+
+    /// Returns the paths of all files that were written.
+    fn finish(mut self) -> anyhow::Result<Vec<PathBuf>> {
+        self.finish_current_file()?;
+        Ok(self.paths)
+    }
+}
+
+/// `out.rrd` -> `out_0003.rrd`
+fn numbered_path(path_template: &Path, index: usize) -> PathBuf {
+    suffixed_path(path_template, &format!("{index:04}"))
+}
+
+/// `out.rrd` -> `out_<suffix>.rrd`
+pub(crate) fn suffixed_path(path: &Path, suffix: &str) -> PathBuf {
+    let stem = path
+        .file_stem()
+        .map(|stem| stem.to_string_lossy().to_string())
+        .unwrap_or_default();
+    let file_name = if let Some(extension) = path.extension() {
+        format!("{stem}_{suffix}.{}", extension.to_string_lossy())
+    } else {
+        format!("{stem}_{suffix}")
+    };
+    path.with_file_name(file_name)
+}
+
+#[cfg(test)]
+mod tests {
+    use re_chunk::RowId;
+
+    use super::*;
+    use crate::commands::rrd::test_util::{
+        chunks, fake_recording, path_to_string, read_rrd, write_rrd,
+    };
+
+    fn split_command(input: &Path, output: &Path) -> SplitCommand {
+        SplitCommand {
+            path_to_input_rrds: vec![path_to_string(input)],
+            path_to_output_rrd: path_to_string(output),
+            timeline: None,
+            from: None,
+            to: None,
+            drop_other_timelines: false,
+            max_bytes: None,
+            continue_on_error: false,
+        }
+    }
+
+    fn temporal_row_ids(chunks: &[Chunk]) -> Vec<RowId> {
+        chunks
+            .iter()
+            .filter(|chunk| !chunk.is_static())
+            .flat_map(|chunk| chunk.row_ids().collect::<Vec<_>>())
+            .collect()
+    }
+
+    #[test]
+    fn numbered_paths() {
+        assert_eq!(
+            PathBuf::from("/tmp/out_0003.rrd"),
+            numbered_path(Path::new("/tmp/out.rrd"), 3)
+        );
+        assert_eq!(
+            PathBuf::from("out_0012"),
+            numbered_path(Path::new("out"), 12)
+        );
+        assert_eq!(
+            PathBuf::from("/tmp/out_rec_42.parquet"),
+            suffixed_path(Path::new("/tmp/out.parquet"), "rec_42")
+        );
+    }
+
+    #[test]
+    fn split_by_size_roundtrip() {
+        let dir = tempfile::tempdir().unwrap();
+        let input = dir.path().join("input.rrd");
+        write_rrd(&input, &fake_recording("split", 20, 10));
+        let original = read_rrd(&input);
+
+        let input_size = std::fs::metadata(&input).unwrap().len();
+        let output = dir.path().join("out.rrd");
+        SplitCommand {
+            max_bytes: Some(input_size / 5),
+            ..split_command(&input, &output)
+        }
+        .run()
+        .unwrap();
+
+        let outputs = (0..)
+            .map(|index| numbered_path(&output, index))
+            .take_while(|path| path.exists())
+            .map(|path| read_rrd(&path))
+            .collect::<Vec<_>>();
+        assert!(outputs.len() > 1, "expected several files");
+
+        // Every file starts with the store info and static data, so that it can be opened on its own.
+        let preamble = &original[..2];
+        for messages in &outputs {
+            assert_eq!(chunks(preamble), chunks(&messages[..2]));
+            assert!(matches!(messages[0], LogMsg::SetStoreInfo(_)));
+        }
+
+        // Concatenating all the files, minus the repeated preambles, gives back the original.
+        let mut concatenated = outputs[0].clone();
+        for messages in &outputs[1..] {
+            concatenated.extend_from_slice(&messages[2..]);
+        }
+        assert_eq!(original.len(), concatenated.len());
+        assert_eq!(chunks(&original), chunks(&concatenated));
+    }
+
+    #[test]
+    fn split_by_time_range_roundtrip() {
+        let dir = tempfile::tempdir().unwrap();
+        let input = dir.path().join("input.rrd");
+        write_rrd(&input, &fake_recording("split", 10, 10));
+        let original = chunks(&read_rrd(&input));
+
+        // Cut in the middle of a chunk.
+        let before = dir.path().join("before.rrd");
+        SplitCommand {
+            timeline: Some("frame".to_owned()),
+            to: Some("44".to_owned()),
+            ..split_command(&input, &before)
+        }
+        .run()
+        .unwrap();
+
+        let after = dir.path().join("after.rrd");
+        SplitCommand {
+            timeline: Some("frame".to_owned()),
+            from: Some("45".to_owned()),
+            drop_other_timelines: true,
+            ..split_command(&input, &after)
+        }
+        .run()
+        .unwrap();
+
+        let before = chunks(&read_rrd(&before));
+        let after = chunks(&read_rrd(&after));
+
+        let frame = re_log_types::Timeline::new_sequence("frame");
+        let frames = |chunks: &[Chunk]| {
+            chunks
+                .iter()
+                .filter_map(|chunk| chunk.timelines().get(&frame))
+                .flat_map(|time_column| time_column.times_raw().to_vec())
+                .collect::<Vec<_>>()
+        };
+        assert_eq!((0..=44).collect::<Vec<_>>(), frames(&before));
+        assert_eq!((45..100).collect::<Vec<_>>(), frames(&after));
+
+        // Static data is kept on both sides.
+        assert!(before.iter().any(Chunk::is_static));
+        assert!(after.iter().any(Chunk::is_static));
+
+        // Other timelines are only dropped when asked to.
+        assert!(before
+            .iter()
+            .filter(|chunk| !chunk.is_static())
+            .all(|chunk| chunk.timelines().len() == 2));
+        assert!(after
+            .iter()
+            .filter(|chunk| !chunk.is_static())
+            .all(|chunk| chunk.timelines().keys().eq([&frame])));
+
+        // Both halves put together hold every single row of the original, in order.
+        assert_eq!(
+            temporal_row_ids(&original),
+            [temporal_row_ids(&before), temporal_row_ids(&after)].concat()
+        );
+    }
+
+    #[test]
+    fn split_by_relative_time_range() {
+        let dir = tempfile::tempdir().unwrap();
+        let input = dir.path().join("input.rrd");
+
+        // Real recordings don't start at the epoch: move everything an hour later.
+        let log_time = re_log_types::Timeline::log_time();
+        let messages = fake_recording("split", 10, 10)
+            .into_iter()
+            .map(|msg| match msg {
+                LogMsg::ArrowMsg(store_id, arrow_msg) => {
+                    let chunk = Chunk::from_arrow_msg(&arrow_msg)
+                        .unwrap()
+                        .time_shifted(&log_time, 3_600_000_000_000);
+                    LogMsg::ArrowMsg(store_id, chunk.to_arrow_msg().unwrap())
+                }
+                msg => msg,
+            })
+            .collect::<Vec<_>>();
+        write_rrd(&input, &messages);
+
+        // Frame `n` is logged `n` ms after the first one: the range is relative to that first time.
+        let output = dir.path().join("output.rrd");
+        SplitCommand {
+            timeline: Some("log_time".to_owned()),
+            from: Some("20ms".to_owned()),
+            to: Some("30ms".to_owned()),
+            ..split_command(&input, &output)
+        }
+        .run()
+        .unwrap();
+
+        let frame = re_log_types::Timeline::new_sequence("frame");
+        let frames = chunks(&read_rrd(&output))
+            .iter()
+            .filter_map(|chunk| chunk.timelines().get(&frame))
+            .flat_map(|time_column| time_column.times_raw().to_vec())
+            .collect::<Vec<_>>();
+        assert_eq!((20..=30).collect::<Vec<_>>(), frames);
+    }
+}
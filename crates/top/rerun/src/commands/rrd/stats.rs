@@ -0,0 +1,469 @@
+use std::collections::BTreeMap;
+
+use anyhow::Context as _;
+
+use re_byte_size::SizeBytes as _;
+use re_chunk::external::arrow2::array::Array as _;
+use re_log_types::{LogMsg, ResolvedTimeRange, StoreId, TimeType};
+use re_sdk::log::Chunk;
+
+use crate::commands::read_rrd_streams_from_file_or_stdin;
+
+// ---
+
+#[derive(Debug, Clone, clap::Parser)]
+pub struct StatsCommand {
+    /// Paths to read from. Reads from standard input if none are specified.
+    path_to_input_rrds: Vec<String>,
+
+    /// If set, outputs the statistics as JSON instead of a human-readable summary.
+    #[clap(long, default_value_t = false)]
+    json: bool,
+
+    /// If set, will try to proceed even in the face of IO and/or decoding errors in the input data.
+    #[clap(long = "continue-on-error", default_value_t = true)]
+    continue_on_error: bool,
+}
+
+impl StatsCommand {
+    pub fn run(&self) -> anyhow::Result<()> {
+        let Self {
+            path_to_input_rrds,
+            json,
+            continue_on_error,
+        } = self;
+
+        // TODO(cmc): might want to make this configurable at some point.
+        let version_policy = re_log_encoding::VersionPolicy::Warn;
+        let (rx, rx_size_bytes) =
+            read_rrd_streams_from_file_or_stdin(version_policy, path_to_input_rrds);
+
+        let mut stats = RrdStats::default();
+
+        for res in rx {
+            let mut is_success = true;
+
+            match res {
+                Ok(msg) => {
+                    if let Err(err) = stats.add_msg(msg) {
+                        re_log::error!(err = re_error::format(err));
+                        is_success = false;
+                    }
+                }
+
+                Err(err) => {
+                    re_log::error!(err = re_error::format(err));
+                    is_success = false;
+                }
+            }
+
+            if !*continue_on_error && !is_success {
+                anyhow::bail!(
+                    "one or more IO and/or decoding failures in the input stream (check logs)"
+                )
+            }
+        }
+
+        stats.encoded_size_bytes = rx_size_bytes.recv().ok();
+
+        if *json {
+            let json = serde_json::to_string_pretty(&stats).context("couldn't serialize stats")?;
+            println!("{json}");
+        } else {
+            stats.print();
+        }
+
+        Ok(())
+    }
+}
+
+// ---
+
+/// Statistics about the contents of one or more .rrd/.rbl files/streams.
+#[derive(Default, serde::Serialize)]
+struct RrdStats {
+    /// Size of the input data, as encoded on disk/on the wire.
+    encoded_size_bytes: Option<u64>,
+
+    /// All messages, of any kind, across all stores.
+    num_messages: u64,
+
+    /// Keyed by store ID.
+    stores: BTreeMap<String, StoreStats>,
+}
+
+#[derive(serde::Serialize)]
+struct StoreStats {
+    kind: String,
+    /// Only known if the stream contains the `SetStoreInfo` message of this store.
+    application_id: Option<String>,
+
+    /// All chunks of this store, static and temporal.
+    num_chunks: u64,
+
+    /// Total number of rows across all the chunks of this store.
+    num_rows: u64,
+
+    /// In-memory size of the chunks, as computed by [`re_byte_size::SizeBytes`].
+    size_bytes: u64,
+
+    /// Keyed by entity path.
+    entities: BTreeMap<String, EntityStats>,
+}
+
+#[derive(Default, serde::Serialize)]
+struct EntityStats {
+    /// Chunks that don't have any timeline.
+    num_static_chunks: u64,
+
+    /// Chunks that have data on at least one timeline.
+    num_temporal_chunks: u64,
+
+    /// Total number of rows across all the chunks of this entity, static and temporal.
+    num_rows: u64,
+
+    /// In-memory size of the chunks of this entity.
+    size_bytes: u64,
+
+    /// Keyed by timeline name.
+    timelines: BTreeMap<String, TimelineStats>,
+
+    /// Keyed by component name.
+    components: BTreeMap<String, ComponentStats>,
+}
+
+#[derive(serde::Serialize)]
+struct TimelineStats {
+    #[serde(rename = "type")]
+    typ: &'static str,
+
+    /// How many temporal chunks have data on this timeline.
+    num_chunks: u64,
+
+    /// The earliest time with data on this timeline.
+    min: i64,
+
+    /// The latest time with data on this timeline.
+    max: i64,
+
+    #[serde(skip)]
+    time_type: TimeType,
+}
+
+#[derive(Default, serde::Serialize)]
+struct ComponentStats {
+    /// Static chunks that have a column for this component.
+    num_static_chunks: u64,
+
+    /// Temporal chunks that have a column for this component.
+    num_temporal_chunks: u64,
+
+    /// Rows of static chunks where this component is present, i.e. not null.
+    num_static_rows: u64,
+
+    /// Rows of temporal chunks where this component is present, i.e. not null.
+    num_temporal_rows: u64,
+
+    /// In-memory size of the columns of this component, across all chunks.
+    size_bytes: u64,
+}
+
+impl RrdStats {
+    fn store_mut(&mut self, store_id: &StoreId) -> &mut StoreStats {
+        self.stores
+            .entry(store_id.to_string())
+            .or_insert_with(|| StoreStats {
+                kind: store_id.kind.to_string(),
+                application_id: None,
+                num_chunks: 0,
+                num_rows: 0,
+                size_bytes: 0,
+                entities: Default::default(),
+            })
+    }
+
+    fn add_msg(&mut self, msg: LogMsg) -> anyhow::Result<()> {
+        self.num_messages += 1;
+
+        match msg {
+            LogMsg::SetStoreInfo(msg) => {
+                let store = self.store_mut(&msg.info.store_id);
+                store.application_id = Some(msg.info.application_id.to_string());
+            }
+
+            LogMsg::ArrowMsg(store_id, arrow_msg) => {
+                let chunk = Chunk::from_arrow_msg(&arrow_msg).context("skipped corrupt chunk")?;
+                self.store_mut(&store_id).add_chunk(&chunk);
+            }
+
+            LogMsg::BlueprintActivationCommand(_) => {}
+        }
+
+        Ok(())
+    }
+
+    fn print(&self) {
+        let Self {
+            encoded_size_bytes,
+            num_messages,
+            stores,
+        } = self;
+
+        println!(
+            "{} messages, {} stores ({})",
+            re_format::format_uint(*num_messages),
+            re_format::format_uint(stores.len()),
+            encoded_size_bytes.map_or_else(
+                || "<unknown> encoded".to_owned(),
+                |size| format!("{} encoded", re_format::format_bytes(size as _))
+            ),
+        );
+
+        for (store_id, store) in stores {
+            println!();
+            println!(
+                "{} {store_id:?} (app: {}) - {} chunks, {} rows ({})",
+                store.kind,
+                store.application_id.as_deref().unwrap_or("<unknown>"),
+                re_format::format_uint(store.num_chunks),
+                re_format::format_uint(store.num_rows),
+                re_format::format_bytes(store.size_bytes as _),
+            );
+
+            for (entity_path, entity) in &store.entities {
+                println!(
+                    "  {entity_path} - {} static + {} temporal chunks, {} rows ({})",
+                    re_format::format_uint(entity.num_static_chunks),
+                    re_format::format_uint(entity.num_temporal_chunks),
+                    re_format::format_uint(entity.num_rows),
+                    re_format::format_bytes(entity.size_bytes as _),
+                );
+
+                for (timeline, stats) in &entity.timelines {
+                    println!(
+                        "    timeline {timeline:?}: {} ({} chunks)",
+                        stats
+                            .time_type
+                            .format_range_utc(ResolvedTimeRange::new(stats.min, stats.max)),
+                        re_format::format_uint(stats.num_chunks),
+                    );
+                }
+
+                for (component_name, component) in &entity.components {
+                    println!(
+                        "    {component_name}: {} static + {} temporal chunks, {} static + {} temporal rows ({})",
+                        re_format::format_uint(component.num_static_chunks),
+                        re_format::format_uint(component.num_temporal_chunks),
+                        re_format::format_uint(component.num_static_rows),
+                        re_format::format_uint(component.num_temporal_rows),
+                        re_format::format_bytes(component.size_bytes as _),
+                    );
+                }
+            }
+        }
+    }
+}
+
+impl StoreStats {
+    fn add_chunk(&mut self, chunk: &Chunk) {
+        let num_rows = chunk.num_rows() as u64;
+        let size_bytes = chunk.total_size_bytes();
+        let is_static = chunk.is_static();
+
+        self.num_chunks += 1;
+        self.num_rows += num_rows;
+        self.size_bytes += size_bytes;
+
+        let entity = self
+            .entities
+            .entry(chunk.entity_path().to_string())
+            .or_default();
+
+        if is_static {
+            entity.num_static_chunks += 1;
+        } else {
+            entity.num_temporal_chunks += 1;
+        }
+        entity.num_rows += num_rows;
+        entity.size_bytes += size_bytes;
+
+        for (timeline, time_column) in chunk.timelines() {
+            let time_range = time_column.time_range();
+            entity
+                .timelines
+                .entry(timeline.name().to_string())
+                .and_modify(|stats| {
+                    stats.num_chunks += 1;
+                    stats.min = stats.min.min(time_range.min().as_i64());
+                    stats.max = stats.max.max(time_range.max().as_i64());
+                })
+                .or_insert_with(|| TimelineStats {
+                    typ: match timeline.typ() {
+                        TimeType::Time => "time",
+                        TimeType::Sequence => "sequence",
+                    },
+                    num_chunks: 1,
+                    min: time_range.min().as_i64(),
+                    max: time_range.max().as_i64(),
+                    time_type: timeline.typ(),
+                });
+        }
+
+        for (component_desc, list_array) in chunk.components().iter_flattened() {
+            let component = entity
+                .components
+                .entry(component_desc.component_name.to_string())
+                .or_default();
+
+            let num_rows = (list_array.len() - list_array.null_count()) as u64;
+            if is_static {
+                component.num_static_chunks += 1;
+                component.num_static_rows += num_rows;
+            } else {
+                component.num_temporal_chunks += 1;
+                component.num_temporal_rows += num_rows;
+            }
+            component.size_bytes += list_array.total_size_bytes();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used)] // acceptable for tests
+
+    use re_chunk::RowId;
+    use re_log_types::{
+        ApplicationId, SetStoreInfo, StoreInfo, StoreKind, StoreSource, Time, TimePoint, Timeline,
+    };
+    use re_types::{
+        components::{Color, Position3D},
+        Component as _,
+    };
+
+    use super::*;
+
+    fn arrow_msg(store_id: &StoreId, chunk: &Chunk) -> LogMsg {
+        LogMsg::ArrowMsg(store_id.clone(), chunk.to_arrow_msg().unwrap())
+    }
+
+    #[test]
+    fn aggregation() {
+        let store_id = StoreId::random(StoreKind::Recording);
+        let other_store_id = StoreId::random(StoreKind::Blueprint);
+        let frame = Timeline::new_sequence("frame");
+
+        let positions = [Position3D::new(1.0, 2.0, 3.0)];
+        let colors = [Color::from_rgb(255, 0, 0)];
+
+        let static_chunk = Chunk::builder("points".into())
+            .with_component_batches(
+                RowId::new(),
+                TimePoint::default(),
+                [&positions as _, &colors as _],
+            )
+            .build()
+            .unwrap();
+
+        // Three rows on `frame`, with a color on only one of them.
+        let temporal_chunk = Chunk::builder("points".into())
+            .with_sparse_component_batches(
+                RowId::new(),
+                [(frame, 3_i64)],
+                [
+                    (Position3D::descriptor(), Some(&positions as _)),
+                    (Color::descriptor(), Some(&colors as _)),
+                ],
+            )
+            .with_sparse_component_batches(
+                RowId::new(),
+                [(frame, 1_i64)],
+                [
+                    (Position3D::descriptor(), Some(&positions as _)),
+                    (Color::descriptor(), None),
+                ],
+            )
+            .with_sparse_component_batches(
+                RowId::new(),
+                [(frame, 2_i64)],
+                [
+                    (Position3D::descriptor(), Some(&positions as _)),
+                    (Color::descriptor(), None),
+                ],
+            )
+            .build()
+            .unwrap();
+
+        let other_temporal_chunk = Chunk::builder("points".into())
+            .with_component_batches(RowId::new(), [(frame, 10_i64)], [&positions as _])
+            .build()
+            .unwrap();
+
+        let mut stats = RrdStats::default();
+        let messages = [
+            LogMsg::SetStoreInfo(SetStoreInfo {
+                row_id: *RowId::new(),
+                info: StoreInfo {
+                    application_id: ApplicationId("test_app".to_owned()),
+                    store_id: store_id.clone(),
+                    cloned_from: None,
+                    is_official_example: false,
+                    started: Time::now(),
+                    store_source: StoreSource::Unknown,
+                    store_version: None,
+                },
+            }),
+            arrow_msg(&store_id, &static_chunk),
+            arrow_msg(&store_id, &temporal_chunk),
+            arrow_msg(&store_id, &other_temporal_chunk),
+            arrow_msg(&other_store_id, &other_temporal_chunk),
+        ];
+        for msg in messages {
+            stats.add_msg(msg).unwrap();
+        }
+
+        assert_eq!(5, stats.num_messages);
+        assert_eq!(2, stats.stores.len());
+
+        let store = &stats.stores[&store_id.to_string()];
+        assert_eq!(Some("test_app"), store.application_id.as_deref());
+        assert_eq!(3, store.num_chunks);
+        assert_eq!(1 + 3 + 1, store.num_rows);
+        assert_eq!(
+            static_chunk.total_size_bytes()
+                + temporal_chunk.total_size_bytes()
+                + other_temporal_chunk.total_size_bytes(),
+            store.size_bytes
+        );
+
+        let entity = &store.entities["/points"];
+        assert_eq!(1, entity.num_static_chunks);
+        assert_eq!(2, entity.num_temporal_chunks);
+        assert_eq!(store.num_rows, entity.num_rows);
+        assert_eq!(store.size_bytes, entity.size_bytes);
+
+        let timeline = &entity.timelines["frame"];
+        assert_eq!("sequence", timeline.typ);
+        assert_eq!(2, timeline.num_chunks);
+        assert_eq!((1, 10), (timeline.min, timeline.max));
+
+        let position = &entity.components[&Position3D::name().to_string()];
+        assert_eq!(1, position.num_static_chunks);
+        assert_eq!(2, position.num_temporal_chunks);
+        assert_eq!(1, position.num_static_rows);
+        assert_eq!(3 + 1, position.num_temporal_rows);
+
+        let color = &entity.components[&Color::name().to_string()];
+        assert_eq!(1, color.num_static_chunks);
+        assert_eq!(1, color.num_temporal_chunks);
+        assert_eq!(1, color.num_static_rows);
+        assert_eq!(1, color.num_temporal_rows);
+
+        // Stores are kept apart.
+        let other_store = &stats.stores[&other_store_id.to_string()];
+        assert_eq!(None, other_store.application_id);
+        assert_eq!(1, other_store.num_chunks);
+        assert_eq!(1, other_store.num_rows);
+        assert_eq!(1, other_store.entities["/points"].num_temporal_chunks);
+    }
+}
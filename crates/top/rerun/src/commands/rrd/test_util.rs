@@ -0,0 +1,113 @@
+//! Helpers shared by the tests of the `rrd` subcommands.
+
+#![allow(clippy::unwrap_used)] // acceptable for tests
+
+use std::path::Path;
+
+use re_build_info::CrateVersion;
+use re_chunk::{Chunk, RowId};
+use re_log_types::{
+    ApplicationId, LogMsg, SetStoreInfo, StoreId, StoreInfo, StoreKind, StoreSource, Time,
+    TimePoint, Timeline,
+};
+use re_types::components::{Color, Position3D};
+
+/// A small recording: its store info, one static chunk, then `num_chunks` temporal chunks of
+/// `rows_per_chunk` rows each, on both `frame` and `log_time`.
+///
+/// Frames increase monotonically across chunks, starting at 0.
+pub(crate) fn fake_recording(
+    application_id: &str,
+    num_chunks: i64,
+    rows_per_chunk: i64,
+) -> Vec<LogMsg> {
+    let store_id = StoreId::random(StoreKind::Recording);
+
+    let mut messages = vec![
+        LogMsg::SetStoreInfo(SetStoreInfo {
+            row_id: *RowId::new(),
+            info: StoreInfo {
+                application_id: ApplicationId(application_id.to_owned()),
+                store_id: store_id.clone(),
+                cloned_from: None,
+                is_official_example: false,
+                started: Time::from_ns_since_epoch(0),
+                store_source: StoreSource::Unknown,
+                store_version: Some(CrateVersion::LOCAL),
+            },
+        }),
+        LogMsg::ArrowMsg(
+            store_id.clone(),
+            Chunk::builder("static".into())
+                .with_component_batches(
+                    RowId::new(),
+                    TimePoint::default(),
+                    [&[Color::from_rgb(255, 0, 0)] as _],
+                )
+                .build()
+                .unwrap()
+                .to_arrow_msg()
+                .unwrap(),
+        ),
+    ];
+
+    for chunk_index in 0..num_chunks {
+        let mut builder = Chunk::builder("points".into());
+        for row in 0..rows_per_chunk {
+            let frame = chunk_index * rows_per_chunk + row;
+            builder = builder.with_component_batches(
+                RowId::new(),
+                [
+                    (Timeline::new_sequence("frame"), frame),
+                    (Timeline::log_time(), 1_000_000 * frame),
+                ],
+                [&[Position3D::new(frame as f32, 0.0, 0.0)] as _],
+            );
+        }
+        messages.push(LogMsg::ArrowMsg(
+            store_id.clone(),
+            builder.build().unwrap().to_arrow_msg().unwrap(),
+        ));
+    }
+
+    messages
+}
+
+pub(crate) fn write_rrd(path: &Path, messages: &[LogMsg]) {
+    let file = std::fs::File::create(path).unwrap();
+    re_log_encoding::encoder::encode_ref(
+        CrateVersion::LOCAL,
+        re_log_encoding::EncodingOptions::MSGPACK_COMPRESSED,
+        messages.iter().map(Ok),
+        &mut std::io::BufWriter::new(file),
+    )
+    .unwrap();
+}
+
+pub(crate) fn read_rrd(path: &Path) -> Vec<LogMsg> {
+    let file = std::fs::File::open(path).unwrap();
+    re_log_encoding::decoder::Decoder::new(
+        re_log_encoding::VersionPolicy::Error,
+        std::io::BufReader::new(file),
+    )
+    .unwrap()
+    .collect::<Result<Vec<_>, _>>()
+    .unwrap()
+}
+
+pub(crate) fn path_to_string(path: &Path) -> String {
+    path.to_string_lossy().to_string()
+}
+
+/// The chunks of all the `ArrowMsg`s in `messages`, in order.
+///
+/// Comparing chunks rather than messages ignores differences in arrow metadata.
+pub(crate) fn chunks(messages: &[LogMsg]) -> Vec<Chunk> {
+    messages
+        .iter()
+        .filter_map(|msg| match msg {
+            LogMsg::ArrowMsg(_, arrow_msg) => Some(Chunk::from_arrow_msg(arrow_msg).unwrap()),
+            LogMsg::SetStoreInfo(_) | LogMsg::BlueprintActivationCommand(_) => None,
+        })
+        .collect()
+}
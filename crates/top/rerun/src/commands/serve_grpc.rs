@@ -0,0 +1,62 @@
+use anyhow::Context as _;
+
+// ---
+
+#[derive(Debug, Clone, clap::Parser)]
+pub struct ServeGrpcCommand {
+    /// Directory to index.
+    ///
+    /// All the .rrd files found in there (recursively) are loaded in memory and served.
+    path_to_directory: String,
+
+    /// What bind address IP to use.
+    #[clap(long, default_value = "0.0.0.0")]
+    bind: String,
+
+    /// What port to listen to for incoming gRPC connections.
+    #[clap(long, default_value_t = re_grpc_server::DEFAULT_SERVER_PORT)]
+    port: u16,
+
+    /// Let clients register additional .rrd files, as long as they live within the indexed
+    /// directory.
+    ///
+    /// Disabled by default, since it lets clients make the server read arbitrary files.
+    #[clap(long)]
+    allow_register: bool,
+}
+
+impl ServeGrpcCommand {
+    pub fn run(&self) -> anyhow::Result<()> {
+        let Self {
+            path_to_directory,
+            bind,
+            port,
+            allow_register,
+        } = self;
+
+        let addr: std::net::SocketAddr = format!("{bind}:{port}")
+            .parse()
+            .with_context(|| format!("invalid bind address {bind:?}"))?;
+
+        let catalog = re_grpc_server::Catalog::from_directory(path_to_directory)?;
+        let mut service = re_grpc_server::StorageNodeService::new(catalog);
+        if *allow_register {
+            service = service.with_registration_root(path_to_directory);
+        }
+
+        let serve = re_grpc_server::serve(addr, service);
+
+        // The `rerun` binary may or may not already be running inside a tokio runtime, depending
+        // on which features it was compiled with.
+        if let Ok(handle) = tokio::runtime::Handle::try_current() {
+            tokio::task::block_in_place(|| handle.block_on(serve))?;
+        } else {
+            tokio::runtime::Builder::new_multi_thread()
+                .enable_all()
+                .build()?
+                .block_on(serve)?;
+        }
+
+        Ok(())
+    }
+}
@@ -165,6 +165,20 @@ pub const EXTERNAL_DATA_LOADER_PREFIX: &str = "rerun-loader-";
 // NOTE: this constant is duplicated in `re_data_source` to avoid an extra dependency here.
 pub const EXTERNAL_DATA_LOADER_INCOMPATIBLE_EXIT_CODE: i32 = 66;
 
+/// When an external `DataLoader` is executed with this flag (and nothing else), it can describe
+/// itself by printing a JSON manifest on its standard output, e.g.
+/// `{"extensions": ["rs"], "priority": 0}`.
+///
+/// The viewer will then only execute it for the files it claims to support.
+// NOTE: this constant is duplicated in `re_data_loader` to avoid an extra dependency here.
+pub const EXTERNAL_DATA_LOADER_DESCRIBE_FLAG: &str = "--describe";
+
+/// Lines that an external `DataLoader` writes to its standard error and that start with this
+/// prefix are parsed as JSON status updates (progress, warnings & errors), and surfaced in the
+/// viewer, e.g. `rerun-loader-status: {"type": "progress", "message": "Decoding", "fraction": 0.5}`.
+// NOTE: this constant is duplicated in `re_data_loader` to avoid an extra dependency here.
+pub const EXTERNAL_DATA_LOADER_STATUS_PREFIX: &str = "rerun-loader-status:";
+
 /// Re-exports of other crates.
 pub mod external {
     pub use anyhow;
@@ -0,0 +1,85 @@
+//! Shared-secret authentication helpers for the Rerun servers.
+//!
+//! The SDK TCP and gRPC servers, as well as the WebSocket server, can all require clients to
+//! present an auth token: this is where they check it.
+
+/// What auth tokens are replaced with in logs and urls meant for humans, see [`redact_query_param`].
+pub const REDACTED: &str = "<redacted>";
+
+/// Compares two auth tokens in constant time with respect to their content.
+///
+/// Only the length of the expected token can leak through timing.
+pub fn tokens_match(presented: &str, expected: &str) -> bool {
+    let (presented, expected) = (presented.as_bytes(), expected.as_bytes());
+    presented.len() == expected.len()
+        && presented
+            .iter()
+            .zip(expected)
+            .fold(0, |acc, (a, b)| acc | (a ^ b))
+            == 0
+}
+
+/// Replaces the value of every `key` query parameter in `url` with [`REDACTED`].
+///
+/// Also applies to urls nested in the query of another one, e.g.
+/// `http://localhost:9090?url=ws://localhost:9877?token=secret`.
+pub fn redact_query_param(url: &str, key: &str) -> String {
+    let mut redacted = String::with_capacity(url.len());
+    let mut rest = url;
+
+    while let Some(pos) = rest.find(['?', '&']) {
+        let (before, after) = rest.split_at(pos + 1);
+        redacted.push_str(before);
+        rest = after;
+
+        if let Some(value) = rest
+            .strip_prefix(key)
+            .and_then(|rest| rest.strip_prefix('='))
+        {
+            redacted.push_str(key);
+            redacted.push('=');
+            redacted.push_str(REDACTED);
+            rest = &value[value.find('&').unwrap_or(value.len())..];
+        }
+    }
+    redacted.push_str(rest);
+
+    redacted
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn tokens() {
+        assert!(tokens_match("secret", "secret"));
+        assert!(!tokens_match("secreT", "secret"));
+        assert!(!tokens_match("secret!", "secret"));
+        assert!(!tokens_match("", "secret"));
+        assert!(tokens_match("", ""));
+    }
+
+    #[test]
+    fn redaction() {
+        assert_eq!(
+            "ws://localhost:9877",
+            redact_query_param("ws://localhost:9877", "token")
+        );
+        assert_eq!(
+            "ws://localhost:9877?token=<redacted>",
+            redact_query_param("ws://localhost:9877?token=secret", "token")
+        );
+        assert_eq!(
+            "http://localhost:9090?url=ws://localhost:9877?token=<redacted>&renderer=webgl",
+            redact_query_param(
+                "http://localhost:9090?url=ws://localhost:9877?token=secret&renderer=webgl",
+                "token"
+            )
+        );
+        assert_eq!(
+            "ws://localhost:9877?a=1&token=<redacted>&tokens=2",
+            redact_query_param("ws://localhost:9877?a=1&token=secret&tokens=2", "token")
+        );
+    }
+}
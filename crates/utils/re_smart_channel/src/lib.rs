@@ -60,6 +60,12 @@ pub enum SmartChannelSource {
     /// We are a TCP server listening on this port.
     TcpServer { port: u16 },
 
+    /// The channel was created in the context of receiving data from one or more Rerun SDKs
+    /// over gRPC, using the `WriteMessages` streaming RPC.
+    ///
+    /// We are a gRPC server listening on this port.
+    GrpcServer { port: u16 },
+
     /// The channel was created in the context of streaming in RRD data from standard input.
     Stdin,
 
@@ -80,6 +86,7 @@ impl std::fmt::Display for SmartChannelSource {
             Self::Sdk => "SDK".fmt(f),
             Self::WsClient { ws_server_url } => ws_server_url.fmt(f),
             Self::TcpServer { port } => write!(f, "TCP server, port {port}"),
+            Self::GrpcServer { port } => write!(f, "gRPC server, port {port}"),
             Self::Stdin => "Standard input".fmt(f),
         }
     }
@@ -93,6 +100,7 @@ impl SmartChannelSource {
             | Self::WsClient { .. }
             | Self::JsChannel { .. }
             | Self::TcpServer { .. }
+            | Self::GrpcServer { .. }
             | Self::RerunGrpcStream { .. } => true,
         }
     }
@@ -147,6 +155,12 @@ pub enum SmartMessageSource {
         addr: Option<std::net::SocketAddr>,
     },
 
+    /// The sender is a gRPC client streaming log messages to us.
+    GrpcClient {
+        // NOTE: Optional as the transport doesn't always know the peer's address.
+        addr: Option<std::net::SocketAddr>,
+    },
+
     /// The data is streaming in from standard input.
     Stdin,
 
@@ -171,6 +185,10 @@ impl std::fmt::Display for SmartMessageSource {
                 "tcp://{}",
                 addr.map_or_else(|| "(unknown ip)".to_owned(), |addr| addr.to_string())
             ),
+            Self::GrpcClient { addr } => format!(
+                "grpc://{}",
+                addr.map_or_else(|| "(unknown ip)".to_owned(), |addr| addr.to_string())
+            ),
             Self::Stdin => "stdin".into(),
         })
     }
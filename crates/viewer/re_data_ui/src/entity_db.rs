@@ -103,12 +103,13 @@ impl crate::DataUi for EntityDb {
             }
 
             {
-                let &ChunkStoreConfig {
+                let ChunkStoreConfig {
                     enable_changelog: _,
                     chunk_max_bytes,
                     chunk_max_rows,
                     chunk_max_rows_if_unsorted,
-                } = self.storage_engine().store().config();
+                    spill_directory,
+                } = self.storage_engine().store().config().clone();
 
                 ui.grid_left_hand_label("Compaction");
                 ui.label(format!(
@@ -153,6 +154,18 @@ impl crate::DataUi for EntityDb {
                     )),
                 );
                 ui.end_row();
+
+                if let Some(spill_directory) = spill_directory {
+                    ui.grid_left_hand_label("Spill directory");
+                    ui.label(spill_directory.display().to_string())
+                        .on_hover_text(format!(
+                            "Data evicted from memory is written to this directory, and read back \
+                             from disk when needed.\n\
+                             Configured with the {} environment variable.",
+                            ChunkStoreConfig::ENV_CHUNK_SPILL_DIR,
+                        ));
+                    ui.end_row();
+                }
             }
 
             if let Some(data_source) = &self.data_source {
@@ -135,7 +135,11 @@ mode sets the default time range to _everything_. You can override this in the s
             .collect();
 
         let sparse_fill_strategy = if view_query.latest_at_enabled()? {
-            SparseFillStrategy::LatestAtGlobal
+            if view_query.latest_at_within_range()? {
+                SparseFillStrategy::LatestAtView
+            } else {
+                SparseFillStrategy::LatestAtGlobal
+            }
         } else {
             SparseFillStrategy::None
         };
@@ -145,6 +149,7 @@ mode sets the default time range to _everything_. You can override this in the s
             filtered_index: Some(view_query.timeline(ctx)?),
             filtered_index_range: Some(view_query.filter_by_range()?),
             filtered_is_not_null: view_query.filter_is_not_null()?,
+            filtered_is_not_null_mode: view_query.filter_is_not_null_mode()?,
             sparse_fill_strategy,
             selection: None,
 
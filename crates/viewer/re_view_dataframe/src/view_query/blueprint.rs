@@ -2,7 +2,9 @@ use std::collections::HashSet;
 
 use crate::dataframe_ui::HideColumnAction;
 use crate::view_query::Query;
-use re_chunk_store::{ColumnDescriptor, ColumnSelector, ComponentColumnSelector};
+use re_chunk_store::{
+    ColumnDescriptor, ColumnSelector, ComponentColumnSelector, FilterIsNotNullMode,
+};
 use re_log_types::{EntityPath, ResolvedTimeRange, TimelineName};
 use re_types::blueprint::{components, datatypes};
 use re_viewer_context::{ViewSystemExecutionError, ViewerContext};
@@ -75,34 +77,63 @@ impl Query {
     /// Get the filter column for the filter-is-not-null feature, if active.
     pub(crate) fn filter_is_not_null(
         &self,
-    ) -> Result<Option<ComponentColumnSelector>, ViewSystemExecutionError> {
+    ) -> Result<Vec<ComponentColumnSelector>, ViewSystemExecutionError> {
         Ok(self
             .filter_is_not_null_raw()?
+            .into_iter()
             .filter(|filter_is_not_null| filter_is_not_null.active())
             .map(|filter| {
                 ComponentColumnSelector::new_for_component_name(
                     filter.entity_path(),
                     filter.component_name(),
                 )
-            }))
+            })
+            .collect())
     }
 
-    /// Get the raw [`components::FilterIsNotNull`] struct (for ui purposes).
+    /// Get the raw [`components::FilterIsNotNull`] structs (for ui purposes).
     pub(super) fn filter_is_not_null_raw(
         &self,
-    ) -> Result<Option<components::FilterIsNotNull>, ViewSystemExecutionError> {
+    ) -> Result<Vec<components::FilterIsNotNull>, ViewSystemExecutionError> {
         Ok(self
             .query_property
-            .component_or_empty::<components::FilterIsNotNull>()?)
+            .component_array_or_empty::<components::FilterIsNotNull>()?)
     }
 
     pub(super) fn save_filter_is_not_null(
         &self,
         ctx: &ViewerContext<'_>,
-        filter_is_not_null: &components::FilterIsNotNull,
+        filter_is_not_null: &[components::FilterIsNotNull],
     ) {
         self.query_property
-            .save_blueprint_component(ctx, filter_is_not_null);
+            .save_blueprint_component(ctx, &filter_is_not_null);
+    }
+
+    pub(crate) fn filter_is_not_null_mode(
+        &self,
+    ) -> Result<FilterIsNotNullMode, ViewSystemExecutionError> {
+        Ok(match self.filter_is_not_null_mode_raw()? {
+            components::FilterIsNotNullMode::All => FilterIsNotNullMode::All,
+            components::FilterIsNotNullMode::Any => FilterIsNotNullMode::Any,
+        })
+    }
+
+    /// Get the raw [`components::FilterIsNotNullMode`] (for ui purposes).
+    pub(super) fn filter_is_not_null_mode_raw(
+        &self,
+    ) -> Result<components::FilterIsNotNullMode, ViewSystemExecutionError> {
+        Ok(self
+            .query_property
+            .component_or_empty::<components::FilterIsNotNullMode>()?
+            .unwrap_or_default())
+    }
+
+    pub(super) fn save_filter_is_not_null_mode(
+        &self,
+        ctx: &ViewerContext<'_>,
+        mode: components::FilterIsNotNullMode,
+    ) {
+        self.query_property.save_blueprint_component(ctx, &mode);
     }
 
     pub(crate) fn latest_at_enabled(&self) -> Result<bool, ViewSystemExecutionError> {
@@ -117,6 +148,18 @@ impl Query {
             .save_blueprint_component(ctx, &components::ApplyLatestAt(enabled.into()));
     }
 
+    pub(crate) fn latest_at_within_range(&self) -> Result<bool, ViewSystemExecutionError> {
+        Ok(self
+            .query_property
+            .component_or_empty::<components::LatestAtWithinRange>()?
+            .map_or(false, |comp| *comp.0))
+    }
+
+    pub(crate) fn save_latest_at_within_range(&self, ctx: &ViewerContext<'_>, within_range: bool) {
+        self.query_property
+            .save_blueprint_component(ctx, &components::LatestAtWithinRange(within_range.into()));
+    }
+
     pub(super) fn save_selected_columns(
         &self,
         ctx: &ViewerContext<'_>,
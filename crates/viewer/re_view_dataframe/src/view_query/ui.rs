@@ -1,5 +1,6 @@
 use std::collections::{BTreeSet, HashSet};
 
+use itertools::Itertools as _;
 use re_chunk_store::{ColumnDescriptor, ColumnSelector};
 use re_log_types::{
     EntityPath, ResolvedTimeRange, TimeInt, TimeType, TimeZone, Timeline, TimelineName,
@@ -138,119 +139,151 @@ impl Query {
         //
 
         let original_filter_is_not_null = self.filter_is_not_null_raw()?;
+        let original_mode = self.filter_is_not_null_mode_raw()?;
 
-        let (mut active, filter_entity, filter_component) = original_filter_is_not_null
-            .as_ref()
+        // The active flag is stored on each individual filter, but they are all toggled at once.
+        let mut active = original_filter_is_not_null
+            .iter()
+            .any(|filter| filter.active());
+
+        let all_entities = all_pov_entities_for_view(ctx, view_id, timeline);
+
+        // There is always at least one column to pick from, even when the filter is inactive.
+        let mut columns = original_filter_is_not_null
+            .iter()
             .map(|filter| {
-                (
-                    filter.active(),
+                filter_is_not_null_column(
+                    ctx,
+                    timeline,
+                    &all_entities,
                     Some(filter.entity_path()),
                     Some(filter.component_name()),
                 )
             })
-            .unwrap_or((false, None, None));
+            .collect_vec();
+        if columns.is_empty() {
+            columns.push(filter_is_not_null_column(
+                ctx,
+                timeline,
+                &all_entities,
+                None,
+                None,
+            ));
+        }
 
         //
         // Filter active?
         //
 
-        ui.re_checkbox(&mut active, "Filter rows where column is not null:");
+        ui.re_checkbox(&mut active, "Filter rows where columns are not null:");
 
         //
-        // Filter entity
+        // UI for filter entities and components
         //
 
-        let all_entities = all_pov_entities_for_view(ctx, view_id, timeline);
-
-        let mut filter_entity = filter_entity
-            .and_then(|entity| all_entities.contains(&entity).then_some(entity))
-            .or_else(|| all_entities.iter().next().cloned())
-            .unwrap_or_else(|| EntityPath::from("/"));
+        let mut mode = original_mode;
 
-        //
-        // Filter component
-        //
+        ui.add_enabled_ui(active, |ui| {
+            ui.spacing_mut().item_spacing.y = 0.0;
 
-        let all_components = ctx
-            .recording_engine()
-            .store()
-            .all_components_on_timeline_sorted(timeline, &filter_entity)
-            .unwrap_or_default();
-
-        // The list of suggested components is build as follows:
-        // - consider all indicator components
-        // - for the matching archetypes, take all required components
-        // - keep those that are actually present
-        let suggested_components = || {
-            all_components
-                .iter()
-                .filter_map(|c| {
-                    c.indicator_component_archetype()
-                        .and_then(|archetype_short_name| {
-                            ctx.reflection
-                                .archetype_reflection_from_short_name(&archetype_short_name)
-                        })
-                })
-                .flat_map(|archetype_reflection| {
-                    archetype_reflection
-                        .required_fields()
-                        .map(|field| field.component_name)
-                })
-                .filter(|c| all_components.contains(c))
-                .collect::<ComponentNameSet>()
-        };
+            let can_remove = columns.len() > 1;
+            let mut removed_column_idx = None;
 
-        // If the currently saved component, we auto-switch it to a reasonable one.
-        let mut filter_component = filter_component
-            .and_then(|component| all_components.contains(&component).then_some(component))
-            .or_else(|| suggested_components().first().copied())
-            .unwrap_or_else(|| ComponentName::from("-"));
+            for (column_idx, (filter_entity, filter_component)) in columns.iter_mut().enumerate() {
+                let all_components = ctx
+                    .recording_engine()
+                    .store()
+                    .all_components_on_timeline_sorted(timeline, filter_entity)
+                    .unwrap_or_default();
 
-        //
-        // UI for filter entity and component
-        //
+                ui.list_item_flat_noninteractive(
+                    list_item::PropertyContent::new("Entity")
+                        .action_button_with_enabled(&re_ui::icons::REMOVE, can_remove, || {
+                            removed_column_idx = Some(column_idx);
+                        })
+                        .value_fn(|ui, _| {
+                            egui::ComboBox::new(("pov_entity", column_idx), "")
+                                .selected_text(filter_entity.to_string())
+                                .show_ui(ui, |ui| {
+                                    for entity in &all_entities {
+                                        let label = entity.to_string();
+                                        ui.selectable_value(filter_entity, entity.clone(), label);
+                                    }
+                                });
+                        }),
+                );
+
+                ui.list_item_flat_noninteractive(
+                    list_item::PropertyContent::new("Component").value_fn(|ui, _| {
+                        egui::ComboBox::new(("pov_component", column_idx), "")
+                            .selected_text(filter_component.short_name())
+                            .show_ui(ui, |ui| {
+                                for component in all_components {
+                                    let label = component.short_name();
+                                    ui.selectable_value(filter_component, component, label);
+                                }
+                            });
+                    }),
+                );
+            }
 
-        ui.add_enabled_ui(active, |ui| {
-            ui.spacing_mut().item_spacing.y = 0.0;
+            if let Some(column_idx) = removed_column_idx {
+                columns.remove(column_idx);
+            }
 
-            ui.list_item_flat_noninteractive(list_item::PropertyContent::new("Entity").value_fn(
-                |ui, _| {
-                    egui::ComboBox::new("pov_entity", "")
-                        .selected_text(filter_entity.to_string())
-                        .show_ui(ui, |ui| {
-                            for entity in all_entities {
-                                let label = entity.to_string();
-                                ui.selectable_value(&mut filter_entity, entity, label);
-                            }
-                        });
-                },
-            ));
+            if ui
+                .small_icon_button(&re_ui::icons::ADD)
+                .on_hover_text("Add a column to filter on")
+                .clicked()
+            {
+                columns.push(filter_is_not_null_column(
+                    ctx,
+                    timeline,
+                    &all_entities,
+                    None,
+                    None,
+                ));
+            }
 
-            ui.list_item_flat_noninteractive(
-                list_item::PropertyContent::new("Component").value_fn(|ui, _| {
-                    egui::ComboBox::new("pov_component", "")
-                        .selected_text(filter_component.short_name())
-                        .show_ui(ui, |ui| {
-                            for component in all_components {
-                                let label = component.short_name();
-                                ui.selectable_value(&mut filter_component, component, label);
-                            }
+            if columns.len() > 1 {
+                ui.list_item_flat_noninteractive(
+                    list_item::PropertyContent::new("Keep rows where").value_fn(|ui, _| {
+                        ui.horizontal(|ui| {
+                            ui.re_radio_value(
+                                &mut mode,
+                                components::FilterIsNotNullMode::All,
+                                "all are not null",
+                            );
+                            ui.re_radio_value(
+                                &mut mode,
+                                components::FilterIsNotNullMode::Any,
+                                "any is not null",
+                            );
                         });
-                }),
-            );
+                    }),
+                );
+            }
         });
 
         //
         // Save filter if changed
         //
 
-        let filter_is_not_null =
-            components::FilterIsNotNull::new(active, &filter_entity, filter_component);
+        let filter_is_not_null = columns
+            .iter()
+            .map(|(filter_entity, filter_component)| {
+                components::FilterIsNotNull::new(active, filter_entity, *filter_component)
+            })
+            .collect_vec();
 
-        if original_filter_is_not_null.as_ref() != Some(&filter_is_not_null) {
+        if original_filter_is_not_null != filter_is_not_null {
             self.save_filter_is_not_null(ctx, &filter_is_not_null);
         }
 
+        if original_mode != mode {
+            self.save_filter_is_not_null_mode(ctx, mode);
+        }
+
         Ok(())
     }
 
@@ -410,10 +443,78 @@ impl Query {
             self.save_latest_at_enabled(ctx, latest_at);
         }
 
+        let mut within_range = self.latest_at_within_range()?;
+        let changed = ui
+            .add_enabled_ui(latest_at, |ui| {
+                ui.re_checkbox(&mut within_range, "Only use values within the range")
+                    .on_hover_text(
+                        "Never fill empty cells with values logged before the start of the \
+                        filtered range.",
+                    )
+                    .changed()
+            })
+            .inner;
+
+        if changed {
+            self.save_latest_at_within_range(ctx, within_range);
+        }
+
         Ok(())
     }
 }
 
+/// Resolve a "filter is not null" column to an entity and component that actually exist.
+///
+/// If the currently saved entity or component is invalid, we auto-switch to a reasonable one.
+fn filter_is_not_null_column(
+    ctx: &ViewerContext<'_>,
+    timeline: &Timeline,
+    all_entities: &BTreeSet<EntityPath>,
+    filter_entity: Option<EntityPath>,
+    filter_component: Option<ComponentName>,
+) -> (EntityPath, ComponentName) {
+    let filter_entity = filter_entity
+        .and_then(|entity| all_entities.contains(&entity).then_some(entity))
+        .or_else(|| all_entities.iter().next().cloned())
+        .unwrap_or_else(|| EntityPath::from("/"));
+
+    let all_components = ctx
+        .recording_engine()
+        .store()
+        .all_components_on_timeline_sorted(timeline, &filter_entity)
+        .unwrap_or_default();
+
+    // The list of suggested components is build as follows:
+    // - consider all indicator components
+    // - for the matching archetypes, take all required components
+    // - keep those that are actually present
+    let suggested_components = || {
+        all_components
+            .iter()
+            .filter_map(|c| {
+                c.indicator_component_archetype()
+                    .and_then(|archetype_short_name| {
+                        ctx.reflection
+                            .archetype_reflection_from_short_name(&archetype_short_name)
+                    })
+            })
+            .flat_map(|archetype_reflection| {
+                archetype_reflection
+                    .required_fields()
+                    .map(|field| field.component_name)
+            })
+            .filter(|c| all_components.contains(c))
+            .collect::<ComponentNameSet>()
+    };
+
+    let filter_component = filter_component
+        .and_then(|component| all_components.contains(&component).then_some(component))
+        .or_else(|| suggested_components().first().copied())
+        .unwrap_or_else(|| ComponentName::from("-"));
+
+    (filter_entity, filter_component)
+}
+
 /// Gather all entities that can meaningfully be used as point-of-view for this view.
 ///
 /// Meaning:
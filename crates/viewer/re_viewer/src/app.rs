@@ -43,6 +43,10 @@ pub struct StartupOptions {
     /// When the total process RAM reaches this limit, we GC old data.
     pub memory_limit: re_memory::MemoryLimit,
 
+    /// If set, we continuously GC the data that falls outside of this time span, regardless of
+    /// memory use.
+    pub time_limit: Option<TimeLimit>,
+
     pub persist_state: bool,
 
     /// Whether or not the app is running in the context of a Jupyter Notebook.
@@ -113,6 +117,7 @@ impl Default for StartupOptions {
     fn default() -> Self {
         Self {
             memory_limit: re_memory::MemoryLimit::from_fraction_of_total(0.75),
+            time_limit: None,
             persist_state: true,
             is_in_notebook: false,
 
@@ -142,6 +147,24 @@ impl Default for StartupOptions {
     }
 }
 
+/// Only keep the latest `time_span` worth of data on a given timeline.
+///
+/// See [`StartupOptions::time_limit`].
+#[derive(Clone, Debug)]
+pub struct TimeLimit {
+    /// Name of the timeline, e.g. `log_time`.
+    ///
+    /// Recordings without that timeline are never affected.
+    pub timeline: re_log_types::TimelineName,
+
+    /// In the native unit of the timeline, i.e. nanoseconds for temporal timelines and ticks for
+    /// sequence timelines.
+    pub time_span: i64,
+}
+
+/// How often we check for data that falls outside of [`StartupOptions::time_limit`].
+const TIME_LIMIT_GC_INTERVAL: std::time::Duration = std::time::Duration::from_secs(1);
+
 // ----------------------------------------------------------------------------
 
 #[cfg(not(target_arch = "wasm32"))]
@@ -199,6 +222,9 @@ pub struct App {
     memory_panel: crate::memory_panel::MemoryPanel,
     memory_panel_open: bool,
 
+    /// Last time we ran the GC because of [`StartupOptions::time_limit`].
+    last_time_limit_gc: web_time::Instant,
+
     egui_debug_panel_open: bool,
 
     pub(crate) latest_queue_interest: web_time::Instant,
@@ -338,6 +364,8 @@ impl App {
             memory_panel: Default::default(),
             memory_panel_open: false,
 
+            last_time_limit_gc: web_time::Instant::now(),
+
             egui_debug_panel_open: false,
 
             latest_queue_interest: long_time_ago,
@@ -470,6 +498,7 @@ impl App {
                     | SmartChannelSource::RrdWebEventListener
                     | SmartChannelSource::Sdk
                     | SmartChannelSource::TcpServer { .. }
+                    | SmartChannelSource::GrpcServer { .. }
                     | SmartChannelSource::Stdin => true,
                 });
             }
@@ -998,6 +1027,7 @@ impl App {
                 self.memory_panel.ui(
                     ui,
                     &self.startup_options.memory_limit,
+                    self.startup_options.time_limit.as_ref(),
                     gpu_resource_stats,
                     store_stats,
                 );
@@ -1324,6 +1354,28 @@ impl App {
         }
     }
 
+    fn purge_old_data_if_needed(&mut self, store_hub: &mut StoreHub) {
+        let Some(TimeLimit {
+            timeline,
+            time_span,
+        }) = &self.startup_options.time_limit
+        else {
+            return;
+        };
+
+        if self.last_time_limit_gc.elapsed() < TIME_LIMIT_GC_INTERVAL {
+            return;
+        }
+        self.last_time_limit_gc = web_time::Instant::now();
+
+        re_tracing::profile_function!();
+
+        re_log::debug_once!(
+            "Time limit of {time_span} on timeline {timeline:?} is set, dropping older data."
+        );
+        store_hub.purge_older_than(timeline, *time_span);
+    }
+
     fn purge_memory_if_needed(&mut self, store_hub: &mut StoreHub) {
         re_tracing::profile_function!();
 
@@ -1507,7 +1559,7 @@ impl App {
                     return true; // We expect data soon, so fade-in
                 }
 
-                SmartChannelSource::TcpServer { .. } => {
+                SmartChannelSource::TcpServer { .. } | SmartChannelSource::GrpcServer { .. } => {
                     // We start a TCP server by default in native rerun, i.e. when just running `rerun`,
                     // and in that case fading in the welcome screen would be slightly annoying.
                     // However, we also use the TCP server for sending data from the logging SDKs
@@ -1838,6 +1890,7 @@ impl eframe::App for App {
         self.check_keyboard_shortcuts(egui_ctx);
 
         self.purge_memory_if_needed(&mut store_hub);
+        self.purge_old_data_if_needed(&mut store_hub);
 
         {
             // TODO(andreas): store the re_renderer somewhere else.
@@ -1855,6 +1908,11 @@ impl eframe::App for App {
             store_hub.begin_frame(renderer_active_frame_idx);
         }
 
+        if store_hub.handle_paged_in_chunks() {
+            // Spilled data is being read back from disk: show it as soon as it's there.
+            egui_ctx.request_repaint();
+        }
+
         self.receive_messages(&mut store_hub, egui_ctx);
 
         if self.app_options().blueprint_gc {
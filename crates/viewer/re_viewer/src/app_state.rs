@@ -630,6 +630,7 @@ fn recording_config_entry<'cfgs>(
                 | re_smart_channel::SmartChannelSource::Sdk
                 | re_smart_channel::SmartChannelSource::WsClient { .. }
                 | re_smart_channel::SmartChannelSource::TcpServer { .. }
+                | re_smart_channel::SmartChannelSource::GrpcServer { .. }
                 | re_smart_channel::SmartChannelSource::Stdin
                 | re_smart_channel::SmartChannelSource::JsChannel { .. } => PlayState::Following,
             }
@@ -13,6 +13,7 @@ pub use re_types::blueprint::components::Corner2D;
 pub use re_types::blueprint::components::Enabled;
 pub use re_types::blueprint::components::FilterByRange;
 pub use re_types::blueprint::components::FilterIsNotNull;
+pub use re_types::blueprint::components::FilterIsNotNullMode;
 pub use re_types::blueprint::components::ForceDistance;
 pub use re_types::blueprint::components::ForceIterations;
 pub use re_types::blueprint::components::ForceStrength;
@@ -20,6 +21,7 @@ pub use re_types::blueprint::components::GridColumns;
 pub use re_types::blueprint::components::GridSpacing;
 pub use re_types::blueprint::components::IncludedContent;
 pub use re_types::blueprint::components::Interactive;
+pub use re_types::blueprint::components::LatestAtWithinRange;
 pub use re_types::blueprint::components::LockRangeDuringZoom;
 pub use re_types::blueprint::components::MapProvider;
 pub use re_types::blueprint::components::NearClipPlane;
@@ -57,6 +59,7 @@ pub fn is_valid_blueprint(blueprint: &EntityDb) -> bool {
         && validate_component::<Enabled>(blueprint)
         && validate_component::<FilterByRange>(blueprint)
         && validate_component::<FilterIsNotNull>(blueprint)
+        && validate_component::<FilterIsNotNullMode>(blueprint)
         && validate_component::<ForceDistance>(blueprint)
         && validate_component::<ForceIterations>(blueprint)
         && validate_component::<ForceStrength>(blueprint)
@@ -64,6 +67,7 @@ pub fn is_valid_blueprint(blueprint: &EntityDb) -> bool {
         && validate_component::<GridSpacing>(blueprint)
         && validate_component::<IncludedContent>(blueprint)
         && validate_component::<Interactive>(blueprint)
+        && validate_component::<LatestAtWithinRange>(blueprint)
         && validate_component::<LockRangeDuringZoom>(blueprint)
         && validate_component::<MapProvider>(blueprint)
         && validate_component::<NearClipPlane>(blueprint)
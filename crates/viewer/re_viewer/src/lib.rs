@@ -28,7 +28,7 @@ pub mod blueprint;
 
 pub(crate) use {app_state::AppState, ui::memory_panel};
 
-pub use app::{App, StartupOptions};
+pub use app::{App, StartupOptions, TimeLimit};
 
 pub use re_capabilities::MainThreadToken;
 
@@ -1,12 +1,12 @@
 use re_chunk_store::{ChunkStoreChunkStats, ChunkStoreConfig, ChunkStoreStats};
-use re_format::{format_bytes, format_uint};
+use re_format::{format_bytes, format_int, format_uint};
 use re_memory::{util::sec_since_start, MemoryHistory, MemoryLimit, MemoryUse};
 use re_query::{CacheStats, CachesStats};
 use re_renderer::WgpuResourcePoolStatistics;
 use re_ui::UiExt as _;
 use re_viewer_context::store_hub::StoreHubStats;
 
-use crate::env_vars::RERUN_TRACK_ALLOCATIONS;
+use crate::{env_vars::RERUN_TRACK_ALLOCATIONS, TimeLimit};
 
 // ----------------------------------------------------------------------------
 
@@ -52,6 +52,7 @@ impl MemoryPanel {
         &self,
         ui: &mut egui::Ui,
         limit: &MemoryLimit,
+        time_limit: Option<&TimeLimit>,
         gpu_resource_stats: &WgpuResourcePoolStatistics,
         store_stats: Option<&StoreHubStats>,
     ) {
@@ -65,7 +66,7 @@ impl MemoryPanel {
             .min_width(250.0)
             .default_width(300.0)
             .show_inside(ui, |ui| {
-                Self::left_side(ui, limit, gpu_resource_stats, store_stats);
+                Self::left_side(ui, limit, time_limit, gpu_resource_stats, store_stats);
             });
 
         egui::CentralPanel::default().show_inside(ui, |ui| {
@@ -77,6 +78,7 @@ impl MemoryPanel {
     fn left_side(
         ui: &mut egui::Ui,
         limit: &MemoryLimit,
+        time_limit: Option<&TimeLimit>,
         gpu_resource_stats: &WgpuResourcePoolStatistics,
         store_stats: Option<&StoreHubStats>,
     ) {
@@ -84,7 +86,7 @@ impl MemoryPanel {
 
         ui.separator();
         ui.collapsing("CPU Resources", |ui| {
-            Self::cpu_stats(ui, limit);
+            Self::cpu_stats(ui, limit, time_limit);
         });
 
         ui.separator();
@@ -118,7 +120,7 @@ impl MemoryPanel {
         }
     }
 
-    fn cpu_stats(ui: &mut egui::Ui, limit: &MemoryLimit) {
+    fn cpu_stats(ui: &mut egui::Ui, limit: &MemoryLimit, time_limit: Option<&TimeLimit>) {
         if let Some(max_bytes) = limit.max_bytes {
             ui.label(format!("Memory limit: {}", format_bytes(max_bytes as _)));
         } else {
@@ -130,6 +132,28 @@ impl MemoryPanel {
             ui.separator();
         }
 
+        if let Some(TimeLimit {
+            timeline,
+            time_span,
+        }) = time_limit
+        {
+            ui.label(format!(
+                "Time limit: latest {} on timeline {timeline:?}",
+                format_int(*time_span)
+            ))
+            .on_hover_text(
+                "Older data is dropped regardless of memory use. \
+                In the native unit of the timeline: nanoseconds for temporal timelines, ticks for sequence timelines.",
+            );
+        } else {
+            ui.horizontal(|ui| {
+                ui.spacing_mut().item_spacing.x = 0.0;
+                ui.label("You can only keep the most recent data with the command-line option ");
+                ui.code("--time-limit");
+            });
+            ui.separator();
+        }
+
         let mem_use = MemoryUse::capture();
 
         if mem_use.resident.is_some() || mem_use.counted.is_some() {
@@ -67,6 +67,7 @@ fn loading_receivers_ui(ctx: &ViewerContext<'_>, rx: &ReceiveSet<LogMsg>, ui: &m
             | SmartChannelSource::Sdk
             | SmartChannelSource::WsClient { .. }
             | SmartChannelSource::TcpServer { .. }
+            | SmartChannelSource::GrpcServer { .. }
             | SmartChannelSource::Stdin => {
                 // These show up in the top panel - see `top_panel.rs`.
                 continue;
@@ -91,7 +92,9 @@ fn loading_receivers_ui(ctx: &ViewerContext<'_>, rx: &ReceiveSet<LogMsg>, ui: &m
                     resp
                 }),
             );
-            if let SmartChannelSource::TcpServer { .. } = source.as_ref() {
+            if let SmartChannelSource::TcpServer { .. } | SmartChannelSource::GrpcServer { .. } =
+                source.as_ref()
+            {
                 response.on_hover_text("You can connect to this viewer from a Rerun SDK");
             }
         }
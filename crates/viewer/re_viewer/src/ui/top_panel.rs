@@ -169,6 +169,7 @@ fn connection_status_ui(ui: &mut egui::Ui, rx: &ReceiveSet<re_log_types::LogMsg>
                 | re_smart_channel::SmartChannelSource::Sdk
                 | re_smart_channel::SmartChannelSource::WsClient { .. }
                 | re_smart_channel::SmartChannelSource::TcpServer { .. }
+                | re_smart_channel::SmartChannelSource::GrpcServer { .. }
                 | re_smart_channel::SmartChannelSource::JsChannel { .. } => true,
             }
         })
@@ -205,7 +206,7 @@ fn connection_status_ui(ui: &mut egui::Ui, rx: &ReceiveSet<re_log_types::LogMsg>
             | SmartChannelSource::Sdk
             | SmartChannelSource::WsClient { .. } => None,
 
-            SmartChannelSource::TcpServer { .. } => {
+            SmartChannelSource::TcpServer { .. } | SmartChannelSource::GrpcServer { .. } => {
                 Some("Waiting for an SDK to connect".to_owned())
             }
         };
@@ -241,6 +242,9 @@ fn connection_status_ui(ui: &mut egui::Ui, rx: &ReceiveSet<re_log_types::LogMsg>
             re_smart_channel::SmartChannelSource::TcpServer { port } => {
                 format!("Listening on TCP port {port}")
             }
+            re_smart_channel::SmartChannelSource::GrpcServer { port } => {
+                format!("Listening on gRPC port {port}")
+            }
         }
     }
 }
@@ -85,6 +85,9 @@ enum EndpointCategory {
     /// Could be a link to either an `.rrd` recording or a `.rbl` blueprint.
     HttpRrd(String),
 
+    /// Any other remote url (`http://foo.com/bar.ply`), which is handed over to all data-loaders.
+    HttpUri(String),
+
     /// gRPC Rerun Data Platform URL, e.g. `rerun://ip:port/recording/1234`
     RerunGrpc(String),
 
@@ -97,7 +100,9 @@ enum EndpointCategory {
 
 impl EndpointCategory {
     fn categorize_uri(uri: String) -> Self {
-        if uri.starts_with("http") || uri.ends_with(".rrd") || uri.ends_with(".rbl") {
+        if uri.starts_with("http") && !re_data_source::is_rrd_http_url(&uri) {
+            Self::HttpUri(uri)
+        } else if uri.starts_with("http") || uri.ends_with(".rrd") || uri.ends_with(".rbl") {
             Self::HttpRrd(uri)
         } else if uri.starts_with("rerun://") {
             Self::RerunGrpc(uri)
@@ -138,6 +143,11 @@ pub fn url_to_receiver(
             ),
         ),
 
+        EndpointCategory::HttpUri(url) => {
+            re_data_source::DataSource::Uri(re_log_types::FileSource::Uri, url)
+                .stream(Some(ui_waker))
+        }
+
         #[cfg(feature = "grpc")]
         EndpointCategory::RerunGrpc(url) => {
             re_grpc_client::stream_from_redap(url, Some(ui_waker)).map_err(|err| err.into())
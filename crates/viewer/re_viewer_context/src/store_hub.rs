@@ -8,7 +8,7 @@ use re_chunk_store::{
     GarbageCollectionTarget,
 };
 use re_entity_db::{EntityDb, StoreBundle};
-use re_log_types::{ApplicationId, ResolvedTimeRange, StoreId, StoreKind};
+use re_log_types::{ApplicationId, ResolvedTimeRange, StoreId, StoreKind, TimelineName};
 use re_query::CachesStats;
 
 use crate::{BlueprintUndoState, Caches, StoreContext};
@@ -665,6 +665,38 @@ impl StoreHub {
         // we can get an accurate reading of the current memory used and decide if we should go on.
     }
 
+    /// Call [`EntityDb::purge_older_than`] on every recording that has data on the timeline
+    /// named `timeline`.
+    pub fn purge_older_than(&mut self, timeline: &TimelineName, time_span: i64) {
+        re_tracing::profile_function!();
+
+        let Self {
+            store_bundle,
+            caches_per_recording,
+            ..
+        } = self;
+
+        for entity_db in store_bundle.entity_dbs_mut() {
+            if entity_db.store_kind() != StoreKind::Recording {
+                continue;
+            }
+
+            let Some(timeline) = entity_db
+                .timelines()
+                .find(|t| t.name() == timeline)
+                .copied()
+            else {
+                continue;
+            };
+
+            let store_events = entity_db.purge_older_than(timeline, time_span);
+
+            if let Some(caches) = caches_per_recording.get_mut(&entity_db.store_id()) {
+                caches.on_store_events(&store_events);
+            }
+        }
+    }
+
     /// Remove any recordings with a network source pointing at this `uri`.
     pub fn remove_recording_by_uri(&mut self, uri: &str) {
         self.retain(|db| {
@@ -739,6 +771,19 @@ impl StoreHub {
         }
     }
 
+    /// Lets the query caches know about the spilled data that was read back from disk.
+    ///
+    /// Returns `true` while more is on its way, in which case the viewer should keep repainting.
+    ///
+    /// See [`EntityDb::handle_paged_in_chunks`].
+    pub fn handle_paged_in_chunks(&mut self) -> bool {
+        let mut is_paging_in = false;
+        for entity_db in self.store_bundle.entity_dbs_mut() {
+            is_paging_in |= entity_db.handle_paged_in_chunks();
+        }
+        is_paging_in
+    }
+
     /// Persist any in-use blueprints to durable storage.
     pub fn save_app_blueprints(&mut self) -> anyhow::Result<()> {
         let Some(saver) = &self.persistence.saver else {
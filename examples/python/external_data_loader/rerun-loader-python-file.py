@@ -4,6 +4,7 @@
 from __future__ import annotations
 
 import argparse
+import json
 import os
 
 import rerun as rr  # pip install rerun-sdk
@@ -27,11 +28,15 @@ treated as an external data-loader.
 This particular one will log Python source code files as markdown documents, and return a
 special exit code to indicate that it doesn't support anything else.
 
+It also describes itself when called with `--describe`, so that the Rerun Viewer doesn't
+even bother executing it for anything but Python source files.
+
 To try it out, copy it in your $PATH as `rerun-loader-python-file`, then open a Python source
 file with Rerun (`rerun file.py`).
 """
 )
-parser.add_argument("filepath", type=str)
+parser.add_argument("filepath", type=str, nargs="?")
+parser.add_argument("--describe", action="store_true", default=False, help="print the manifest of this loader, then exit")
 parser.add_argument("--application-id", type=str, help="optional recommended ID for the application")
 parser.add_argument("--recording-id", type=str, help="optional recommended ID for the recording")
 parser.add_argument("--entity-path-prefix", type=str, help="optional prefix for all entity paths")
@@ -53,6 +58,14 @@ args = parser.parse_args()
 
 
 def main() -> None:
+    if args.describe:
+        # Let the Rerun Viewer know which files we support.
+        print(json.dumps({"extensions": ["py"], "mime_types": ["text/x-python"]}))
+        return
+
+    if args.filepath is None:
+        parser.error("expected a path to load, or `--describe`")
+
     is_file = os.path.isfile(args.filepath)
     is_python_file = os.path.splitext(args.filepath)[1].lower() == ".py"
 
@@ -1,6 +1,6 @@
 //! Example of an external data-loader executable plugin for the Rerun Viewer.
 
-use rerun::EXTERNAL_DATA_LOADER_INCOMPATIBLE_EXIT_CODE;
+use rerun::{EXTERNAL_DATA_LOADER_DESCRIBE_FLAG, EXTERNAL_DATA_LOADER_INCOMPATIBLE_EXIT_CODE};
 
 // The Rerun Viewer will always pass at least these two pieces of information:
 // 1. The path to be loaded, as a positional arg.
@@ -20,6 +20,9 @@ use rerun::EXTERNAL_DATA_LOADER_INCOMPATIBLE_EXIT_CODE;
 /// This particular one will log Rust source code files as markdown documents, and return a
 /// special exit code to indicate that it doesn't support anything else.
 ///
+/// It also describes itself when called with `--describe`, so that the Rerun Viewer doesn't
+/// even bother executing it for anything but Rust source files.
+///
 /// To try it out, install it in your $PATH (`cargo install --path . -f`), then open a
 /// Rust source file with Rerun (`rerun file.rs`).
 ///
@@ -27,7 +30,11 @@ use rerun::EXTERNAL_DATA_LOADER_INCOMPATIBLE_EXIT_CODE;
 #[derive(argh::FromArgs, Debug)]
 struct Args {
     #[argh(positional)]
-    filepath: std::path::PathBuf,
+    filepath: Option<std::path::PathBuf>,
+
+    /// print the manifest of this data-loader as JSON, then exit
+    #[argh(switch)]
+    describe: bool,
 
     /// optional recommended ID for the application
     #[argh(option)]
@@ -69,8 +76,18 @@ fn extension(path: &std::path::Path) -> String {
 fn main() -> anyhow::Result<()> {
     let args: Args = argh::from_env();
 
-    let is_file = args.filepath.is_file();
-    let is_rust_file = extension(&args.filepath) == "rs";
+    if args.describe {
+        // Let the Rerun Viewer know which files we support, see `rerun::EXTERNAL_DATA_LOADER_DESCRIBE_FLAG`.
+        println!(r#"{{"extensions": ["rs"]}}"#);
+        return Ok(());
+    }
+
+    let Some(filepath) = args.filepath.as_deref() else {
+        anyhow::bail!("expected a path to load, or `{EXTERNAL_DATA_LOADER_DESCRIBE_FLAG}`");
+    };
+
+    let is_file = filepath.is_file();
+    let is_rust_file = extension(filepath) == "rs";
 
     // Inform the Rerun Viewer that we do not support that kind of file.
     if !is_file || !is_rust_file {
@@ -78,7 +95,7 @@ fn main() -> anyhow::Result<()> {
         std::process::exit(EXTERNAL_DATA_LOADER_INCOMPATIBLE_EXIT_CODE);
     }
 
-    let body = std::fs::read_to_string(&args.filepath)?;
+    let body = std::fs::read_to_string(filepath)?;
     let text = format!("## Some Rust code\n```rust\n{body}\n```\n");
 
     let rec = {
@@ -104,7 +121,7 @@ fn main() -> anyhow::Result<()> {
         .map_or_else(|| rerun::EntityPath::new(vec![]), rerun::EntityPath::from);
 
     rec.log_with_static(
-        entity_path_prefix.join(&rerun::EntityPath::from_file_path(&args.filepath)),
+        entity_path_prefix.join(&rerun::EntityPath::from_file_path(filepath)),
         args.statically || args.timeless,
         &rerun::TextDocument::from_markdown(text),
     )?;
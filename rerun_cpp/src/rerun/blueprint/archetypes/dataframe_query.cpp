@@ -15,7 +15,7 @@ namespace rerun {
         ) {
         using namespace blueprint::archetypes;
         std::vector<ComponentBatch> cells;
-        cells.reserve(6);
+        cells.reserve(8);
 
         if (archetype.timeline.has_value()) {
             auto result = ComponentBatch::from_loggable(
@@ -53,6 +53,18 @@ namespace rerun {
             RR_RETURN_NOT_OK(result.error);
             cells.push_back(std::move(result.value));
         }
+        if (archetype.filter_is_not_null_mode.has_value()) {
+            auto result = ComponentBatch::from_loggable(
+                archetype.filter_is_not_null_mode.value(),
+                ComponentDescriptor(
+                    "rerun.blueprint.archetypes.DataframeQuery",
+                    "filter_is_not_null_mode",
+                    "rerun.blueprint.components.FilterIsNotNullMode"
+                )
+            );
+            RR_RETURN_NOT_OK(result.error);
+            cells.push_back(std::move(result.value));
+        }
         if (archetype.apply_latest_at.has_value()) {
             auto result = ComponentBatch::from_loggable(
                 archetype.apply_latest_at.value(),
@@ -65,6 +77,18 @@ namespace rerun {
             RR_RETURN_NOT_OK(result.error);
             cells.push_back(std::move(result.value));
         }
+        if (archetype.latest_at_within_range.has_value()) {
+            auto result = ComponentBatch::from_loggable(
+                archetype.latest_at_within_range.value(),
+                ComponentDescriptor(
+                    "rerun.blueprint.archetypes.DataframeQuery",
+                    "latest_at_within_range",
+                    "rerun.blueprint.components.LatestAtWithinRange"
+                )
+            );
+            RR_RETURN_NOT_OK(result.error);
+            cells.push_back(std::move(result.value));
+        }
         if (archetype.select.has_value()) {
             auto result = ComponentBatch::from_loggable(
                 archetype.select.value(),
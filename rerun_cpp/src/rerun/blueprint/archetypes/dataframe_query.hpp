@@ -6,6 +6,8 @@
 #include "../../blueprint/components/apply_latest_at.hpp"
 #include "../../blueprint/components/filter_by_range.hpp"
 #include "../../blueprint/components/filter_is_not_null.hpp"
+#include "../../blueprint/components/filter_is_not_null_mode.hpp"
+#include "../../blueprint/components/latest_at_within_range.hpp"
 #include "../../blueprint/components/selected_columns.hpp"
 #include "../../blueprint/components/timeline_name.hpp"
 #include "../../collection.hpp"
@@ -32,12 +34,25 @@ namespace rerun::blueprint::archetypes {
         /// Note: will be unset as soon as `timeline` is changed.
         std::optional<rerun::blueprint::components::FilterByRange> filter_by_range;
 
-        /// If provided, only show rows which contains a logged event for the specified component.
-        std::optional<rerun::blueprint::components::FilterIsNotNull> filter_is_not_null;
+        /// If provided, only show rows which contains a logged event for the specified components.
+        ///
+        /// See `filter_is_not_null_mode` for how multiple columns are combined.
+        std::optional<Collection<rerun::blueprint::components::FilterIsNotNull>> filter_is_not_null;
+
+        /// Whether rows must contain all of the `filter_is_not_null` columns, or any of them.
+        ///
+        /// Defaults to all.
+        std::optional<rerun::blueprint::components::FilterIsNotNullMode> filter_is_not_null_mode;
 
         /// Should empty cells be filled with latest-at queries?
         std::optional<rerun::blueprint::components::ApplyLatestAt> apply_latest_at;
 
+        /// Should the latest-at queries only consider data within the filtered range?
+        ///
+        /// If false (the default), empty cells may be filled with values logged before the start of the range.
+        /// Has no effect unless `apply_latest_at` is true.
+        std::optional<rerun::blueprint::components::LatestAtWithinRange> latest_at_within_range;
+
         /// Selected columns. If unset, all columns are selected.
         std::optional<rerun::blueprint::components::SelectedColumns> select;
 
@@ -72,15 +87,28 @@ namespace rerun::blueprint::archetypes {
             RR_WITH_MAYBE_UNINITIALIZED_DISABLED(return std::move(*this);)
         }
 
-        /// If provided, only show rows which contains a logged event for the specified component.
+        /// If provided, only show rows which contains a logged event for the specified components.
+        ///
+        /// See `filter_is_not_null_mode` for how multiple columns are combined.
         DataframeQuery with_filter_is_not_null(
-            rerun::blueprint::components::FilterIsNotNull _filter_is_not_null
+            Collection<rerun::blueprint::components::FilterIsNotNull> _filter_is_not_null
         ) && {
             filter_is_not_null = std::move(_filter_is_not_null);
             // See: https://github.com/rerun-io/rerun/issues/4027
             RR_WITH_MAYBE_UNINITIALIZED_DISABLED(return std::move(*this);)
         }
 
+        /// Whether rows must contain all of the `filter_is_not_null` columns, or any of them.
+        ///
+        /// Defaults to all.
+        DataframeQuery with_filter_is_not_null_mode(
+            rerun::blueprint::components::FilterIsNotNullMode _filter_is_not_null_mode
+        ) && {
+            filter_is_not_null_mode = std::move(_filter_is_not_null_mode);
+            // See: https://github.com/rerun-io/rerun/issues/4027
+            RR_WITH_MAYBE_UNINITIALIZED_DISABLED(return std::move(*this);)
+        }
+
         /// Should empty cells be filled with latest-at queries?
         DataframeQuery with_apply_latest_at(
             rerun::blueprint::components::ApplyLatestAt _apply_latest_at
@@ -90,6 +118,18 @@ namespace rerun::blueprint::archetypes {
             RR_WITH_MAYBE_UNINITIALIZED_DISABLED(return std::move(*this);)
         }
 
+        /// Should the latest-at queries only consider data within the filtered range?
+        ///
+        /// If false (the default), empty cells may be filled with values logged before the start of the range.
+        /// Has no effect unless `apply_latest_at` is true.
+        DataframeQuery with_latest_at_within_range(
+            rerun::blueprint::components::LatestAtWithinRange _latest_at_within_range
+        ) && {
+            latest_at_within_range = std::move(_latest_at_within_range);
+            // See: https://github.com/rerun-io/rerun/issues/4027
+            RR_WITH_MAYBE_UNINITIALIZED_DISABLED(return std::move(*this);)
+        }
+
         /// Selected columns. If unset, all columns are selected.
         DataframeQuery with_select(rerun::blueprint::components::SelectedColumns _select) && {
             select = std::move(_select);
@@ -14,6 +14,7 @@
 #include "blueprint/components/enabled.hpp"
 #include "blueprint/components/filter_by_range.hpp"
 #include "blueprint/components/filter_is_not_null.hpp"
+#include "blueprint/components/filter_is_not_null_mode.hpp"
 #include "blueprint/components/force_distance.hpp"
 #include "blueprint/components/force_iterations.hpp"
 #include "blueprint/components/force_strength.hpp"
@@ -21,6 +22,7 @@
 #include "blueprint/components/grid_spacing.hpp"
 #include "blueprint/components/included_content.hpp"
 #include "blueprint/components/interactive.hpp"
+#include "blueprint/components/latest_at_within_range.hpp"
 #include "blueprint/components/lock_range_during_zoom.hpp"
 #include "blueprint/components/map_provider.hpp"
 #include "blueprint/components/near_clip_plane.hpp"
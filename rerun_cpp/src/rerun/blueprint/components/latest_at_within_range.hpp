@@ -0,0 +1,76 @@
+// DO NOT EDIT! This file was auto-generated by crates/build/re_types_builder/src/codegen/cpp/mod.rs
+// Based on "crates/store/re_types/definitions/rerun/blueprint/components/latest_at_within_range.fbs".
+
+#pragma once
+
+#include "../../component_descriptor.hpp"
+#include "../../datatypes/bool.hpp"
+#include "../../result.hpp"
+
+#include <cstdint>
+#include <memory>
+
+namespace rerun::blueprint::components {
+    /// **Component**: Whether the latest-at queries filling empty cells in a dataframe should only consider data within the filtered range.
+    struct LatestAtWithinRange {
+        rerun::datatypes::Bool latest_at_within_range;
+
+      public:
+        LatestAtWithinRange() = default;
+
+        LatestAtWithinRange(rerun::datatypes::Bool latest_at_within_range_)
+            : latest_at_within_range(latest_at_within_range_) {}
+
+        LatestAtWithinRange& operator=(rerun::datatypes::Bool latest_at_within_range_) {
+            latest_at_within_range = latest_at_within_range_;
+            return *this;
+        }
+
+        LatestAtWithinRange(bool value_) : latest_at_within_range(value_) {}
+
+        LatestAtWithinRange& operator=(bool value_) {
+            latest_at_within_range = value_;
+            return *this;
+        }
+
+        /// Cast to the underlying Bool datatype
+        operator rerun::datatypes::Bool() const {
+            return latest_at_within_range;
+        }
+    };
+} // namespace rerun::blueprint::components
+
+namespace rerun {
+    static_assert(sizeof(rerun::datatypes::Bool) == sizeof(blueprint::components::LatestAtWithinRange));
+
+    /// \private
+    template <>
+    struct Loggable<blueprint::components::LatestAtWithinRange> {
+        static constexpr ComponentDescriptor Descriptor =
+            "rerun.blueprint.components.LatestAtWithinRange";
+
+        /// Returns the arrow data type this type corresponds to.
+        static const std::shared_ptr<arrow::DataType>& arrow_datatype() {
+            return Loggable<rerun::datatypes::Bool>::arrow_datatype();
+        }
+
+        /// Serializes an array of `rerun::blueprint:: components::LatestAtWithinRange` into an arrow array.
+        static Result<std::shared_ptr<arrow::Array>> to_arrow(
+            const blueprint::components::LatestAtWithinRange* instances, size_t num_instances
+        ) {
+            if (num_instances == 0) {
+                return Loggable<rerun::datatypes::Bool>::to_arrow(nullptr, 0);
+            } else if (instances == nullptr) {
+                return rerun::Error(
+                    ErrorCode::UnexpectedNullArgument,
+                    "Passed array instances is null when num_elements> 0."
+                );
+            } else {
+                return Loggable<rerun::datatypes::Bool>::to_arrow(
+                    &instances->latest_at_within_range,
+                    num_instances
+                );
+            }
+        }
+    };
+} // namespace rerun
@@ -28,7 +28,9 @@ class DataframeQuery(DataframeQueryExt, Archetype):
             timeline=None,  # type: ignore[arg-type]
             filter_by_range=None,  # type: ignore[arg-type]
             filter_is_not_null=None,  # type: ignore[arg-type]
+            filter_is_not_null_mode=None,  # type: ignore[arg-type]
             apply_latest_at=None,  # type: ignore[arg-type]
+            latest_at_within_range=None,  # type: ignore[arg-type]
             select=None,  # type: ignore[arg-type]
         )
 
@@ -66,7 +68,20 @@ class DataframeQuery(DataframeQueryExt, Archetype):
         default=None,
         converter=blueprint_components.FilterIsNotNullBatch._optional,  # type: ignore[misc]
     )
-    # If provided, only show rows which contains a logged event for the specified component.
+    # If provided, only show rows which contains a logged event for the specified components.
+    #
+    # See `filter_is_not_null_mode` for how multiple columns are combined.
+    #
+    # (Docstring intentionally commented out to hide this field from the docs)
+
+    filter_is_not_null_mode: blueprint_components.FilterIsNotNullModeBatch | None = field(
+        metadata={"component": "optional"},
+        default=None,
+        converter=blueprint_components.FilterIsNotNullModeBatch._optional,  # type: ignore[misc]
+    )
+    # Whether rows must contain all of the `filter_is_not_null` columns, or any of them.
+    #
+    # Defaults to all.
     #
     # (Docstring intentionally commented out to hide this field from the docs)
 
@@ -79,6 +94,18 @@ class DataframeQuery(DataframeQueryExt, Archetype):
     #
     # (Docstring intentionally commented out to hide this field from the docs)
 
+    latest_at_within_range: blueprint_components.LatestAtWithinRangeBatch | None = field(
+        metadata={"component": "optional"},
+        default=None,
+        converter=blueprint_components.LatestAtWithinRangeBatch._optional,  # type: ignore[misc]
+    )
+    # Should the latest-at queries only consider data within the filtered range?
+    #
+    # If false (the default), empty cells may be filled with values logged before the start of the range.
+    # Has no effect unless `apply_latest_at` is true.
+    #
+    # (Docstring intentionally commented out to hide this field from the docs)
+
     select: blueprint_components.SelectedColumnsBatch | None = field(
         metadata={"component": "optional"},
         default=None,
@@ -1,6 +1,6 @@
 from __future__ import annotations
 
-from typing import Any
+from typing import Any, Sequence
 
 from ... import datatypes
 from ...blueprint import components as blueprint_components, datatypes as blueprint_datatypes
@@ -17,8 +17,12 @@ class DataframeQueryExt:
         filter_by_range: tuple[datatypes.TimeInt, datatypes.TimeInt]
         | blueprint_datatypes.FilterByRangeLike
         | None = None,
-        filter_is_not_null: blueprint_datatypes.ComponentColumnSelectorLike | None = None,
+        filter_is_not_null: blueprint_datatypes.ComponentColumnSelectorLike
+        | Sequence[blueprint_datatypes.ComponentColumnSelectorLike]
+        | None = None,
+        filter_is_not_null_mode: blueprint_components.FilterIsNotNullModeLike | None = None,
         apply_latest_at: bool = False,
+        latest_at_within_range: bool = False,
         select: list[blueprint_datatypes.ComponentColumnSelectorLike | datatypes.Utf8Like | str] | None = None,
     ):
         """
@@ -34,11 +38,19 @@ class DataframeQueryExt:
 
         filter_is_not_null:
             If provided, the dataframe will only contain rows corresponding to timestamps at which an event was logged
-            for the provided column.
+            for the provided column(s).
+
+        filter_is_not_null_mode:
+            Whether rows must contain all of the `filter_is_not_null` columns (`"all"`, the default), or any of them
+            (`"any"`).
 
         apply_latest_at:
             Should empty cells be filled with latest-at queries?
 
+        latest_at_within_range:
+            Should the latest-at queries only consider data within the filtered range?
+            Has no effect unless `apply_latest_at` is true.
+
         select:
             Selected columns. If unset, all columns are selected.
 
@@ -49,12 +61,18 @@ class DataframeQueryExt:
             filter_by_range = blueprint_components.FilterByRange(start, end)
 
         if filter_is_not_null is not None:
-            if isinstance(filter_is_not_null, str):
-                column = blueprint_datatypes.ComponentColumnSelector(spec=filter_is_not_null)
-            else:
-                column = filter_is_not_null
+            if isinstance(filter_is_not_null, (str, blueprint_datatypes.ComponentColumnSelector)):
+                filter_is_not_null = [filter_is_not_null]
 
-            new_filter_is_not_null = blueprint_components.FilterIsNotNull(active=True, column=column)
+            new_filter_is_not_null = [
+                blueprint_components.FilterIsNotNull(
+                    active=True,
+                    column=blueprint_datatypes.ComponentColumnSelector(spec=column)
+                    if isinstance(column, str)
+                    else column,
+                )
+                for column in filter_is_not_null
+            ]
         else:
             new_filter_is_not_null = None
 
@@ -63,7 +81,9 @@ class DataframeQueryExt:
                 timeline=timeline,
                 filter_by_range=filter_by_range,
                 filter_is_not_null=new_filter_is_not_null,
+                filter_is_not_null_mode=filter_is_not_null_mode,
                 apply_latest_at=apply_latest_at,
+                latest_at_within_range=latest_at_within_range,
                 select=select,
             )
             return
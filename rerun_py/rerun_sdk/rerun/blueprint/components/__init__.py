@@ -14,6 +14,12 @@ from .corner2d import Corner2D, Corner2DArrayLike, Corner2DBatch, Corner2DLike
 from .enabled import Enabled, EnabledBatch
 from .filter_by_range import FilterByRange, FilterByRangeBatch
 from .filter_is_not_null import FilterIsNotNull, FilterIsNotNullBatch
+from .filter_is_not_null_mode import (
+    FilterIsNotNullMode,
+    FilterIsNotNullModeArrayLike,
+    FilterIsNotNullModeBatch,
+    FilterIsNotNullModeLike,
+)
 from .force_distance import ForceDistance, ForceDistanceBatch
 from .force_iterations import ForceIterations, ForceIterationsBatch
 from .force_strength import ForceStrength, ForceStrengthBatch
@@ -21,6 +27,7 @@ from .grid_columns import GridColumns, GridColumnsBatch
 from .grid_spacing import GridSpacing, GridSpacingBatch
 from .included_content import IncludedContent, IncludedContentBatch
 from .interactive import Interactive, InteractiveBatch
+from .latest_at_within_range import LatestAtWithinRange, LatestAtWithinRangeBatch
 from .lock_range_during_zoom import LockRangeDuringZoom, LockRangeDuringZoomBatch
 from .map_provider import MapProvider, MapProviderArrayLike, MapProviderBatch, MapProviderLike
 from .near_clip_plane import NearClipPlane, NearClipPlaneBatch
@@ -73,6 +80,10 @@ __all__ = [
     "FilterByRangeBatch",
     "FilterIsNotNull",
     "FilterIsNotNullBatch",
+    "FilterIsNotNullMode",
+    "FilterIsNotNullModeArrayLike",
+    "FilterIsNotNullModeBatch",
+    "FilterIsNotNullModeLike",
     "ForceDistance",
     "ForceDistanceBatch",
     "ForceIterations",
@@ -87,6 +98,8 @@ __all__ = [
     "IncludedContentBatch",
     "Interactive",
     "InteractiveBatch",
+    "LatestAtWithinRange",
+    "LatestAtWithinRangeBatch",
     "LockRangeDuringZoom",
     "LockRangeDuringZoomBatch",
     "MapProvider",
@@ -0,0 +1,33 @@
+# DO NOT EDIT! This file was auto-generated by crates/build/re_types_builder/src/codegen/python/mod.rs
+# Based on "crates/store/re_types/definitions/rerun/blueprint/components/latest_at_within_range.fbs".
+
+# You can extend this class by creating a "LatestAtWithinRangeExt" class in "latest_at_within_range_ext.py".
+
+from __future__ import annotations
+
+from ... import datatypes
+from ..._baseclasses import (
+    ComponentBatchMixin,
+    ComponentDescriptor,
+    ComponentMixin,
+)
+
+__all__ = ["LatestAtWithinRange", "LatestAtWithinRangeBatch"]
+
+
+class LatestAtWithinRange(datatypes.Bool, ComponentMixin):
+    """**Component**: Whether the latest-at queries filling empty cells in a dataframe should only consider data within the filtered range."""
+
+    _BATCH_TYPE = None
+    # You can define your own __init__ function as a member of LatestAtWithinRangeExt in latest_at_within_range_ext.py
+
+    # Note: there are no fields here because LatestAtWithinRange delegates to datatypes.Bool
+    pass
+
+
+class LatestAtWithinRangeBatch(datatypes.BoolBatch, ComponentBatchMixin):
+    _COMPONENT_DESCRIPTOR: ComponentDescriptor = ComponentDescriptor("rerun.blueprint.components.LatestAtWithinRange")
+
+
+# This is patched in late to avoid circular dependencies.
+LatestAtWithinRange._BATCH_TYPE = LatestAtWithinRangeBatch  # type: ignore[assignment]
@@ -20,8 +20,8 @@ use pyo3::{
 
 use re_chunk_store::{
     ChunkStore, ChunkStoreConfig, ChunkStoreHandle, ColumnDescriptor, ColumnSelector,
-    ComponentColumnDescriptor, ComponentColumnSelector, QueryExpression, SparseFillStrategy,
-    TimeColumnDescriptor, TimeColumnSelector, ViewContentsSelector,
+    ComponentColumnDescriptor, ComponentColumnSelector, FilterIsNotNullMode, QueryExpression,
+    SparseFillStrategy, TimeColumnDescriptor, TimeColumnSelector, ViewContentsSelector,
 };
 use re_dataframe::{QueryEngine, StorageEngine};
 use re_log_encoding::VersionPolicy;
@@ -1105,7 +1105,7 @@ impl PyRecordingView {
         let column = column.into_selector();
 
         let mut query_expression = self.query_expression.clone();
-        query_expression.filtered_is_not_null = Some(column?);
+        query_expression.filtered_is_not_null = vec![column?];
 
         Ok(Self {
             recording: self.recording.clone(),
@@ -1375,7 +1375,8 @@ impl PyRecording {
             filtered_index_range: None,
             filtered_index_values: None,
             using_index_values: None,
-            filtered_is_not_null: None,
+            filtered_is_not_null: Vec::new(),
+            filtered_is_not_null_mode: FilterIsNotNullMode::All,
             sparse_fill_strategy: SparseFillStrategy::None,
             selection: None,
         };
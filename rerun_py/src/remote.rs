@@ -17,7 +17,10 @@ use pyo3::{
 };
 use re_chunk::{Chunk, TransportChunk};
 use re_chunk_store::ChunkStore;
-use re_dataframe::{ChunkStoreHandle, QueryExpression, SparseFillStrategy, ViewContentsSelector};
+use re_dataframe::{
+    ChunkStoreHandle, FilterIsNotNullMode, QueryExpression, SparseFillStrategy,
+    ViewContentsSelector,
+};
 use re_grpc_client::TonicStatusError;
 use re_log_encoding::codec::wire::{decode, encode};
 use re_log_types::{EntityPathFilter, StoreInfo, StoreSource};
@@ -687,7 +690,8 @@ impl PyRemoteRecording {
             filtered_index_range: None,
             filtered_index_values: None,
             using_index_values: None,
-            filtered_is_not_null: None,
+            filtered_is_not_null: Vec::new(),
+            filtered_is_not_null_mode: FilterIsNotNullMode::All,
             sparse_fill_strategy: SparseFillStrategy::None,
             selection: None,
         };
@@ -128,6 +128,7 @@ def test_dataframe_query_property() -> None:
         filter_by_range=(TimeInt(seq=1), TimeInt(seq=10)),
         filter_is_not_null="/entity/path:ComponentName",
         apply_latest_at=True,
+        latest_at_within_range=True,
         select=[
             "t",
             "/entity/path:ComponentName",
@@ -146,6 +147,9 @@ def test_dataframe_query_property() -> None:
     )
 
     assert query.apply_latest_at == blueprint_components.ApplyLatestAtBatch(blueprint_components.ApplyLatestAt(True))
+    assert query.latest_at_within_range == blueprint_components.LatestAtWithinRangeBatch(
+        blueprint_components.LatestAtWithinRange(True)
+    )
 
     assert query.select == blueprint_components.SelectedColumnsBatch(
         blueprint_components.SelectedColumns([
@@ -185,3 +189,24 @@ def test_dataframe_query_property_explicit() -> None:
             blueprint_components.ComponentColumnSelector(entity_path="/world/robot", component="Position3D"),
         ])
     )
+
+
+def test_dataframe_query_property_multiple_filter_is_not_null() -> None:
+    query = DataframeQuery(
+        filter_is_not_null=["/robot:Transform3D", "/camera:Image"],
+        filter_is_not_null_mode="any",
+    )
+
+    assert query.filter_is_not_null == blueprint_components.FilterIsNotNullBatch([
+        blueprint_components.FilterIsNotNull(
+            active=True,
+            column=blueprint_components.ComponentColumnSelector(entity_path="/robot", component="Transform3D"),
+        ),
+        blueprint_components.FilterIsNotNull(
+            active=True,
+            column=blueprint_components.ComponentColumnSelector(entity_path="/camera", component="Image"),
+        ),
+    ])
+    assert query.filter_is_not_null_mode == blueprint_components.FilterIsNotNullModeBatch(
+        blueprint_components.FilterIsNotNullMode.Any
+    )